@@ -373,6 +373,43 @@ impl fmt::Display for CannotReallocInPlace {
     }
 }
 
+/// Augments `AllocErr` with a `CapacityOverflow` variant.
+///
+/// This is the error returned by the fallible `try_reserve` methods of the
+/// collections.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+pub enum CollectionAllocErr {
+    /// Error due to the computed capacity exceeding the collection's maximum
+    /// (usually `isize::MAX` bytes).
+    CapacityOverflow,
+    /// Error due to the allocator (see the `AllocErr` type's docs).
+    AllocErr(AllocErr),
+}
+
+#[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+impl From<AllocErr> for CollectionAllocErr {
+    fn from(err: AllocErr) -> Self {
+        CollectionAllocErr::AllocErr(err)
+    }
+}
+
+impl CollectionAllocErr {
+    pub fn description(&self) -> &str {
+        match *self {
+            CollectionAllocErr::CapacityOverflow => "capacity overflow",
+            CollectionAllocErr::AllocErr(ref err) => err.description(),
+        }
+    }
+}
+
+// (we need this for downstream impl of trait Error)
+impl fmt::Display for CollectionAllocErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
 /// An implementation of `Alloc` can allocate, reallocate, and
 /// deallocate arbitrary blocks of data described via `Layout`.
 ///
@@ -1062,3 +1099,22 @@ pub unsafe trait Alloc {
         }
     }
 }
+
+/// An allocator whose clones all share the same memory.
+///
+/// Collections such as `BTreeMap` give clones of their allocator to the
+/// collections they create, and then move memory from one to the other. For
+/// example, the map returned by `BTreeMap::split_off` frees nodes which the
+/// original map allocated. They can only do that with allocators which
+/// implement this trait.
+///
+/// # Safety
+///
+/// Implementors must ensure that a block of memory allocated through one
+/// clone of an allocator can be passed to any other clone of it, as if that
+/// clone had allocated it. In particular, the memory must stay valid for as
+/// long as any clone is alive, not just the one which allocated it.
+///
+/// A per-instance arena whose `clone` creates a new, empty arena must not
+/// implement this trait.
+pub unsafe trait SharedAlloc: Alloc + Clone {}
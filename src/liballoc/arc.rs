@@ -31,7 +31,7 @@ use core::hash::{Hash, Hasher};
 use core::{isize, usize};
 use core::convert::From;
 
-use heap::{Heap, Alloc, Layout, box_free};
+use heap::{Heap, Alloc, Layout, dealloc_box};
 use boxed::Box;
 use string::String;
 use vec::Vec;
@@ -580,7 +580,7 @@ impl<T: ?Sized> Arc<T> {
                 value_size);
 
             // Free the allocation without dropping its contents
-            box_free(bptr);
+            dealloc_box(bptr, Heap);
 
             Arc { ptr: NonNull::new_unchecked(ptr), phantom: PhantomData }
         }
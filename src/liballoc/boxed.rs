@@ -106,11 +106,21 @@ pub struct ExchangeHeapSingleton {
 /// A pointer type for heap allocation.
 ///
 /// See the [module-level documentation](../../std/boxed/index.html) for more.
+#[cfg(stage0)]
 #[lang = "owned_box"]
 #[fundamental]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct Box<T: ?Sized>(Unique<T>);
 
+/// A pointer type for heap allocation.
+///
+/// See the [module-level documentation](../../std/boxed/index.html) for more.
+#[cfg(not(stage0))]
+#[lang = "owned_box"]
+#[fundamental]
+#[stable(feature = "rust1", since = "1.0.0")]
+pub struct Box<T: ?Sized, A: Alloc = Heap>(Unique<T>, A);
+
 /// `IntermediateBox` represents uninitialized backing storage for `Box`.
 ///
 /// FIXME (pnkfelix): Ideally we would just reuse `Box<T>` instead of
@@ -269,7 +279,19 @@ impl<T: ?Sized> Box<T> {
     #[stable(feature = "box_raw", since = "1.4.0")]
     #[inline]
     pub unsafe fn from_raw(raw: *mut T) -> Self {
-        Box(Unique::new_unchecked(raw))
+        Box::from_unique(Unique::new_unchecked(raw))
+    }
+
+    #[cfg(stage0)]
+    #[inline]
+    unsafe fn from_unique(unique: Unique<T>) -> Self {
+        Box(unique)
+    }
+
+    #[cfg(not(stage0))]
+    #[inline]
+    unsafe fn from_unique(unique: Unique<T>) -> Self {
+        Box(unique, Heap)
     }
 
     /// Consumes the `Box`, returning the wrapped raw pointer.
@@ -387,13 +409,108 @@ impl<T: ?Sized> Box<T> {
     }
 }
 
+#[cfg(not(stage0))]
+impl<T, A: Alloc> Box<T, A> {
+    /// Allocates memory with `alloc` and then places `x` into it.
+    ///
+    /// This doesn't actually allocate if `T` is zero-sized. The box
+    /// keeps hold of `alloc` and gives the memory back to it when it
+    /// is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::heap::Heap;
+    ///
+    /// let five = Box::new_in(5, Heap);
+    /// assert_eq!(*five, 5);
+    /// ```
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    #[inline]
+    pub fn new_in(x: T, mut alloc: A) -> Box<T, A> {
+        let layout = Layout::new::<T>();
+        unsafe {
+            let unique = if layout.size() == 0 {
+                Unique::empty()
+            } else {
+                match alloc.alloc(layout) {
+                    Ok(ptr) => Unique::new_unchecked(ptr as *mut T),
+                    Err(err) => alloc.oom(err),
+                }
+            };
+            ptr::write(unique.as_ptr(), x);
+            Box(unique, alloc)
+        }
+    }
+}
+
+#[cfg(not(stage0))]
+impl<T: ?Sized, A: Alloc> Box<T, A> {
+    /// Constructs a box from a raw pointer and the allocator it was
+    /// allocated with.
+    ///
+    /// After calling this function, the raw pointer is owned by the
+    /// resulting `Box`, which will call the destructor of `T` and free
+    /// the memory through `alloc`. The only valid pointer to pass to
+    /// this function is one taken, together with its allocator, from
+    /// another `Box` via [`Box::into_raw_with_alloc`].
+    ///
+    /// [`Box::into_raw_with_alloc`]: struct.Box.html#method.into_raw_with_alloc
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    #[inline]
+    pub unsafe fn from_raw_in(raw: *mut T, alloc: A) -> Box<T, A> {
+        Box(Unique::new_unchecked(raw), alloc)
+    }
+
+    /// Consumes the `Box`, returning the wrapped raw pointer and the
+    /// allocator which owns its memory.
+    ///
+    /// The pair can be turned back into a `Box` with
+    /// [`Box::from_raw_in`].
+    ///
+    /// Note: this is an associated function, which means that you have
+    /// to call it as `Box::into_raw_with_alloc(b)` instead of
+    /// `b.into_raw_with_alloc()`.
+    ///
+    /// [`Box::from_raw_in`]: struct.Box.html#method.from_raw_in
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    #[inline]
+    pub fn into_raw_with_alloc(b: Box<T, A>) -> (*mut T, A) {
+        let ptr = b.0.as_ptr();
+        let alloc = unsafe { ptr::read(&b.1) };
+        mem::forget(b);
+        (ptr, alloc)
+    }
+
+    /// Returns a reference to the allocator backing the `Box`.
+    ///
+    /// Note: this is an associated function, which means that you have
+    /// to call it as `Box::alloc(&b)` instead of `b.alloc()`.
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    #[inline]
+    pub fn alloc(b: &Box<T, A>) -> &A {
+        &b.1
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
+#[cfg(stage0)]
 unsafe impl<#[may_dangle] T: ?Sized> Drop for Box<T> {
     fn drop(&mut self) {
         // FIXME: Do nothing, drop is currently performed by compiler.
     }
 }
 
+#[stable(feature = "rust1", since = "1.0.0")]
+#[cfg(not(stage0))]
+unsafe impl<#[may_dangle] T: ?Sized, A: Alloc> Drop for Box<T, A> {
+    fn drop(&mut self) {
+        // FIXME: Do nothing, drop is currently performed by compiler.
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Default> Default for Box<T> {
     /// Creates a `Box<T>`, with the `Default` value for T.
@@ -661,12 +778,21 @@ impl<T: fmt::Display + ?Sized> fmt::Display for Box<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
+#[cfg(stage0)]
 impl<T: fmt::Debug + ?Sized> fmt::Debug for Box<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Debug::fmt(&**self, f)
     }
 }
 
+#[stable(feature = "rust1", since = "1.0.0")]
+#[cfg(not(stage0))]
+impl<T: fmt::Debug + ?Sized, A: Alloc> fmt::Debug for Box<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: ?Sized> fmt::Pointer for Box<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -678,6 +804,7 @@ impl<T: ?Sized> fmt::Pointer for Box<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
+#[cfg(stage0)]
 impl<T: ?Sized> Deref for Box<T> {
     type Target = T;
 
@@ -687,12 +814,31 @@ impl<T: ?Sized> Deref for Box<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
+#[cfg(not(stage0))]
+impl<T: ?Sized, A: Alloc> Deref for Box<T, A> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &**self
+    }
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+#[cfg(stage0)]
 impl<T: ?Sized> DerefMut for Box<T> {
     fn deref_mut(&mut self) -> &mut T {
         &mut **self
     }
 }
 
+#[stable(feature = "rust1", since = "1.0.0")]
+#[cfg(not(stage0))]
+impl<T: ?Sized, A: Alloc> DerefMut for Box<T, A> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut **self
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<I: Iterator + ?Sized> Iterator for Box<I> {
     type Item = I::Item;
@@ -11,12 +11,14 @@
 use core::cmp::Ordering;
 use core::fmt::Debug;
 use core::hash::{Hash, Hasher};
-use core::iter::{FromIterator, Peekable, FusedIterator};
+use core::iter::{self, FromIterator, Peekable, FusedIterator};
 use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
 use core::ops::Index;
 use core::{fmt, intrinsics, mem, ptr};
 
 use borrow::Borrow;
+use heap::{Alloc, Heap, SharedAlloc};
 use Bound::{Excluded, Included, Unbounded};
 use range::RangeArgument;
 
@@ -131,14 +133,29 @@ use self::Entry::*;
 /// let stat = player_stats.entry("attack").or_insert(100);
 /// *stat += random_stat_buff();
 /// ```
+///
+/// # Allocators
+///
+/// The nodes of a `BTreeMap` come from the global [`Heap`] unless the map is created with
+/// [`BTreeMap::new_in`]. The map keeps the allocator and hands out clones of it to the maps
+/// it creates itself, for example in `split_off` or `clone`. Nodes move between those maps,
+/// in `split_off` and `append`, and are freed by whichever map ends up with them. That is why
+/// the allocator has to implement [`SharedAlloc`]. `BTreeMap::new`, `Default` and
+/// `FromIterator` are only available for `BTreeMap<K, V>`.
+///
+/// [`Heap`]: ../../std/heap/struct.Heap.html
+/// [`SharedAlloc`]: ../../std/heap/trait.SharedAlloc.html
+/// [`BTreeMap::new_in`]: #method.new_in
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct BTreeMap<K, V> {
+pub struct BTreeMap<K, V, A: SharedAlloc = Heap> {
     root: node::Root<K, V>,
     length: usize,
+    // Only ever dropped by the `IntoIter` that `drop` turns the map into.
+    alloc: ManuallyDrop<A>,
 }
 
 #[stable(feature = "btree_drop", since = "1.7.0")]
-unsafe impl<#[may_dangle] K, #[may_dangle] V> Drop for BTreeMap<K, V> {
+unsafe impl<#[may_dangle] K, #[may_dangle] V, A: SharedAlloc> Drop for BTreeMap<K, V, A> {
     fn drop(&mut self) {
         unsafe {
             drop(ptr::read(self).into_iter());
@@ -147,19 +164,24 @@ unsafe impl<#[may_dangle] K, #[may_dangle] V> Drop for BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
-    fn clone(&self) -> BTreeMap<K, V> {
-        fn clone_subtree<K: Clone, V: Clone>(node: node::NodeRef<marker::Immut,
-                                                                 K,
-                                                                 V,
-                                                                 marker::LeafOrInternal>)
-                                             -> BTreeMap<K, V> {
+impl<K: Clone, V: Clone, A: SharedAlloc> Clone for BTreeMap<K, V, A> {
+    fn clone(&self) -> BTreeMap<K, V, A> {
+        fn clone_subtree<K, V, A>(node: node::NodeRef<marker::Immut,
+                                                      K,
+                                                      V,
+                                                      marker::LeafOrInternal>,
+                                  alloc: &A)
+                                  -> BTreeMap<K, V, A>
+            where K: Clone, V: Clone, A: SharedAlloc
+        {
 
             match node.force() {
                 Leaf(leaf) => {
+                    let mut alloc = alloc.clone();
                     let mut out_tree = BTreeMap {
-                        root: node::Root::new_leaf(),
+                        root: node::Root::new_leaf(&mut alloc),
                         length: 0,
+                        alloc: ManuallyDrop::new(alloc),
                     };
 
                     {
@@ -181,10 +203,10 @@ impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
                     out_tree
                 }
                 Internal(internal) => {
-                    let mut out_tree = clone_subtree(internal.first_edge().descend());
+                    let mut out_tree = clone_subtree(internal.first_edge().descend(), alloc);
 
                     {
-                        let mut out_node = out_tree.root.push_level();
+                        let mut out_node = out_tree.root.push_level(&mut *out_tree.alloc);
                         let mut in_edge = internal.first_edge();
                         while let Ok(kv) = in_edge.right_kv() {
                             let (k, v) = kv.into_kv();
@@ -192,13 +214,14 @@ impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
 
                             let k = (*k).clone();
                             let v = (*v).clone();
-                            let subtree = clone_subtree(in_edge.descend());
+                            let mut subtree = clone_subtree(in_edge.descend(), alloc);
 
                             // We can't destructure subtree directly
                             // because BTreeMap implements Drop
                             let (subroot, sublength) = unsafe {
                                 let root = ptr::read(&subtree.root);
                                 let length = subtree.length;
+                                ManuallyDrop::drop(&mut subtree.alloc);
                                 mem::forget(subtree);
                                 (root, length)
                             };
@@ -213,13 +236,14 @@ impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
             }
         }
 
-        clone_subtree(self.root.as_ref())
+        clone_subtree(self.root.as_ref(), &*self.alloc)
     }
 }
 
-impl<K, Q: ?Sized> super::Recover<Q> for BTreeMap<K, ()>
+impl<K, Q: ?Sized, A> super::Recover<Q> for BTreeMap<K, (), A>
     where K: Borrow<Q> + Ord,
-          Q: Ord
+          Q: Ord,
+          A: SharedAlloc
 {
     type Key = K;
 
@@ -236,6 +260,7 @@ impl<K, Q: ?Sized> super::Recover<Q> for BTreeMap<K, ()>
                 Some(OccupiedEntry {
                          handle,
                          length: &mut self.length,
+                         alloc: &mut *self.alloc,
                          _marker: PhantomData,
                      }
                      .remove_kv()
@@ -253,6 +278,7 @@ impl<K, Q: ?Sized> super::Recover<Q> for BTreeMap<K, ()>
                     key,
                     handle,
                     length: &mut self.length,
+                    alloc: &mut *self.alloc,
                     _marker: PhantomData,
                 }
                 .insert(());
@@ -304,14 +330,15 @@ pub struct IterMut<'a, K: 'a, V: 'a> {
 /// [`into_iter`]: struct.BTreeMap.html#method.into_iter
 /// [`BTreeMap`]: struct.BTreeMap.html
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct IntoIter<K, V> {
+pub struct IntoIter<K, V, A: SharedAlloc = Heap> {
     front: Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge>,
     back: Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge>,
     length: usize,
+    alloc: A,
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for IntoIter<K, V> {
+impl<K: fmt::Debug, V: fmt::Debug, A: SharedAlloc> fmt::Debug for IntoIter<K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let range = Range {
             front: self.front.reborrow(),
@@ -419,6 +446,31 @@ impl<'a, K: 'a + fmt::Debug, V: 'a + fmt::Debug> fmt::Debug for RangeMut<'a, K,
     }
 }
 
+/// An iterator produced by calling `drain_filter` on BTreeMap.
+///
+/// This `struct` is created by the [`drain_filter`] method on [`BTreeMap`]. See its
+/// documentation for more.
+///
+/// [`drain_filter`]: struct.BTreeMap.html#method.drain_filter
+/// [`BTreeMap`]: struct.BTreeMap.html
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+pub struct DrainFilter<'a, K: 'a, V: 'a, F, A: 'a + SharedAlloc = Heap>
+    where F: FnMut(&K, &mut V) -> bool
+{
+    pred: F,
+    inner: DrainFilterInner<'a, K, V, A>,
+}
+
+/// The part of `DrainFilter` which doesn't depend on the predicate, shared with `BTreeSet`.
+///
+/// The map is emptied up front and its old contents are walked in order; the pairs which are
+/// kept get pushed back onto the right edge of the map, as in `append`. The right edge is
+/// rebalanced once, when the iterator is dropped.
+pub(super) struct DrainFilterInner<'a, K: 'a, V: 'a, A: 'a + SharedAlloc = Heap> {
+    map: &'a mut BTreeMap<K, V, A>,
+    iter: IntoIter<K, V, A>,
+}
+
 /// A view into a single entry in a map, which may either be vacant or occupied.
 ///
 /// This `enum` is constructed from the [`entry`] method on [`BTreeMap`].
@@ -426,20 +478,20 @@ impl<'a, K: 'a + fmt::Debug, V: 'a + fmt::Debug> fmt::Debug for RangeMut<'a, K,
 /// [`BTreeMap`]: struct.BTreeMap.html
 /// [`entry`]: struct.BTreeMap.html#method.entry
 #[stable(feature = "rust1", since = "1.0.0")]
-pub enum Entry<'a, K: 'a, V: 'a> {
+pub enum Entry<'a, K: 'a, V: 'a, A: 'a + SharedAlloc = Heap> {
     /// A vacant entry.
     #[stable(feature = "rust1", since = "1.0.0")]
     Vacant(#[stable(feature = "rust1", since = "1.0.0")]
-           VacantEntry<'a, K, V>),
+           VacantEntry<'a, K, V, A>),
 
     /// An occupied entry.
     #[stable(feature = "rust1", since = "1.0.0")]
     Occupied(#[stable(feature = "rust1", since = "1.0.0")]
-             OccupiedEntry<'a, K, V>),
+             OccupiedEntry<'a, K, V, A>),
 }
 
 #[stable(feature= "debug_btree_map", since = "1.12.0")]
-impl<'a, K: 'a + Debug + Ord, V: 'a + Debug> Debug for Entry<'a, K, V> {
+impl<'a, K: 'a + Debug + Ord, V: 'a + Debug, A: SharedAlloc> Debug for Entry<'a, K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Vacant(ref v) => f.debug_tuple("Entry")
@@ -457,17 +509,18 @@ impl<'a, K: 'a + Debug + Ord, V: 'a + Debug> Debug for Entry<'a, K, V> {
 ///
 /// [`Entry`]: enum.Entry.html
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct VacantEntry<'a, K: 'a, V: 'a> {
+pub struct VacantEntry<'a, K: 'a, V: 'a, A: 'a + SharedAlloc = Heap> {
     key: K,
     handle: Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>,
     length: &'a mut usize,
+    alloc: &'a mut A,
 
     // Be invariant in `K` and `V`
     _marker: PhantomData<&'a mut (K, V)>,
 }
 
 #[stable(feature= "debug_btree_map", since = "1.12.0")]
-impl<'a, K: 'a + Debug + Ord, V: 'a> Debug for VacantEntry<'a, K, V> {
+impl<'a, K: 'a + Debug + Ord, V: 'a, A: SharedAlloc> Debug for VacantEntry<'a, K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_tuple("VacantEntry")
          .field(self.key())
@@ -480,17 +533,19 @@ impl<'a, K: 'a + Debug + Ord, V: 'a> Debug for VacantEntry<'a, K, V> {
 ///
 /// [`Entry`]: enum.Entry.html
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct OccupiedEntry<'a, K: 'a, V: 'a> {
+pub struct OccupiedEntry<'a, K: 'a, V: 'a, A: 'a + SharedAlloc = Heap> {
     handle: Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>,
 
     length: &'a mut usize,
+    alloc: &'a mut A,
 
     // Be invariant in `K` and `V`
     _marker: PhantomData<&'a mut (K, V)>,
 }
 
 #[stable(feature= "debug_btree_map", since = "1.12.0")]
-impl<'a, K: 'a + Debug + Ord, V: 'a + Debug> Debug for OccupiedEntry<'a, K, V> {
+impl<'a, K: 'a + Debug + Ord, V: 'a + Debug, A: SharedAlloc> Debug
+    for OccupiedEntry<'a, K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("OccupiedEntry")
          .field("key", self.key())
@@ -522,9 +577,31 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn new() -> BTreeMap<K, V> {
+        BTreeMap::new_in(Heap)
+    }
+}
+
+impl<K: Ord, V, A: SharedAlloc> BTreeMap<K, V, A> {
+    /// Makes a new empty BTreeMap whose nodes are allocated from `alloc`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::heap::Heap;
+    ///
+    /// let mut map = BTreeMap::new_in(Heap);
+    /// map.insert(1, "a");
+    /// assert_eq!(map[&1], "a");
+    /// ```
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn new_in(mut alloc: A) -> BTreeMap<K, V, A> {
         BTreeMap {
-            root: node::Root::new_leaf(),
+            root: node::Root::new_leaf(&mut alloc),
             length: 0,
+            alloc: ManuallyDrop::new(alloc),
         }
     }
 
@@ -545,7 +622,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn clear(&mut self) {
         // FIXME(gereeter) .clear() allocates
-        *self = BTreeMap::new();
+        *self = BTreeMap::new_in((*self.alloc).clone());
     }
 
     /// Returns a reference to the value corresponding to the key.
@@ -697,6 +774,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                 Some(OccupiedEntry {
                          handle,
                          length: &mut self.length,
+                         alloc: &mut *self.alloc,
                          _marker: PhantomData,
                      }
                      .remove())
@@ -747,8 +825,10 @@ impl<K: Ord, V> BTreeMap<K, V> {
         }
 
         // First, we merge `self` and `other` into a sorted sequence in linear time.
-        let self_iter = mem::replace(self, BTreeMap::new()).into_iter();
-        let other_iter = mem::replace(other, BTreeMap::new()).into_iter();
+        let self_alloc = (*self.alloc).clone();
+        let other_alloc = (*other.alloc).clone();
+        let self_iter = mem::replace(self, BTreeMap::new_in(self_alloc)).into_iter();
+        let other_iter = mem::replace(other, BTreeMap::new_in(other_alloc)).into_iter();
         let iter = MergeIter {
             left: self_iter.peekable(),
             right: other_iter.peekable(),
@@ -862,12 +942,13 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// assert_eq!(count["a"], 3);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn entry(&mut self, key: K) -> Entry<K, V> {
+    pub fn entry(&mut self, key: K) -> Entry<K, V, A> {
         match search::search_tree(self.root.as_mut(), &key) {
             Found(handle) => {
                 Occupied(OccupiedEntry {
                     handle,
                     length: &mut self.length,
+                    alloc: &mut *self.alloc,
                     _marker: PhantomData,
                 })
             }
@@ -876,12 +957,16 @@ impl<K: Ord, V> BTreeMap<K, V> {
                     key,
                     handle,
                     length: &mut self.length,
+                    alloc: &mut *self.alloc,
                     _marker: PhantomData,
                 })
             }
         }
     }
 
+}
+
+impl<K, V, A: SharedAlloc> BTreeMap<K, V, A> {
     fn from_sorted_iter<I: Iterator<Item = (K, V)>>(&mut self, iter: I) {
         let mut cur_node = last_leaf_edge(self.root.as_mut()).into_node();
         // Iterate through all key-value pairs, pushing them into nodes at the right level.
@@ -908,7 +993,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                         }
                         Err(node) => {
                             // We are at the top, create a new root node and push there.
-                            open_node = node.into_root_mut().push_level();
+                            open_node = node.into_root_mut().push_level(&mut *self.alloc);
                             break;
                         }
                     }
@@ -916,9 +1001,9 @@ impl<K: Ord, V> BTreeMap<K, V> {
 
                 // Push key-value pair and new right subtree.
                 let tree_height = open_node.height() - 1;
-                let mut right_tree = node::Root::new_leaf();
+                let mut right_tree = node::Root::new_leaf(&mut *self.alloc);
                 for _ in 0..tree_height {
-                    right_tree.push_level();
+                    right_tree.push_level(&mut *self.alloc);
                 }
                 open_node.push(key, value, right_tree);
 
@@ -951,7 +1036,9 @@ impl<K: Ord, V> BTreeMap<K, V> {
             cur_node = last_edge.descend();
         }
     }
+}
 
+impl<K: Ord, V, A: SharedAlloc> BTreeMap<K, V, A> {
     /// Splits the collection into two at the given key. Returns everything after the given key,
     /// including the key.
     ///
@@ -984,16 +1071,152 @@ impl<K: Ord, V> BTreeMap<K, V> {
     #[stable(feature = "btree_split_off", since = "1.11.0")]
     pub fn split_off<Q: ?Sized + Ord>(&mut self, key: &Q) -> Self
         where K: Borrow<Q>
+    {
+        self.split_off_at(key, true)
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all pairs `(k, v)` such that `f(&k, &mut v)` returns `false`.
+    /// The map is rebuilt in a single pass, so nodes are rebalanced in bulk rather than after
+    /// every removal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_retain)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<i32, i32> = (0..8).map(|x| (x, x * 10)).collect();
+    /// map.retain(|&k, _| k % 2 == 0);
+    /// assert!(map.into_iter().eq(vec![(0, 0), (2, 20), (4, 40), (6, 60)]));
+    /// ```
+    #[unstable(feature = "btree_retain", reason = "new API", issue = "0")]
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(&K, &mut V) -> bool
+    {
+        self.drain_filter(|k, v| !f(k, v));
+    }
+
+    /// Creates an iterator which uses a closure to determine if an element should be removed.
+    ///
+    /// If the closure returns true, the element is removed from the map and yielded.
+    /// If the closure returns false, the element remains in the map and will not be yielded
+    /// by the iterator.
+    ///
+    /// Elements are visited in ascending key order. If the iterator is dropped before being
+    /// fully consumed, the rest of the elements are still filtered.
+    ///
+    /// If the closure panics, the pair it was called on is dropped. A panic from `next` still
+    /// drops the iterator, which goes on calling the closure on the remaining elements while
+    /// unwinding. A panic while the iterator is being dropped leaves the remaining elements
+    /// in the map.
+    ///
+    /// Note that `drain_filter` lets you mutate every value in the filter closure, regardless
+    /// of whether you choose to keep or remove it.
+    ///
+    /// # Examples
+    ///
+    /// Splitting a map into even and odd keys, reusing the original map:
+    ///
+    /// ```
+    /// #![feature(drain_filter)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<i32, i32> = (0..8).map(|x| (x, x)).collect();
+    /// let evens: BTreeMap<_, _> = map.drain_filter(|k, _v| k % 2 == 0).collect();
+    /// let odds = map;
+    /// assert_eq!(evens.keys().cloned().collect::<Vec<_>>(), vec![0, 2, 4, 6]);
+    /// assert_eq!(odds.keys().cloned().collect::<Vec<_>>(), vec![1, 3, 5, 7]);
+    /// ```
+    #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<K, V, F, A>
+        where F: FnMut(&K, &mut V) -> bool
+    {
+        DrainFilter {
+            pred,
+            inner: self.drain_filter_inner(),
+        }
+    }
+
+    pub(super) fn drain_filter_inner(&mut self) -> DrainFilterInner<K, V, A> {
+        let alloc = (*self.alloc).clone();
+        let iter = mem::replace(self, BTreeMap::new_in(alloc)).into_iter();
+        DrainFilterInner {
+            map: self,
+            iter,
+        }
+    }
+
+    /// Removes all elements whose keys fall within `range`, returning them as a new map.
+    ///
+    /// The range is given in the same way as for [`range`]. The removed part is cut out of
+    /// the tree at both ends, and the elements after it are moved back onto the remaining
+    /// tree in bulk, without comparing keys.
+    ///
+    /// [`range`]: #method.range
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_remove_range)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<i32, &str> = (1..7).zip(vec!["a", "b", "c", "d", "e", "f"])
+    ///                                         .collect();
+    ///
+    /// let removed = map.remove_range(2..5);
+    /// assert_eq!(removed.keys().cloned().collect::<Vec<_>>(), vec![2, 3, 4]);
+    /// assert_eq!(map.keys().cloned().collect::<Vec<_>>(), vec![1, 5, 6]);
+    ///
+    /// map.remove_range(..);
+    /// assert!(map.is_empty());
+    /// ```
+    #[unstable(feature = "btree_remove_range", reason = "new API", issue = "0")]
+    pub fn remove_range<T: ?Sized, R>(&mut self, range: R) -> Self
+        where T: Ord, K: Borrow<T>, R: RangeArgument<T>
+    {
+        check_range(&range);
+
+        let mut removed = match range.start() {
+            Included(key) => self.split_off_at(key, true),
+            Excluded(key) => self.split_off_at(key, false),
+            Unbounded => {
+                let alloc = (*self.alloc).clone();
+                mem::replace(self, BTreeMap::new_in(alloc))
+            }
+        };
+        let rest = match range.end() {
+            Included(key) => removed.split_off_at(key, false),
+            Excluded(key) => removed.split_off_at(key, true),
+            Unbounded => return removed,
+        };
+
+        // Everything in `rest` is greater than everything left in `self`.
+        self.from_sorted_iter(rest.into_iter());
+        self.fix_right_edge();
+        removed
+    }
+
+    /// Like `split_off`, but if `key_goes_right` is false an element equal to `key` stays in
+    /// `self`.
+    fn split_off_at<Q: ?Sized + Ord>(&mut self, key: &Q, key_goes_right: bool) -> Self
+        where K: Borrow<Q>
     {
         if self.is_empty() {
-            return Self::new();
+            return Self::new_in((*self.alloc).clone());
         }
 
         let total_num = self.len();
 
-        let mut right = Self::new();
+        let mut right = Self::new_in((*self.alloc).clone());
         for _ in 0..(self.root.as_ref().height()) {
-            right.root.push_level();
+            right.root.push_level(&mut *right.alloc);
         }
 
         {
@@ -1002,8 +1225,13 @@ impl<K: Ord, V> BTreeMap<K, V> {
 
             loop {
                 let mut split_edge = match search::search_node(left_node, key) {
-                    // key is going to the right tree
-                    Found(handle) => handle.left_edge(),
+                    Found(handle) => {
+                        if key_goes_right {
+                            handle.left_edge()
+                        } else {
+                            handle.right_edge()
+                        }
+                    }
                     GoDown(handle) => handle,
                 };
 
@@ -1073,7 +1301,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                     break;
                 }
             }
-            self.root.pop_level();
+            self.root.pop_level(&mut *self.alloc);
         }
     }
 
@@ -1087,7 +1315,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                 let mut last_kv = node.last_kv();
 
                 if last_kv.can_merge() {
-                    cur_node = last_kv.merge().descend();
+                    cur_node = last_kv.merge(&mut *self.alloc).descend();
                 } else {
                     let right_len = last_kv.reborrow().right_edge().descend().len();
                     // `MINLEN + 1` to avoid readjust if merge happens on the next level.
@@ -1113,7 +1341,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
                 let mut first_kv = node.first_kv();
 
                 if first_kv.can_merge() {
-                    cur_node = first_kv.merge().descend();
+                    cur_node = first_kv.merge(&mut *self.alloc).descend();
                 } else {
                     let left_len = first_kv.reborrow().left_edge().descend().len();
                     if left_len < node::MIN_LEN + 1 {
@@ -1129,7 +1357,7 @@ impl<K: Ord, V> BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, K: 'a, V: 'a> IntoIterator for &'a BTreeMap<K, V> {
+impl<'a, K: 'a, V: 'a, A: SharedAlloc> IntoIterator for &'a BTreeMap<K, V, A> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
 
@@ -1189,7 +1417,7 @@ impl<'a, K, V> Clone for Iter<'a, K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, K: 'a, V: 'a> IntoIterator for &'a mut BTreeMap<K, V> {
+impl<'a, K: 'a, V: 'a, A: SharedAlloc> IntoIterator for &'a mut BTreeMap<K, V, A> {
     type Item = (&'a K, &'a mut V);
     type IntoIter = IterMut<'a, K, V>;
 
@@ -1239,34 +1467,36 @@ impl<'a, K: 'a, V: 'a> ExactSizeIterator for IterMut<'a, K, V> {
 impl<'a, K, V> FusedIterator for IterMut<'a, K, V> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> IntoIterator for BTreeMap<K, V> {
+impl<K, V, A: SharedAlloc> IntoIterator for BTreeMap<K, V, A> {
     type Item = (K, V);
-    type IntoIter = IntoIter<K, V>;
+    type IntoIter = IntoIter<K, V, A>;
 
-    fn into_iter(self) -> IntoIter<K, V> {
+    fn into_iter(self) -> IntoIter<K, V, A> {
         let root1 = unsafe { ptr::read(&self.root).into_ref() };
         let root2 = unsafe { ptr::read(&self.root).into_ref() };
         let len = self.length;
+        let alloc = unsafe { ManuallyDrop::into_inner(ptr::read(&self.alloc)) };
         mem::forget(self);
 
         IntoIter {
             front: first_leaf_edge(root1),
             back: last_leaf_edge(root2),
             length: len,
+            alloc,
         }
     }
 }
 
 #[stable(feature = "btree_drop", since = "1.7.0")]
-impl<K, V> Drop for IntoIter<K, V> {
+impl<K, V, A: SharedAlloc> Drop for IntoIter<K, V, A> {
     fn drop(&mut self) {
         for _ in &mut *self {
         }
         unsafe {
             let leaf_node = ptr::read(&self.front).into_node();
-            if let Some(first_parent) = leaf_node.deallocate_and_ascend() {
+            if let Some(first_parent) = leaf_node.deallocate_and_ascend(&mut self.alloc) {
                 let mut cur_node = first_parent.into_node();
-                while let Some(parent) = cur_node.deallocate_and_ascend() {
+                while let Some(parent) = cur_node.deallocate_and_ascend(&mut self.alloc) {
                     cur_node = parent.into_node()
                 }
             }
@@ -1275,7 +1505,7 @@ impl<K, V> Drop for IntoIter<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> Iterator for IntoIter<K, V> {
+impl<K, V, A: SharedAlloc> Iterator for IntoIter<K, V, A> {
     type Item = (K, V);
 
     fn next(&mut self) -> Option<(K, V)> {
@@ -1295,7 +1525,7 @@ impl<K, V> Iterator for IntoIter<K, V> {
                 return Some((k, v));
             }
             Err(last_edge) => unsafe {
-                unwrap_unchecked(last_edge.into_node().deallocate_and_ascend())
+                unwrap_unchecked(last_edge.into_node().deallocate_and_ascend(&mut self.alloc))
             },
         };
 
@@ -1308,7 +1538,8 @@ impl<K, V> Iterator for IntoIter<K, V> {
                     return Some((k, v));
                 }
                 Err(last_edge) => unsafe {
-                    cur_handle = unwrap_unchecked(last_edge.into_node().deallocate_and_ascend());
+                    let parent = last_edge.into_node().deallocate_and_ascend(&mut self.alloc);
+                    cur_handle = unwrap_unchecked(parent);
                 },
             }
         }
@@ -1320,7 +1551,7 @@ impl<K, V> Iterator for IntoIter<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+impl<K, V, A: SharedAlloc> DoubleEndedIterator for IntoIter<K, V, A> {
     fn next_back(&mut self) -> Option<(K, V)> {
         if self.length == 0 {
             return None;
@@ -1338,7 +1569,7 @@ impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
                 return Some((k, v));
             }
             Err(last_edge) => unsafe {
-                unwrap_unchecked(last_edge.into_node().deallocate_and_ascend())
+                unwrap_unchecked(last_edge.into_node().deallocate_and_ascend(&mut self.alloc))
             },
         };
 
@@ -1351,7 +1582,8 @@ impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
                     return Some((k, v));
                 }
                 Err(last_edge) => unsafe {
-                    cur_handle = unwrap_unchecked(last_edge.into_node().deallocate_and_ascend());
+                    let parent = last_edge.into_node().deallocate_and_ascend(&mut self.alloc);
+                    cur_handle = unwrap_unchecked(parent);
                 },
             }
         }
@@ -1359,14 +1591,14 @@ impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V> ExactSizeIterator for IntoIter<K, V> {
+impl<K, V, A: SharedAlloc> ExactSizeIterator for IntoIter<K, V, A> {
     fn len(&self) -> usize {
         self.length
     }
 }
 
 #[unstable(feature = "fused", issue = "35602")]
-impl<K, V> FusedIterator for IntoIter<K, V> {}
+impl<K, V, A: SharedAlloc> FusedIterator for IntoIter<K, V, A> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a, K, V> Iterator for Keys<'a, K, V> {
@@ -1665,6 +1897,72 @@ impl<'a, K, V> RangeMut<'a, K, V> {
     }
 }
 
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, K, V, F, A> Iterator for DrainFilter<'a, K, V, F, A>
+    where F: FnMut(&K, &mut V) -> bool, A: SharedAlloc
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        self.inner.next(&mut self.pred)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, K, V, F, A> Drop for DrainFilter<'a, K, V, F, A>
+    where F: FnMut(&K, &mut V) -> bool, A: SharedAlloc
+{
+    fn drop(&mut self) {
+        for _ in self.by_ref() { }
+    }
+}
+
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, K, V, F, A> FusedIterator for DrainFilter<'a, K, V, F, A>
+    where F: FnMut(&K, &mut V) -> bool, A: SharedAlloc
+{}
+
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, K, V, F, A> fmt::Debug for DrainFilter<'a, K, V, F, A>
+    where F: FnMut(&K, &mut V) -> bool, A: SharedAlloc
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("DrainFilter { .. }")
+    }
+}
+
+impl<'a, K: 'a, V: 'a, A: SharedAlloc> DrainFilterInner<'a, K, V, A> {
+    pub(super) fn next<F>(&mut self, pred: &mut F) -> Option<(K, V)>
+        where F: FnMut(&K, &mut V) -> bool
+    {
+        while let Some((k, mut v)) = self.iter.next() {
+            if pred(&k, &mut v) {
+                return Some((k, v));
+            }
+            self.map.from_sorted_iter(iter::once((k, v)));
+        }
+        None
+    }
+
+    pub(super) fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.iter.len()))
+    }
+}
+
+impl<'a, K: 'a, V: 'a, A: SharedAlloc> Drop for DrainFilterInner<'a, K, V, A> {
+    fn drop(&mut self) {
+        // `DrainFilter` has already run the predicate over everything, so `iter` is only
+        // non-empty if the predicate panicked while `DrainFilter` was being dropped. The pair
+        // it panicked on is gone, dropped in `next`; the ones after it are kept.
+        self.map.from_sorted_iter(&mut self.iter);
+        self.map.fix_right_edge();
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<K: Ord, V> FromIterator<(K, V)> for BTreeMap<K, V> {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> BTreeMap<K, V> {
@@ -1675,7 +1973,7 @@ impl<K: Ord, V> FromIterator<(K, V)> for BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, V> Extend<(K, V)> for BTreeMap<K, V> {
+impl<K: Ord, V, A: SharedAlloc> Extend<(K, V)> for BTreeMap<K, V, A> {
     #[inline]
     fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
         for (k, v) in iter {
@@ -1685,14 +1983,15 @@ impl<K: Ord, V> Extend<(K, V)> for BTreeMap<K, V> {
 }
 
 #[stable(feature = "extend_ref", since = "1.2.0")]
-impl<'a, K: Ord + Copy, V: Copy> Extend<(&'a K, &'a V)> for BTreeMap<K, V> {
+impl<'a, K: Ord + Copy, V: Copy, A: SharedAlloc> Extend<(&'a K, &'a V)>
+    for BTreeMap<K, V, A> {
     fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
         self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Hash, V: Hash> Hash for BTreeMap<K, V> {
+impl<K: Hash, V: Hash, A: SharedAlloc> Hash for BTreeMap<K, V, A> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         for elt in self {
             elt.hash(state);
@@ -1709,42 +2008,43 @@ impl<K: Ord, V> Default for BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: PartialEq, V: PartialEq> PartialEq for BTreeMap<K, V> {
-    fn eq(&self, other: &BTreeMap<K, V>) -> bool {
+impl<K: PartialEq, V: PartialEq, A: SharedAlloc> PartialEq for BTreeMap<K, V, A> {
+    fn eq(&self, other: &BTreeMap<K, V, A>) -> bool {
         self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Eq, V: Eq> Eq for BTreeMap<K, V> {}
+impl<K: Eq, V: Eq, A: SharedAlloc> Eq for BTreeMap<K, V, A> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: PartialOrd, V: PartialOrd> PartialOrd for BTreeMap<K, V> {
+impl<K: PartialOrd, V: PartialOrd, A: SharedAlloc> PartialOrd for BTreeMap<K, V, A> {
     #[inline]
-    fn partial_cmp(&self, other: &BTreeMap<K, V>) -> Option<Ordering> {
+    fn partial_cmp(&self, other: &BTreeMap<K, V, A>) -> Option<Ordering> {
         self.iter().partial_cmp(other.iter())
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, V: Ord> Ord for BTreeMap<K, V> {
+impl<K: Ord, V: Ord, A: SharedAlloc> Ord for BTreeMap<K, V, A> {
     #[inline]
-    fn cmp(&self, other: &BTreeMap<K, V>) -> Ordering {
+    fn cmp(&self, other: &BTreeMap<K, V, A>) -> Ordering {
         self.iter().cmp(other.iter())
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Debug, V: Debug> Debug for BTreeMap<K, V> {
+impl<K: Debug, V: Debug, A: SharedAlloc> Debug for BTreeMap<K, V, A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, K: Ord, Q: ?Sized, V> Index<&'a Q> for BTreeMap<K, V>
+impl<'a, K: Ord, Q: ?Sized, V, A> Index<&'a Q> for BTreeMap<K, V, A>
     where K: Borrow<Q>,
-          Q: Ord
+          Q: Ord,
+          A: SharedAlloc
 {
     type Output = V;
 
@@ -1785,14 +2085,7 @@ fn last_leaf_edge<BorrowType, K, V>
     }
 }
 
-fn range_search<BorrowType, K, V, Q: ?Sized, R: RangeArgument<Q>>(
-    root1: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
-    root2: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
-    range: R
-)-> (Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>,
-     Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>)
-        where Q: Ord, K: Borrow<Q>
-{
+fn check_range<Q: ?Sized + Ord, R: RangeArgument<Q>>(range: &R) {
     match (range.start(), range.end()) {
         (Excluded(s), Excluded(e)) if s==e =>
             panic!("range start and end are equal and excluded in BTreeMap"),
@@ -1803,6 +2096,17 @@ fn range_search<BorrowType, K, V, Q: ?Sized, R: RangeArgument<Q>>(
             panic!("range start is greater than range end in BTreeMap"),
         _ => {},
     };
+}
+
+fn range_search<BorrowType, K, V, Q: ?Sized, R: RangeArgument<Q>>(
+    root1: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
+    root2: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
+    range: R
+)-> (Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>,
+     Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>)
+        where Q: Ord, K: Borrow<Q>
+{
+    check_range(&range);
 
     let mut min_node = root1;
     let mut max_node = root2;
@@ -1870,7 +2174,7 @@ unsafe fn unwrap_unchecked<T>(val: Option<T>) -> T {
     })
 }
 
-impl<K, V> BTreeMap<K, V> {
+impl<K, V, A: SharedAlloc> BTreeMap<K, V, A> {
     /// Gets an iterator over the entries of the map, sorted by key.
     ///
     /// # Examples
@@ -2045,7 +2349,7 @@ impl<K, V> BTreeMap<K, V> {
     }
 }
 
-impl<'a, K: Ord, V> Entry<'a, K, V> {
+impl<'a, K: Ord, V, A: SharedAlloc> Entry<'a, K, V, A> {
     /// Ensures a value is in the entry by inserting the default if empty, and returns
     /// a mutable reference to the value in the entry.
     ///
@@ -2142,7 +2446,7 @@ impl<'a, K: Ord, V> Entry<'a, K, V> {
     }
 }
 
-impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
+impl<'a, K: Ord, V: Default, A: SharedAlloc> Entry<'a, K, V, A> {
     #[unstable(feature = "entry_or_default", issue = "44324")]
     /// Ensures a value is in the entry by inserting the default value if empty,
     /// and returns a mutable reference to the value in the entry.
@@ -2169,7 +2473,7 @@ impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
 
 }
 
-impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+impl<'a, K: Ord, V, A: SharedAlloc> VacantEntry<'a, K, V, A> {
     /// Gets a reference to the key that would be used when inserting a value
     /// through the VacantEntry.
     ///
@@ -2232,7 +2536,7 @@ impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
         let mut ins_v;
         let mut ins_edge;
 
-        let mut cur_parent = match self.handle.insert(self.key, value) {
+        let mut cur_parent = match self.handle.insert(self.key, value, &mut *self.alloc) {
             (Fit(handle), _) => return handle.into_kv_mut().1,
             (Split(left, k, v, right), ptr) => {
                 ins_k = k;
@@ -2246,7 +2550,7 @@ impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
         loop {
             match cur_parent {
                 Ok(parent) => {
-                    match parent.insert(ins_k, ins_v, ins_edge) {
+                    match parent.insert(ins_k, ins_v, ins_edge, &mut *self.alloc) {
                         Fit(_) => return unsafe { &mut *out_ptr },
                         Split(left, k, v, right) => {
                             ins_k = k;
@@ -2257,7 +2561,7 @@ impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
                     }
                 }
                 Err(root) => {
-                    root.push_level().push(ins_k, ins_v, ins_edge);
+                    root.push_level(&mut *self.alloc).push(ins_k, ins_v, ins_edge);
                     return unsafe { &mut *out_ptr };
                 }
             }
@@ -2265,7 +2569,7 @@ impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
     }
 }
 
-impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
+impl<'a, K: Ord, V, A: SharedAlloc> OccupiedEntry<'a, K, V, A> {
     /// Gets a reference to the key in the entry.
     ///
     /// # Examples
@@ -2441,13 +2745,13 @@ impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
         // Handle underflow
         let mut cur_node = small_leaf.forget_type();
         while cur_node.len() < node::CAPACITY / 2 {
-            match handle_underfull_node(cur_node) {
+            match handle_underfull_node(cur_node, &mut *self.alloc) {
                 AtRoot => break,
                 EmptyParent(_) => unreachable!(),
                 Merged(parent) => {
                     if parent.len() == 0 {
                         // We must be at the root
-                        parent.into_root_mut().pop_level();
+                        parent.into_root_mut().pop_level(&mut *self.alloc);
                         break;
                     } else {
                         cur_node = parent.forget_type();
@@ -2468,8 +2772,11 @@ enum UnderflowResult<'a, K, V> {
     Stole(NodeRef<marker::Mut<'a>, K, V, marker::Internal>),
 }
 
-fn handle_underfull_node<'a, K, V>(node: NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>)
-                                   -> UnderflowResult<'a, K, V> {
+fn handle_underfull_node<'a, K, V, A>(node: NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
+                                      alloc: &mut A)
+                                      -> UnderflowResult<'a, K, V>
+    where A: Alloc
+{
     let parent = if let Ok(parent) = node.ascend() {
         parent
     } else {
@@ -2489,7 +2796,7 @@ fn handle_underfull_node<'a, K, V>(node: NodeRef<marker::Mut<'a>, K, V, marker::
     };
 
     if handle.can_merge() {
-        Merged(handle.merge().into_node())
+        Merged(handle.merge(alloc).into_node())
     } else {
         if is_left {
             handle.steal_left();
@@ -121,6 +121,21 @@ pub struct Range<'a, T: 'a> {
     iter: ::btree_map::Range<'a, T, ()>,
 }
 
+/// An iterator produced by calling `drain_filter` on BTreeSet.
+///
+/// This `struct` is created by the [`drain_filter`] method on [`BTreeSet`].
+/// See its documentation for more.
+///
+/// [`BTreeSet`]: struct.BTreeSet.html
+/// [`drain_filter`]: struct.BTreeSet.html#method.drain_filter
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+pub struct DrainFilter<'a, T: 'a, F>
+    where F: FnMut(&T) -> bool
+{
+    pred: F,
+    inner: super::map::DrainFilterInner<'a, T, ()>,
+}
+
 /// A lazy iterator producing elements in the difference of `BTreeSet`s.
 ///
 /// This `struct` is created by the [`difference`] method on [`BTreeSet`].
@@ -683,6 +698,92 @@ impl<T: Ord> BTreeSet<T> {
     pub fn split_off<Q: ?Sized + Ord>(&mut self, key: &Q) -> Self where T: Borrow<Q> {
         BTreeSet { map: self.map.split_off(key) }
     }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` such that `f(&e)` returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_retain)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut set: BTreeSet<i32> = (0..8).collect();
+    /// set.retain(|&k| k % 2 == 0);
+    /// assert!(set.into_iter().eq(vec![0, 2, 4, 6]));
+    /// ```
+    #[unstable(feature = "btree_retain", reason = "new API", issue = "0")]
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(&T) -> bool
+    {
+        self.drain_filter(|v| !f(v));
+    }
+
+    /// Creates an iterator which uses a closure to determine if a value should be removed.
+    ///
+    /// If the closure returns true, the value is removed from the set and yielded.
+    /// If the closure returns false, the value remains in the set and will not be yielded
+    /// by the iterator.
+    ///
+    /// Values are visited in ascending order. If the iterator is dropped before being fully
+    /// consumed, the rest of the values are still filtered.
+    ///
+    /// # Examples
+    ///
+    /// Splitting a set into even and odd values, reusing the original set:
+    ///
+    /// ```
+    /// #![feature(drain_filter)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut set: BTreeSet<i32> = (0..8).collect();
+    /// let evens: BTreeSet<_> = set.drain_filter(|v| v % 2 == 0).collect();
+    /// let odds = set;
+    /// assert_eq!(evens.into_iter().collect::<Vec<_>>(), vec![0, 2, 4, 6]);
+    /// assert_eq!(odds.into_iter().collect::<Vec<_>>(), vec![1, 3, 5, 7]);
+    /// ```
+    #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<T, F>
+        where F: FnMut(&T) -> bool
+    {
+        DrainFilter {
+            pred,
+            inner: self.map.drain_filter_inner(),
+        }
+    }
+
+    /// Removes all values within `range`, returning them as a new set.
+    ///
+    /// The range is given in the same way as for [`range`]. The removed part is cut out of
+    /// the tree at both ends, and the values after it are moved back onto the remaining
+    /// tree in bulk, without comparing them.
+    ///
+    /// [`range`]: #method.range
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_remove_range)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut set: BTreeSet<i32> = (1..7).collect();
+    ///
+    /// let removed = set.remove_range(2..5);
+    /// assert_eq!(removed.into_iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+    /// assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![1, 5, 6]);
+    /// ```
+    #[unstable(feature = "btree_remove_range", reason = "new API", issue = "0")]
+    pub fn remove_range<K: ?Sized, R>(&mut self, range: R) -> Self
+        where K: Ord, T: Borrow<K>, R: RangeArgument<K>
+    {
+        BTreeSet { map: self.map.remove_range(range) }
+    }
 }
 
 impl<T> BTreeSet<T> {
@@ -1000,6 +1101,45 @@ impl<'a, T> DoubleEndedIterator for Range<'a, T> {
 #[unstable(feature = "fused", issue = "35602")]
 impl<'a, T> FusedIterator for Range<'a, T> {}
 
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, T, F> Iterator for DrainFilter<'a, T, F>
+    where F: FnMut(&T) -> bool
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let pred = &mut self.pred;
+        self.inner.next(&mut |k: &T, _: &mut ()| pred(k)).map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, T, F> Drop for DrainFilter<'a, T, F>
+    where F: FnMut(&T) -> bool
+{
+    fn drop(&mut self) {
+        for _ in self.by_ref() { }
+    }
+}
+
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, T, F> FusedIterator for DrainFilter<'a, T, F>
+    where F: FnMut(&T) -> bool
+{}
+
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, T, F> fmt::Debug for DrainFilter<'a, T, F>
+    where F: FnMut(&T) -> bool
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("DrainFilter { .. }")
+    }
+}
+
 /// Compare `x` and `y`, but return `short` if x is None and `long` if y is None
 fn cmp_opt<T: Ord>(x: Option<&T>, y: Option<&T>, short: Ordering, long: Ordering) -> Ordering {
     match (x, y) {
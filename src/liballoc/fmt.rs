@@ -87,6 +87,24 @@
 //! arguments which have names. Like with positional parameters, it is not
 //! valid to provide named parameters that are unused by the format string.
 //!
+//! With the unstable `format_args_capture` feature, a name which is not given
+//! as an argument is taken from the scope the format string was written in,
+//! as if `name = name` had been passed. This also applies to names used for
+//! [width](#width) and [precision](#precision):
+//!
+//! ```
+//! #![feature(format_args_capture)]
+//!
+//! let name = "world";
+//! let width = 8;
+//! assert_eq!(format!("Hello {name}!"), "Hello world!");
+//! assert_eq!(format!("[{name:>width$}]"), "[   world]");
+//! ```
+//!
+//! Identifiers are only captured from a string literal written directly in
+//! the macro invocation, not from one produced by another macro such as
+//! [`concat!`].
+//!
 //! ## Argument types
 //!
 //! Each argument's type is dictated by the format string.
@@ -494,6 +512,7 @@
 //! [`write!`]: ../../std/macro.write.html
 //! [`Debug`]: trait.Debug.html
 //! [`format!`]: ../../std/macro.format.html
+//! [`concat!`]: ../../std/macro.concat.html
 //! [`writeln!`]: ../../std/macro.writeln.html
 //! [`write_fmt`]: ../../std/io/trait.Write.html#method.write_fmt
 //! [`std::io::Write`]: ../../std/io/trait.Write.html
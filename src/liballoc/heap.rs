@@ -17,6 +17,8 @@
 
 use core::intrinsics::{min_align_of_val, size_of_val};
 use core::mem::{self, ManuallyDrop};
+#[cfg(not(stage0))]
+use core::ptr::Unique;
 use core::usize;
 
 pub use allocator::*;
@@ -228,6 +230,8 @@ unsafe impl Alloc for Heap {
     }
 }
 
+unsafe impl SharedAlloc for Heap {}
+
 /// An arbitrary non-null address to represent zero-size allocations.
 ///
 /// This preserves the non-null invariant for types like `Box<T>`. The address
@@ -252,15 +256,31 @@ unsafe fn exchange_malloc(size: usize, align: usize) -> *mut u8 {
     }
 }
 
+#[cfg(stage0)]
 #[cfg_attr(not(test), lang = "box_free")]
 #[inline]
 pub(crate) unsafe fn box_free<T: ?Sized>(ptr: *mut T) {
+    dealloc_box(ptr, Heap)
+}
+
+/// Frees the memory of a `Box<T, A>` whose contents have been dropped.
+///
+/// The compiler passes in the fields of the box: its pointer and its allocator.
+#[cfg(not(stage0))]
+#[cfg_attr(not(test), lang = "box_free")]
+#[inline]
+pub(crate) unsafe fn box_free<T: ?Sized, A: Alloc>(ptr: Unique<T>, alloc: A) {
+    dealloc_box(ptr.as_ptr(), alloc)
+}
+
+#[inline]
+pub(crate) unsafe fn dealloc_box<T: ?Sized, A: Alloc>(ptr: *mut T, mut alloc: A) {
     let size = size_of_val(&*ptr);
     let align = min_align_of_val(&*ptr);
     // We do not allocate for Box<T> when T is ZST, so deallocation is also not necessary.
     if size != 0 {
         let layout = Layout::from_size_align_unchecked(size, align);
-        Heap.dealloc(ptr as *mut u8, layout);
+        alloc.dealloc(ptr as *mut u8, layout);
     }
 }
 
@@ -259,7 +259,7 @@ use core::ops::CoerceUnsized;
 use core::ptr::{self, NonNull};
 use core::convert::From;
 
-use heap::{Heap, Alloc, Layout, box_free};
+use heap::{Heap, Alloc, Layout, dealloc_box};
 use string::String;
 use vec::Vec;
 
@@ -693,7 +693,7 @@ impl<T: ?Sized> Rc<T> {
                 value_size);
 
             // Free the allocation without dropping its contents
-            box_free(bptr);
+            dealloc_box(bptr, Heap);
 
             Rc { ptr: NonNull::new_unchecked(ptr), phantom: PhantomData }
         }
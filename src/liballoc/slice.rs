@@ -105,6 +105,7 @@ use core::slice as core_slice;
 
 use borrow::{Borrow, BorrowMut, ToOwned};
 use boxed::Box;
+use heap::Alloc;
 use vec::Vec;
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -1906,14 +1907,14 @@ impl<T: Clone, V: Borrow<[T]>> SliceConcatExt<T> for [V] {
 ////////////////////////////////////////////////////////////////////////////////
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> Borrow<[T]> for Vec<T> {
+impl<T, A: Alloc> Borrow<[T]> for Vec<T, A> {
     fn borrow(&self) -> &[T] {
         &self[..]
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> BorrowMut<[T]> for Vec<T> {
+impl<T, A: Alloc> BorrowMut<[T]> for Vec<T, A> {
     fn borrow_mut(&mut self) -> &mut [T] {
         &mut self[..]
     }
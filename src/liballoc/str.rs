@@ -41,7 +41,7 @@
 use core::fmt;
 use core::str as core_str;
 use core::str::pattern::Pattern;
-use core::str::pattern::{Searcher, ReverseSearcher, DoubleEndedSearcher};
+use core::str::pattern::{Searcher, SearchStep, ReverseSearcher, DoubleEndedSearcher};
 use core::mem;
 use core::iter::FusedIterator;
 use std_unicode::str::{UnicodeStr, Utf16Encoder};
@@ -2217,3 +2217,396 @@ impl str {
 pub unsafe fn from_boxed_utf8_unchecked(v: Box<[u8]>) -> Box<str> {
     Box::from_raw(Box::into_raw(v) as *mut str)
 }
+
+/// A set of substrings which is compiled once and can then be searched for
+/// in any number of haystacks.
+///
+/// `&Substrings` is a pattern which matches like the slice of needles it was
+/// created from: a match is found at the leftmost position where any of the
+/// needles matches, and if several of them match there, the one which comes
+/// first wins. Searching from the back finds the rightmost end position
+/// instead, with the same tie-breaking. Empty needles are handled like the
+/// pattern `""`, except that no empty match is reported where another match
+/// has just ended.
+///
+/// A `&[&str]` pattern builds a small automaton for every search, and falls
+/// back to comparing each needle in turn if there are too many needles. A
+/// `Substrings` instead builds Aho-Corasick automata for any number of
+/// needles up front, so that every search takes a single pass over the
+/// haystack.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(str_substrings)]
+/// use std::str::Substrings;
+///
+/// let keywords = Substrings::new(&["error", "warning", "panicked"]);
+/// let log = "warning: unused variable\nthread 'main' panicked at 'oops'";
+///
+/// assert_eq!(log.find(&keywords), Some(0));
+/// assert_eq!(log.rfind(&keywords), Some(39));
+/// assert_eq!(log.matches(&keywords).collect::<Vec<_>>(), ["warning", "panicked"]);
+/// assert_eq!(log.replace(&keywords, "*"), "*: unused variable\nthread 'main' * at 'oops'");
+/// ```
+#[unstable(feature = "str_substrings", issue = "0")]
+#[derive(Clone)]
+pub struct Substrings {
+    needles: Vec<String>,
+    /// Automata for the needles and for the reversed needles, used when
+    /// searching forwards and backwards respectively.
+    forward: Automaton,
+    backward: Automaton,
+}
+
+impl Substrings {
+    /// Compiles `needles` for searching.
+    #[unstable(feature = "str_substrings", issue = "0")]
+    pub fn new(needles: &[&str]) -> Substrings {
+        let needles: Vec<String> = needles.iter().map(|&needle| needle.to_owned()).collect();
+        Substrings {
+            forward: Automaton::new(&needles, false),
+            backward: Automaton::new(&needles, true),
+            needles,
+        }
+    }
+
+    /// Returns the needles, in the order they were given.
+    #[unstable(feature = "str_substrings", issue = "0")]
+    pub fn needles(&self) -> &[String] {
+        &self.needles
+    }
+}
+
+#[unstable(feature = "str_substrings", issue = "0")]
+impl fmt::Debug for Substrings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Substrings")
+            .field("needles", &self.needles)
+            .finish()
+    }
+}
+
+/// Marks a missing transition while the trie of an `Automaton` is built.
+const NO_STATE: usize = !0;
+
+/// An Aho-Corasick automaton over the bytes of a set of needles, which are
+/// read either front to back or back to front. Empty needles are only
+/// recorded in the root state and never take part in `find`.
+///
+/// State 0 is the root. The transitions are stored as a table with a row for
+/// every state, so following one costs a single lookup.
+#[derive(Clone)]
+struct Automaton {
+    /// Maps every byte to its column in `next`. All bytes which occur in no
+    /// needle share one column.
+    classes: [u8; 256],
+    num_classes: usize,
+    /// `next[state * num_classes + class]` is the state for the longest
+    /// suffix of the state's prefix, followed by a byte of `class`, which is
+    /// a prefix of some needle.
+    next: Vec<usize>,
+    states: Vec<AcState>,
+}
+
+#[derive(Clone, Copy)]
+struct AcState {
+    /// The length of the prefix this state stands for.
+    depth: usize,
+    /// The first needle which is equal to this state's prefix.
+    exact: Option<usize>,
+    /// The length and index of the longest non-empty needle which is a
+    /// suffix of this state's prefix, the first one listed on ties.
+    out: Option<(usize, usize)>,
+}
+
+impl Automaton {
+    fn new(needles: &[String], reverse: bool) -> Automaton {
+        let mut used = [false; 256];
+        for needle in needles {
+            for &byte in needle.as_bytes() {
+                used[byte as usize] = true;
+            }
+        }
+        let mut classes = [0; 256];
+        let mut num_classes = 0;
+        for byte in 0..256 {
+            if used[byte] {
+                classes[byte] = num_classes as u8;
+                num_classes += 1;
+            }
+        }
+        if num_classes < 256 {
+            for byte in 0..256 {
+                if !used[byte] {
+                    classes[byte] = num_classes as u8;
+                }
+            }
+            num_classes += 1;
+        }
+
+        let root = AcState { depth: 0, exact: None, out: None };
+        let mut ac = Automaton {
+            classes,
+            num_classes,
+            next: vec![NO_STATE; num_classes],
+            states: vec![root],
+        };
+
+        // Build the trie of the needles.
+        for (i, needle) in needles.iter().enumerate() {
+            let bytes = needle.as_bytes();
+            let mut state = 0;
+            for depth in 0..bytes.len() {
+                let byte = if reverse { bytes[bytes.len() - 1 - depth] } else { bytes[depth] };
+                let index = ac.index(state, byte);
+                state = match ac.next[index] {
+                    NO_STATE => {
+                        let child = ac.states.len();
+                        ac.next[index] = child;
+                        ac.states.push(AcState { depth: depth + 1, ..root });
+                        let len = ac.next.len();
+                        ac.next.resize(len + num_classes, NO_STATE);
+                        child
+                    }
+                    child => child,
+                };
+            }
+            if ac.states[state].exact.is_none() {
+                ac.states[state].exact = Some(i);
+            }
+        }
+
+        // Replace the missing transitions with those of the state for the
+        // longest proper suffix in the trie (its failure link). That state
+        // is shallower, so its row is complete by the time we get to a state
+        // in breadth-first order.
+        let mut fail = vec![0; ac.states.len()];
+        let mut queue = VecDeque::new();
+        for class in 0..num_classes {
+            match ac.next[class] {
+                NO_STATE => ac.next[class] = 0,
+                child => queue.push_back(child),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let s = ac.states[state];
+            ac.states[state].out = match s.exact {
+                Some(needle) => Some((s.depth, needle)),
+                None => ac.states[fail[state]].out,
+            };
+            for class in 0..num_classes {
+                let index = state * num_classes + class;
+                let fail_next = ac.next[fail[state] * num_classes + class];
+                match ac.next[index] {
+                    NO_STATE => ac.next[index] = fail_next,
+                    child => {
+                        fail[child] = fail_next;
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+        ac
+    }
+
+    #[inline]
+    fn index(&self, state: usize, byte: u8) -> usize {
+        state * self.num_classes + self.classes[byte as usize] as usize
+    }
+
+    /// Returns the length of the first needle which matches at the start of
+    /// `bytes`, by walking down the trie.
+    fn match_len<I: Iterator<Item = u8>>(&self, bytes: I, allow_empty: bool) -> Option<usize> {
+        let mut best = match self.states[0].exact {
+            Some(needle) if allow_empty => Some((needle, 0)),
+            _ => None,
+        };
+        let mut state = 0;
+        for byte in bytes {
+            let next = self.next[self.index(state, byte)];
+            // Any other transition leaves the trie.
+            if self.states[next].depth != self.states[state].depth + 1 {
+                break;
+            }
+            state = next;
+            let s = &self.states[state];
+            if let Some(needle) = s.exact {
+                if best.map_or(true, |(best_needle, _)| needle < best_needle) {
+                    best = Some((needle, s.depth));
+                }
+            }
+        }
+        best.map(|(_, len)| len)
+    }
+
+    /// Returns the offset and length of the leftmost non-empty match in
+    /// `bytes`, the first needle listed winning on ties.
+    fn find<I: Iterator<Item = u8>>(&self, bytes: I) -> Option<(usize, usize)> {
+        // The offset, length and needle of the best match so far.
+        let mut best: Option<(usize, usize, usize)> = None;
+        let mut state = 0;
+        for (i, byte) in bytes.enumerate() {
+            state = self.next[self.index(state, byte)];
+            let s = &self.states[state];
+            let pos = i + 1;
+            if let Some((offset, _, _)) = best {
+                // Any match still to be found would start after `offset`.
+                if pos - s.depth > offset {
+                    break;
+                }
+            }
+            if let Some((len, needle)) = s.out {
+                let offset = pos - len;
+                if best.map_or(true, |(o, _, n)| offset < o || (offset == o && needle < n)) {
+                    best = Some((offset, len, needle));
+                }
+            }
+        }
+        best.map(|(offset, len, _)| (offset, len))
+    }
+}
+
+impl<'a, 'b> Pattern<'a> for &'b Substrings {
+    type Searcher = SubstringsSearcher<'a, 'b>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &'a str) -> SubstringsSearcher<'a, 'b> {
+        SubstringsSearcher {
+            haystack,
+            substrings: self,
+            position: 0,
+            end: haystack.len(),
+            is_match_fw: false,
+            is_match_bw: false,
+        }
+    }
+}
+
+/// Associated type for `<&Substrings as Pattern<'a>>::Searcher`.
+#[unstable(feature = "str_substrings", issue = "0")]
+#[derive(Clone, Debug)]
+pub struct SubstringsSearcher<'a, 'b> {
+    haystack: &'a str,
+    substrings: &'b Substrings,
+
+    /// The part of the haystack which has not been searched yet.
+    position: usize,
+    end: usize,
+    /// Whether a match ended at `position` (started at `end`), in which case
+    /// an empty needle is not allowed to match there.
+    is_match_fw: bool,
+    is_match_bw: bool,
+}
+
+impl<'a, 'b> SubstringsSearcher<'a, 'b> {
+    #[inline]
+    fn has_empty(&self) -> bool {
+        self.substrings.forward.states[0].exact.is_some()
+    }
+}
+
+unsafe impl<'a, 'b> Searcher<'a> for SubstringsSearcher<'a, 'b> {
+    #[inline]
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let start = self.position;
+        let rest = &self.haystack.as_bytes()[start..self.end];
+        let allow_empty = !self.is_match_fw;
+        if let Some(len) = self.substrings.forward.match_len(rest.iter().cloned(), allow_empty) {
+            self.position += len;
+            self.is_match_fw = true;
+            return SearchStep::Match(start, start + len);
+        }
+        match self.haystack[start..self.end].chars().next() {
+            Some(ch) => {
+                self.position += ch.len_utf8();
+                self.is_match_fw = false;
+                SearchStep::Reject(start, self.position)
+            }
+            None => SearchStep::Done,
+        }
+    }
+
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        if self.has_empty() {
+            loop {
+                match self.next() {
+                    SearchStep::Match(a, b) => return Some((a, b)),
+                    SearchStep::Done => return None,
+                    SearchStep::Reject(..) => {}
+                }
+            }
+        }
+
+        // All needles are non-empty, so they start with a byte which is not
+        // a UTF-8 continuation byte, and a match can only be found on a char
+        // boundary.
+        let rest = &self.haystack.as_bytes()[self.position..self.end];
+        match self.substrings.forward.find(rest.iter().cloned()) {
+            Some((offset, len)) => {
+                let start = self.position + offset;
+                self.position = start + len;
+                self.is_match_fw = true;
+                Some((start, start + len))
+            }
+            None => {
+                self.position = self.end;
+                None
+            }
+        }
+    }
+}
+
+unsafe impl<'a, 'b> ReverseSearcher<'a> for SubstringsSearcher<'a, 'b> {
+    fn next_back(&mut self) -> SearchStep {
+        let end = self.end;
+        let rest = &self.haystack.as_bytes()[self.position..end];
+        let allow_empty = !self.is_match_bw;
+        let backward = &self.substrings.backward;
+        if let Some(len) = backward.match_len(rest.iter().rev().cloned(), allow_empty) {
+            self.end -= len;
+            self.is_match_bw = true;
+            return SearchStep::Match(end - len, end);
+        }
+        match self.haystack[self.position..end].chars().next_back() {
+            Some(ch) => {
+                self.end -= ch.len_utf8();
+                self.is_match_bw = false;
+                SearchStep::Reject(self.end, end)
+            }
+            None => SearchStep::Done,
+        }
+    }
+
+    fn next_match_back(&mut self) -> Option<(usize, usize)> {
+        if self.has_empty() {
+            loop {
+                match self.next_back() {
+                    SearchStep::Match(a, b) => return Some((a, b)),
+                    SearchStep::Done => return None,
+                    SearchStep::Reject(..) => {}
+                }
+            }
+        }
+
+        // As in `next_match`, a non-empty needle can only end on a char
+        // boundary, since it consists of whole chars.
+        let rest = &self.haystack.as_bytes()[self.position..self.end];
+        match self.substrings.backward.find(rest.iter().rev().cloned()) {
+            Some((offset, len)) => {
+                let end = self.end - offset;
+                self.end = end - len;
+                self.is_match_bw = true;
+                Some((end - len, end))
+            }
+            None => {
+                self.end = self.position;
+                None
+            }
+        }
+    }
+}
@@ -8,9 +8,11 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::cell::Cell;
 use std::collections::BTreeMap;
 use std::collections::Bound::{self, Excluded, Included, Unbounded};
 use std::collections::btree_map::Entry::{Occupied, Vacant};
+use std::panic::{self, AssertUnwindSafe};
 use std::rc::Rc;
 
 use std::iter::FromIterator;
@@ -685,3 +687,210 @@ fn test_split_off_large_random_sorted() {
     assert!(map.into_iter().eq(data.clone().into_iter().filter(|x| x.0 < key)));
     assert!(right.into_iter().eq(data.into_iter().filter(|x| x.0 >= key)));
 }
+
+// Removes every key in turn, which fails if the tree was left with underfull nodes.
+fn check_remove_all(mut map: BTreeMap<i32, i32>) {
+    let keys: Vec<_> = map.keys().cloned().collect();
+    for (i, k) in keys.iter().enumerate() {
+        assert_eq!(map.remove(k), Some(*k * 10));
+        assert_eq!(map.len(), keys.len() - i - 1);
+    }
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_retain() {
+    let mut map: BTreeMap<i32, i32> = (0..1000).map(|x| (x, x * 10)).collect();
+
+    map.retain(|&k, _| k % 3 == 1);
+    assert_eq!(map.len(), 333);
+    assert!(map.iter().map(|(&k, _)| k).eq((0..1000).filter(|k| k % 3 == 1)));
+    check_remove_all(map);
+}
+
+#[test]
+fn test_drain_filter() {
+    for &len in &[0, 1, 11, 100, 1000] {
+        let mut map: BTreeMap<i32, i32> = (0..len).map(|x| (x, x * 10)).collect();
+
+        let drained: Vec<_> = map.drain_filter(|&k, _| k % 2 == 0).collect();
+        assert!(drained.into_iter().eq((0..len).filter(|k| k % 2 == 0).map(|k| (k, k * 10))));
+        assert!(map.iter().map(|(&k, &v)| (k, v))
+                   .eq((0..len).filter(|k| k % 2 == 1).map(|k| (k, k * 10))));
+        assert_eq!(map.len(), (len / 2) as usize);
+        check_remove_all(map);
+    }
+}
+
+#[test]
+fn test_drain_filter_partial() {
+    let mut map: BTreeMap<i32, i32> = (0..500).map(|x| (x, x)).collect();
+    {
+        let mut iter = map.drain_filter(|&k, v| {
+            *v *= 10;
+            k >= 100
+        });
+        assert_eq!(iter.size_hint(), (0, Some(500)));
+        assert_eq!(iter.next(), Some((100, 1000)));
+        assert_eq!(iter.size_hint(), (0, Some(399)));
+    }
+    assert_eq!(map.len(), 100);
+    assert!(map.iter().map(|(&k, &v)| (k, v)).eq((0..100).map(|k| (k, k * 10))));
+    map.insert(1000, 10000);
+    check_remove_all(map);
+}
+
+#[test]
+fn test_remove_range() {
+    fn check<R: Clone + ::std::collections::range::RangeArgument<i32>>(len: i32, range: R) {
+        let mut map: BTreeMap<i32, i32> = (0..len).map(|x| (x, x * 10)).collect();
+        let expected: Vec<_> = map.range(range.clone()).map(|(&k, &v)| (k, v)).collect();
+
+        let removed = map.remove_range(range);
+        assert!(removed.iter().map(|(&k, &v)| (k, v)).eq(expected.iter().cloned()));
+        assert_eq!(map.len() + removed.len(), len as usize);
+        assert!(map.keys().all(|k| expected.binary_search_by_key(k, |&(k, _)| k).is_err()));
+        check_remove_all(map);
+        check_remove_all(removed);
+    }
+
+    for &len in &[0, 10, 100, 1000] {
+        check(len, ..);
+        check(len, 5..);
+        check(len, ..5);
+        check(len, 0..0);
+        check(len, 3..7);
+        check(len, len / 4..len / 2);
+        check(len, len / 2..len + 5);
+        check(len, (Excluded(1), Included(len / 3 + 1)));
+        check(len, (Included(-5), Excluded(len - 1)));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_remove_range_equal_excluded() {
+    let mut map: BTreeMap<i32, i32> = (0..10).map(|x| (x, x)).collect();
+    map.remove_range((Excluded(5), Excluded(5)));
+}
+
+#[test]
+fn test_custom_allocator() {
+    use std::heap::{Alloc, AllocErr, Heap, Layout, SharedAlloc};
+
+    // Forwards to the global heap, keeping track of the bytes in use by all
+    // of its clones.
+    #[derive(Clone)]
+    struct CountingAlloc<'a> { live: &'a Cell<usize> }
+    unsafe impl<'a> Alloc for CountingAlloc<'a> {
+        unsafe fn alloc(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+            let ptr = Heap.alloc(layout.clone())?;
+            self.live.set(self.live.get() + layout.size());
+            Ok(ptr)
+        }
+        unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+            self.live.set(self.live.get() - layout.size());
+            Heap.dealloc(ptr, layout)
+        }
+    }
+    // The clones count together, and any of them can free what another has allocated.
+    unsafe impl<'a> SharedAlloc for CountingAlloc<'a> {}
+
+    let live = Cell::new(0);
+    {
+        let mut map = BTreeMap::new_in(CountingAlloc { live: &live });
+        assert!(live.get() > 0);
+        for i in 0..200 {
+            map.insert(i, i * 10);
+        }
+        *map.entry(7).or_insert(0) += 1;
+        assert_eq!(map[&7], 71);
+        assert_eq!(map.remove(&8), Some(80));
+
+        let mut other = map.clone();
+        assert_eq!(map, other);
+        let right = other.split_off(&100);
+        assert_eq!(right.len(), 100);
+        other.append(&mut map.split_off(&150));
+        assert_eq!(other.len(), 149);
+
+        map.retain(|k, _| k % 2 == 0);
+        assert_eq!(map.len(), 74);
+        assert_eq!(map.drain_filter(|k, _| *k < 50).count(), 24);
+        assert_eq!(map.remove_range(60..80).len(), 10);
+
+        let mut iter = right.into_iter();
+        assert_eq!(iter.next(), Some((100, 1000)));
+        assert_eq!(iter.next_back(), Some((199, 1990)));
+    }
+    assert_eq!(live.get(), 0);
+}
+
+struct DropCounter<'a>(&'a Cell<usize>);
+
+impl<'a> Drop for DropCounter<'a> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn test_drain_filter_consumed_panic() {
+    let drops = Cell::new(0);
+    let calls = Cell::new(0);
+    let mut map: BTreeMap<i32, DropCounter> =
+        (0..100).map(|k| (k, DropCounter(&drops))).collect();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let iter = map.drain_filter(|&k, _| {
+            calls.set(calls.get() + 1);
+            if calls.get() == 52 {
+                panic!("predicate panicked on {}", k);
+            }
+            k % 2 == 0
+        });
+        for (k, _) in iter {
+            assert_eq!(k % 2, 0);
+        }
+    }));
+    assert!(result.is_err());
+
+    // The predicate is called again on the rest of the elements while unwinding, but 51, which
+    // it panicked on, is dropped even though it would have been kept.
+    assert_eq!(calls.get(), 100);
+    assert_eq!(drops.get(), 51);
+    assert!(map.keys().cloned().eq((0..100).filter(|k| k % 2 == 1 && *k != 51)));
+    map.insert(51, DropCounter(&drops));
+    assert_eq!(map.len(), 50);
+    drop(map);
+    assert_eq!(drops.get(), 101);
+}
+
+#[test]
+fn test_drain_filter_unconsumed_panic() {
+    let drops = Cell::new(0);
+    let calls = Cell::new(0);
+    let mut map: BTreeMap<i32, DropCounter> =
+        (0..100).map(|k| (k, DropCounter(&drops))).collect();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut iter = map.drain_filter(|&k, _| {
+            calls.set(calls.get() + 1);
+            if calls.get() == 52 {
+                panic!("predicate panicked on {}", k);
+            }
+            k % 2 == 0
+        });
+        assert_eq!(iter.next().map(|(k, _)| k), Some(0));
+    }));
+    assert!(result.is_err());
+
+    // The panic happened while the iterator was being dropped, so everything after 51 is kept.
+    assert_eq!(calls.get(), 52);
+    assert_eq!(drops.get(), 27);
+    assert!(map.keys().cloned().eq((0..51).filter(|k| k % 2 == 1).chain(52..100)));
+    map.insert(51, DropCounter(&drops));
+    assert_eq!(map.len(), 74);
+    drop(map);
+    assert_eq!(drops.get(), 101);
+}
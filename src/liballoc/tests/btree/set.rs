@@ -329,3 +329,30 @@ fn test_split_off_large_random_sorted() {
     assert!(set.into_iter().eq(data.clone().into_iter().filter(|x| *x < key)));
     assert!(right.into_iter().eq(data.into_iter().filter(|x| *x >= key)));
 }
+
+#[test]
+fn test_retain() {
+    let mut set: BTreeSet<i32> = (0..100).collect();
+    set.retain(|&k| k % 2 == 0);
+    assert!(set.iter().cloned().eq((0..100).filter(|k| k % 2 == 0)));
+}
+
+#[test]
+fn test_drain_filter() {
+    let mut set: BTreeSet<i32> = (0..100).collect();
+    let drained: Vec<_> = set.drain_filter(|&k| k >= 10 && k < 90).collect();
+    assert!(drained.into_iter().eq(10..90));
+    assert!(set.iter().cloned().eq((0..10).chain(90..100)));
+}
+
+#[test]
+fn test_remove_range() {
+    let mut set: BTreeSet<i32> = (0..100).collect();
+    let removed = set.remove_range(10..90);
+    assert!(removed.into_iter().eq(10..90));
+    assert!(set.iter().cloned().eq((0..10).chain(90..100)));
+    for k in 0..100 {
+        assert_eq!(set.remove(&k), k < 10 || k >= 90);
+    }
+    assert!(set.is_empty());
+}
@@ -9,7 +9,8 @@
 // except according to those terms.
 
 use alloc_system::System;
-use std::heap::{Heap, Alloc, Layout};
+use std::cell::Cell;
+use std::heap::{Heap, Alloc, AllocErr, Layout};
 
 /// https://github.com/rust-lang/rust/issues/45955
 ///
@@ -43,3 +44,38 @@ fn check_overalign_requests<T: Alloc>(mut allocator: T) {
         }
     }
 }
+
+#[test]
+fn box_new_in() {
+    struct CountingAlloc<'a> { live: &'a Cell<usize> }
+    unsafe impl<'a> Alloc for CountingAlloc<'a> {
+        unsafe fn alloc(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+            let ptr = Heap.alloc(layout.clone())?;
+            self.live.set(self.live.get() + layout.size());
+            Ok(ptr)
+        }
+        unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+            self.live.set(self.live.get() - layout.size());
+            Heap.dealloc(ptr, layout)
+        }
+    }
+
+    let live = Cell::new(0);
+    {
+        let mut b = Box::new_in([1u64; 4], CountingAlloc { live: &live });
+        assert_eq!(live.get(), 32);
+        b[2] = 5;
+        assert_eq!(*b, [1, 1, 5, 1]);
+        assert_eq!(format!("{:?}", b), "[1, 1, 5, 1]");
+        assert_eq!(Box::alloc(&b).live.get(), 32);
+
+        let (ptr, alloc) = Box::into_raw_with_alloc(b);
+        assert_eq!(live.get(), 32);
+        let b = unsafe { Box::from_raw_in(ptr, alloc) };
+        assert_eq!(b[2], 5);
+
+        let _unit = Box::new_in((), CountingAlloc { live: &live });
+        assert_eq!(live.get(), 32);
+    }
+    assert_eq!(live.get(), 0);
+}
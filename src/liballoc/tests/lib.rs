@@ -14,8 +14,11 @@
 #![feature(alloc_system)]
 #![feature(attr_literals)]
 #![feature(box_syntax)]
+#![feature(btree_remove_range)]
+#![feature(btree_retain)]
 #![feature(inclusive_range_syntax)]
 #![feature(collection_placement)]
+#![feature(collections_range)]
 #![feature(const_fn)]
 #![feature(drain_filter)]
 #![feature(exact_size_is_empty)]
@@ -25,7 +28,9 @@
 #![feature(rand)]
 #![feature(splice)]
 #![feature(str_escape)]
+#![feature(str_substrings)]
 #![feature(string_retain)]
+#![feature(try_reserve)]
 #![feature(unboxed_closures)]
 #![feature(unicode)]
 #![feature(exact_chunks)]
@@ -10,7 +10,7 @@
 
 use std::borrow::Cow;
 use std::cmp::Ordering::{Equal, Greater, Less};
-use std::str::from_utf8;
+use std::str::{from_utf8, Substrings};
 
 #[test]
 fn test_le() {
@@ -291,6 +291,73 @@ fn test_replace_pattern() {
     assert_eq!(data.replace(|c| c == 'γ', "😺😺😺"), "abcdαβ😺😺😺δabcdαβ😺😺😺δ");
 }
 
+#[test]
+fn test_str_slice_pattern() {
+    let keywords: &[&str] = &["error", "warn", "err"];
+    let log = "warn: disk; error: fs; err";
+    assert_eq!(log.find(keywords), Some(0));
+    assert_eq!(log.rfind(keywords), Some(23));
+    assert_eq!(log.matches(keywords).collect::<Vec<_>>(), ["warn", "error", "err"]);
+    assert_eq!(log.rmatch_indices(keywords).collect::<Vec<_>>(),
+               [(23, "err"), (12, "error"), (0, "warn")]);
+    assert_eq!(log.split(keywords).collect::<Vec<_>>(), ["", ": disk; ", ": fs; ", ""]);
+    assert_eq!(log.replace(keywords, "*"), "*: disk; *: fs; *");
+    assert!(log.starts_with(keywords));
+    assert!(log.ends_with(keywords));
+    assert!(!log.contains(&["fatal", "panic"] as &[&str]));
+
+    let data = "abcdαβγδabcdαβγδ";
+    assert_eq!(data.replace(&["dαβ", "γ"] as &[&str], "😺"), "abc😺😺δabc😺😺δ");
+}
+
+#[test]
+fn test_substrings_pattern() {
+    // Far more prefixes than a `&[&str]` pattern has room for in its
+    // automaton, so the slice compares each needle in turn.
+    let needles: &[&str] = &[
+        "TRACE", "DEBUG", "INFO", "WARN", "ERROR", "FATAL",
+        "panicked", "aborted", "killed", "SIGSEGV", "SIGKILL", "segfault",
+        "timeout", "timed out", "retrying", "retry", "refused", "connection reset",
+        "broken pipe", "ECONNRESET", "EPIPE", "EAGAIN", "unreachable", "not found",
+        "denied", "unauthorized", "forbidden", "failed", "failure", "deadlock",
+        "out of memory", "OOM", "overflow", "corrupt", "checksum mismatch", "disk full",
+        "no space left", "ENOSPC", "too many open files", "shutdown", "restart",
+    ];
+    let keywords = Substrings::new(needles);
+    let log = "INFO worker 3: retrying after timeout\n\
+               ERROR worker 3: connection reset by peer (ECONNRESET)\n\
+               WARN disk full, no space left on device\n\
+               FATAL thread 'main' panicked at 'out of memory'\n";
+
+    assert_eq!(log.find(&keywords), Some(0));
+    assert_eq!(log.rfind(&keywords), Some(165));
+    assert_eq!(log.matches(&keywords).collect::<Vec<_>>(),
+               ["INFO", "retrying", "timeout", "ERROR", "connection reset", "ECONNRESET",
+                "WARN", "disk full", "no space left", "FATAL", "panicked", "out of memory"]);
+    assert_eq!(log.rmatches(&keywords).count(), 12);
+    assert!(log.starts_with(&keywords));
+    assert!(!log.ends_with(&keywords));
+    assert!(!"all good".contains(&keywords));
+
+    let line = "WARN disk full, no space left on device";
+    assert_eq!(line.replace(&keywords, "*"), "* *, * on device");
+    assert_eq!(line.split(&keywords).collect::<Vec<_>>(), ["", " ", ", ", " on device"]);
+
+    // The same `Substrings` can be used for any number of searches, and it
+    // matches exactly like the slice of needles.
+    for line in log.lines() {
+        assert_eq!(line.match_indices(&keywords).collect::<Vec<_>>(),
+                   line.match_indices(needles).collect::<Vec<_>>());
+        assert_eq!(line.rmatch_indices(&keywords).collect::<Vec<_>>(),
+                   line.rmatch_indices(needles).collect::<Vec<_>>());
+    }
+
+    let with_empty = Substrings::new(&["b", ""]);
+    assert_eq!("ab".match_indices(&with_empty).collect::<Vec<_>>(), [(0, ""), (1, "b")]);
+    assert_eq!("ab".match_indices(&with_empty).collect::<Vec<_>>(),
+               "ab".match_indices(&["b", ""] as &[&str]).collect::<Vec<_>>());
+}
+
 #[test]
 fn test_slice() {
     assert_eq!("ab", &"abc"[0..2]);
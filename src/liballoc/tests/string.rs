@@ -9,6 +9,7 @@
 // except according to those terms.
 
 use std::borrow::Cow;
+use std::collections::CollectionAllocErr::*;
 
 pub trait IntoCow<'a, B: ?Sized> where B: ToOwned {
     fn into_cow(self) -> Cow<'a, B>;
@@ -504,3 +505,16 @@ fn test_into_boxed_str() {
     let ys = xs.into_boxed_str();
     assert_eq!(&*ys, "hello my name is bob");
 }
+
+#[test]
+fn test_try_reserve() {
+    let mut s = String::from("hello");
+    assert_eq!(s.try_reserve(10), Ok(()));
+    assert!(s.capacity() >= 15);
+    assert_eq!(s.try_reserve_exact(20), Ok(()));
+    assert!(s.capacity() >= 25);
+
+    assert_eq!(s.try_reserve(usize::max_value()), Err(CapacityOverflow));
+    assert_eq!(s.try_reserve_exact(usize::max_value()), Err(CapacityOverflow));
+    assert_eq!(s, "hello");
+}
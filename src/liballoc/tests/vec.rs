@@ -9,6 +9,7 @@
 // except according to those terms.
 
 use std::borrow::Cow;
+use std::collections::CollectionAllocErr::*;
 use std::mem::size_of;
 use std::panic;
 use std::vec::{Drain, IntoIter};
@@ -965,3 +966,127 @@ fn drain_filter_complex() {
         assert_eq!(vec, vec![1, 3, 5, 7, 9, 11, 13, 15, 17, 19]);
     }
 }
+
+#[test]
+fn test_try_reserve() {
+    let mut empty_bytes: Vec<u8> = Vec::new();
+    assert_eq!(empty_bytes.try_reserve(10), Ok(()));
+    assert!(empty_bytes.capacity() >= 10);
+    assert_eq!(empty_bytes.try_reserve_exact(20), Ok(()));
+    assert!(empty_bytes.capacity() >= 20);
+
+    // More than `isize::MAX` bytes is rejected up front on 32-bit platforms,
+    // and no allocator can satisfy it on 64-bit ones. Either way it must be
+    // reported rather than aborting.
+    match Vec::<u8>::new().try_reserve(isize::max_value() as usize + 1) {
+        Err(AllocErr(_)) | Err(CapacityOverflow) => {}
+        r => panic!("isize::MAX bytes should not fit: {:?}", r),
+    }
+
+    // The element count overflows once it is multiplied by the element size.
+    let mut ten_u32s: Vec<u32> = vec![0; 10];
+    assert_eq!(ten_u32s.try_reserve(usize::max_value() / 4), Err(CapacityOverflow));
+    assert_eq!(ten_u32s.try_reserve_exact(usize::max_value() / 4), Err(CapacityOverflow));
+
+    // The length overflows before anything is multiplied.
+    assert_eq!(ten_u32s.try_reserve(usize::max_value()), Err(CapacityOverflow));
+    assert_eq!(ten_u32s.try_reserve_exact(usize::max_value() - 9), Err(CapacityOverflow));
+
+    // A failed reservation leaves the vector untouched.
+    assert_eq!(ten_u32s, vec![0; 10]);
+}
+
+#[test]
+fn test_custom_allocator() {
+    use std::heap::{Alloc, AllocErr, Heap, Layout};
+
+    // Forwards to the global heap, keeping track of the bytes in use.
+    struct CountingAlloc { live: usize }
+    unsafe impl Alloc for CountingAlloc {
+        unsafe fn alloc(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+            let ptr = Heap.alloc(layout.clone())?;
+            self.live += layout.size();
+            Ok(ptr)
+        }
+        unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+            self.live -= layout.size();
+            Heap.dealloc(ptr, layout)
+        }
+    }
+
+    let mut v = Vec::new_in(CountingAlloc { live: 0 });
+    assert_eq!(v.alloc().live, 0);
+    v.push(1u32);
+    v.push(2);
+    v.push(3);
+    assert_eq!(&v[..], &[1, 2, 3]);
+    assert_eq!(v.alloc().live, v.capacity() * size_of::<u32>());
+
+    v.insert(0, 0);
+    assert_eq!(v.remove(3), 3);
+    assert_eq!(v.pop(), Some(2));
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 2);
+    assert_eq!(v.alloc().live, 2 * size_of::<u32>());
+
+    let mut count = 0;
+    {
+        let mut v = Vec::with_capacity_in(4, CountingAlloc { live: 0 });
+        assert_eq!(v.alloc().live, 4 * size_of::<DropCounter>());
+        v.push(DropCounter { count: &mut count });
+        v.clear();
+        assert!(v.is_empty());
+        assert_eq!(v.capacity(), 4);
+    }
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn test_custom_allocator_traits() {
+    use std::cell::Cell;
+    use std::heap::{Alloc, AllocErr, Heap, Layout};
+
+    // Forwards to the global heap, keeping track of the bytes in use by all
+    // of its clones.
+    #[derive(Clone)]
+    struct SharedAlloc<'a> { live: &'a Cell<usize> }
+    unsafe impl<'a> Alloc for SharedAlloc<'a> {
+        unsafe fn alloc(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+            let ptr = Heap.alloc(layout.clone())?;
+            self.live.set(self.live.get() + layout.size());
+            Ok(ptr)
+        }
+        unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+            self.live.set(self.live.get() - layout.size());
+            Heap.dealloc(ptr, layout)
+        }
+    }
+
+    let live = Cell::new(0);
+    {
+        let mut v: Vec<u32, _> = Vec::new_in(SharedAlloc { live: &live });
+        v.extend(0..4);
+        v.extend(&[4, 5]);
+        assert_eq!(v, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(v, vec![0, 1, 2, 3, 4, 5]);
+
+        let mut w = v.clone();
+        assert_eq!(v, w);
+        assert!(live.get() >= 12 * size_of::<u32>());
+
+        for x in &mut w {
+            *x *= 2;
+        }
+        assert!(v < w);
+
+        assert_eq!(v.split_off(4), [4, 5]);
+        assert_eq!(v.drain(1..3).collect::<Vec<_>>(), [1, 2]);
+        v.retain(|&x| x != 3);
+        assert_eq!(v, [0]);
+
+        let mut iter = w.into_iter();
+        assert_eq!(iter.next_back(), Some(10));
+        assert_eq!(iter.clone().collect::<Vec<_>>(), [0, 2, 4, 6, 8]);
+    }
+    assert_eq!(live.get(), 0);
+}
@@ -8,9 +8,12 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::cell::Cell;
 use std::collections::VecDeque;
+use std::collections::CollectionAllocErr::*;
 use std::fmt::Debug;
 use std::collections::vec_deque::{Drain};
+use std::panic::{self, AssertUnwindSafe};
 
 use self::Taggy::*;
 use self::Taggypar::*;
@@ -934,6 +937,87 @@ fn test_retain() {
     assert_eq!(&v[..], &[2, 4]);
 }
 
+#[test]
+fn test_drain_filter() {
+    // Start the elements near the end of the buffer so that they wrap around.
+    let mut buf = VecDeque::with_capacity(7);
+    for i in 0..6 {
+        buf.push_back(i);
+        buf.pop_front();
+    }
+    buf.extend(0..7);
+    assert_eq!(buf.as_slices().1.len(), 5);
+
+    let evens: Vec<_> = buf.drain_filter(|x| *x % 2 == 0).collect();
+    assert_eq!(evens, [0, 2, 4, 6]);
+    assert_eq!(buf, [1, 3, 5]);
+    buf.push_back(7);
+    assert_eq!(buf, [1, 3, 5, 7]);
+}
+
+#[test]
+fn test_drain_filter_partial() {
+    let mut buf: VecDeque<_> = (0..10).collect();
+    {
+        let mut iter = buf.drain_filter(|x| *x >= 5);
+        assert_eq!(iter.size_hint(), (0, Some(10)));
+        assert_eq!(iter.next(), Some(5));
+        assert_eq!(iter.size_hint(), (0, Some(4)));
+    }
+    assert_eq!(buf, [0, 1, 2, 3, 4]);
+
+    let mut buf: VecDeque<()> = (0..5).map(|_| ()).collect();
+    assert_eq!(buf.drain_filter(|_| true).count(), 5);
+    assert!(buf.is_empty());
+}
+
+struct DropCounter<'a>(&'a Cell<usize>);
+
+impl<'a> Drop for DropCounter<'a> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn test_drain_filter_panic_after_removal() {
+    // Wrap around the end of the buffer, as in `test_drain_filter`.
+    let drops = Cell::new(0);
+    let calls = Cell::new(0);
+    let mut buf = VecDeque::with_capacity(7);
+    for _ in 0..6 {
+        buf.push_back((0, DropCounter(&drops)));
+        buf.pop_front();
+    }
+    buf.extend((0..7).map(|i| (i, DropCounter(&drops))));
+    assert_eq!(drops.get(), 6);
+    drops.set(0);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let iter = buf.drain_filter(|&mut (i, _)| {
+            calls.set(calls.get() + 1);
+            if i == 3 {
+                panic!("predicate panicked on {}", i);
+            }
+            i % 2 == 0
+        });
+        for (i, _) in iter {
+            assert_eq!(i % 2, 0);
+        }
+    }));
+    assert!(result.is_err());
+
+    // 0 and 2 were removed and dropped. 3, which the predicate panicked on,
+    // and everything after it are kept without calling the predicate again.
+    assert_eq!(calls.get(), 4);
+    assert_eq!(drops.get(), 2);
+    assert!(buf.iter().map(|&(i, _)| i).eq(vec![1, 3, 4, 5, 6]));
+    buf.push_back((7, DropCounter(&drops)));
+    assert!(buf.iter().map(|&(i, _)| i).eq(vec![1, 3, 4, 5, 6, 7]));
+    drop(buf);
+    assert_eq!(drops.get(), 8);
+}
+
 #[test]
 fn test_extend_ref() {
     let mut v = VecDeque::new();
@@ -1022,3 +1106,20 @@ fn test_placement_in() {
     }
     assert_eq!(buf, [5,4,3,1,2,6]);
 }
+
+#[test]
+fn test_try_reserve() {
+    let mut ten_u32s: VecDeque<u32> = (0..10).collect();
+    assert_eq!(ten_u32s.try_reserve(10), Ok(()));
+    assert!(ten_u32s.capacity() >= 20);
+    assert_eq!(ten_u32s.try_reserve_exact(30), Ok(()));
+    assert!(ten_u32s.capacity() >= 40);
+
+    // The capacity is rounded up to a power of two, which overflows here.
+    assert_eq!(ten_u32s.try_reserve(usize::max_value() / 2), Err(CapacityOverflow));
+    assert_eq!(ten_u32s.try_reserve(usize::max_value()), Err(CapacityOverflow));
+    // And here the element count overflows once multiplied by its size.
+    assert_eq!(ten_u32s.try_reserve_exact(usize::max_value() / 8), Err(CapacityOverflow));
+
+    assert!(ten_u32s.iter().cloned().eq(0..10));
+}
@@ -81,9 +81,11 @@ use core::ptr;
 use core::ptr::NonNull;
 use core::slice;
 
+use allocator::CollectionAllocErr;
 use borrow::ToOwned;
 use borrow::Cow;
 use boxed::Box;
+use heap::{Alloc, Heap};
 use raw_vec::RawVec;
 use super::range::RangeArgument;
 use Bound::{Excluded, Included, Unbounded};
@@ -284,6 +286,21 @@ use Bound::{Excluded, Included, Unbounded};
 /// `Vec` does not currently guarantee the order in which elements are dropped.
 /// The order has changed in the past and may change again.
 ///
+/// # Allocators
+///
+/// By default a `Vec` allocates from the global [`Heap`]. Any other [`Alloc`]
+/// can be supplied with [`Vec::new_in`] or [`Vec::with_capacity_in`], in
+/// which case the vector owns the allocator and releases its buffer through
+/// it. Constructors and conversions that have to create an allocator out of
+/// thin air, such as [`Vec::new`], `Default`, `FromIterator` and the `From`
+/// impls, as well as the conversions to and from [`Box<[T]>`][owned slice],
+/// are only available for `Vec<T>`.
+///
+/// [`Heap`]: ../../std/heap/struct.Heap.html
+/// [`Alloc`]: ../../std/heap/trait.Alloc.html
+/// [`Vec::new_in`]: ../../std/vec/struct.Vec.html#method.new_in
+/// [`Vec::with_capacity_in`]: ../../std/vec/struct.Vec.html#method.with_capacity_in
+/// [`Vec::new`]: ../../std/vec/struct.Vec.html#method.new
 /// [`vec!`]: ../../std/macro.vec.html
 /// [`Index`]: ../../std/ops/trait.Index.html
 /// [`String`]: ../../std/string/struct.String.html
@@ -299,8 +316,8 @@ use Bound::{Excluded, Included, Unbounded};
 /// [`reserve`]: ../../std/vec/struct.Vec.html#method.reserve
 /// [owned slice]: ../../std/boxed/struct.Box.html
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct Vec<T> {
-    buf: RawVec<T>,
+pub struct Vec<T, A: Alloc = Heap> {
+    buf: RawVec<T, A>,
     len: usize,
 }
 
@@ -426,6 +443,100 @@ impl<T> Vec<T> {
             len: length,
         }
     }
+}
+
+impl<T, A: Alloc> Vec<T, A> {
+    /// Constructs a new, empty `Vec<T, A>` which will allocate from `alloc`.
+    ///
+    /// The vector will not allocate until elements are pushed onto it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::heap::System;
+    ///
+    /// let mut vec = Vec::new_in(System);
+    /// vec.push(1);
+    /// assert_eq!(&vec[..], &[1]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn new_in(alloc: A) -> Vec<T, A> {
+        Vec {
+            buf: RawVec::new_in(alloc),
+            len: 0,
+        }
+    }
+
+    /// Constructs a new, empty `Vec<T, A>` with the specified capacity,
+    /// allocated from `alloc`.
+    ///
+    /// See [`with_capacity`] for how the capacity behaves.
+    ///
+    /// [`with_capacity`]: #method.with_capacity
+    ///
+    /// # Panics
+    ///
+    /// Panics if the requested capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::heap::System;
+    ///
+    /// let mut vec = Vec::with_capacity_in(10, System);
+    /// assert_eq!(vec.capacity(), 10);
+    ///
+    /// for i in 0..10 {
+    ///     vec.push(i);
+    /// }
+    /// assert_eq!(vec.capacity(), 10);
+    /// ```
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Vec<T, A> {
+        Vec {
+            buf: RawVec::with_capacity_in(capacity, alloc),
+            len: 0,
+        }
+    }
+
+    /// Creates a `Vec<T, A>` directly from the raw components of another
+    /// vector and the allocator its buffer was allocated from.
+    ///
+    /// # Safety
+    ///
+    /// This has the same requirements as [`from_raw_parts`], and in addition
+    /// `ptr` must have been allocated by `alloc`.
+    ///
+    /// [`from_raw_parts`]: #method.from_raw_parts
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub unsafe fn from_raw_parts_in(ptr: *mut T, length: usize, capacity: usize,
+                                    alloc: A) -> Vec<T, A> {
+        Vec {
+            buf: RawVec::from_raw_parts_in(ptr, capacity, alloc),
+            len: length,
+        }
+    }
+
+    /// Returns a shared reference to the allocator backing this vector.
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn alloc(&self) -> &A {
+        self.buf.alloc()
+    }
+
+    /// Returns a mutable reference to the allocator backing this vector.
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn alloc_mut(&mut self) -> &mut A {
+        self.buf.alloc_mut()
+    }
 
     /// Returns the number of elements the vector can hold without
     /// reallocating.
@@ -489,57 +600,100 @@ impl<T> Vec<T> {
         self.buf.reserve_exact(self.len, additional);
     }
 
-    /// Shrinks the capacity of the vector as much as possible.
+    /// Tries to reserve capacity for at least `additional` more elements to be inserted
+    /// in the given `Vec<T>`. The collection may reserve more space to avoid
+    /// frequent reallocations. After calling `try_reserve`, capacity will be
+    /// greater than or equal to `self.len() + additional`. Does nothing if
+    /// capacity is already sufficient.
     ///
-    /// It will drop down as close as possible to the length but the allocator
-    /// may still inform the vector that there is space for a few more elements.
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
     ///
     /// # Examples
     ///
     /// ```
-    /// let mut vec = Vec::with_capacity(10);
-    /// vec.extend([1, 2, 3].iter().cloned());
-    /// assert_eq!(vec.capacity(), 10);
-    /// vec.shrink_to_fit();
-    /// assert!(vec.capacity() >= 3);
+    /// #![feature(try_reserve)]
+    /// use std::collections::CollectionAllocErr;
+    ///
+    /// fn process_data(data: &[u32]) -> Result<Vec<u32>, CollectionAllocErr> {
+    ///     let mut output = Vec::new();
+    ///
+    ///     // Pre-reserve the memory, exiting if we can't
+    ///     output.try_reserve(data.len())?;
+    ///
+    ///     // Now we know this can't OOM in the middle of our complex work
+    ///     output.extend(data.iter().map(|&val| {
+    ///         val * 2 + 5 // very complicated
+    ///     }));
+    ///
+    ///     Ok(output)
+    /// }
+    /// # process_data(&[1, 2, 3]).expect("why is the test harness OOMing on 12 bytes?");
     /// ```
-    #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn shrink_to_fit(&mut self) {
-        self.buf.shrink_to_fit(self.len);
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), CollectionAllocErr> {
+        self.buf.try_reserve(self.len, additional)
     }
 
-    /// Converts the vector into [`Box<[T]>`][owned slice].
+    /// Tries to reserve the minimum capacity for exactly `additional` more elements to
+    /// be inserted in the given `Vec<T>`. After calling `try_reserve_exact`,
+    /// capacity will be greater than or equal to `self.len() + additional`.
+    /// Does nothing if the capacity is already sufficient.
     ///
-    /// Note that this will drop any excess capacity.
+    /// Note that the allocator may give the collection more space than it
+    /// requests. Therefore capacity can not be relied upon to be precisely
+    /// minimal. Prefer `try_reserve` if future insertions are expected.
     ///
-    /// [owned slice]: ../../std/boxed/struct.Box.html
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
     ///
     /// # Examples
     ///
     /// ```
-    /// let v = vec![1, 2, 3];
+    /// #![feature(try_reserve)]
+    /// use std::collections::CollectionAllocErr;
     ///
-    /// let slice = v.into_boxed_slice();
+    /// fn process_data(data: &[u32]) -> Result<Vec<u32>, CollectionAllocErr> {
+    ///     let mut output = Vec::new();
+    ///
+    ///     // Pre-reserve the memory, exiting if we can't
+    ///     output.try_reserve_exact(data.len())?;
+    ///
+    ///     // Now we know this can't OOM in the middle of our complex work
+    ///     output.extend(data.iter().map(|&val| {
+    ///         val * 2 + 5 // very complicated
+    ///     }));
+    ///
+    ///     Ok(output)
+    /// }
+    /// # process_data(&[1, 2, 3]).expect("why is the test harness OOMing on 12 bytes?");
     /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), CollectionAllocErr> {
+        self.buf.try_reserve_exact(self.len, additional)
+    }
+
+    /// Shrinks the capacity of the vector as much as possible.
     ///
-    /// Any excess capacity is removed:
+    /// It will drop down as close as possible to the length but the allocator
+    /// may still inform the vector that there is space for a few more elements.
+    ///
+    /// # Examples
     ///
     /// ```
     /// let mut vec = Vec::with_capacity(10);
     /// vec.extend([1, 2, 3].iter().cloned());
-    ///
     /// assert_eq!(vec.capacity(), 10);
-    /// let slice = vec.into_boxed_slice();
-    /// assert_eq!(slice.into_vec().capacity(), 3);
+    /// vec.shrink_to_fit();
+    /// assert!(vec.capacity() >= 3);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn into_boxed_slice(mut self) -> Box<[T]> {
-        unsafe {
-            self.shrink_to_fit();
-            let buf = ptr::read(&self.buf);
-            mem::forget(self);
-            buf.into_box()
-        }
+    pub fn shrink_to_fit(&mut self) {
+        self.buf.shrink_to_fit(self.len);
     }
 
     /// Shortens the vector, keeping the first `len` elements and dropping
@@ -788,26 +942,6 @@ impl<T> Vec<T> {
         }
     }
 
-    /// Retains only the elements specified by the predicate.
-    ///
-    /// In other words, remove all elements `e` such that `f(&e)` returns `false`.
-    /// This method operates in place and preserves the order of the retained
-    /// elements.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// let mut vec = vec![1, 2, 3, 4];
-    /// vec.retain(|&x| x%2 == 0);
-    /// assert_eq!(vec, [2, 4]);
-    /// ```
-    #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn retain<F>(&mut self, mut f: F)
-        where F: FnMut(&T) -> bool
-    {
-        self.drain_filter(|x| !f(x));
-    }
-
     /// Removes all but the first of consecutive elements in the vector that resolve to the same
     /// key.
     ///
@@ -981,7 +1115,7 @@ impl<T> Vec<T> {
     #[unstable(feature = "collection_placement",
                reason = "placement protocol is subject to change",
                issue = "30172")]
-    pub fn place_back(&mut self) -> PlaceBack<T> {
+    pub fn place_back(&mut self) -> PlaceBack<T, A> {
         PlaceBack { vec: self }
     }
 
@@ -1044,6 +1178,115 @@ impl<T> Vec<T> {
         self.len += count;
     }
 
+    /// Clears the vector, removing all values.
+    ///
+    /// Note that this method has no effect on the allocated capacity
+    /// of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut v = vec![1, 2, 3];
+    ///
+    /// v.clear();
+    ///
+    /// assert!(v.is_empty());
+    /// ```
+    #[inline]
+    #[stable(feature = "rust1", since = "1.0.0")]
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Returns the number of elements in the vector, also referred to
+    /// as its 'length'.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let a = vec![1, 2, 3];
+    /// assert_eq!(a.len(), 3);
+    /// ```
+    #[inline]
+    #[stable(feature = "rust1", since = "1.0.0")]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut v = Vec::new();
+    /// assert!(v.is_empty());
+    ///
+    /// v.push(1);
+    /// assert!(!v.is_empty());
+    /// ```
+    #[stable(feature = "rust1", since = "1.0.0")]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Vec<T> {
+    /// Converts the vector into [`Box<[T]>`][owned slice].
+    ///
+    /// Note that this will drop any excess capacity.
+    ///
+    /// [owned slice]: ../../std/boxed/struct.Box.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let v = vec![1, 2, 3];
+    ///
+    /// let slice = v.into_boxed_slice();
+    /// ```
+    ///
+    /// Any excess capacity is removed:
+    ///
+    /// ```
+    /// let mut vec = Vec::with_capacity(10);
+    /// vec.extend([1, 2, 3].iter().cloned());
+    ///
+    /// assert_eq!(vec.capacity(), 10);
+    /// let slice = vec.into_boxed_slice();
+    /// assert_eq!(slice.into_vec().capacity(), 3);
+    /// ```
+    #[stable(feature = "rust1", since = "1.0.0")]
+    pub fn into_boxed_slice(mut self) -> Box<[T]> {
+        unsafe {
+            self.shrink_to_fit();
+            let buf = ptr::read(&self.buf);
+            mem::forget(self);
+            buf.into_box()
+        }
+    }
+}
+
+impl<T, A: Alloc> Vec<T, A> {
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` such that `f(&e)` returns `false`.
+    /// This method operates in place and preserves the order of the retained
+    /// elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut vec = vec![1, 2, 3, 4];
+    /// vec.retain(|&x| x%2 == 0);
+    /// assert_eq!(vec, [2, 4]);
+    /// ```
+    #[stable(feature = "rust1", since = "1.0.0")]
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(&T) -> bool
+    {
+        self.drain_filter(|x| !f(x));
+    }
+
     /// Creates a draining iterator that removes the specified range in the vector
     /// and yields the removed items.
     ///
@@ -1071,7 +1314,7 @@ impl<T> Vec<T> {
     /// assert_eq!(v, &[]);
     /// ```
     #[stable(feature = "drain", since = "1.6.0")]
-    pub fn drain<R>(&mut self, range: R) -> Drain<T>
+    pub fn drain<R>(&mut self, range: R) -> Drain<T, A>
         where R: RangeArgument<usize>
     {
         // Memory safety
@@ -1113,58 +1356,9 @@ impl<T> Vec<T> {
             }
         }
     }
+}
 
-    /// Clears the vector, removing all values.
-    ///
-    /// Note that this method has no effect on the allocated capacity
-    /// of the vector.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// let mut v = vec![1, 2, 3];
-    ///
-    /// v.clear();
-    ///
-    /// assert!(v.is_empty());
-    /// ```
-    #[inline]
-    #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn clear(&mut self) {
-        self.truncate(0)
-    }
-
-    /// Returns the number of elements in the vector, also referred to
-    /// as its 'length'.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// let a = vec![1, 2, 3];
-    /// assert_eq!(a.len(), 3);
-    /// ```
-    #[inline]
-    #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn len(&self) -> usize {
-        self.len
-    }
-
-    /// Returns `true` if the vector contains no elements.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// let mut v = Vec::new();
-    /// assert!(v.is_empty());
-    ///
-    /// v.push(1);
-    /// assert!(!v.is_empty());
-    /// ```
-    #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
-    }
-
+impl<T, A: Alloc + Clone> Vec<T, A> {
     /// Splits the collection into two at the given index.
     ///
     /// Returns a newly allocated `Self`. `self` contains elements `[0, at)`,
@@ -1190,7 +1384,7 @@ impl<T> Vec<T> {
         assert!(at <= self.len(), "`at` out of bounds");
 
         let other_len = self.len - at;
-        let mut other = Vec::with_capacity(other_len);
+        let mut other = Vec::with_capacity_in(other_len, self.buf.alloc().clone());
 
         // Unsafely `set_len` and copy items to `other`.
         unsafe {
@@ -1205,7 +1399,7 @@ impl<T> Vec<T> {
     }
 }
 
-impl<T: Clone> Vec<T> {
+impl<T: Clone, A: Alloc> Vec<T, A> {
     /// Resizes the `Vec` in-place so that `len` is equal to `new_len`.
     ///
     /// If `new_len` is greater than `len`, the `Vec` is extended by the
@@ -1262,7 +1456,7 @@ impl<T: Clone> Vec<T> {
     }
 }
 
-impl<T: Default> Vec<T> {
+impl<T: Default, A: Alloc> Vec<T, A> {
     /// Resizes the `Vec` in-place so that `len` is equal to `new_len`.
     ///
     /// If `new_len` is greater than `len`, the `Vec` is extended by the
@@ -1317,7 +1511,7 @@ impl<T: Default> ExtendWith<T> for ExtendDefault {
     fn next(&self) -> T { Default::default() }
     fn last(self) -> T { Default::default() }
 }
-impl<T> Vec<T> {
+impl<T, A: Alloc> Vec<T, A> {
     /// Extend the vector by `n` values, using the given generator.
     fn extend_with<E: ExtendWith<T>>(&mut self, n: usize, value: E) {
         self.reserve(n);
@@ -1377,7 +1571,7 @@ impl<'a> Drop for SetLenOnDrop<'a> {
     }
 }
 
-impl<T: PartialEq> Vec<T> {
+impl<T: PartialEq, A: Alloc> Vec<T, A> {
     /// Removes consecutive repeated elements in the vector.
     ///
     /// If the vector is sorted, this removes all duplicates.
@@ -1497,28 +1691,29 @@ impl_spec_from_elem!(f64, |x: f64| x == 0. && x.is_sign_positive());
 ////////////////////////////////////////////////////////////////////////////////
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Clone> Clone for Vec<T> {
-    #[cfg(not(test))]
-    fn clone(&self) -> Vec<T> {
-        <[T]>::to_vec(&**self)
+impl<T: Clone, A: Alloc + Clone> Clone for Vec<T, A> {
+    fn clone(&self) -> Vec<T, A> {
+        let mut vec = Vec::with_capacity_in(self.len(), self.buf.alloc().clone());
+        vec.extend_from_slice(self);
+        vec
     }
 
-    // HACK(japaric): with cfg(test) the inherent `[T]::to_vec` method, which is
-    // required for this method definition, is not available. Instead use the
-    // `slice::to_vec`  function which is only available with cfg(test)
-    // NB see the slice::hack module in slice.rs for more information
-    #[cfg(test)]
-    fn clone(&self) -> Vec<T> {
-        ::slice::to_vec(&**self)
-    }
+    fn clone_from(&mut self, other: &Vec<T, A>) {
+        // drop anything in self that will not be overwritten
+        self.truncate(other.len());
+        let len = self.len();
 
-    fn clone_from(&mut self, other: &Vec<T>) {
-        other.as_slice().clone_into(self);
+        // reuse the contained values' allocations/resources.
+        self.clone_from_slice(&other[..len]);
+
+        // self.len <= other.len due to the truncate above, so the
+        // slice here is always in-bounds.
+        self.extend_from_slice(&other[len..]);
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Hash> Hash for Vec<T> {
+impl<T: Hash, A: Alloc> Hash for Vec<T, A> {
     #[inline]
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
         Hash::hash(&**self, state)
@@ -1527,7 +1722,7 @@ impl<T: Hash> Hash for Vec<T> {
 
 #[stable(feature = "rust1", since = "1.0.0")]
 #[rustc_on_unimplemented = "vector indices are of type `usize` or ranges of `usize`"]
-impl<T, I> Index<I> for Vec<T>
+impl<T, I, A: Alloc> Index<I> for Vec<T, A>
 where
     I: ::core::slice::SliceIndex<[T]>,
 {
@@ -1541,7 +1736,7 @@ where
 
 #[stable(feature = "rust1", since = "1.0.0")]
 #[rustc_on_unimplemented = "vector indices are of type `usize` or ranges of `usize`"]
-impl<T, I> IndexMut<I> for Vec<T>
+impl<T, I, A: Alloc> IndexMut<I> for Vec<T, A>
 where
     I: ::core::slice::SliceIndex<[T]>,
 {
@@ -1552,7 +1747,7 @@ where
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ops::Deref for Vec<T> {
+impl<T, A: Alloc> ops::Deref for Vec<T, A> {
     type Target = [T];
 
     fn deref(&self) -> &[T] {
@@ -1565,7 +1760,7 @@ impl<T> ops::Deref for Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ops::DerefMut for Vec<T> {
+impl<T, A: Alloc> ops::DerefMut for Vec<T, A> {
     fn deref_mut(&mut self) -> &mut [T] {
         unsafe {
             let ptr = self.buf.ptr();
@@ -1579,14 +1774,14 @@ impl<T> ops::DerefMut for Vec<T> {
 impl<T> FromIterator<T> for Vec<T> {
     #[inline]
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Vec<T> {
-        <Self as SpecExtend<T, I::IntoIter>>::from_iter(iter.into_iter())
+        <Self as SpecFromIter<T, I::IntoIter>>::from_iter(iter.into_iter())
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> IntoIterator for Vec<T> {
+impl<T, A: Alloc> IntoIterator for Vec<T, A> {
     type Item = T;
-    type IntoIter = IntoIter<T>;
+    type IntoIter = IntoIter<T, A>;
 
     /// Creates a consuming iterator, that is, one that moves each value out of
     /// the vector (from start to end). The vector cannot be used after calling
@@ -1602,7 +1797,7 @@ impl<T> IntoIterator for Vec<T> {
     /// }
     /// ```
     #[inline]
-    fn into_iter(mut self) -> IntoIter<T> {
+    fn into_iter(mut self) -> IntoIter<T, A> {
         unsafe {
             let begin = self.as_mut_ptr();
             assume(!begin.is_null());
@@ -1611,12 +1806,11 @@ impl<T> IntoIterator for Vec<T> {
             } else {
                 begin.offset(self.len() as isize) as *const T
             };
-            let cap = self.buf.cap();
+            let buf = ptr::read(&self.buf);
             mem::forget(self);
             IntoIter {
-                buf: NonNull::new_unchecked(begin),
+                buf,
                 phantom: PhantomData,
-                cap,
                 ptr: begin,
                 end,
             }
@@ -1625,7 +1819,7 @@ impl<T> IntoIterator for Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, T> IntoIterator for &'a Vec<T> {
+impl<'a, T, A: Alloc> IntoIterator for &'a Vec<T, A> {
     type Item = &'a T;
     type IntoIter = slice::Iter<'a, T>;
 
@@ -1635,7 +1829,7 @@ impl<'a, T> IntoIterator for &'a Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, T> IntoIterator for &'a mut Vec<T> {
+impl<'a, T, A: Alloc> IntoIterator for &'a mut Vec<T, A> {
     type Item = &'a mut T;
     type IntoIter = slice::IterMut<'a, T>;
 
@@ -1645,20 +1839,19 @@ impl<'a, T> IntoIterator for &'a mut Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> Extend<T> for Vec<T> {
+impl<T, A: Alloc> Extend<T> for Vec<T, A> {
     #[inline]
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         <Self as SpecExtend<T, I::IntoIter>>::spec_extend(self, iter.into_iter())
     }
 }
 
-// Specialization trait used for Vec::from_iter and Vec::extend
-trait SpecExtend<T, I> {
+// Specialization trait used for Vec::from_iter
+trait SpecFromIter<T, I> {
     fn from_iter(iter: I) -> Self;
-    fn spec_extend(&mut self, iter: I);
 }
 
-impl<T, I> SpecExtend<T, I> for Vec<T>
+impl<T, I> SpecFromIter<T, I> for Vec<T>
     where I: Iterator<Item=T>,
 {
     default fn from_iter(mut iterator: I) -> Self {
@@ -1682,13 +1875,9 @@ impl<T, I> SpecExtend<T, I> for Vec<T>
         <Vec<T> as SpecExtend<T, I>>::spec_extend(&mut vector, iterator);
         vector
     }
-
-    default fn spec_extend(&mut self, iter: I) {
-        self.extend_desugared(iter)
-    }
 }
 
-impl<T, I> SpecExtend<T, I> for Vec<T>
+impl<T, I> SpecFromIter<T, I> for Vec<T>
     where I: TrustedLen<Item=T>,
 {
     default fn from_iter(iterator: I) -> Self {
@@ -1696,7 +1885,53 @@ impl<T, I> SpecExtend<T, I> for Vec<T>
         vector.spec_extend(iterator);
         vector
     }
+}
+
+impl<T> SpecFromIter<T, IntoIter<T>> for Vec<T> {
+    fn from_iter(iterator: IntoIter<T>) -> Self {
+        // A common case is passing a vector into a function which immediately
+        // re-collects into a vector. We can short circuit this if the IntoIter
+        // has not been advanced at all.
+        if iterator.buf.ptr() as *const _ == iterator.ptr {
+            unsafe {
+                let len = iterator.len();
+                let buf = ptr::read(&iterator.buf);
+                mem::forget(iterator);
+                Vec { buf, len }
+            }
+        } else {
+            let mut vector = Vec::new();
+            vector.spec_extend(iterator);
+            vector
+        }
+    }
+}
 
+impl<'a, T: 'a, I> SpecFromIter<&'a T, I> for Vec<T>
+    where I: Iterator<Item=&'a T>,
+          T: Clone,
+{
+    fn from_iter(iterator: I) -> Self {
+        SpecFromIter::from_iter(iterator.cloned())
+    }
+}
+
+// Specialization trait used for Vec::extend
+trait SpecExtend<T, I> {
+    fn spec_extend(&mut self, iter: I);
+}
+
+impl<T, I, A: Alloc> SpecExtend<T, I> for Vec<T, A>
+    where I: Iterator<Item=T>,
+{
+    default fn spec_extend(&mut self, iter: I) {
+        self.extend_desugared(iter)
+    }
+}
+
+impl<T, I, A: Alloc> SpecExtend<T, I> for Vec<T, A>
+    where I: TrustedLen<Item=T>,
+{
     default fn spec_extend(&mut self, iterator: I) {
         // This is the case for a TrustedLen iterator.
         let (low, high) = iterator.size_hint();
@@ -1723,27 +1958,8 @@ impl<T, I> SpecExtend<T, I> for Vec<T>
     }
 }
 
-impl<T> SpecExtend<T, IntoIter<T>> for Vec<T> {
-    fn from_iter(iterator: IntoIter<T>) -> Self {
-        // A common case is passing a vector into a function which immediately
-        // re-collects into a vector. We can short circuit this if the IntoIter
-        // has not been advanced at all.
-        if iterator.buf.as_ptr() as *const _ == iterator.ptr {
-            unsafe {
-                let vec = Vec::from_raw_parts(iterator.buf.as_ptr(),
-                                              iterator.len(),
-                                              iterator.cap);
-                mem::forget(iterator);
-                vec
-            }
-        } else {
-            let mut vector = Vec::new();
-            vector.spec_extend(iterator);
-            vector
-        }
-    }
-
-    fn spec_extend(&mut self, mut iterator: IntoIter<T>) {
+impl<T, A: Alloc, B: Alloc> SpecExtend<T, IntoIter<T, B>> for Vec<T, A> {
+    fn spec_extend(&mut self, mut iterator: IntoIter<T, B>) {
         unsafe {
             self.append_elements(iterator.as_slice() as _);
         }
@@ -1751,20 +1967,16 @@ impl<T> SpecExtend<T, IntoIter<T>> for Vec<T> {
     }
 }
 
-impl<'a, T: 'a, I> SpecExtend<&'a T, I> for Vec<T>
+impl<'a, T: 'a, I, A: Alloc> SpecExtend<&'a T, I> for Vec<T, A>
     where I: Iterator<Item=&'a T>,
           T: Clone,
 {
-    default fn from_iter(iterator: I) -> Self {
-        SpecExtend::from_iter(iterator.cloned())
-    }
-
     default fn spec_extend(&mut self, iterator: I) {
         self.spec_extend(iterator.cloned())
     }
 }
 
-impl<'a, T: 'a> SpecExtend<&'a T, slice::Iter<'a, T>> for Vec<T>
+impl<'a, T: 'a, A: Alloc> SpecExtend<&'a T, slice::Iter<'a, T>> for Vec<T, A>
     where T: Copy,
 {
     fn spec_extend(&mut self, iterator: slice::Iter<'a, T>) {
@@ -1778,7 +1990,7 @@ impl<'a, T: 'a> SpecExtend<&'a T, slice::Iter<'a, T>> for Vec<T>
     }
 }
 
-impl<T> Vec<T> {
+impl<T, A: Alloc> Vec<T, A> {
     fn extend_desugared<I: Iterator<Item = T>>(&mut self, mut iterator: I) {
         // This is the case for a general iterator.
         //
@@ -1838,7 +2050,7 @@ impl<T> Vec<T> {
     /// ```
     #[inline]
     #[stable(feature = "vec_splice", since = "1.21.0")]
-    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<I::IntoIter>
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<I::IntoIter, A>
         where R: RangeArgument<usize>, I: IntoIterator<Item=T>
     {
         Splice {
@@ -1893,7 +2105,7 @@ impl<T> Vec<T> {
     /// assert_eq!(odds, vec![1, 3, 5, 9, 11, 13, 15]);
     /// ```
     #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
-    pub fn drain_filter<F>(&mut self, filter: F) -> DrainFilter<T, F>
+    pub fn drain_filter<F>(&mut self, filter: F) -> DrainFilter<T, F, A>
         where F: FnMut(&mut T) -> bool,
     {
         let old_len = self.len();
@@ -1918,19 +2130,19 @@ impl<T> Vec<T> {
 ///
 /// [`copy_from_slice`]: ../../std/primitive.slice.html#method.copy_from_slice
 #[stable(feature = "extend_ref", since = "1.2.0")]
-impl<'a, T: 'a + Copy> Extend<&'a T> for Vec<T> {
+impl<'a, T: 'a + Copy, A: Alloc> Extend<&'a T> for Vec<T, A> {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
         self.spec_extend(iter.into_iter())
     }
 }
 
 macro_rules! __impl_slice_eq1 {
-    ($Lhs: ty, $Rhs: ty) => {
-        __impl_slice_eq1! { $Lhs, $Rhs, Sized }
+    ([$($vars:tt)*] $Lhs: ty, $Rhs: ty) => {
+        __impl_slice_eq1! { [$($vars)*] $Lhs, $Rhs, Sized }
     };
-    ($Lhs: ty, $Rhs: ty, $Bound: ident) => {
+    ([$($vars:tt)*] $Lhs: ty, $Rhs: ty, $Bound: ident) => {
         #[stable(feature = "rust1", since = "1.0.0")]
-        impl<'a, 'b, A: $Bound, B> PartialEq<$Rhs> for $Lhs where A: PartialEq<B> {
+        impl<'a, 'b, T: $Bound, U, $($vars)*> PartialEq<$Rhs> for $Lhs where T: PartialEq<U> {
             #[inline]
             fn eq(&self, other: &$Rhs) -> bool { self[..] == other[..] }
             #[inline]
@@ -1939,23 +2151,23 @@ macro_rules! __impl_slice_eq1 {
     }
 }
 
-__impl_slice_eq1! { Vec<A>, Vec<B> }
-__impl_slice_eq1! { Vec<A>, &'b [B] }
-__impl_slice_eq1! { Vec<A>, &'b mut [B] }
-__impl_slice_eq1! { Cow<'a, [A]>, &'b [B], Clone }
-__impl_slice_eq1! { Cow<'a, [A]>, &'b mut [B], Clone }
-__impl_slice_eq1! { Cow<'a, [A]>, Vec<B>, Clone }
+__impl_slice_eq1! { [A: Alloc, B: Alloc] Vec<T, A>, Vec<U, B> }
+__impl_slice_eq1! { [A: Alloc] Vec<T, A>, &'b [U] }
+__impl_slice_eq1! { [A: Alloc] Vec<T, A>, &'b mut [U] }
+__impl_slice_eq1! { [] Cow<'a, [T]>, &'b [U], Clone }
+__impl_slice_eq1! { [] Cow<'a, [T]>, &'b mut [U], Clone }
+__impl_slice_eq1! { [A: Alloc] Cow<'a, [T]>, Vec<U, A>, Clone }
 
 macro_rules! array_impls {
     ($($N: expr)+) => {
         $(
             // NOTE: some less important impls are omitted to reduce code bloat
-            __impl_slice_eq1! { Vec<A>, [B; $N] }
-            __impl_slice_eq1! { Vec<A>, &'b [B; $N] }
-            // __impl_slice_eq1! { Vec<A>, &'b mut [B; $N] }
-            // __impl_slice_eq1! { Cow<'a, [A]>, [B; $N], Clone }
-            // __impl_slice_eq1! { Cow<'a, [A]>, &'b [B; $N], Clone }
-            // __impl_slice_eq1! { Cow<'a, [A]>, &'b mut [B; $N], Clone }
+            __impl_slice_eq1! { [A: Alloc] Vec<T, A>, [U; $N] }
+            __impl_slice_eq1! { [A: Alloc] Vec<T, A>, &'b [U; $N] }
+            // __impl_slice_eq1! { [A: Alloc] Vec<T, A>, &'b mut [U; $N] }
+            // __impl_slice_eq1! { [] Cow<'a, [T]>, [U; $N], Clone }
+            // __impl_slice_eq1! { [] Cow<'a, [T]>, &'b [U; $N], Clone }
+            // __impl_slice_eq1! { [] Cow<'a, [T]>, &'b mut [U; $N], Clone }
         )+
     }
 }
@@ -1969,27 +2181,27 @@ array_impls! {
 
 /// Implements comparison of vectors, lexicographically.
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: PartialOrd> PartialOrd for Vec<T> {
+impl<T: PartialOrd, A: Alloc> PartialOrd for Vec<T, A> {
     #[inline]
-    fn partial_cmp(&self, other: &Vec<T>) -> Option<Ordering> {
+    fn partial_cmp(&self, other: &Vec<T, A>) -> Option<Ordering> {
         PartialOrd::partial_cmp(&**self, &**other)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Eq> Eq for Vec<T> {}
+impl<T: Eq, A: Alloc> Eq for Vec<T, A> {}
 
 /// Implements ordering of vectors, lexicographically.
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Ord> Ord for Vec<T> {
+impl<T: Ord, A: Alloc> Ord for Vec<T, A> {
     #[inline]
-    fn cmp(&self, other: &Vec<T>) -> Ordering {
+    fn cmp(&self, other: &Vec<T, A>) -> Ordering {
         Ord::cmp(&**self, &**other)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-unsafe impl<#[may_dangle] T> Drop for Vec<T> {
+unsafe impl<#[may_dangle] T, A: Alloc> Drop for Vec<T, A> {
     fn drop(&mut self) {
         unsafe {
             // use drop for [T]
@@ -2008,35 +2220,35 @@ impl<T> Default for Vec<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: fmt::Debug> fmt::Debug for Vec<T> {
+impl<T: fmt::Debug, A: Alloc> fmt::Debug for Vec<T, A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Debug::fmt(&**self, f)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> AsRef<Vec<T>> for Vec<T> {
-    fn as_ref(&self) -> &Vec<T> {
+impl<T, A: Alloc> AsRef<Vec<T, A>> for Vec<T, A> {
+    fn as_ref(&self) -> &Vec<T, A> {
         self
     }
 }
 
 #[stable(feature = "vec_as_mut", since = "1.5.0")]
-impl<T> AsMut<Vec<T>> for Vec<T> {
-    fn as_mut(&mut self) -> &mut Vec<T> {
+impl<T, A: Alloc> AsMut<Vec<T, A>> for Vec<T, A> {
+    fn as_mut(&mut self) -> &mut Vec<T, A> {
         self
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> AsRef<[T]> for Vec<T> {
+impl<T, A: Alloc> AsRef<[T]> for Vec<T, A> {
     fn as_ref(&self) -> &[T] {
         self
     }
 }
 
 #[stable(feature = "vec_as_mut", since = "1.5.0")]
-impl<T> AsMut<[T]> for Vec<T> {
+impl<T, A: Alloc> AsMut<[T]> for Vec<T, A> {
     fn as_mut(&mut self) -> &mut [T] {
         self
     }
@@ -2135,16 +2347,15 @@ impl<'a, T> FromIterator<T> for Cow<'a, [T]> where T: Clone {
 /// [`Vec`]: struct.Vec.html
 /// [`IntoIterator`]: ../../std/iter/trait.IntoIterator.html
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct IntoIter<T> {
-    buf: NonNull<T>,
+pub struct IntoIter<T, A: Alloc = Heap> {
+    buf: RawVec<T, A>,
     phantom: PhantomData<T>,
-    cap: usize,
     ptr: *const T,
     end: *const T,
 }
 
 #[stable(feature = "vec_intoiter_debug", since = "1.13.0")]
-impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+impl<T: fmt::Debug, A: Alloc> fmt::Debug for IntoIter<T, A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_tuple("IntoIter")
             .field(&self.as_slice())
@@ -2152,7 +2363,7 @@ impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
     }
 }
 
-impl<T> IntoIter<T> {
+impl<T, A: Alloc> IntoIter<T, A> {
     /// Returns the remaining items of this iterator as a slice.
     ///
     /// # Examples
@@ -2193,12 +2404,12 @@ impl<T> IntoIter<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-unsafe impl<T: Send> Send for IntoIter<T> {}
+unsafe impl<T: Send, A: Alloc + Send> Send for IntoIter<T, A> {}
 #[stable(feature = "rust1", since = "1.0.0")]
-unsafe impl<T: Sync> Sync for IntoIter<T> {}
+unsafe impl<T: Sync, A: Alloc + Sync> Sync for IntoIter<T, A> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> Iterator for IntoIter<T> {
+impl<T, A: Alloc> Iterator for IntoIter<T, A> {
     type Item = T;
 
     #[inline]
@@ -2242,7 +2453,7 @@ impl<T> Iterator for IntoIter<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> DoubleEndedIterator for IntoIter<T> {
+impl<T, A: Alloc> DoubleEndedIterator for IntoIter<T, A> {
     #[inline]
     fn next_back(&mut self) -> Option<T> {
         unsafe {
@@ -2267,33 +2478,34 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ExactSizeIterator for IntoIter<T> {
+impl<T, A: Alloc> ExactSizeIterator for IntoIter<T, A> {
     fn is_empty(&self) -> bool {
         self.ptr == self.end
     }
 }
 
 #[unstable(feature = "fused", issue = "35602")]
-impl<T> FusedIterator for IntoIter<T> {}
+impl<T, A: Alloc> FusedIterator for IntoIter<T, A> {}
 
 #[unstable(feature = "trusted_len", issue = "37572")]
-unsafe impl<T> TrustedLen for IntoIter<T> {}
+unsafe impl<T, A: Alloc> TrustedLen for IntoIter<T, A> {}
 
 #[stable(feature = "vec_into_iter_clone", since = "1.8.0")]
-impl<T: Clone> Clone for IntoIter<T> {
-    fn clone(&self) -> IntoIter<T> {
-        self.as_slice().to_owned().into_iter()
+impl<T: Clone, A: Alloc + Clone> Clone for IntoIter<T, A> {
+    fn clone(&self) -> IntoIter<T, A> {
+        let mut vec = Vec::with_capacity_in(self.len(), self.buf.alloc().clone());
+        vec.extend_from_slice(self.as_slice());
+        vec.into_iter()
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-unsafe impl<#[may_dangle] T> Drop for IntoIter<T> {
+unsafe impl<#[may_dangle] T, A: Alloc> Drop for IntoIter<T, A> {
     fn drop(&mut self) {
         // destroy the remaining elements
         for _x in self.by_ref() {}
 
         // RawVec handles deallocation
-        let _ = unsafe { RawVec::from_raw_parts(self.buf.as_ptr(), self.cap) };
     }
 }
 
@@ -2304,18 +2516,18 @@ unsafe impl<#[may_dangle] T> Drop for IntoIter<T> {
 /// [`drain`]: struct.Vec.html#method.drain
 /// [`Vec`]: struct.Vec.html
 #[stable(feature = "drain", since = "1.6.0")]
-pub struct Drain<'a, T: 'a> {
+pub struct Drain<'a, T: 'a, A: 'a + Alloc = Heap> {
     /// Index of tail to preserve
     tail_start: usize,
     /// Length of tail
     tail_len: usize,
     /// Current remaining range to remove
     iter: slice::Iter<'a, T>,
-    vec: NonNull<Vec<T>>,
+    vec: NonNull<Vec<T, A>>,
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl<'a, T: 'a + fmt::Debug> fmt::Debug for Drain<'a, T> {
+impl<'a, T: 'a + fmt::Debug, A: Alloc> fmt::Debug for Drain<'a, T, A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_tuple("Drain")
          .field(&self.iter.as_slice())
@@ -2324,12 +2536,12 @@ impl<'a, T: 'a + fmt::Debug> fmt::Debug for Drain<'a, T> {
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-unsafe impl<'a, T: Sync> Sync for Drain<'a, T> {}
+unsafe impl<'a, T: Sync, A: Alloc + Sync> Sync for Drain<'a, T, A> {}
 #[stable(feature = "drain", since = "1.6.0")]
-unsafe impl<'a, T: Send> Send for Drain<'a, T> {}
+unsafe impl<'a, T: Send, A: Alloc + Send> Send for Drain<'a, T, A> {}
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl<'a, T> Iterator for Drain<'a, T> {
+impl<'a, T, A: Alloc> Iterator for Drain<'a, T, A> {
     type Item = T;
 
     #[inline]
@@ -2343,7 +2555,7 @@ impl<'a, T> Iterator for Drain<'a, T> {
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl<'a, T> DoubleEndedIterator for Drain<'a, T> {
+impl<'a, T, A: Alloc> DoubleEndedIterator for Drain<'a, T, A> {
     #[inline]
     fn next_back(&mut self) -> Option<T> {
         self.iter.next_back().map(|elt| unsafe { ptr::read(elt as *const _) })
@@ -2351,7 +2563,7 @@ impl<'a, T> DoubleEndedIterator for Drain<'a, T> {
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl<'a, T> Drop for Drain<'a, T> {
+impl<'a, T, A: Alloc> Drop for Drain<'a, T, A> {
     fn drop(&mut self) {
         // exhaust self first
         while let Some(_) = self.next() {}
@@ -2373,14 +2585,14 @@ impl<'a, T> Drop for Drain<'a, T> {
 
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl<'a, T> ExactSizeIterator for Drain<'a, T> {
+impl<'a, T, A: Alloc> ExactSizeIterator for Drain<'a, T, A> {
     fn is_empty(&self) -> bool {
         self.iter.is_empty()
     }
 }
 
 #[unstable(feature = "fused", issue = "35602")]
-impl<'a, T> FusedIterator for Drain<'a, T> {}
+impl<'a, T, A: Alloc> FusedIterator for Drain<'a, T, A> {}
 
 /// A place for insertion at the back of a `Vec`.
 ///
@@ -2390,15 +2602,15 @@ impl<'a, T> FusedIterator for Drain<'a, T> {}
            reason = "struct name and placement protocol are subject to change",
            issue = "30172")]
 #[derive(Debug)]
-pub struct PlaceBack<'a, T: 'a> {
-    vec: &'a mut Vec<T>,
+pub struct PlaceBack<'a, T: 'a, A: 'a + Alloc = Heap> {
+    vec: &'a mut Vec<T, A>,
 }
 
 #[unstable(feature = "collection_placement",
            reason = "placement protocol is subject to change",
            issue = "30172")]
-impl<'a, T> Placer<T> for PlaceBack<'a, T> {
-    type Place = PlaceBack<'a, T>;
+impl<'a, T, A: Alloc> Placer<T> for PlaceBack<'a, T, A> {
+    type Place = PlaceBack<'a, T, A>;
 
     fn make_place(self) -> Self {
         // This will panic or abort if we would allocate > isize::MAX bytes
@@ -2413,7 +2625,7 @@ impl<'a, T> Placer<T> for PlaceBack<'a, T> {
 #[unstable(feature = "collection_placement",
            reason = "placement protocol is subject to change",
            issue = "30172")]
-unsafe impl<'a, T> Place<T> for PlaceBack<'a, T> {
+unsafe impl<'a, T, A: Alloc> Place<T> for PlaceBack<'a, T, A> {
     fn pointer(&mut self) -> *mut T {
         unsafe { self.vec.as_mut_ptr().offset(self.vec.len as isize) }
     }
@@ -2422,7 +2634,7 @@ unsafe impl<'a, T> Place<T> for PlaceBack<'a, T> {
 #[unstable(feature = "collection_placement",
            reason = "placement protocol is subject to change",
            issue = "30172")]
-impl<'a, T> InPlace<T> for PlaceBack<'a, T> {
+impl<'a, T, A: Alloc> InPlace<T> for PlaceBack<'a, T, A> {
     type Owner = &'a mut T;
 
     unsafe fn finalize(mut self) -> &'a mut T {
@@ -2442,13 +2654,13 @@ impl<'a, T> InPlace<T> for PlaceBack<'a, T> {
 /// [`Vec`]: struct.Vec.html
 #[derive(Debug)]
 #[stable(feature = "vec_splice", since = "1.21.0")]
-pub struct Splice<'a, I: Iterator + 'a> {
-    drain: Drain<'a, I::Item>,
+pub struct Splice<'a, I: Iterator + 'a, A: 'a + Alloc = Heap> {
+    drain: Drain<'a, I::Item, A>,
     replace_with: I,
 }
 
 #[stable(feature = "vec_splice", since = "1.21.0")]
-impl<'a, I: Iterator> Iterator for Splice<'a, I> {
+impl<'a, I: Iterator, A: Alloc> Iterator for Splice<'a, I, A> {
     type Item = I::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -2461,18 +2673,18 @@ impl<'a, I: Iterator> Iterator for Splice<'a, I> {
 }
 
 #[stable(feature = "vec_splice", since = "1.21.0")]
-impl<'a, I: Iterator> DoubleEndedIterator for Splice<'a, I> {
+impl<'a, I: Iterator, A: Alloc> DoubleEndedIterator for Splice<'a, I, A> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.drain.next_back()
     }
 }
 
 #[stable(feature = "vec_splice", since = "1.21.0")]
-impl<'a, I: Iterator> ExactSizeIterator for Splice<'a, I> {}
+impl<'a, I: Iterator, A: Alloc> ExactSizeIterator for Splice<'a, I, A> {}
 
 
 #[stable(feature = "vec_splice", since = "1.21.0")]
-impl<'a, I: Iterator> Drop for Splice<'a, I> {
+impl<'a, I: Iterator, A: Alloc> Drop for Splice<'a, I, A> {
     fn drop(&mut self) {
         // exhaust drain first
         while let Some(_) = self.drain.next() {}
@@ -2515,7 +2727,7 @@ impl<'a, I: Iterator> Drop for Splice<'a, I> {
 }
 
 /// Private helper methods for `Splice::drop`
-impl<'a, T> Drain<'a, T> {
+impl<'a, T, A: Alloc> Drain<'a, T, A> {
     /// The range from `self.vec.len` to `self.tail_start` contains elements
     /// that have been moved out.
     /// Fill that range as much as possible with new elements from the `replace_with` iterator.
@@ -2556,10 +2768,10 @@ impl<'a, T> Drain<'a, T> {
 /// An iterator produced by calling `drain_filter` on Vec.
 #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
 #[derive(Debug)]
-pub struct DrainFilter<'a, T: 'a, F>
+pub struct DrainFilter<'a, T: 'a, F, A: 'a + Alloc = Heap>
     where F: FnMut(&mut T) -> bool,
 {
-    vec: &'a mut Vec<T>,
+    vec: &'a mut Vec<T, A>,
     idx: usize,
     del: usize,
     old_len: usize,
@@ -2567,7 +2779,7 @@ pub struct DrainFilter<'a, T: 'a, F>
 }
 
 #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
-impl<'a, T, F> Iterator for DrainFilter<'a, T, F>
+impl<'a, T, F, A: Alloc> Iterator for DrainFilter<'a, T, F, A>
     where F: FnMut(&mut T) -> bool,
 {
     type Item = T;
@@ -2601,7 +2813,7 @@ impl<'a, T, F> Iterator for DrainFilter<'a, T, F>
 }
 
 #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
-impl<'a, T, F> Drop for DrainFilter<'a, T, F>
+impl<'a, T, F, A: Alloc> Drop for DrainFilter<'a, T, F, A>
     where F: FnMut(&mut T) -> bool,
 {
     fn drop(&mut self) {
@@ -29,6 +29,7 @@ use core::slice;
 use core::hash::{Hash, Hasher};
 use core::cmp;
 
+use allocator::CollectionAllocErr;
 use raw_vec::RawVec;
 
 use super::range::RangeArgument;
@@ -566,6 +567,97 @@ impl<T> VecDeque<T> {
         }
     }
 
+    /// Tries to reserve the minimum capacity for exactly `additional` more elements to
+    /// be inserted in the given `VecDeque<T>`. After calling `try_reserve_exact`,
+    /// capacity will be greater than or equal to `self.len() + additional`.
+    /// Does nothing if the capacity is already sufficient.
+    ///
+    /// Note that the allocator may give the collection more space than it
+    /// requests. Therefore capacity can not be relied upon to be precisely
+    /// minimal. Prefer `try_reserve` if future insertions are expected.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::CollectionAllocErr;
+    /// use std::collections::VecDeque;
+    ///
+    /// fn process_data(data: &[u32]) -> Result<VecDeque<u32>, CollectionAllocErr> {
+    ///     let mut output = VecDeque::new();
+    ///
+    ///     // Pre-reserve the memory, exiting if we can't
+    ///     output.try_reserve_exact(data.len())?;
+    ///
+    ///     // Now we know this can't OOM in the middle of our complex work
+    ///     output.extend(data.iter().map(|&val| {
+    ///         val * 2 + 5 // very complicated
+    ///     }));
+    ///
+    ///     Ok(output)
+    /// }
+    /// # process_data(&[1, 2, 3]).expect("why is the test harness OOMing on 12 bytes?");
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), CollectionAllocErr> {
+        self.try_reserve(additional)
+    }
+
+    /// Tries to reserve capacity for at least `additional` more elements to be inserted
+    /// in the given `VecDeque<T>`. The collection may reserve more space to avoid
+    /// frequent reallocations. After calling `try_reserve`, capacity will be
+    /// greater than or equal to `self.len() + additional`. Does nothing if
+    /// capacity is already sufficient.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::CollectionAllocErr;
+    /// use std::collections::VecDeque;
+    ///
+    /// fn process_data(data: &[u32]) -> Result<VecDeque<u32>, CollectionAllocErr> {
+    ///     let mut output = VecDeque::new();
+    ///
+    ///     // Pre-reserve the memory, exiting if we can't
+    ///     output.try_reserve(data.len())?;
+    ///
+    ///     // Now we know this can't OOM in the middle of our complex work
+    ///     output.extend(data.iter().map(|&val| {
+    ///         val * 2 + 5 // very complicated
+    ///     }));
+    ///
+    ///     Ok(output)
+    /// }
+    /// # process_data(&[1, 2, 3]).expect("why is the test harness OOMing on 12 bytes?");
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), CollectionAllocErr> {
+        let old_cap = self.cap();
+        let used_cap = self.len() + 1;
+        let new_cap = used_cap.checked_add(additional)
+            .and_then(|needed_cap| needed_cap.checked_next_power_of_two())
+            .ok_or(CollectionAllocErr::CapacityOverflow)?;
+
+        if new_cap > old_cap {
+            self.buf.try_reserve_exact(used_cap, new_cap - used_cap)?;
+            unsafe {
+                self.handle_cap_increase(old_cap);
+            }
+        }
+        Ok(())
+    }
+
     /// Shrinks the capacity of the `VecDeque` as much as possible.
     ///
     /// It will drop down as close as possible to the length but the allocator may still inform the
@@ -1749,6 +1841,57 @@ impl<T> VecDeque<T> {
         }
     }
 
+    /// Creates an iterator which uses a closure to determine if an element should be removed.
+    ///
+    /// If the closure returns true, then the element is removed and yielded.
+    /// If the closure returns false, the element will remain in the deque and will not be yielded
+    /// by the iterator.
+    ///
+    /// The remaining elements keep their order, and are shifted towards the front in bulk as the
+    /// iterator advances. If the iterator is dropped before being fully consumed, the rest of the
+    /// elements are still filtered.
+    ///
+    /// If the closure panics, the element it panicked on and every element it had not yet been
+    /// called on are kept, and the closure is not called again.
+    ///
+    /// Note that `drain_filter` also lets you mutate every element in the filter closure,
+    /// regardless of whether you choose to keep or remove it.
+    ///
+    /// # Examples
+    ///
+    /// Splitting a deque into evens and odds:
+    ///
+    /// ```
+    /// #![feature(drain_filter)]
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut numbers: VecDeque<_> = vec![1, 2, 3, 4, 5, 6, 8, 9, 11, 13, 14, 15].into();
+    ///
+    /// let evens = numbers.drain_filter(|x| *x % 2 == 0).collect::<Vec<_>>();
+    /// let odds = numbers;
+    ///
+    /// assert_eq!(evens, vec![2, 4, 6, 8, 14]);
+    /// assert_eq!(odds, [1, 3, 5, 9, 11, 13, 15]);
+    /// ```
+    #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+    pub fn drain_filter<F>(&mut self, filter: F) -> DrainFilter<T, F>
+        where F: FnMut(&mut T) -> bool,
+    {
+        let old_len = self.len();
+
+        // Guard against us getting leaked (leak amplification)
+        self.head = self.tail;
+
+        DrainFilter {
+            deque: self,
+            idx: 0,
+            del: 0,
+            old_len,
+            pred: filter,
+            panic_flag: false,
+        }
+    }
+
     // This may panic or abort
     #[inline]
     fn grow_if_necessary(&mut self) {
@@ -2250,6 +2393,104 @@ impl<'a, T: 'a> ExactSizeIterator for Drain<'a, T> {}
 #[unstable(feature = "fused", issue = "35602")]
 impl<'a, T: 'a> FusedIterator for Drain<'a, T> {}
 
+/// An iterator produced by calling `drain_filter` on VecDeque.
+///
+/// This `struct` is created by the [`drain_filter`] method on [`VecDeque`].
+/// See its documentation for more.
+///
+/// [`drain_filter`]: struct.VecDeque.html#method.drain_filter
+/// [`VecDeque`]: struct.VecDeque.html
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+#[derive(Debug)]
+pub struct DrainFilter<'a, T: 'a, F>
+    where F: FnMut(&mut T) -> bool,
+{
+    deque: &'a mut VecDeque<T>,
+    idx: usize,
+    del: usize,
+    old_len: usize,
+    pred: F,
+    panic_flag: bool,
+}
+
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, T, F> Iterator for DrainFilter<'a, T, F>
+    where F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        unsafe {
+            while self.idx != self.old_len {
+                let i = self.idx;
+                let src = self.deque.wrap_add(self.deque.tail, i);
+                self.panic_flag = true;
+                let drained = (self.pred)(&mut *self.deque.ptr().offset(src as isize));
+                self.panic_flag = false;
+                // Only move past the element once the predicate has returned, so
+                // that if it panics the element is shifted back with the rest.
+                self.idx += 1;
+                if drained {
+                    self.del += 1;
+                    return Some(self.deque.buffer_read(src));
+                } else if self.del > 0 {
+                    let dst = self.deque.wrap_add(self.deque.tail, i - self.del);
+                    // This is safe because the deque is empty while we are
+                    // filtering it, so its elements will not have Drop::drop
+                    // called on them in the event of a panic.
+                    ptr::copy_nonoverlapping(self.deque.ptr().offset(src as isize),
+                                             self.deque.ptr().offset(dst as isize),
+                                             1);
+                }
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.idx))
+    }
+}
+
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, T, F> Drop for DrainFilter<'a, T, F>
+    where F: FnMut(&mut T) -> bool,
+{
+    fn drop(&mut self) {
+        struct BackshiftOnDrop<'a, 'b: 'a, T: 'b, F: 'a>
+            where F: FnMut(&mut T) -> bool,
+        {
+            drain: &'a mut DrainFilter<'b, T, F>,
+        }
+
+        impl<'a, 'b, T, F> Drop for BackshiftOnDrop<'a, 'b, T, F>
+            where F: FnMut(&mut T) -> bool,
+        {
+            fn drop(&mut self) {
+                let drain = &mut *self.drain;
+                let tail = drain.deque.tail;
+                if drain.idx != drain.old_len && drain.del > 0 {
+                    // The predicate panicked: close the gap left by the removed
+                    // elements with the ones it never got to.
+                    unsafe {
+                        let src = drain.deque.wrap_add(tail, drain.idx);
+                        let dst = drain.deque.wrap_add(tail, drain.idx - drain.del);
+                        drain.deque.wrap_copy(dst, src, drain.old_len - drain.idx);
+                    }
+                }
+                drain.deque.head = drain.deque.wrap_add(tail, drain.old_len - drain.del);
+            }
+        }
+
+        let backshift = BackshiftOnDrop { drain: self };
+
+        // Don't call the predicate again if it has already panicked.
+        if !backshift.drain.panic_flag {
+            backshift.drain.by_ref().for_each(drop);
+        }
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<A: PartialEq> PartialEq for VecDeque<A> {
     fn eq(&self, other: &VecDeque<A>) -> bool {
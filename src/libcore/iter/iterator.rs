@@ -8,14 +8,15 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use array::FixedSizeArray;
 use cmp::Ordering;
 use ops::Try;
 
 use super::{AlwaysOk, LoopState};
-use super::{Chain, Cycle, Cloned, Enumerate, Filter, FilterMap, Fuse};
-use super::{Flatten, FlatMap, flatten_compat};
+use super::{ArrayBuffer, ArrayChunks, Chain, Cycle, Cloned, DedupBy, Enumerate, Filter, FilterMap};
+use super::{Flatten, FlatMap, flatten_compat, Fuse, GroupBy, Intersperse};
 use super::{Inspect, Map, Peekable, Scan, Skip, SkipWhile, StepBy, Take, TakeWhile, Rev};
-use super::{Zip, Sum, Product};
+use super::{TryShunt, Zip, Sum, Product};
 use super::{ChainState, FromIterator, ZipImpl};
 
 fn _assert_is_object_safe(_: &Iterator<Item=()>) {}
@@ -301,6 +302,98 @@ pub trait Iterator {
         StepBy{iter: self, step: step - 1, first_take: true}
     }
 
+    /// Creates an iterator which places a copy of `separator` between
+    /// adjacent elements of the original iterator.
+    ///
+    /// No separator is placed before the first element or after the last
+    /// one.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iterator_intersperse)]
+    ///
+    /// let a = [0, 1, 2];
+    /// let mut iter = a.iter().intersperse(&100);
+    ///
+    /// assert_eq!(iter.next(), Some(&0));
+    /// assert_eq!(iter.next(), Some(&100));
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next(), Some(&100));
+    /// assert_eq!(iter.next(), Some(&2));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    ///
+    /// Joining strings:
+    ///
+    /// ```
+    /// #![feature(iterator_intersperse)]
+    ///
+    /// let words = ["alpha", "beta", "gamma"];
+    /// let joined: String = words.iter().cloned().intersperse(", ").collect();
+    /// assert_eq!(joined, "alpha, beta, gamma");
+    /// ```
+    #[inline]
+    #[unstable(feature = "iterator_intersperse", issue = "0")]
+    fn intersperse(self, separator: Self::Item) -> Intersperse<Self>
+        where Self: Sized, Self::Item: Clone
+    {
+        Intersperse { iter: self.peekable(), separator, needs_sep: false }
+    }
+
+    /// Creates an iterator over the elements of the original iterator in
+    /// arrays of type `A`, such as `[T; 4]`.
+    ///
+    /// The arrays do not overlap. If the number of elements is not a
+    /// multiple of the array length, the last elements are not yielded; they
+    /// are available from [`remainder`] once the iterator is exhausted.
+    ///
+    /// [`remainder`]: struct.ArrayChunks.html#method.remainder
+    ///
+    /// # Panics
+    ///
+    /// Panics if `A` has a length of zero.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iterator_array_chunks)]
+    ///
+    /// let mut iter = "lorem".chars().array_chunks::<[_; 2]>();
+    ///
+    /// assert_eq!(iter.next(), Some(['l', 'o']));
+    /// assert_eq!(iter.next(), Some(['r', 'e']));
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(iter.remainder(), &['m']);
+    /// ```
+    ///
+    /// The array type can often be inferred:
+    ///
+    /// ```
+    /// #![feature(iterator_array_chunks)]
+    ///
+    /// let data = [1, 1, 2, 0, 6, -4, 3];
+    /// let sums: Vec<i32> = data.iter()
+    ///                          .cloned()
+    ///                          .array_chunks()
+    ///                          .map(|pair: [i32; 2]| pair[0] + pair[1])
+    ///                          .collect();
+    /// assert_eq!(sums, [2, 2, 2]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iterator_array_chunks", issue = "0")]
+    fn array_chunks<A>(self) -> ArrayChunks<Self, A>
+        where Self: Sized, A: FixedSizeArray<Self::Item>
+    {
+        let buf = ArrayBuffer::new();
+        assert!(buf.capacity() != 0, "chunk size must be non-zero");
+        ArrayChunks { iter: self, buf }
+    }
+
     /// Takes two iterators and creates a new iterator over both in sequence.
     ///
     /// `chain()` will return a new iterator which will first iterate over
@@ -1101,6 +1194,75 @@ pub trait Iterator {
         Flatten { inner: flatten_compat(self) }
     }
 
+    /// Creates an iterator which yields only the first element of each run
+    /// of consecutive elements that `same` considers equal.
+    ///
+    /// `same` is called with the first element of the current run and a
+    /// candidate element, in that order. Like [`Vec::dedup_by`], this only
+    /// removes consecutive duplicates.
+    ///
+    /// [`Vec::dedup_by`]: ../../std/vec/struct.Vec.html#method.dedup_by
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iterator_group_by)]
+    ///
+    /// let words = ["apple", "avocado", "banana", "blueberry", "apricot"];
+    /// let firsts: Vec<_> = words.iter()
+    ///                           .dedup_by(|a, b| a.as_bytes()[0] == b.as_bytes()[0])
+    ///                           .collect();
+    /// assert_eq!(firsts, [&"apple", &"banana", &"apricot"]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iterator_group_by", issue = "0")]
+    fn dedup_by<F>(self, same: F) -> DedupBy<Self, F>
+        where Self: Sized, F: FnMut(&Self::Item, &Self::Item) -> bool
+    {
+        DedupBy { iter: self, same, peeked: None }
+    }
+
+    /// Creates an iterator over the runs of consecutive elements with equal
+    /// keys, yielding each run's key along with its length.
+    ///
+    /// The key of every element is computed exactly once. Only consecutive
+    /// elements are grouped together, so a key can be yielded more than
+    /// once.
+    ///
+    /// # Examples
+    ///
+    /// Run-length encoding:
+    ///
+    /// ```
+    /// #![feature(iterator_group_by)]
+    ///
+    /// let runs: Vec<_> = "aaabccdddd".chars().group_by(|&c| c).collect();
+    /// assert_eq!(runs, [('a', 3), ('b', 1), ('c', 2), ('d', 4)]);
+    /// ```
+    ///
+    /// Grouping by a computed key:
+    ///
+    /// ```
+    /// #![feature(iterator_group_by)]
+    ///
+    /// let a = [1, 3, 5, 2, 4, 7];
+    /// let mut iter = a.iter().group_by(|&&x| x % 2 == 0);
+    ///
+    /// assert_eq!(iter.next(), Some((false, 3)));
+    /// assert_eq!(iter.next(), Some((true, 2)));
+    /// assert_eq!(iter.next(), Some((false, 1)));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iterator_group_by", issue = "0")]
+    fn group_by<K, F>(self, key: F) -> GroupBy<Self, K, F>
+        where Self: Sized, F: FnMut(&Self::Item) -> K, K: PartialEq
+    {
+        GroupBy { iter: self, key, peeked: None }
+    }
+
     /// Creates an iterator which ends after the first [`None`].
     ///
     /// After an iterator returns [`None`], future calls may or may not yield
@@ -1380,6 +1542,67 @@ pub trait Iterator {
         FromIterator::from_iter(self)
     }
 
+    /// Transforms an iterator of fallible values into a collection, stopping
+    /// at the first failure.
+    ///
+    /// The elements can be of any type implementing [`Try`], such as
+    /// [`Option`] or [`Result`]. Their successful values are collected
+    /// directly into `B` as they are produced, and the result is wrapped
+    /// into `R`. As soon as an element fails, iteration stops and its error
+    /// is returned instead.
+    ///
+    /// Unlike `collect()`, the wrapper `R` only has to share the error type
+    /// of the elements, so an iterator of [`Option`]s can be collected into
+    /// a [`Result`] with a [`NoneError`], for example.
+    ///
+    /// [`Try`]: ../../std/ops/trait.Try.html
+    /// [`Option`]: ../../std/option/enum.Option.html
+    /// [`Result`]: ../../std/result/enum.Result.html
+    /// [`NoneError`]: ../../std/option/struct.NoneError.html
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iterator_try_collect)]
+    ///
+    /// let strings = ["1", "2", "3"];
+    /// let numbers: Result<Vec<i32>, _> = strings.iter().map(|s| s.parse()).try_collect();
+    /// assert_eq!(numbers, Ok(vec![1, 2, 3]));
+    ///
+    /// let strings = ["1", "two", "3"];
+    /// let numbers: Result<Vec<i32>, _> = strings.iter().map(|s| s.parse()).try_collect();
+    /// assert!(numbers.is_err());
+    /// ```
+    ///
+    /// The iterator is not advanced past the first failure:
+    ///
+    /// ```
+    /// #![feature(iterator_try_collect)]
+    ///
+    /// let a = [Some(1), Some(2), None, Some(4)];
+    /// let mut iter = a.iter().cloned();
+    ///
+    /// let collected: Option<Vec<i32>> = iter.by_ref().try_collect();
+    /// assert_eq!(collected, None);
+    /// assert_eq!(iter.next(), Some(Some(4)));
+    /// ```
+    #[inline]
+    #[unstable(feature = "iterator_try_collect", issue = "0")]
+    fn try_collect<R, B>(self) -> R
+        where Self: Sized, Self::Item: Try,
+              R: Try<Ok=B, Error=<Self::Item as Try>::Error>,
+              B: FromIterator<<Self::Item as Try>::Ok>,
+    {
+        let mut shunt = TryShunt { iter: self, error: None };
+        let collection = FromIterator::from_iter(shunt.by_ref());
+        match shunt.error {
+            Some(e) => R::from_error(e),
+            None => R::from_ok(collection),
+        }
+    }
+
     /// Consumes an iterator, creating two collections from it.
     ///
     /// The predicate passed to `partition()` can return `true`, or `false`.
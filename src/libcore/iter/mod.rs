@@ -316,10 +316,14 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
+use array::FixedSizeArray;
 use cmp;
 use fmt;
 use iter_private::TrustedRandomAccess;
+use marker::PhantomData;
+use mem::{self, ManuallyDrop};
 use ops::Try;
+use ptr;
 use usize;
 use intrinsics;
 
@@ -3085,3 +3089,480 @@ impl<I: ExactSizeIterator, F> ExactSizeIterator for Inspect<I, F>
 #[unstable(feature = "fused", issue = "35602")]
 impl<I: FusedIterator, F> FusedIterator for Inspect<I, F>
     where F: FnMut(&I::Item) {}
+
+/// An iterator that places a copy of a separator between adjacent elements
+/// of another iterator.
+///
+/// This `struct` is created by the [`intersperse`] method on [`Iterator`]. See
+/// its documentation for more.
+///
+/// [`intersperse`]: trait.Iterator.html#method.intersperse
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[unstable(feature = "iterator_intersperse", issue = "0")]
+pub struct Intersperse<I: Iterator> where I::Item: Clone {
+    iter: Peekable<I>,
+    separator: I::Item,
+    needs_sep: bool,
+}
+
+#[unstable(feature = "iterator_intersperse", issue = "0")]
+impl<I> Iterator for Intersperse<I> where I: Iterator, I::Item: Clone {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.needs_sep && self.iter.peek().is_some() {
+            self.needs_sep = false;
+            Some(self.separator.clone())
+        } else {
+            self.needs_sep = true;
+            self.iter.next()
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every element but the one we yield next is preceded by a separator.
+        let (lo, hi) = self.iter.size_hint();
+        let next_is_elem = !self.needs_sep as usize;
+        let lo = lo.saturating_sub(next_is_elem).saturating_add(lo);
+        let hi = hi.and_then(|hi| hi.saturating_sub(next_is_elem).checked_add(hi));
+        (lo, hi)
+    }
+
+    #[inline]
+    fn fold<Acc, Fold>(mut self, init: Acc, mut fold: Fold) -> Acc
+        where Fold: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let mut acc = init;
+        if !self.needs_sep {
+            match self.iter.next() {
+                Some(x) => acc = fold(acc, x),
+                None => return acc,
+            }
+        }
+
+        let separator = self.separator;
+        self.iter.fold(acc, move |acc, x| {
+            let acc = fold(acc, separator.clone());
+            fold(acc, x)
+        })
+    }
+}
+
+#[unstable(feature = "fused", issue = "35602")]
+impl<I> FusedIterator for Intersperse<I> where I: FusedIterator, I::Item: Clone {}
+
+// If the doubled length overflows, the true length exceeds `usize::MAX` too.
+#[unstable(feature = "trusted_len", issue = "37572")]
+unsafe impl<I> TrustedLen for Intersperse<I> where I: TrustedLen, I::Item: Clone {}
+
+/// The elements of an unfinished chunk, stored in place until the chunk fills.
+struct ArrayBuffer<T, A: FixedSizeArray<T>> {
+    array: ManuallyDrop<A>,
+    /// The number of leading elements of `array` that are initialized.
+    len: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T, A: FixedSizeArray<T>> ArrayBuffer<T, A> {
+    #[inline]
+    fn new() -> Self {
+        ArrayBuffer {
+            array: ManuallyDrop::new(unsafe { mem::uninitialized() }),
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.array.as_slice().len()
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[T] {
+        &self.array.as_slice()[..self.len]
+    }
+
+    /// Appends `elt`, returning the whole array once it is full and leaving
+    /// the buffer empty again. The capacity must not be zero.
+    #[inline]
+    fn push(&mut self, elt: T) -> Option<A> {
+        unsafe {
+            ptr::write(self.array.as_mut_slice().get_unchecked_mut(self.len), elt);
+        }
+        self.len += 1;
+        if self.len == self.capacity() {
+            self.len = 0;
+            Some(unsafe { ptr::read(&*self.array) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Clone, A: FixedSizeArray<T>> Clone for ArrayBuffer<T, A> {
+    fn clone(&self) -> Self {
+        let mut buf = ArrayBuffer::new();
+        for elt in self.as_slice() {
+            buf.push(elt.clone());
+        }
+        buf
+    }
+}
+
+impl<T, A: FixedSizeArray<T>> Drop for ArrayBuffer<T, A> {
+    fn drop(&mut self) {
+        let len = self.len;
+        unsafe {
+            ptr::drop_in_place(&mut self.array.as_mut_slice()[..len]);
+        }
+    }
+}
+
+/// An iterator over an iterator's elements in fixed-size arrays.
+///
+/// This `struct` is created by the [`array_chunks`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`array_chunks`]: trait.Iterator.html#method.array_chunks
+/// [`Iterator`]: trait.Iterator.html
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[unstable(feature = "iterator_array_chunks", issue = "0")]
+pub struct ArrayChunks<I: Iterator, A: FixedSizeArray<I::Item>> {
+    iter: I,
+    buf: ArrayBuffer<I::Item, A>,
+}
+
+impl<I, A> ArrayChunks<I, A> where I: Iterator, A: FixedSizeArray<I::Item> {
+    /// Returns the elements taken from the underlying iterator that did not
+    /// make up a whole chunk.
+    ///
+    /// This is only the final, short chunk once the iterator has returned
+    /// [`None`].
+    ///
+    /// [`None`]: ../../std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iterator_array_chunks)]
+    ///
+    /// let mut iter = (1..6).array_chunks::<[_; 2]>();
+    /// assert_eq!(iter.next(), Some([1, 2]));
+    /// assert_eq!(iter.next(), Some([3, 4]));
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(iter.remainder(), &[5]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iterator_array_chunks", issue = "0")]
+    pub fn remainder(&self) -> &[I::Item] {
+        self.buf.as_slice()
+    }
+}
+
+#[unstable(feature = "iterator_array_chunks", issue = "0")]
+impl<I, A> Clone for ArrayChunks<I, A>
+    where I: Iterator + Clone, I::Item: Clone, A: FixedSizeArray<I::Item>,
+{
+    fn clone(&self) -> Self {
+        ArrayChunks { iter: self.iter.clone(), buf: self.buf.clone() }
+    }
+}
+
+#[unstable(feature = "iterator_array_chunks", issue = "0")]
+impl<I, A> fmt::Debug for ArrayChunks<I, A>
+    where I: Iterator + fmt::Debug, I::Item: fmt::Debug, A: FixedSizeArray<I::Item>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ArrayChunks")
+            .field("iter", &self.iter)
+            .field("remainder", &self.remainder())
+            .finish()
+    }
+}
+
+#[unstable(feature = "iterator_array_chunks", issue = "0")]
+impl<I, A> Iterator for ArrayChunks<I, A> where I: Iterator, A: FixedSizeArray<I::Item> {
+    type Item = A;
+
+    #[inline]
+    fn next(&mut self) -> Option<A> {
+        let buf = &mut self.buf;
+        self.iter.try_fold((), |(), x| match buf.push(x) {
+            Some(chunk) => LoopState::Break(chunk),
+            None => LoopState::Continue(()),
+        }).break_value()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        let buffered = self.buf.len;
+        let size = self.buf.capacity();
+        let lo = lo.saturating_add(buffered) / size;
+        let hi = hi.and_then(|hi| hi.checked_add(buffered)).map(|hi| hi / size);
+        (lo, hi)
+    }
+
+    #[inline]
+    fn try_fold<Acc, Fold, R>(&mut self, init: Acc, mut fold: Fold) -> R where
+        Self: Sized, Fold: FnMut(Acc, Self::Item) -> R, R: Try<Ok=Acc>
+    {
+        let buf = &mut self.buf;
+        self.iter.try_fold(init, move |acc, x| match buf.push(x) {
+            Some(chunk) => fold(acc, chunk),
+            None => Try::from_ok(acc),
+        })
+    }
+
+    #[inline]
+    fn fold<Acc, Fold>(self, init: Acc, mut fold: Fold) -> Acc
+        where Fold: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let mut buf = self.buf;
+        self.iter.fold(init, move |acc, x| match buf.push(x) {
+            Some(chunk) => fold(acc, chunk),
+            None => acc,
+        })
+    }
+}
+
+#[unstable(feature = "iterator_array_chunks", issue = "0")]
+impl<I, A> ExactSizeIterator for ArrayChunks<I, A>
+    where I: ExactSizeIterator, A: FixedSizeArray<I::Item> {}
+
+#[unstable(feature = "fused", issue = "35602")]
+impl<I, A> FusedIterator for ArrayChunks<I, A>
+    where I: FusedIterator, A: FixedSizeArray<I::Item> {}
+
+// ArrayChunks is not TrustedLen: an upper bound of `None` means the inner
+// iterator is longer than `usize::MAX`, which the number of chunks need not be.
+
+/// An iterator that yields only the first element of each run of equal
+/// elements.
+///
+/// This `struct` is created by the [`dedup_by`] method on [`Iterator`]. See its
+/// documentation for more.
+///
+/// [`dedup_by`]: trait.Iterator.html#method.dedup_by
+/// [`Iterator`]: trait.Iterator.html
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[unstable(feature = "iterator_group_by", issue = "0")]
+#[derive(Clone)]
+pub struct DedupBy<I: Iterator, F> {
+    iter: I,
+    same: F,
+    /// The first element of the next run, if it has been pulled already.
+    peeked: Option<I::Item>,
+}
+
+#[unstable(feature = "iterator_group_by", issue = "0")]
+impl<I, F> fmt::Debug for DedupBy<I, F> where I: Iterator + fmt::Debug, I::Item: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DedupBy")
+            .field("iter", &self.iter)
+            .field("peeked", &self.peeked)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iterator_group_by", issue = "0")]
+impl<I: Iterator, F> Iterator for DedupBy<I, F>
+    where F: FnMut(&I::Item, &I::Item) -> bool
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        let first = match self.peeked.take() {
+            Some(x) => x,
+            None => self.iter.next()?,
+        };
+        let same = &mut self.same;
+        self.peeked = self.iter.find(|x| !same(&first, x));
+        Some(first)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        let peeked = self.peeked.is_some() as usize;
+        let lo = if lo > 0 || peeked > 0 { 1 } else { 0 };
+        (lo, hi.and_then(|hi| hi.checked_add(peeked)))
+    }
+
+    #[inline]
+    fn fold<Acc, Fold>(self, init: Acc, mut fold: Fold) -> Acc
+        where Fold: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let DedupBy { mut iter, mut same, peeked } = self;
+        let first = match peeked.or_else(|| iter.next()) {
+            Some(x) => x,
+            None => return init,
+        };
+        let (acc, last) = iter.fold((init, first), |(acc, prev), x| {
+            if same(&prev, &x) {
+                (acc, prev)
+            } else {
+                (fold(acc, prev), x)
+            }
+        });
+        fold(acc, last)
+    }
+}
+
+#[unstable(feature = "fused", issue = "35602")]
+impl<I: FusedIterator, F> FusedIterator for DedupBy<I, F>
+    where F: FnMut(&I::Item, &I::Item) -> bool {}
+
+/// An iterator over the lengths of runs of elements with equal keys.
+///
+/// This `struct` is created by the [`group_by`] method on [`Iterator`]. See its
+/// documentation for more.
+///
+/// [`group_by`]: trait.Iterator.html#method.group_by
+/// [`Iterator`]: trait.Iterator.html
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[unstable(feature = "iterator_group_by", issue = "0")]
+#[derive(Clone)]
+pub struct GroupBy<I, K, F> {
+    iter: I,
+    key: F,
+    /// The key of the first element of the next run, if it has been pulled
+    /// already.
+    peeked: Option<K>,
+}
+
+#[unstable(feature = "iterator_group_by", issue = "0")]
+impl<I: fmt::Debug, K: fmt::Debug, F> fmt::Debug for GroupBy<I, K, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GroupBy")
+            .field("iter", &self.iter)
+            .field("peeked", &self.peeked)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iterator_group_by", issue = "0")]
+impl<I: Iterator, K, F> Iterator for GroupBy<I, K, F>
+    where F: FnMut(&I::Item) -> K, K: PartialEq
+{
+    type Item = (K, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<(K, usize)> {
+        let key = match self.peeked.take() {
+            Some(key) => key,
+            None => {
+                let x = self.iter.next()?;
+                (self.key)(&x)
+            }
+        };
+        let mut len = 1;
+        let key_fn = &mut self.key;
+        let mut next_key = None;
+        self.iter.find(|x| {
+            let k = key_fn(x);
+            if k == key {
+                len += 1;
+                false
+            } else {
+                next_key = Some(k);
+                true
+            }
+        });
+        self.peeked = next_key;
+        Some((key, len))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        let peeked = self.peeked.is_some() as usize;
+        let lo = if lo > 0 || peeked > 0 { 1 } else { 0 };
+        (lo, hi.and_then(|hi| hi.checked_add(peeked)))
+    }
+
+    #[inline]
+    fn fold<Acc, Fold>(self, init: Acc, mut fold: Fold) -> Acc
+        where Fold: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let GroupBy { mut iter, key: mut key_fn, peeked } = self;
+        let first = match peeked {
+            Some(key) => key,
+            None => match iter.next() {
+                Some(x) => key_fn(&x),
+                None => return init,
+            },
+        };
+        let (acc, key, len) = iter.fold((init, first, 1), |(acc, key, len), x| {
+            let k = key_fn(&x);
+            if k == key {
+                (acc, key, len + 1)
+            } else {
+                (fold(acc, (key, len)), k, 1)
+            }
+        });
+        fold(acc, (key, len))
+    }
+}
+
+#[unstable(feature = "fused", issue = "35602")]
+impl<I: FusedIterator, K, F> FusedIterator for GroupBy<I, K, F>
+    where F: FnMut(&I::Item) -> K, K: PartialEq {}
+
+/// Adapts an iterator of `Try` values into an iterator of their `Ok` values,
+/// stopping at the first error and stashing it. Used by `try_collect()`.
+struct TryShunt<I, E> {
+    iter: I,
+    error: Option<E>,
+}
+
+impl<I, T, E> Iterator for TryShunt<I, E> where I: Iterator, I::Item: Try<Ok=T, Error=E> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.error.is_some() {
+            return None;
+        }
+        match self.iter.next().map(Try::into_result) {
+            Some(Ok(x)) => Some(x),
+            Some(Err(e)) => {
+                self.error = Some(e);
+                None
+            }
+            None => None,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.error.is_some() {
+            (0, Some(0))
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+
+    #[inline]
+    fn try_fold<Acc, Fold, R>(&mut self, init: Acc, mut fold: Fold) -> R where
+        Self: Sized, Fold: FnMut(Acc, Self::Item) -> R, R: Try<Ok=Acc>
+    {
+        if self.error.is_some() {
+            return Try::from_ok(init);
+        }
+        let error = &mut self.error;
+        self.iter.try_fold(init, |acc, x| match Try::into_result(x) {
+            Ok(x) => LoopState::from_try(fold(acc, x)),
+            Err(e) => {
+                *error = Some(e);
+                LoopState::Break(Try::from_ok(acc))
+            }
+        }).into_try()
+    }
+}
@@ -15,6 +15,7 @@
 use convert::{Infallible, TryFrom};
 use fmt;
 use intrinsics;
+use mem;
 use ops;
 use str::FromStr;
 
@@ -1493,6 +1494,402 @@ $EndFeature, "
             #[inline]
             pub fn is_negative(self) -> bool { self < 0 }
         }
+
+        doc_comment! {
+            concat!("Calculates the quotient of Euclidean division of `self` by `rhs`.
+
+This computes the integer `n` such that `self = n * rhs + self.mod_euc(rhs)`.
+In other words, the result is `self / rhs` rounded towards negative infinity
+if `rhs` is positive, and towards positive infinity if it is negative.
+
+# Panics
+
+This function will panic if `rhs` is 0, or if the division results in overflow.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "let a: ", stringify!($SelfT), " = 7; // or any other integer type
+let b = 4;
+
+assert_eq!(a.div_euc(b), 1); // 7 >= 4 * 1
+assert_eq!(a.div_euc(-b), -1); // 7 >= -4 * -1
+assert_eq!((-a).div_euc(b), -2); // -7 >= 4 * -2
+assert_eq!((-a).div_euc(-b), 2); // -7 >= -4 * 2",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn div_euc(self, rhs: Self) -> Self {
+                let q = self / rhs;
+                if self % rhs < 0 {
+                    return if rhs > 0 { q - 1 } else { q + 1 }
+                }
+                q
+            }
+        }
+
+        doc_comment! {
+            concat!("Calculates the least nonnegative remainder of `self (mod rhs)`.
+
+This is done as if by the Euclidean division algorithm: the result
+`r` satisfies `0 <= r < rhs.abs()` and
+`self == self.div_euc(rhs) * rhs + r`.
+
+# Panics
+
+This function will panic if `rhs` is 0, or if the division results in overflow.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "let a: ", stringify!($SelfT), " = 7; // or any other integer type
+let b = 4;
+
+assert_eq!(a.mod_euc(b), 3);
+assert_eq!((-a).mod_euc(b), 1);
+assert_eq!(a.mod_euc(-b), 3);
+assert_eq!((-a).mod_euc(-b), 1);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn mod_euc(self, rhs: Self) -> Self {
+                let r = self % rhs;
+                if r < 0 {
+                    if rhs < 0 { r - rhs } else { r + rhs }
+                } else {
+                    r
+                }
+            }
+        }
+
+        doc_comment! {
+            concat!("Checked Euclidean division. Computes `self.div_euc(rhs)`,
+returning `None` if `rhs == 0` or the division results in overflow.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!((", stringify!($SelfT),
+"::min_value() + 1).checked_div_euc(-1), Some(", stringify!($SelfT), "::max_value()));
+assert_eq!(", stringify!($SelfT), "::min_value().checked_div_euc(-1), None);
+assert_eq!((1", stringify!($SelfT), ").checked_div_euc(0), None);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn checked_div_euc(self, rhs: Self) -> Option<Self> {
+                if rhs == 0 || (self == Self::min_value() && rhs == -1) {
+                    None
+                } else {
+                    Some(self.div_euc(rhs))
+                }
+            }
+        }
+
+        doc_comment! {
+            concat!("Checked Euclidean remainder. Computes `self.mod_euc(rhs)`, returning
+`None` if `rhs == 0` or the division results in overflow.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(5", stringify!($SelfT), ".checked_mod_euc(2), Some(1));
+assert_eq!(5", stringify!($SelfT), ".checked_mod_euc(0), None);
+assert_eq!(", stringify!($SelfT), "::min_value().checked_mod_euc(-1), None);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn checked_mod_euc(self, rhs: Self) -> Option<Self> {
+                if rhs == 0 || (self == Self::min_value() && rhs == -1) {
+                    None
+                } else {
+                    Some(self.mod_euc(rhs))
+                }
+            }
+        }
+
+        doc_comment! {
+            concat!("Wrapping Euclidean division. Computes `self.div_euc(rhs)`,
+wrapping around at the boundary of the type.
+
+Wrapping will only occur in `MIN / -1` on a signed type (where `MIN` is the negative minimal value
+for the type). This is equivalent to `-MIN`, a positive value that is too large to represent in the
+type. In this case, this method returns `MIN` itself.
+
+# Panics
+
+This function will panic if `rhs` is 0.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(100", stringify!($SelfT), ".wrapping_div_euc(10), 10);
+assert_eq!(", stringify!($SelfT), "::min_value().wrapping_div_euc(-1), ", stringify!($SelfT),
+"::min_value());",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn wrapping_div_euc(self, rhs: Self) -> Self {
+                self.overflowing_div_euc(rhs).0
+            }
+        }
+
+        doc_comment! {
+            concat!("Wrapping Euclidean remainder. Computes `self.mod_euc(rhs)`, wrapping around
+at the boundary of the type.
+
+Wrapping will only occur in `MIN % -1` on a signed type (where `MIN` is the negative minimal value
+for the type). In this case, this method returns 0.
+
+# Panics
+
+This function will panic if `rhs` is 0.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(100", stringify!($SelfT), ".wrapping_mod_euc(10), 0);
+assert_eq!(", stringify!($SelfT), "::min_value().wrapping_mod_euc(-1), 0);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn wrapping_mod_euc(self, rhs: Self) -> Self {
+                self.overflowing_mod_euc(rhs).0
+            }
+        }
+
+        doc_comment! {
+            concat!("Calculates the quotient of Euclidean division `self.div_euc(rhs)`.
+
+Returns a tuple of the divisor along with a boolean indicating whether an arithmetic overflow would
+occur. If an overflow would occur then `self` is returned.
+
+# Panics
+
+This function will panic if `rhs` is 0.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(5", stringify!($SelfT), ".overflowing_div_euc(2), (2, false));
+assert_eq!(", stringify!($SelfT), "::min_value().overflowing_div_euc(-1), (", stringify!($SelfT),
+"::min_value(), true));",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn overflowing_div_euc(self, rhs: Self) -> (Self, bool) {
+                if self == Self::min_value() && rhs == -1 {
+                    (self, true)
+                } else {
+                    (self.div_euc(rhs), false)
+                }
+            }
+        }
+
+        doc_comment! {
+            concat!("Calculates the remainder `self.mod_euc(rhs)` by Euclidean division.
+
+Returns a tuple of the remainder after dividing along with a boolean indicating whether an
+arithmetic overflow would occur. If an overflow would occur then 0 is returned.
+
+# Panics
+
+This function will panic if `rhs` is 0.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(5", stringify!($SelfT), ".overflowing_mod_euc(2), (1, false));
+assert_eq!(", stringify!($SelfT), "::min_value().overflowing_mod_euc(-1), (0, true));",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn overflowing_mod_euc(self, rhs: Self) -> (Self, bool) {
+                if self == Self::min_value() && rhs == -1 {
+                    (0, true)
+                } else {
+                    (self.mod_euc(rhs), false)
+                }
+            }
+        }
+
+        doc_comment! {
+            concat!("Returns the memory representation of this integer as a byte array in
+big-endian (network) byte order.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(int_to_from_bytes)]
+", $Feature, "let bytes = 0x12", stringify!($SelfT), ".to_be_bytes();
+assert_eq!(bytes[bytes.len() - 1], 0x12);
+assert!(bytes[..bytes.len() - 1].iter().all(|&b| b == 0));",
+$EndFeature, "
+```"),
+            #[unstable(feature = "int_to_from_bytes", issue = "0")]
+            #[inline]
+            pub fn to_be_bytes(self) -> [u8; $BITS / 8] {
+                self.to_be().to_ne_bytes()
+            }
+        }
+
+        doc_comment! {
+            concat!("Returns the memory representation of this integer as a byte array in
+little-endian byte order.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(int_to_from_bytes)]
+", $Feature, "let bytes = 0x12", stringify!($SelfT), ".to_le_bytes();
+assert_eq!(bytes[0], 0x12);
+assert!(bytes[1..].iter().all(|&b| b == 0));",
+$EndFeature, "
+```"),
+            #[unstable(feature = "int_to_from_bytes", issue = "0")]
+            #[inline]
+            pub fn to_le_bytes(self) -> [u8; $BITS / 8] {
+                self.to_le().to_ne_bytes()
+            }
+        }
+
+        doc_comment! {
+            concat!("Returns the memory representation of this integer as a byte array in
+native byte order.
+
+As the target platform's native endianness is used, portable code
+should use [`to_be_bytes`] or [`to_le_bytes`], as appropriate, instead.
+
+[`to_be_bytes`]: #method.to_be_bytes
+[`to_le_bytes`]: #method.to_le_bytes
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(int_to_from_bytes)]
+", $Feature, "let bytes = 0x12", stringify!($SelfT), ".to_ne_bytes();
+if cfg!(target_endian = \"big\") {
+    assert_eq!(bytes, 0x12", stringify!($SelfT), ".to_be_bytes());
+} else {
+    assert_eq!(bytes, 0x12", stringify!($SelfT), ".to_le_bytes());
+}",
+$EndFeature, "
+```"),
+            #[unstable(feature = "int_to_from_bytes", issue = "0")]
+            #[inline]
+            pub fn to_ne_bytes(self) -> [u8; $BITS / 8] {
+                unsafe { mem::transmute(self) }
+            }
+        }
+
+        doc_comment! {
+            concat!("Creates an integer value from its representation as a byte array in
+big-endian (network) byte order.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(int_to_from_bytes)]
+", $Feature, "let mut bytes = 0", stringify!($SelfT), ".to_be_bytes();
+bytes[bytes.len() - 1] = 0x12;
+assert_eq!(", stringify!($SelfT), "::from_be_bytes(bytes), 0x12);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "int_to_from_bytes", issue = "0")]
+            #[inline]
+            pub fn from_be_bytes(bytes: [u8; $BITS / 8]) -> Self {
+                Self::from_be(Self::from_ne_bytes(bytes))
+            }
+        }
+
+        doc_comment! {
+            concat!("Creates an integer value from its representation as a byte array in
+little-endian byte order.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(int_to_from_bytes)]
+", $Feature, "let mut bytes = 0", stringify!($SelfT), ".to_le_bytes();
+bytes[0] = 0x12;
+assert_eq!(", stringify!($SelfT), "::from_le_bytes(bytes), 0x12);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "int_to_from_bytes", issue = "0")]
+            #[inline]
+            pub fn from_le_bytes(bytes: [u8; $BITS / 8]) -> Self {
+                Self::from_le(Self::from_ne_bytes(bytes))
+            }
+        }
+
+        doc_comment! {
+            concat!("Creates an integer value from its memory representation as a byte
+array in native byte order.
+
+As the target platform's native endianness is used, portable code
+likely wants to use [`from_be_bytes`] or [`from_le_bytes`], as
+appropriate, instead.
+
+[`from_be_bytes`]: #method.from_be_bytes
+[`from_le_bytes`]: #method.from_le_bytes
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(int_to_from_bytes)]
+", $Feature, "let bytes = 0x12", stringify!($SelfT), ".to_ne_bytes();
+assert_eq!(", stringify!($SelfT), "::from_ne_bytes(bytes), 0x12);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "int_to_from_bytes", issue = "0")]
+            #[inline]
+            pub fn from_ne_bytes(bytes: [u8; $BITS / 8]) -> Self {
+                unsafe { mem::transmute(bytes) }
+            }
+        }
     }
 }
 
@@ -2784,6 +3181,471 @@ $EndFeature, "
                 self.one_less_than_next_power_of_two().checked_add(1)
             }
         }
+
+        doc_comment! {
+            concat!("Performs Euclidean division.
+
+For unsigned types, this is just the same as `self / rhs`.
+
+# Panics
+
+This function will panic if `rhs` is 0.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(7", stringify!($SelfT), ".div_euc(4), 1);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn div_euc(self, rhs: Self) -> Self {
+                self / rhs
+            }
+        }
+
+        doc_comment! {
+            concat!("Calculates the least remainder of `self (mod rhs)`.
+
+For unsigned types, this is just the same as `self % rhs`.
+
+# Panics
+
+This function will panic if `rhs` is 0.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(7", stringify!($SelfT), ".mod_euc(4), 3);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn mod_euc(self, rhs: Self) -> Self {
+                self % rhs
+            }
+        }
+
+        doc_comment! {
+            concat!("Checked Euclidean division. Computes `self.div_euc(rhs)`, returning `None`
+if `rhs == 0`.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(128", stringify!($SelfT), ".checked_div_euc(2), Some(64));
+assert_eq!(1", stringify!($SelfT), ".checked_div_euc(0), None);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn checked_div_euc(self, rhs: Self) -> Option<Self> {
+                self.checked_div(rhs)
+            }
+        }
+
+        doc_comment! {
+            concat!("Checked Euclidean remainder. Computes `self.mod_euc(rhs)`, returning `None`
+if `rhs == 0`.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(5", stringify!($SelfT), ".checked_mod_euc(2), Some(1));
+assert_eq!(5", stringify!($SelfT), ".checked_mod_euc(0), None);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn checked_mod_euc(self, rhs: Self) -> Option<Self> {
+                self.checked_rem(rhs)
+            }
+        }
+
+        doc_comment! {
+            concat!("Wrapping Euclidean division. Computes `self.div_euc(rhs)`.
+Wrapped division on unsigned types is just normal division.
+There's no way wrapping could ever happen.
+This function exists, so that all operations
+are accounted for in the wrapping operations.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(100", stringify!($SelfT), ".wrapping_div_euc(10), 10);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn wrapping_div_euc(self, rhs: Self) -> Self {
+                self / rhs
+            }
+        }
+
+        doc_comment! {
+            concat!("Wrapping Euclidean remainder. Computes `self.mod_euc(rhs)`.
+Wrapped modulo calculation on unsigned types is
+just the regular remainder calculation.
+There's no way wrapping could ever happen.
+This function exists, so that all operations
+are accounted for in the wrapping operations.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(100", stringify!($SelfT), ".wrapping_mod_euc(10), 0);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn wrapping_mod_euc(self, rhs: Self) -> Self {
+                self % rhs
+            }
+        }
+
+        doc_comment! {
+            concat!("Calculates the quotient of Euclidean division `self.div_euc(rhs)`.
+
+Returns a tuple of the divisor along with a boolean indicating
+whether an arithmetic overflow would occur. Note that for unsigned
+integers overflow never occurs, so the second value is always
+`false`.
+
+# Panics
+
+This function will panic if `rhs` is 0.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(5", stringify!($SelfT), ".overflowing_div_euc(2), (2, false));",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn overflowing_div_euc(self, rhs: Self) -> (Self, bool) {
+                (self / rhs, false)
+            }
+        }
+
+        doc_comment! {
+            concat!("Calculates the remainder `self.mod_euc(rhs)` by Euclidean division.
+
+Returns a tuple of the remainder after dividing along with a boolean
+indicating whether an arithmetic overflow would occur. Note that for
+unsigned integers overflow never occurs, so the second value is
+always `false`.
+
+# Panics
+
+This function will panic if `rhs` is 0.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(euclidean_division)]
+", $Feature, "assert_eq!(5", stringify!($SelfT), ".overflowing_mod_euc(2), (1, false));",
+$EndFeature, "
+```"),
+            #[unstable(feature = "euclidean_division", issue = "0")]
+            #[inline]
+            pub fn overflowing_mod_euc(self, rhs: Self) -> (Self, bool) {
+                (self % rhs, false)
+            }
+        }
+
+        doc_comment! {
+            concat!("Calculates `self + rhs + carry`, returning the sum along with the carry
+out of the most significant bit.
+
+This can be chained over the digits of a larger number, passing the carry
+from each addition into the next.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(bigint_helper_methods)]
+", $Feature, "assert_eq!(5", stringify!($SelfT), ".carrying_add(2, true), (8, false));
+assert_eq!(", stringify!($SelfT), "::max_value().carrying_add(1, false), (0, true));
+assert_eq!(", stringify!($SelfT), "::max_value().carrying_add(", stringify!($SelfT),
+"::max_value(), true), (", stringify!($SelfT), "::max_value(), true));",
+$EndFeature, "
+```"),
+            #[unstable(feature = "bigint_helper_methods", issue = "0")]
+            #[inline]
+            pub fn carrying_add(self, rhs: Self, carry: bool) -> (Self, bool) {
+                let (a, b) = self.overflowing_add(rhs);
+                let (c, d) = a.overflowing_add(carry as Self);
+                (c, b | d)
+            }
+        }
+
+        doc_comment! {
+            concat!("Calculates `self - rhs - borrow`, returning the difference along with
+whether a borrow out of the most significant bit was needed.
+
+This can be chained over the digits of a larger number, passing the borrow
+from each subtraction into the next.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(bigint_helper_methods)]
+", $Feature, "assert_eq!(5", stringify!($SelfT), ".borrowing_sub(2, true), (2, false));
+assert_eq!(0", stringify!($SelfT), ".borrowing_sub(1, false), (", stringify!($SelfT),
+"::max_value(), true));
+assert_eq!(0", stringify!($SelfT), ".borrowing_sub(0, true), (", stringify!($SelfT),
+"::max_value(), true));",
+$EndFeature, "
+```"),
+            #[unstable(feature = "bigint_helper_methods", issue = "0")]
+            #[inline]
+            pub fn borrowing_sub(self, rhs: Self, borrow: bool) -> (Self, bool) {
+                let (a, b) = self.overflowing_sub(rhs);
+                let (c, d) = a.overflowing_sub(borrow as Self);
+                (c, b | d)
+            }
+        }
+
+        doc_comment! {
+            concat!("Calculates the complete product `self * rhs` without the possibility
+of overflow.
+
+This returns the low-order bits and the high-order bits of the result,
+in that order.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(bigint_helper_methods)]
+", $Feature, "assert_eq!(5", stringify!($SelfT), ".widening_mul(2), (10, 0));
+assert_eq!(", stringify!($SelfT), "::max_value().widening_mul(2), (", stringify!($SelfT),
+"::max_value() - 1, 1));",
+$EndFeature, "
+```"),
+            #[unstable(feature = "bigint_helper_methods", issue = "0")]
+            #[inline]
+            pub fn widening_mul(self, rhs: Self) -> (Self, Self) {
+                // Schoolbook multiplication on half-width digits, which also
+                // works for the widest type.
+                const HALF: u32 = $BITS / 2;
+                let mask: Self = (1 << HALF) - 1;
+                let (a_hi, a_lo) = (self >> HALF, self & mask);
+                let (b_hi, b_lo) = (rhs >> HALF, rhs & mask);
+
+                // None of these sums can overflow.
+                let lo_lo = a_lo * b_lo;
+                let mid = a_hi * b_lo + (lo_lo >> HALF);
+                let mid2 = a_lo * b_hi + (mid & mask);
+                ((mid2 << HALF) | (lo_lo & mask),
+                 a_hi * b_hi + (mid >> HALF) + (mid2 >> HALF))
+            }
+        }
+
+        doc_comment! {
+            concat!("Calculates `self * rhs + carry` without the possibility of overflow.
+
+This returns the low-order bits and the high-order bits of the result,
+in that order. The high-order bits can be used as the carry into the
+next digit when multiplying a larger number by `rhs`.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(bigint_helper_methods)]
+", $Feature, "assert_eq!(5", stringify!($SelfT), ".carrying_mul(2, 3), (13, 0));
+assert_eq!(", stringify!($SelfT), "::max_value().carrying_mul(", stringify!($SelfT),
+"::max_value(), ", stringify!($SelfT), "::max_value()),
+           (0, ", stringify!($SelfT), "::max_value()));",
+$EndFeature, "
+```"),
+            #[unstable(feature = "bigint_helper_methods", issue = "0")]
+            #[inline]
+            pub fn carrying_mul(self, rhs: Self, carry: Self) -> (Self, Self) {
+                let (lo, hi) = self.widening_mul(rhs);
+                let (lo, carried) = lo.overflowing_add(carry);
+                // This can't overflow, as `MAX * MAX + MAX` fits in two digits.
+                (lo, hi + carried as Self)
+            }
+        }
+
+        doc_comment! {
+            concat!("Returns the memory representation of this integer as a byte array in
+big-endian (network) byte order.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(int_to_from_bytes)]
+", $Feature, "let bytes = 0x12", stringify!($SelfT), ".to_be_bytes();
+assert_eq!(bytes[bytes.len() - 1], 0x12);
+assert!(bytes[..bytes.len() - 1].iter().all(|&b| b == 0));",
+$EndFeature, "
+```"),
+            #[unstable(feature = "int_to_from_bytes", issue = "0")]
+            #[inline]
+            pub fn to_be_bytes(self) -> [u8; $BITS / 8] {
+                self.to_be().to_ne_bytes()
+            }
+        }
+
+        doc_comment! {
+            concat!("Returns the memory representation of this integer as a byte array in
+little-endian byte order.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(int_to_from_bytes)]
+", $Feature, "let bytes = 0x12", stringify!($SelfT), ".to_le_bytes();
+assert_eq!(bytes[0], 0x12);
+assert!(bytes[1..].iter().all(|&b| b == 0));",
+$EndFeature, "
+```"),
+            #[unstable(feature = "int_to_from_bytes", issue = "0")]
+            #[inline]
+            pub fn to_le_bytes(self) -> [u8; $BITS / 8] {
+                self.to_le().to_ne_bytes()
+            }
+        }
+
+        doc_comment! {
+            concat!("Returns the memory representation of this integer as a byte array in
+native byte order.
+
+As the target platform's native endianness is used, portable code
+should use [`to_be_bytes`] or [`to_le_bytes`], as appropriate, instead.
+
+[`to_be_bytes`]: #method.to_be_bytes
+[`to_le_bytes`]: #method.to_le_bytes
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(int_to_from_bytes)]
+", $Feature, "let bytes = 0x12", stringify!($SelfT), ".to_ne_bytes();
+if cfg!(target_endian = \"big\") {
+    assert_eq!(bytes, 0x12", stringify!($SelfT), ".to_be_bytes());
+} else {
+    assert_eq!(bytes, 0x12", stringify!($SelfT), ".to_le_bytes());
+}",
+$EndFeature, "
+```"),
+            #[unstable(feature = "int_to_from_bytes", issue = "0")]
+            #[inline]
+            pub fn to_ne_bytes(self) -> [u8; $BITS / 8] {
+                unsafe { mem::transmute(self) }
+            }
+        }
+
+        doc_comment! {
+            concat!("Creates an integer value from its representation as a byte array in
+big-endian (network) byte order.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(int_to_from_bytes)]
+", $Feature, "let mut bytes = 0", stringify!($SelfT), ".to_be_bytes();
+bytes[bytes.len() - 1] = 0x12;
+assert_eq!(", stringify!($SelfT), "::from_be_bytes(bytes), 0x12);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "int_to_from_bytes", issue = "0")]
+            #[inline]
+            pub fn from_be_bytes(bytes: [u8; $BITS / 8]) -> Self {
+                Self::from_be(Self::from_ne_bytes(bytes))
+            }
+        }
+
+        doc_comment! {
+            concat!("Creates an integer value from its representation as a byte array in
+little-endian byte order.
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(int_to_from_bytes)]
+", $Feature, "let mut bytes = 0", stringify!($SelfT), ".to_le_bytes();
+bytes[0] = 0x12;
+assert_eq!(", stringify!($SelfT), "::from_le_bytes(bytes), 0x12);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "int_to_from_bytes", issue = "0")]
+            #[inline]
+            pub fn from_le_bytes(bytes: [u8; $BITS / 8]) -> Self {
+                Self::from_le(Self::from_ne_bytes(bytes))
+            }
+        }
+
+        doc_comment! {
+            concat!("Creates an integer value from its memory representation as a byte
+array in native byte order.
+
+As the target platform's native endianness is used, portable code
+likely wants to use [`from_be_bytes`] or [`from_le_bytes`], as
+appropriate, instead.
+
+[`from_be_bytes`]: #method.from_be_bytes
+[`from_le_bytes`]: #method.from_le_bytes
+
+# Examples
+
+Basic usage:
+
+```
+#![feature(int_to_from_bytes)]
+", $Feature, "let bytes = 0x12", stringify!($SelfT), ".to_ne_bytes();
+assert_eq!(", stringify!($SelfT), "::from_ne_bytes(bytes), 0x12);",
+$EndFeature, "
+```"),
+            #[unstable(feature = "int_to_from_bytes", issue = "0")]
+            #[inline]
+            pub fn from_ne_bytes(bytes: [u8; $BITS / 8]) -> Self {
+                unsafe { mem::transmute(bytes) }
+            }
+        }
     }
 }
 
@@ -147,6 +147,109 @@ pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
     text[..offset].iter().rposition(|elt| *elt == x)
 }
 
+/// Return the first index matching either of the bytes `x1` and `x2` in
+/// `text`.
+pub fn memchr2(x1: u8, x2: u8, text: &[u8]) -> Option<usize> {
+    let (r1, r2) = (repeat_byte(x1), repeat_byte(x2));
+    forward_search(text,
+                   |u| contains_zero_byte(u ^ r1) || contains_zero_byte(u ^ r2),
+                   |b| b == x1 || b == x2)
+}
+
+/// Return the first index matching any of the bytes `x1`, `x2` and `x3` in
+/// `text`.
+pub fn memchr3(x1: u8, x2: u8, x3: u8, text: &[u8]) -> Option<usize> {
+    let (r1, r2, r3) = (repeat_byte(x1), repeat_byte(x2), repeat_byte(x3));
+    forward_search(text,
+                   |u| {
+                       contains_zero_byte(u ^ r1) ||
+                           contains_zero_byte(u ^ r2) ||
+                           contains_zero_byte(u ^ r3)
+                   },
+                   |b| b == x1 || b == x2 || b == x3)
+}
+
+/// Return the last index matching either of the bytes `x1` and `x2` in
+/// `text`.
+pub fn memrchr2(x1: u8, x2: u8, text: &[u8]) -> Option<usize> {
+    let (r1, r2) = (repeat_byte(x1), repeat_byte(x2));
+    reverse_search(text,
+                   |u| contains_zero_byte(u ^ r1) || contains_zero_byte(u ^ r2),
+                   |b| b == x1 || b == x2)
+}
+
+/// Return the last index matching any of the bytes `x1`, `x2` and `x3` in
+/// `text`.
+pub fn memrchr3(x1: u8, x2: u8, x3: u8, text: &[u8]) -> Option<usize> {
+    let (r1, r2, r3) = (repeat_byte(x1), repeat_byte(x2), repeat_byte(x3));
+    reverse_search(text,
+                   |u| {
+                       contains_zero_byte(u ^ r1) ||
+                           contains_zero_byte(u ^ r2) ||
+                           contains_zero_byte(u ^ r3)
+                   },
+                   |b| b == x1 || b == x2 || b == x3)
+}
+
+/// Return the first index in `text` whose byte satisfies `byte_matches`,
+/// skipping a `usize` word at a time while `word_matches` is false for it.
+#[inline(always)]
+fn forward_search<W, B>(text: &[u8], word_matches: W, byte_matches: B) -> Option<usize>
+    where W: Fn(usize) -> bool, B: Fn(u8) -> bool
+{
+    let len = text.len();
+    let ptr = text.as_ptr();
+    let usize_bytes = mem::size_of::<usize>();
+
+    // search up to an aligned boundary
+    let mut offset = cmp::min(ptr.align_offset(usize_bytes), len);
+    if let Some(index) = text[..offset].iter().position(|&b| byte_matches(b)) {
+        return Some(index);
+    }
+
+    // search the body of the text
+    while offset + usize_bytes <= len {
+        let u = unsafe { *(ptr.offset(offset as isize) as *const usize) };
+        if word_matches(u) {
+            break;
+        }
+        offset += usize_bytes;
+    }
+
+    // find the byte in the word the body loop stopped at, or in the tail
+    text[offset..].iter().position(|&b| byte_matches(b)).map(|i| offset + i)
+}
+
+/// Return the last index in `text` whose byte satisfies `byte_matches`,
+/// skipping a `usize` word at a time while `word_matches` is false for it.
+#[inline(always)]
+fn reverse_search<W, B>(text: &[u8], word_matches: W, byte_matches: B) -> Option<usize>
+    where W: Fn(usize) -> bool, B: Fn(u8) -> bool
+{
+    let len = text.len();
+    let ptr = text.as_ptr();
+    let usize_bytes = mem::size_of::<usize>();
+
+    // search to an aligned boundary
+    let end_align = (ptr as usize + len) & (usize_bytes - 1);
+    let mut offset = if end_align >= len { 0 } else { len - end_align };
+    if let Some(index) = text[offset..].iter().rposition(|&b| byte_matches(b)) {
+        return Some(offset + index);
+    }
+
+    // search the body of the text
+    while offset >= usize_bytes {
+        let u = unsafe { *(ptr.offset((offset - usize_bytes) as isize) as *const usize) };
+        if word_matches(u) {
+            break;
+        }
+        offset -= usize_bytes;
+    }
+
+    // find the byte in the word the body loop stopped at, or in the head
+    text[..offset].iter().rposition(|&b| byte_matches(b))
+}
+
 // test fallback implementations on all platforms
 #[test]
 fn matches_one() {
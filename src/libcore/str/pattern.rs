@@ -643,11 +643,115 @@ macro_rules! searcher_methods {
 pub struct CharSliceSearcher<'a, 'b>(<MultiCharEqPattern<&'b [char]> as Pattern<'a>>::Searcher);
 
 unsafe impl<'a, 'b> Searcher<'a> for CharSliceSearcher<'a, 'b> {
-    searcher_methods!(forward);
+    #[inline]
+    fn haystack(&self) -> &'a str {
+        self.0.haystack()
+    }
+    #[inline]
+    fn next(&mut self) -> SearchStep {
+        self.0.next()
+    }
+    #[inline]
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        let set = match AsciiSet::new(self.0.char_eq) {
+            Some(set) => set,
+            None => return self.0.next_match(),
+        };
+        let s = &mut self.0.char_indices;
+        let bytes = s.iter.iter.as_slice();
+        match set.find(bytes) {
+            Some(index) => {
+                // An ASCII byte is a whole char, so the search carries on
+                // right after it.
+                let start = s.front_offset + index;
+                s.iter.iter = bytes[index + 1..].iter();
+                s.front_offset = start + 1;
+                Some((start, start + 1))
+            }
+            None => {
+                s.iter.iter = bytes[bytes.len()..].iter();
+                s.front_offset += bytes.len();
+                None
+            }
+        }
+    }
+    #[inline]
+    fn next_reject(&mut self) -> Option<(usize, usize)> {
+        self.0.next_reject()
+    }
 }
 
 unsafe impl<'a, 'b> ReverseSearcher<'a> for CharSliceSearcher<'a, 'b> {
-    searcher_methods!(reverse);
+    #[inline]
+    fn next_back(&mut self) -> SearchStep {
+        self.0.next_back()
+    }
+    #[inline]
+    fn next_match_back(&mut self) -> Option<(usize, usize)> {
+        let set = match AsciiSet::new(self.0.char_eq) {
+            Some(set) => set,
+            None => return self.0.next_match_back(),
+        };
+        let s = &mut self.0.char_indices;
+        let bytes = s.iter.iter.as_slice();
+        match set.rfind(bytes) {
+            Some(index) => {
+                let start = s.front_offset + index;
+                s.iter.iter = bytes[..index].iter();
+                Some((start, start + 1))
+            }
+            None => {
+                s.iter.iter = bytes[..0].iter();
+                None
+            }
+        }
+    }
+    #[inline]
+    fn next_reject_back(&mut self) -> Option<(usize, usize)> {
+        self.0.next_reject_back()
+    }
+}
+
+/// One to three ASCII chars, which can be searched for directly in the bytes
+/// of the haystack.
+#[derive(Clone, Copy)]
+enum AsciiSet {
+    One(u8),
+    Two(u8, u8),
+    Three(u8, u8, u8),
+}
+
+impl AsciiSet {
+    #[inline]
+    fn new(chars: &[char]) -> Option<AsciiSet> {
+        if !chars.iter().all(|&c| (c as u32) < 0x80) {
+            return None;
+        }
+        match chars.len() {
+            1 => Some(AsciiSet::One(chars[0] as u8)),
+            2 => Some(AsciiSet::Two(chars[0] as u8, chars[1] as u8)),
+            3 => Some(AsciiSet::Three(chars[0] as u8, chars[1] as u8, chars[2] as u8)),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn find(self, bytes: &[u8]) -> Option<usize> {
+        match self {
+            AsciiSet::One(a) => memchr::memchr(a, bytes),
+            AsciiSet::Two(a, b) => memchr::memchr2(a, b, bytes),
+            AsciiSet::Three(a, b, c) => memchr::memchr3(a, b, c, bytes),
+        }
+    }
+
+    #[inline]
+    fn rfind(self, bytes: &[u8]) -> Option<usize> {
+        match self {
+            AsciiSet::One(a) => memchr::memrchr(a, bytes),
+            AsciiSet::Two(a, b) => memchr::memrchr2(a, b, bytes),
+            AsciiSet::Three(a, b, c) => memchr::memrchr3(a, b, c, bytes),
+        }
+    }
 }
 
 impl<'a, 'b> DoubleEndedSearcher<'a> for CharSliceSearcher<'a, 'b> {}
@@ -738,6 +842,505 @@ impl<'a, 'b> Pattern<'a> for &'b str {
 }
 
 
+/////////////////////////////////////////////////////////////////////////////
+// Impl for &[&str]
+/////////////////////////////////////////////////////////////////////////////
+
+/// Searches for any of several substrings in one pass.
+///
+/// A match is found at the leftmost position where any of the needles
+/// matches. If several of them match there, the one which comes first in the
+/// slice wins, so a needle which is a prefix of another should be listed
+/// after it. Searching from the back finds the rightmost end position
+/// instead, with the same tie-breaking.
+///
+/// Empty needles are handled like the pattern `""`, except that no empty
+/// match is reported where another match has just ended.
+///
+/// The searcher compiles the needles into an Aho-Corasick automaton up
+/// front, so a search takes time linear in the length of the haystack. This
+/// works without allocating for needles with up to 63 distinct non-empty
+/// prefixes in total; for larger sets each needle is tried in turn. To
+/// search for many needles, or for the same needles in many haystacks,
+/// compile them into a `std::str::Substrings` once instead.
+impl<'a, 'b, 'c> Pattern<'a> for &'c [&'b str] {
+    type Searcher = StrSliceSearcher<'a, 'b, 'c>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &'a str) -> StrSliceSearcher<'a, 'b, 'c> {
+        StrSliceSearcher::new(haystack, self)
+    }
+
+    /// Checks whether any of the needles matches at the front of the haystack
+    #[inline]
+    fn is_prefix_of(self, haystack: &'a str) -> bool {
+        self.iter().any(|needle| needle.is_prefix_of(haystack))
+    }
+
+    /// Checks whether any of the needles matches at the back of the haystack
+    #[inline]
+    fn is_suffix_of(self, haystack: &'a str) -> bool {
+        self.iter().any(|needle| needle.is_suffix_of(haystack))
+    }
+}
+
+/// Associated type for `<&[&str] as Pattern<'a>>::Searcher`.
+#[derive(Clone, Debug)]
+pub struct StrSliceSearcher<'a, 'b: 'c, 'c> {
+    haystack: &'a str,
+    needles: &'c [&'b str],
+
+    /// Automata for the needles and for the reversed needles, used when
+    /// searching forwards and backwards respectively. Either is `None` if
+    /// its trie has more than `AC_MAX_STATES` states, in which case the
+    /// needles are compared one by one.
+    forward: Option<Automaton>,
+    backward: Option<Automaton>,
+
+    /// The first and last bytes of all non-empty needles. Any byte of the
+    /// haystack not in these sets can be skipped without comparing needles.
+    first_bytes: ByteSet,
+    last_bytes: ByteSet,
+    has_empty: bool,
+
+    /// The part of the haystack which has not been searched yet.
+    position: usize,
+    end: usize,
+    /// Whether a match ended at `position` (started at `end`), in which case
+    /// an empty needle is not allowed to match there.
+    is_match_fw: bool,
+    is_match_bw: bool,
+}
+
+/// A set of bytes, as a 256-bit bitmap.
+#[derive(Clone, Copy, Debug)]
+struct ByteSet([u64; 4]);
+
+impl ByteSet {
+    #[inline]
+    fn insert(&mut self, b: u8) {
+        self.0[(b >> 6) as usize] |= 1u64 << (b & 63);
+    }
+
+    #[inline]
+    fn contains(&self, b: u8) -> bool {
+        self.0[(b >> 6) as usize] & (1u64 << (b & 63)) != 0
+    }
+}
+
+/// The maximum number of trie states of an `Automaton`. It lives inline in
+/// the searcher, as libcore can't allocate.
+const AC_MAX_STATES: usize = 64;
+
+/// Marks the absence of a state or needle index in an `AcState`.
+const AC_NONE: u8 = !0;
+
+/// An Aho-Corasick automaton over the bytes of a set of needles, which are
+/// read either front to back or back to front. Empty needles are only
+/// recorded in the root state and never take part in `find`.
+///
+/// The states form a trie of the needles' prefixes. They are numbered in
+/// breadth-first order, so the children of a state are stored next to each
+/// other, and a state's failure link always points to a lower number.
+#[derive(Clone, Copy)]
+struct Automaton {
+    len: usize,
+    states: [AcState; AC_MAX_STATES],
+}
+
+#[derive(Clone, Copy, Debug)]
+struct AcState {
+    /// The byte on the trie edge leading to this state.
+    byte: u8,
+    /// The length of the prefix this state stands for.
+    depth: u8,
+    /// A needle which has this state's prefix.
+    needle: u8,
+    first_child: u8,
+    num_children: u8,
+    /// The state for the longest proper suffix of this state's prefix which
+    /// is a prefix of some needle.
+    fail: u8,
+    /// The first needle which is equal to this state's prefix, or `AC_NONE`.
+    exact: u8,
+    /// The length and index of the longest non-empty needle which is a
+    /// suffix of this state's prefix, the first one listed on ties. The
+    /// length is 0 if there is none.
+    out_len: u8,
+    out_needle: u8,
+}
+
+const AC_EMPTY_STATE: AcState = AcState {
+    byte: 0,
+    depth: 0,
+    needle: 0,
+    first_child: 0,
+    num_children: 0,
+    fail: 0,
+    exact: AC_NONE,
+    out_len: 0,
+    out_needle: AC_NONE,
+};
+
+impl fmt::Debug for Automaton {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Automaton")
+            .field("states", &&self.states[..self.len])
+            .finish()
+    }
+}
+
+/// Returns the `i`th byte of `needle`, counting from the back if `reverse`.
+#[inline]
+fn nth_byte(needle: &[u8], i: usize, reverse: bool) -> u8 {
+    if reverse { needle[needle.len() - 1 - i] } else { needle[i] }
+}
+
+/// Checks whether the first `len` bytes of `a` and `b`, or the last `len`
+/// bytes if `reverse`, are equal. Both must be at least `len` bytes long.
+#[inline]
+fn same_prefix(a: &[u8], b: &[u8], len: usize, reverse: bool) -> bool {
+    if reverse {
+        a[a.len() - len..] == b[b.len() - len..]
+    } else {
+        a[..len] == b[..len]
+    }
+}
+
+impl Automaton {
+    /// Builds the automaton for `needles`, or returns `None` if there are too
+    /// many of them or their trie doesn't fit into `AC_MAX_STATES` states.
+    fn new(needles: &[&str], reverse: bool) -> Option<Automaton> {
+        if needles.len() >= AC_NONE as usize {
+            return None;
+        }
+        let mut ac = Automaton { len: 1, states: [AC_EMPTY_STATE; AC_MAX_STATES] };
+        if needles.is_empty() {
+            return Some(ac);
+        }
+        if let Some(i) = needles.iter().position(|needle| needle.is_empty()) {
+            ac.states[0].exact = i as u8;
+        }
+
+        // Add the trie one level at a time, so that states end up in
+        // breadth-first order.
+        let mut level = 0..1;
+        let mut depth = 0;
+        while level.start < level.end {
+            for state in level.clone() {
+                ac.states[state].first_child = ac.len as u8;
+                let prefix = needles[ac.states[state].needle as usize].as_bytes();
+                for (i, needle) in needles.iter().enumerate() {
+                    let needle = needle.as_bytes();
+                    if needle.len() <= depth || !same_prefix(needle, prefix, depth, reverse) {
+                        continue;
+                    }
+                    let byte = nth_byte(needle, depth, reverse);
+                    let child = match ac.child(state, byte) {
+                        Some(child) => child,
+                        None => {
+                            if ac.len == AC_MAX_STATES {
+                                return None;
+                            }
+                            ac.states[ac.len] = AcState {
+                                byte,
+                                depth: depth as u8 + 1,
+                                needle: i as u8,
+                                ..AC_EMPTY_STATE
+                            };
+                            ac.states[state].num_children += 1;
+                            ac.len += 1;
+                            ac.len - 1
+                        }
+                    };
+                    if needle.len() == depth + 1 && ac.states[child].exact == AC_NONE {
+                        ac.states[child].exact = i as u8;
+                    }
+                }
+            }
+            level = level.end..ac.len;
+            depth += 1;
+        }
+
+        // Link every state to its longest proper suffix in the trie. Those
+        // are shallower, so they have already been handled when we get to a
+        // state in breadth-first order. The children of the root keep their
+        // link to the root.
+        for state in 1..ac.len {
+            let first_child = ac.states[state].first_child as usize;
+            let num_children = ac.states[state].num_children as usize;
+            for child in first_child..first_child + num_children {
+                let byte = ac.states[child].byte;
+                ac.states[child].fail = ac.next_state(ac.states[state].fail as usize, byte) as u8;
+            }
+        }
+        for state in 1..ac.len {
+            let s = ac.states[state];
+            if s.exact != AC_NONE {
+                ac.states[state].out_len = s.depth;
+                ac.states[state].out_needle = s.exact;
+            } else {
+                let fail = ac.states[s.fail as usize];
+                ac.states[state].out_len = fail.out_len;
+                ac.states[state].out_needle = fail.out_needle;
+            }
+        }
+        Some(ac)
+    }
+
+    /// Returns the child of `state` reached by `byte`.
+    #[inline]
+    fn child(&self, state: usize, byte: u8) -> Option<usize> {
+        let first = self.states[state].first_child as usize;
+        let num = self.states[state].num_children as usize;
+        (first..first + num).find(|&c| self.states[c].byte == byte)
+    }
+
+    /// Returns the state for the longest suffix of `state`'s prefix followed
+    /// by `byte` which is in the trie.
+    #[inline]
+    fn next_state(&self, mut state: usize, byte: u8) -> usize {
+        loop {
+            if let Some(child) = self.child(state, byte) {
+                return child;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.states[state].fail as usize;
+        }
+    }
+
+    /// Returns the length of the first needle which matches at the start of
+    /// `bytes`, by walking down the trie.
+    fn match_len<I: Iterator<Item = u8>>(&self, bytes: I, allow_empty: bool) -> Option<usize> {
+        let mut best = match self.states[0].exact {
+            AC_NONE => None,
+            _ if !allow_empty => None,
+            exact => Some((exact, 0)),
+        };
+        let mut state = 0;
+        for byte in bytes {
+            state = match self.child(state, byte) {
+                Some(child) => child,
+                None => break,
+            };
+            let s = &self.states[state];
+            if s.exact != AC_NONE && best.map_or(true, |(needle, _)| s.exact < needle) {
+                best = Some((s.exact, s.depth as usize));
+            }
+        }
+        best.map(|(_, len)| len)
+    }
+
+    /// Returns the offset and length of the leftmost non-empty match in
+    /// `bytes`, the first needle listed winning on ties.
+    fn find<I: Iterator<Item = u8>>(&self, bytes: I) -> Option<(usize, usize)> {
+        // The offset, length and needle of the best match so far.
+        let mut best: Option<(usize, usize, u8)> = None;
+        let mut state = 0;
+        for (i, byte) in bytes.enumerate() {
+            state = self.next_state(state, byte);
+            let s = &self.states[state];
+            let pos = i + 1;
+            if let Some((offset, _, _)) = best {
+                // Any match still to be found would start after `offset`.
+                if pos - s.depth as usize > offset {
+                    break;
+                }
+            }
+            if s.out_len != 0 {
+                let offset = pos - s.out_len as usize;
+                if best.map_or(true, |(o, _, n)| offset < o || (offset == o && s.out_needle < n)) {
+                    best = Some((offset, s.out_len as usize, s.out_needle));
+                }
+            }
+        }
+        best.map(|(offset, len, _)| (offset, len))
+    }
+}
+
+impl<'a, 'b, 'c> StrSliceSearcher<'a, 'b, 'c> {
+    fn new(haystack: &'a str, needles: &'c [&'b str]) -> StrSliceSearcher<'a, 'b, 'c> {
+        let mut first_bytes = ByteSet([0; 4]);
+        let mut last_bytes = ByteSet([0; 4]);
+        let mut has_empty = false;
+        for needle in needles {
+            let bytes = needle.as_bytes();
+            match (bytes.first(), bytes.last()) {
+                (Some(&first), Some(&last)) => {
+                    first_bytes.insert(first);
+                    last_bytes.insert(last);
+                }
+                _ => has_empty = true,
+            }
+        }
+        StrSliceSearcher {
+            haystack,
+            needles,
+            forward: Automaton::new(needles, false),
+            backward: Automaton::new(needles, true),
+            first_bytes,
+            last_bytes,
+            has_empty,
+            position: 0,
+            end: haystack.len(),
+            is_match_fw: false,
+            is_match_bw: false,
+        }
+    }
+
+    /// Returns the length of the first needle which matches at `start` and
+    /// lies within the unsearched part of the haystack.
+    #[inline]
+    fn match_at(&self, start: usize, allow_empty: bool) -> Option<usize> {
+        let rest = &self.haystack.as_bytes()[start..self.end];
+        if let Some(ref ac) = self.forward {
+            return ac.match_len(rest.iter().cloned(), allow_empty);
+        }
+        self.needles.iter()
+            .map(|needle| needle.as_bytes())
+            .find(|needle| (allow_empty || !needle.is_empty()) && rest.starts_with(needle))
+            .map(|needle| needle.len())
+    }
+
+    /// Returns the length of the first needle which matches ending at `end`
+    /// and lies within the unsearched part of the haystack.
+    #[inline]
+    fn match_at_back(&self, end: usize, allow_empty: bool) -> Option<usize> {
+        let rest = &self.haystack.as_bytes()[self.position..end];
+        if let Some(ref ac) = self.backward {
+            return ac.match_len(rest.iter().rev().cloned(), allow_empty);
+        }
+        self.needles.iter()
+            .map(|needle| needle.as_bytes())
+            .find(|needle| (allow_empty || !needle.is_empty()) && rest.ends_with(needle))
+            .map(|needle| needle.len())
+    }
+}
+
+unsafe impl<'a, 'b, 'c> Searcher<'a> for StrSliceSearcher<'a, 'b, 'c> {
+    #[inline]
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let start = self.position;
+        if let Some(len) = self.match_at(start, !self.is_match_fw) {
+            self.position += len;
+            self.is_match_fw = true;
+            return SearchStep::Match(start, start + len);
+        }
+        match self.haystack[start..self.end].chars().next() {
+            Some(ch) => {
+                self.position += ch.len_utf8();
+                self.is_match_fw = false;
+                SearchStep::Reject(start, self.position)
+            }
+            None => SearchStep::Done,
+        }
+    }
+
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        if self.has_empty {
+            loop {
+                match self.next() {
+                    SearchStep::Match(a, b) => return Some((a, b)),
+                    SearchStep::Done => return None,
+                    SearchStep::Reject(..) => {}
+                }
+            }
+        }
+
+        // All needles are non-empty, so they start with a byte which is not
+        // a UTF-8 continuation byte, and a match can only be found on a char
+        // boundary.
+        let bytes = self.haystack.as_bytes();
+        let found = match self.forward {
+            Some(ref ac) => {
+                ac.find(bytes[self.position..self.end].iter().cloned())
+                  .map(|(offset, len)| (self.position + offset, len))
+            }
+            None => {
+                (self.position..self.end)
+                    .filter(|&start| self.first_bytes.contains(bytes[start]))
+                    .filter_map(|start| self.match_at(start, false).map(|len| (start, len)))
+                    .next()
+            }
+        };
+        match found {
+            Some((start, len)) => {
+                self.position = start + len;
+                self.is_match_fw = true;
+                Some((start, start + len))
+            }
+            None => {
+                self.position = self.end;
+                None
+            }
+        }
+    }
+}
+
+unsafe impl<'a, 'b, 'c> ReverseSearcher<'a> for StrSliceSearcher<'a, 'b, 'c> {
+    fn next_back(&mut self) -> SearchStep {
+        let end = self.end;
+        if let Some(len) = self.match_at_back(end, !self.is_match_bw) {
+            self.end -= len;
+            self.is_match_bw = true;
+            return SearchStep::Match(end - len, end);
+        }
+        match self.haystack[self.position..end].chars().next_back() {
+            Some(ch) => {
+                self.end -= ch.len_utf8();
+                self.is_match_bw = false;
+                SearchStep::Reject(self.end, end)
+            }
+            None => SearchStep::Done,
+        }
+    }
+
+    fn next_match_back(&mut self) -> Option<(usize, usize)> {
+        if self.has_empty {
+            loop {
+                match self.next_back() {
+                    SearchStep::Match(a, b) => return Some((a, b)),
+                    SearchStep::Done => return None,
+                    SearchStep::Reject(..) => {}
+                }
+            }
+        }
+
+        // As in `next_match`, a non-empty needle can only end on a char
+        // boundary, since it consists of whole chars.
+        let bytes = self.haystack.as_bytes();
+        let found = match self.backward {
+            Some(ref ac) => {
+                ac.find(bytes[self.position..self.end].iter().rev().cloned())
+                  .map(|(offset, len)| (self.end - offset, len))
+            }
+            None => {
+                (self.position + 1..self.end + 1).rev()
+                    .filter(|&end| self.last_bytes.contains(bytes[end - 1]))
+                    .filter_map(|end| self.match_at_back(end, false).map(|len| (end, len)))
+                    .next()
+            }
+        };
+        match found {
+            Some((end, len)) => {
+                self.end = end - len;
+                self.is_match_bw = true;
+                Some((end - len, end))
+            }
+            None => {
+                self.end = self.position;
+                None
+            }
+        }
+    }
+}
+
 /////////////////////////////////////////////////////////////////////////////
 // Two Way substring searcher
 /////////////////////////////////////////////////////////////////////////////
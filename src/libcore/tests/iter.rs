@@ -2121,3 +2121,150 @@ fn test_monad_laws_associativity() {
     assert_eq!((0..10).flat_map(f).flat_map(g).sum::<usize>(),
                 (0..10).flat_map(|x| f(x).flat_map(g)).sum::<usize>());
 }
+
+#[test]
+fn test_intersperse() {
+    let v: Vec<_> = (0..4).intersperse(10).collect();
+    assert_eq!(v, [0, 10, 1, 10, 2, 10, 3]);
+
+    let folded = (0..4).intersperse(10).fold(Vec::new(), |mut v, x| { v.push(x); v });
+    assert_eq!(folded, v);
+
+    assert_eq!((0..1).intersperse(10).collect::<Vec<_>>(), [0]);
+    assert_eq!((0..0).intersperse(10).next(), None);
+}
+
+#[test]
+fn test_intersperse_size_hint() {
+    let mut iter = (0..3).intersperse(10);
+    assert_eq!(iter.size_hint(), (5, Some(5)));
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.size_hint(), (4, Some(4)));
+    assert_eq!(iter.next(), Some(10));
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+
+    assert_eq!((0..0).intersperse(10).size_hint(), (0, Some(0)));
+    assert_eq!((0..).intersperse(10).size_hint(), (usize::MAX, None));
+    assert_eq!((0..usize::MAX).intersperse(0).size_hint(), (usize::MAX, None));
+}
+
+#[test]
+fn test_intersperse_fold_after_next() {
+    let mut iter = (0..3).intersperse(10);
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.fold(Vec::new(), |mut v, x| { v.push(x); v }), [10, 1, 10, 2]);
+}
+
+#[test]
+fn test_array_chunks() {
+    let mut iter = (0..8).array_chunks::<[_; 3]>();
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+    assert_eq!(iter.next(), Some([0, 1, 2]));
+    assert_eq!(iter.next(), Some([3, 4, 5]));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.remainder(), &[6, 7]);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+
+    let sums: Vec<i32> = (0..7).array_chunks().map(|[a, b]: [i32; 2]| a + b).collect();
+    assert_eq!(sums, [1, 5, 9]);
+
+    let folded = (0..7).array_chunks::<[_; 2]>().fold(0, |acc, [a, b]| acc + a * b);
+    assert_eq!(folded, 0 * 1 + 2 * 3 + 4 * 5);
+
+    assert_eq!((0..7).array_chunks::<[_; 2]>().len(), 3);
+}
+
+#[test]
+#[should_panic]
+fn test_array_chunks_zero() {
+    let _ = (0..4).array_chunks::<[i32; 0]>();
+}
+
+#[test]
+fn test_array_chunks_drops_remainder() {
+    use std::cell::Cell;
+
+    struct Counted<'a>(&'a Cell<usize>);
+    impl<'a> Drop for Counted<'a> {
+        fn drop(&mut self) { self.0.set(self.0.get() + 1); }
+    }
+
+    let drops = Cell::new(0);
+    {
+        let mut iter = (0..5).map(|_| Counted(&drops)).array_chunks::<[_; 3]>();
+        drop(iter.next());
+        assert_eq!(drops.get(), 3);
+        assert!(iter.next().is_none());
+        assert_eq!(iter.remainder().len(), 2);
+        assert_eq!(drops.get(), 3);
+    }
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn test_dedup_by() {
+    let a = [1, 1, 2, 3, 3, 3, 1, 4, 4];
+    let v: Vec<_> = a.iter().dedup_by(|x, y| x == y).collect();
+    assert_eq!(v, [&1, &2, &3, &1, &4]);
+
+    let folded = a.iter().dedup_by(|x, y| x == y).fold(Vec::new(), |mut v, x| { v.push(x); v });
+    assert_eq!(folded, v);
+
+    // compares against the first element of the run, not the previous one
+    let v: Vec<_> = [1, 2, 3, 4, 5].iter().dedup_by(|&x, &y| y - x < 2).collect();
+    assert_eq!(v, [&1, &3, &5]);
+
+    assert_eq!((0..0).dedup_by(|x, y| x == y).next(), None);
+}
+
+#[test]
+fn test_dedup_by_size_hint() {
+    let mut iter = [1, 1, 2].iter().dedup_by(|x, y| x == y);
+    assert_eq!(iter.size_hint(), (1, Some(3)));
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.size_hint(), (1, Some(1)));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn test_group_by() {
+    let runs: Vec<_> = "aaabccdddd".chars().group_by(|&c| c).collect();
+    assert_eq!(runs, [('a', 3), ('b', 1), ('c', 2), ('d', 4)]);
+
+    let folded = "aaabccdddd".chars()
+                             .group_by(|&c| c)
+                             .fold(Vec::new(), |mut v, x| { v.push(x); v });
+    assert_eq!(folded, runs);
+
+    let mut iter = "aaab".chars().group_by(|&c| c);
+    assert_eq!(iter.next(), Some(('a', 3)));
+    assert_eq!(iter.fold(Vec::new(), |mut v, x| { v.push(x); v }), [('b', 1)]);
+
+    assert_eq!("".chars().group_by(|&c| c).next(), None);
+}
+
+#[test]
+fn test_group_by_key_called_once() {
+    let mut calls = 0;
+    let runs: Vec<_> = [1, 1, 2, 2, 2, 3].iter().group_by(|&&x| { calls += 1; x }).collect();
+    assert_eq!(runs, [(1, 2), (2, 3), (3, 1)]);
+    assert_eq!(calls, 6);
+}
+
+#[test]
+fn test_try_collect() {
+    let v: Option<Vec<i32>> = [Some(1), Some(2)].iter().cloned().try_collect();
+    assert_eq!(v, Some(vec![1, 2]));
+
+    let mut iter = [Ok(1), Err("two"), Ok(3)].iter().cloned();
+    let r: Result<Vec<i32>, &str> = iter.by_ref().try_collect();
+    assert_eq!(r, Err("two"));
+    assert_eq!(iter.next(), Some(Ok(3)));
+
+    let r: Result<String, _> = ["a", "b"].iter().map(|&s| Ok::<_, ()>(s)).try_collect();
+    assert_eq!(r, Ok("ab".to_string()));
+
+    let r: Result<Vec<i32>, _> = [Some(1), None].iter().cloned().try_collect();
+    assert_eq!(r, Err(::core::option::NoneError));
+}
@@ -26,6 +26,10 @@
 #![feature(inclusive_range_syntax)]
 #![feature(iterator_try_fold)]
 #![feature(iterator_flatten)]
+#![feature(iterator_intersperse)]
+#![feature(iterator_array_chunks)]
+#![feature(iterator_group_by)]
+#![feature(iterator_try_collect)]
 #![feature(conservative_impl_trait)]
 #![feature(iter_rfind)]
 #![feature(iter_rfold)]
@@ -37,6 +41,7 @@
 #![feature(refcell_replace_swap)]
 #![feature(sip_hash_13)]
 #![feature(slice_patterns)]
+#![feature(slice_internals)]
 #![feature(sort_internals)]
 #![feature(specialization)]
 #![feature(step_trait)]
@@ -46,6 +51,9 @@
 #![feature(try_trait)]
 #![feature(exact_chunks)]
 #![feature(atomic_nand)]
+#![feature(bigint_helper_methods)]
+#![feature(euclidean_division)]
+#![feature(int_to_from_bytes)]
 
 extern crate core;
 extern crate test;
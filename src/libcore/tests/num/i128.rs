@@ -0,0 +1,52 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+int_module!(i128, i128);
+
+mod wide {
+    use core::i128::{MAX, MIN};
+
+    #[test]
+    fn test_to_from_bytes() {
+        let x = -0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10i128;
+        let be = [0xfe, 0xfd, 0xfc, 0xfb, 0xfa, 0xf9, 0xf8, 0xf7,
+                  0xf6, 0xf5, 0xf4, 0xf3, 0xf2, 0xf1, 0xf0, 0xf0];
+        let mut le = be;
+        le.reverse();
+        assert_eq!(x.to_be_bytes(), be);
+        assert_eq!(x.to_le_bytes(), le);
+        assert_eq!(i128::from_be_bytes(be), x);
+        assert_eq!(i128::from_le_bytes(le), x);
+
+        let mut min = [0; 16];
+        min[0] = 0x80;
+        assert_eq!(MIN.to_be_bytes(), min);
+        assert_eq!(i128::from_be_bytes(min), MIN);
+        let mut max = [0xff; 16];
+        max[0] = 0x7f;
+        assert_eq!(MAX.to_be_bytes(), max);
+        assert_eq!(i128::from_be_bytes(max), MAX);
+        assert_eq!((-2i128).to_le_bytes()[0], 0xfe);
+        assert_eq!(i128::from_le_bytes([0xff; 16]), -1);
+    }
+
+    #[test]
+    fn test_div_mod_euc() {
+        // Values that don't fit in 64 bits on either side of the division.
+        let big = -(1i128 << 100) - 1;
+        assert_eq!(big.div_euc(1 << 64), -(1 << 36) - 1);
+        assert_eq!(big.mod_euc(1 << 64), (1 << 64) - 1);
+        assert_eq!(big.div_euc(-(1 << 64)), (1 << 36) + 1);
+        assert_eq!(big.mod_euc(-(1 << 64)), (1 << 64) - 1);
+        assert_eq!(MIN.div_euc(MAX), -2);
+        assert_eq!(MIN.mod_euc(MAX), MAX - 1);
+    }
+}
@@ -214,6 +214,44 @@ mod tests {
         assert_eq!(r.pow(2), 4 as $T);
         assert_eq!(r.pow(3), -8 as $T);
     }
+
+    #[test]
+    fn test_div_mod_euc() {
+        assert_eq!((7 as $T).div_euc(3), 2);
+        assert_eq!((7 as $T).mod_euc(3), 1);
+        assert_eq!((-7 as $T).div_euc(3), -3);
+        assert_eq!((-7 as $T).mod_euc(3), 2);
+        assert_eq!((7 as $T).div_euc(-3), -2);
+        assert_eq!((7 as $T).mod_euc(-3), 1);
+        assert_eq!((-7 as $T).div_euc(-3), 3);
+        assert_eq!((-7 as $T).mod_euc(-3), 2);
+
+        assert_eq!((5 as $T).checked_div_euc(0), None);
+        assert_eq!((5 as $T).checked_mod_euc(0), None);
+        assert_eq!(MIN.checked_div_euc(-1), None);
+        assert_eq!(MIN.checked_mod_euc(-1), None);
+        assert_eq!(MIN.wrapping_div_euc(-1), MIN);
+        assert_eq!(MIN.wrapping_mod_euc(-1), 0);
+        assert_eq!(MIN.overflowing_div_euc(-1), (MIN, true));
+        assert_eq!(MIN.overflowing_mod_euc(-1), (0, true));
+        assert_eq!((-7 as $T).overflowing_div_euc(3), (-3, false));
+    }
+
+    #[test]
+    fn test_to_from_bytes() {
+        for &x in &[0 as $T, 1, -1, MIN, MAX, 0x12 as $T] {
+            assert_eq!($T::from_be_bytes(x.to_be_bytes()), x);
+            assert_eq!($T::from_le_bytes(x.to_le_bytes()), x);
+            assert_eq!($T::from_ne_bytes(x.to_ne_bytes()), x);
+
+            let mut le = x.to_le_bytes();
+            le.reverse();
+            assert_eq!(le, x.to_be_bytes());
+        }
+        assert!((-1 as $T).to_be_bytes().iter().all(|&b| b == 0xff));
+        assert_eq!(MIN.to_be_bytes()[0], 0x80);
+        assert_eq!(MIN.to_le_bytes()[mem::size_of::<$T>() - 1], 0x80);
+    }
 }
 
 )}
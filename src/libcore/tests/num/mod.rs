@@ -24,6 +24,7 @@ mod i8;
 mod i16;
 mod i32;
 mod i64;
+mod i128;
 
 #[macro_use]
 mod uint_macros;
@@ -32,6 +33,7 @@ mod u8;
 mod u16;
 mod u32;
 mod u64;
+mod u128;
 
 mod flt2dec;
 mod dec2flt;
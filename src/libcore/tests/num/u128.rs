@@ -0,0 +1,55 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+uint_module!(u128, u128);
+
+// The generic tests above only touch the extremes; these make sure that
+// carries move between the two 64-bit halves of the widest type.
+mod wide {
+    use core::u128::MAX;
+    use core::u64;
+
+    const HALF: u128 = 1 << 64;
+
+    #[test]
+    fn test_widening_mul() {
+        let digit = u64::MAX as u128;
+        assert_eq!(digit.widening_mul(digit), (0xffff_ffff_ffff_fffe_0000_0000_0000_0001, 0));
+        assert_eq!(HALF.widening_mul(HALF), (0, 1));
+        assert_eq!((1u128 << 127).widening_mul(4), (0, 2));
+        assert_eq!(MAX.widening_mul(HALF), (MAX << 64, digit));
+        assert_eq!(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128
+                       .widening_mul(0xfedc_ba98_7654_3210_0123_4567_89ab_cdef),
+                   (0xbcb4_48e0_e2b4_bd63_2236_d88f_e561_8cf0,
+                    0x0121_fa00_ad77_d743_1ff2_e48e_8a71_de69));
+    }
+
+    #[test]
+    fn test_carrying_mul() {
+        let digit = u64::MAX as u128;
+        // (2^64 - 1) * (2^64 + 1) + (2^64 - 1) = 2^128 + 2^64 - 2
+        assert_eq!(digit.carrying_mul(HALF + 1, digit), (digit - 1, 1));
+        assert_eq!(MAX.carrying_mul(HALF, MAX), (MAX - HALF, HALF));
+    }
+
+    #[test]
+    fn test_to_from_bytes() {
+        let x = 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10u128;
+        let be = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        let le = [16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1];
+        assert_eq!(x.to_be_bytes(), be);
+        assert_eq!(x.to_le_bytes(), le);
+        assert_eq!(u128::from_be_bytes(be), x);
+        assert_eq!(u128::from_le_bytes(le), x);
+        assert_eq!(MAX.to_le_bytes(), [0xff; 16]);
+        assert_eq!(HALF.to_le_bytes()[8], 1);
+    }
+}
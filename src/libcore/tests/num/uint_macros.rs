@@ -154,5 +154,56 @@ mod tests {
         assert_eq!($T::from_str_radix("Z", 10).ok(), None::<$T>);
         assert_eq!($T::from_str_radix("_", 2).ok(), None::<$T>);
     }
+
+    #[test]
+    fn test_div_mod_euc() {
+        assert_eq!((7 as $T).div_euc(3), 2);
+        assert_eq!((7 as $T).mod_euc(3), 1);
+        assert_eq!((5 as $T).checked_div_euc(0), None);
+        assert_eq!((5 as $T).checked_mod_euc(0), None);
+        assert_eq!(MAX.wrapping_div_euc(MAX), 1);
+        assert_eq!(MAX.overflowing_mod_euc(2), (1, false));
+    }
+
+    #[test]
+    fn test_to_from_bytes() {
+        for &x in &[0 as $T, 1, MAX, 0x12 as $T] {
+            assert_eq!($T::from_be_bytes(x.to_be_bytes()), x);
+            assert_eq!($T::from_le_bytes(x.to_le_bytes()), x);
+            assert_eq!($T::from_ne_bytes(x.to_ne_bytes()), x);
+
+            let mut le = x.to_le_bytes();
+            le.reverse();
+            assert_eq!(le, x.to_be_bytes());
+        }
+        assert_eq!((1 as $T).to_le_bytes()[0], 1);
+        assert_eq!((1 as $T).to_be_bytes()[mem::size_of::<$T>() - 1], 1);
+    }
+
+    #[test]
+    fn test_carrying_add_borrowing_sub() {
+        assert_eq!((5 as $T).carrying_add(2, false), (7, false));
+        assert_eq!((5 as $T).carrying_add(2, true), (8, false));
+        assert_eq!(MAX.carrying_add(0, true), (0, true));
+        assert_eq!(MAX.carrying_add(MAX, true), (MAX, true));
+
+        assert_eq!((5 as $T).borrowing_sub(2, false), (3, false));
+        assert_eq!((5 as $T).borrowing_sub(2, true), (2, false));
+        assert_eq!((0 as $T).borrowing_sub(0, true), (MAX, true));
+        assert_eq!((0 as $T).borrowing_sub(MAX, true), (0, true));
+    }
+
+    #[test]
+    fn test_widening_carrying_mul() {
+        assert_eq!((0 as $T).widening_mul(MAX), (0, 0));
+        assert_eq!((6 as $T).widening_mul(7), (42, 0));
+        assert_eq!(MAX.widening_mul(2), (MAX - 1, 1));
+        // (2^n - 1)^2 = 2^2n - 2^(n+1) + 1
+        assert_eq!(MAX.widening_mul(MAX), (1, MAX - 1));
+
+        assert_eq!((6 as $T).carrying_mul(7, 3), (45, 0));
+        assert_eq!(MAX.carrying_mul(1, 1), (0, 1));
+        assert_eq!(MAX.carrying_mul(MAX, MAX), (0, MAX));
+    }
 }
 )}
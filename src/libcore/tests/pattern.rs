@@ -300,3 +300,115 @@ fn double_ended_regression_test() {
         [InRange(10, 13), Rejects(13, 14), InRange(37, 40), Rejects(34, 37), Done]
     );
 }
+
+#[test]
+fn test_str_slice_search() {
+    search_asserts!("xcaty", &["cat", "at"] as &[&str], "forward iteration for several needles",
+        [next,          next,          next,          next],
+        [Rejects(0, 1), Matches(1, 4), Rejects(4, 5), Done]
+    );
+    search_asserts!("xcaty", &["at", "cat"] as &[&str], "reverse iteration for several needles",
+        [next_back,     next_back,     next_back,     next_back],
+        [Rejects(4, 5), Matches(2, 4), Rejects(1, 2), Rejects(0, 1)]
+    );
+    search_asserts!("cat dog catalog", &["catalog", "cat", "dog"] as &[&str], "next_match with overlapping needles",
+        [next_match,    next_match,    next_match,     next_match],
+        [InRange(0, 3), InRange(4, 7), InRange(8, 15), Done]
+    );
+    search_asserts!("cat dog catalog", &["catalog", "cat", "dog"] as &[&str], "next_match_back with overlapping needles",
+        [next_match_back, next_match_back, next_match_back, next_match_back],
+        [InRange(8, 15),  InRange(4, 7),   InRange(0, 3),   Done]
+    );
+    search_asserts!("我的猫说meow", &["猫", "me"] as &[&str], "next_match for mixed string with shared leading bytes",
+        [next_match,    next_match,      next_match],
+        [InRange(6, 9), InRange(12, 14), Done]
+    );
+    search_asserts!("ab", &["b", ""] as &[&str], "forward iteration with an empty needle",
+        [next,          next,          next,          next],
+        [Matches(0, 0), Rejects(0, 1), Matches(1, 2), Done]
+    );
+    search_asserts!("ab", &[] as &[&str], "forward iteration without needles",
+        [next,          next_match],
+        [Rejects(0, 1), Done]
+    );
+    search_asserts!("abcdx abcde", &["bcd", "abcde"] as &[&str], "next_match with a needle inside another",
+        [next_match,    next_match,     next_match],
+        [InRange(1, 4), InRange(6, 11), Done]
+    );
+    search_asserts!("xabcd ebcda", &["bcd", "ebcda"] as &[&str], "next_match_back with a needle inside another",
+        [next_match_back, next_match_back, next_match_back],
+        [InRange(6, 11),  InRange(2, 5),   Done]
+    );
+}
+
+#[test]
+fn test_str_slice_search_many_needles() {
+    // Too many distinct prefixes for the searcher's automaton.
+    let needles: Vec<String> = (0..40).map(|i| format!("n{}x", i * 7)).collect();
+    let needles: Vec<&str> = needles.iter().map(|s| &s[..]).collect();
+    search_asserts!("n14n7x n133x", &needles[..], "next_match without an automaton",
+        [next_match,    next_match,     next_match],
+        [InRange(3, 6), InRange(7, 12), Done]
+    );
+    search_asserts!("n14n7x n133x", &needles[..], "next_match_back without an automaton",
+        [next_match_back, next_match_back, next_match_back],
+        [InRange(7, 12),  InRange(3, 6),   Done]
+    );
+}
+
+#[test]
+fn test_char_slice_search_every_offset() {
+    // Up to three ASCII chars are searched for with memchr2/memchr3, so move
+    // the matches across the unaligned head, the word loop and the tail.
+    // Sets with more chars or a non-ASCII char take the general path.
+    let sets: &[(&[char], char)] = &[
+        (&['x'], 'x'),
+        (&['y', 'x'], 'x'),
+        (&['y', 'z', 'x'], 'x'),
+        (&['w', 'y', 'z', 'x'], 'x'),
+        (&['x', '水'], 'x'),
+        (&['x', '水'], '水'),
+    ];
+    for &fill in &['.', 'é'] {
+        for &(set, target) in sets {
+            let len = 40;
+            for i in 0..len {
+                let text: String = (0..len).map(|j| {
+                    if j == i || j == len - 1 - i { target } else { fill }
+                }).collect();
+                let mut bounds: Vec<usize> = text.char_indices().map(|(k, _)| k).collect();
+                bounds.push(text.len());
+                for (a, &start) in bounds.iter().enumerate() {
+                    for &end in &bounds[a..] {
+                        let t = &text[start..end];
+                        let expected: Vec<usize> = t.char_indices()
+                            .filter(|&(_, c)| set.contains(&c))
+                            .map(|(k, _)| k)
+                            .collect();
+                        let found: Vec<usize> = t.match_indices(set).map(|(k, _)| k).collect();
+                        let mut rfound: Vec<usize> =
+                            t.rmatch_indices(set).map(|(k, _)| k).collect();
+                        rfound.reverse();
+                        assert_eq!(found, expected, "{:?} in {:?}", set, t);
+                        assert_eq!(rfound, expected, "{:?} in {:?}", set, t);
+                        assert_eq!(t.find(set), expected.first().cloned());
+                        assert_eq!(t.rfind(set), expected.last().cloned());
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_char_slice_search_double_ended() {
+    // Matches taken from the front and the back must not overlap.
+    search_asserts!("a.b.a.c", &['a', 'c'] as &[char], "alternating ends with an ASCII set",
+        [next_match,    next_match_back, next_match,    next_match_back, next_match],
+        [InRange(0, 1), InRange(6, 7),   InRange(4, 5), Done,            Done]
+    );
+    search_asserts!("水.b.水.c", &['水', 'c'] as &[char], "alternating ends with a non-ASCII set",
+        [next_match,    next_match_back, next_match,    next_match_back, next_match],
+        [InRange(0, 3), InRange(10, 11), InRange(6, 9), Done,            Done]
+    );
+}
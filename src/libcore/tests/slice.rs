@@ -481,3 +481,37 @@ fn test_rotate_right() {
         assert_eq!(a[(i + 42) % N], i);
     }
 }
+
+#[test]
+fn test_memchr2_memchr3() {
+    use core::slice::memchr::{memchr2, memchr3, memrchr2, memrchr3};
+
+    let text = b"the quick brown fox jumps over the lazy dog";
+    assert_eq!(Some(4), memchr2(b'q', b'z', text));
+    assert_eq!(Some(37), memrchr2(b'q', b'z', text));
+    assert_eq!(Some(10), memchr3(b'b', b'x', b'y', text));
+    assert_eq!(Some(38), memrchr3(b'b', b'x', b'y', text));
+    assert_eq!(None, memchr3(b'1', b'2', b'3', text));
+    assert_eq!(None, memrchr2(b'1', b'2', b""));
+}
+
+#[test]
+fn test_memchr2_memchr3_every_offset() {
+    use core::slice::memchr::{memchr2, memchr3, memrchr2, memrchr3};
+
+    // exercise the unaligned head and tail on either side of the word loop
+    let mut text = [b'.'; 40];
+    for i in 0..text.len() {
+        text[i] = b'x';
+        for start in 0..i + 1 {
+            for end in i + 1..text.len() + 1 {
+                let t = &text[start..end];
+                assert_eq!(Some(i - start), memchr2(b'y', b'x', t));
+                assert_eq!(Some(i - start), memrchr2(b'y', b'x', t));
+                assert_eq!(Some(i - start), memchr3(b'y', b'z', b'x', t));
+                assert_eq!(Some(i - start), memrchr3(b'y', b'z', b'x', t));
+            }
+        }
+        text[i] = b'.';
+    }
+}
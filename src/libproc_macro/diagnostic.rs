@@ -28,6 +28,34 @@ pub enum Level {
     __Nonexhaustive,
 }
 
+/// Trait implemented by types that can be converted into a set of `Span`s.
+#[unstable(feature = "proc_macro", issue = "38356")]
+pub trait MultiSpan {
+    /// Converts `self` into a `Vec<Span>`.
+    fn into_spans(self) -> Vec<Span>;
+}
+
+#[unstable(feature = "proc_macro", issue = "38356")]
+impl MultiSpan for Span {
+    fn into_spans(self) -> Vec<Span> {
+        vec![self]
+    }
+}
+
+#[unstable(feature = "proc_macro", issue = "38356")]
+impl MultiSpan for Vec<Span> {
+    fn into_spans(self) -> Vec<Span> {
+        self
+    }
+}
+
+#[unstable(feature = "proc_macro", issue = "38356")]
+impl<'a> MultiSpan for &'a [Span] {
+    fn into_spans(self) -> Vec<Span> {
+        self.to_vec()
+    }
+}
+
 /// A structure representing a diagnostic message and associated children
 /// messages.
 #[unstable(feature = "proc_macro", issue = "38356")]
@@ -35,17 +63,19 @@ pub enum Level {
 pub struct Diagnostic {
     level: Level,
     message: String,
-    span: Option<Span>,
+    spans: Vec<Span>,
     children: Vec<Diagnostic>
 }
 
 macro_rules! diagnostic_child_methods {
     ($spanned:ident, $regular:ident, $level:expr) => (
         /// Add a new child diagnostic message to `self` with the level
-        /// identified by this methods name with the given `span` and `message`.
+        /// identified by this methods name with the given `spans` and `message`.
         #[unstable(feature = "proc_macro", issue = "38356")]
-        pub fn $spanned<T: Into<String>>(mut self, span: Span, message: T) -> Diagnostic {
-            self.children.push(Diagnostic::spanned(span, $level, message));
+        pub fn $spanned<S, T>(mut self, spans: S, message: T) -> Diagnostic
+            where S: MultiSpan, T: Into<String>
+        {
+            self.children.push(Diagnostic::spanned(spans, $level, message));
             self
         }
 
@@ -66,19 +96,21 @@ impl Diagnostic {
         Diagnostic {
             level: level,
             message: message.into(),
-            span: None,
+            spans: vec![],
             children: vec![]
         }
     }
 
     /// Create a new diagnostic with the given `level` and `message` pointing to
-    /// the given `span`.
+    /// the given set of `spans`.
     #[unstable(feature = "proc_macro", issue = "38356")]
-    pub fn spanned<T: Into<String>>(span: Span, level: Level, message: T) -> Diagnostic {
+    pub fn spanned<S, T>(spans: S, level: Level, message: T) -> Diagnostic
+        where S: MultiSpan, T: Into<String>
+    {
         Diagnostic {
             level: level,
             message: message.into(),
-            span: Some(span),
+            spans: spans.into_spans(),
             children: vec![]
         }
     }
@@ -94,6 +126,18 @@ impl Diagnostic {
         self.level
     }
 
+    /// Returns the message in `self`.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the `Span`s in `self`.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
     /// Emit the diagnostic.
     #[unstable(feature = "proc_macro", issue = "38356")]
     pub fn emit(self) {
@@ -101,15 +145,12 @@ impl Diagnostic {
             let handler = &sess.span_diagnostic;
             let level = __internal::level_to_internal_level(self.level);
             let mut diag = rustc::DiagnosticBuilder::new(handler, level, &*self.message);
-
-            if let Some(span) = self.span {
-                diag.set_span(span.0);
-            }
+            diag.set_span(__internal::to_internal_multi_span(self.spans));
 
             for child in self.children {
-                let span = child.span.map(|s| s.0);
+                let span = __internal::to_internal_multi_span(child.spans);
                 let level = __internal::level_to_internal_level(child.level);
-                diag.sub(level, &*child.message, span);
+                diag.sub(level, &*child.message, Some(span));
             }
 
             diag.emit();
@@ -120,7 +161,12 @@ impl Diagnostic {
 #[unstable(feature = "proc_macro_internals", issue = "27812")]
 #[doc(hidden)]
 pub mod __internal {
-    use super::{Level, rustc};
+    use super::{Level, Span, rustc};
+    use syntax_pos;
+
+    pub fn to_internal_multi_span(spans: Vec<Span>) -> syntax_pos::MultiSpan {
+        syntax_pos::MultiSpan::from_spans(spans.into_iter().map(|s| s.0).collect())
+    }
 
     pub fn level_to_internal_level(level: Level) -> rustc::Level {
         match level {
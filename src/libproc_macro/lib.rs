@@ -49,7 +49,7 @@ extern crate rustc_data_structures;
 mod diagnostic;
 
 #[unstable(feature = "proc_macro", issue = "38356")]
-pub use diagnostic::{Diagnostic, Level};
+pub use diagnostic::{Diagnostic, Level, MultiSpan};
 
 use std::{ascii, fmt, iter};
 use rustc_data_structures::sync::Lrc;
@@ -430,6 +430,11 @@ pub enum Delimiter {
 }
 
 /// An interned string.
+///
+/// A `Term` used as an identifier resolves according to the span of the
+/// `TokenTree` containing it: use `Span::call_site()` for names that should be
+/// visible to the macro's caller, and `Span::def_site()` for names private to
+/// the macro's expansion.
 #[derive(Copy, Clone, Debug)]
 #[unstable(feature = "proc_macro", issue = "38356")]
 pub struct Term(Symbol);
@@ -547,6 +552,104 @@ impl Literal {
             .map(Into::<char>::into).collect::<String>();
         Literal(token::Literal(token::Lit::ByteStr(Symbol::intern(&string)), None))
     }
+
+    /// The suffix of a numeric literal (`u8` in `1u8`), if any.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn suffix(&self) -> Option<Term> {
+        match self.0 {
+            token::Literal(_, suffix) => suffix.map(Term),
+            _ => None,
+        }
+    }
+
+    /// The value of an integer literal, ignoring any suffix.
+    ///
+    /// Returns `None` if `self` is not an integer literal or if its value
+    /// does not fit in a `u128`. The sign of a negative number is a separate
+    /// `-` token, so it is not part of the literal.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn integer_value(&self) -> Option<u128> {
+        let (digits, suffix) = match self.0 {
+            token::Literal(token::Lit::Integer(digits), suffix) => (digits, suffix),
+            _ => return None,
+        };
+        // `1f32` is lexed as an integer, but is a float.
+        if suffix.map_or(false, |suffix| suffix.as_str().starts_with("f")) {
+            return None;
+        }
+
+        let digits = digits.as_str().chars().filter(|&c| c != '_').collect::<String>();
+        let (digits, radix) = match digits.get(..2) {
+            Some("0x") => (&digits[2..], 16),
+            Some("0o") => (&digits[2..], 8),
+            Some("0b") => (&digits[2..], 2),
+            _ => (&digits[..], 10),
+        };
+        u128::from_str_radix(digits, radix).ok()
+    }
+
+    /// The value of a floating point literal, ignoring any suffix.
+    ///
+    /// Returns `None` if `self` is not a floating point literal.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn float_value(&self) -> Option<f64> {
+        match self.lit_kind() {
+            Some(ast::LitKind::Float(value, _)) |
+            Some(ast::LitKind::FloatUnsuffixed(value)) => value.as_str().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// The value of a string literal, raw or not, with escapes resolved.
+    ///
+    /// Returns `None` if `self` is not a string literal.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn string_value(&self) -> Option<String> {
+        match self.lit_kind() {
+            Some(ast::LitKind::Str(value, _)) => Some(value.as_str().to_string()),
+            _ => None,
+        }
+    }
+
+    /// The value of a character literal.
+    ///
+    /// Returns `None` if `self` is not a character literal.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn character_value(&self) -> Option<char> {
+        match self.lit_kind() {
+            Some(ast::LitKind::Char(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value of a byte literal (`b'a'`).
+    ///
+    /// Returns `None` if `self` is not a byte literal.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn byte_value(&self) -> Option<u8> {
+        match self.lit_kind() {
+            Some(ast::LitKind::Byte(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value of a byte string literal, raw or not, with escapes resolved.
+    ///
+    /// Returns `None` if `self` is not a byte string literal.
+    #[unstable(feature = "proc_macro", issue = "38356")]
+    pub fn byte_string_value(&self) -> Option<Vec<u8>> {
+        match self.lit_kind() {
+            Some(ast::LitKind::ByteStr(value)) => Some((*value).clone()),
+            _ => None,
+        }
+    }
+
+    fn lit_kind(&self) -> Option<ast::LitKind> {
+        match self.0 {
+            token::Literal(lit, suffix) => parse::lit_token(lit, suffix, None).1,
+            _ => None,
+        }
+    }
 }
 
 /// An iterator over `TokenTree`s.
@@ -825,6 +928,24 @@ pub mod __internal {
         fn register_bang_proc_macro(&mut self,
                                     name: &str,
                                     expand: fn(TokenStream) -> TokenStream);
+
+        /// Registers an attribute macro declared with `#[proc_macro_attribute(pure)]`,
+        /// whose expansions the compiler may reuse for identical input. The
+        /// compiler doesn't check that the macro is actually pure.
+        fn register_pure_attr_proc_macro(&mut self,
+                                         name: &str,
+                                         expand: fn(TokenStream, TokenStream) -> TokenStream) {
+            self.register_attr_proc_macro(name, expand)
+        }
+
+        /// Registers a function-like macro declared with `#[proc_macro(pure)]`,
+        /// whose expansions the compiler may reuse for identical input. The
+        /// compiler doesn't check that the macro is actually pure.
+        fn register_pure_bang_proc_macro(&mut self,
+                                         name: &str,
+                                         expand: fn(TokenStream) -> TokenStream) {
+            self.register_bang_proc_macro(name, expand)
+        }
     }
 
     // Emulate scoped_thread_local!() here essentially
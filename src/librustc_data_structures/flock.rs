@@ -13,13 +13,16 @@
 //! This is not meant to be in the standard library, it does nothing with
 //! green/native threading. This is just a bare-bones enough solution for
 //! librustdoc, it is not production quality at all.
+//!
+//! The bootstrap compiler's standard library doesn't have the locks on
+//! `File` yet, so it keeps using the hand-written bindings below.
 
 #![allow(non_camel_case_types)]
 use std::path::Path;
 
 pub use self::imp::Lock;
 
-#[cfg(unix)]
+#[cfg(all(stage0, unix))]
 mod imp {
     use std::ffi::{CString, OsStr};
     use std::os::unix::prelude::*;
@@ -238,7 +241,7 @@ mod imp {
     }
 }
 
-#[cfg(windows)]
+#[cfg(all(stage0, windows))]
 #[allow(bad_style)]
 mod imp {
     use std::io;
@@ -353,6 +356,95 @@ mod imp {
     // automatically when it's closed.
 }
 
+// The locks on `File` are `flock` locks on most Unix platforms, rather than
+// the `fcntl` record locks used above. They belong to the open file rather
+// than to the process, so closing another handle to the lock file no longer
+// drops the lock, and two locks taken within one process now conflict.
+#[cfg(not(stage0))]
+mod imp {
+    use std::fs::File;
+    use std::io;
+    use std::path::Path;
+
+    #[derive(Debug)]
+    pub struct Lock {
+        _file: File,
+    }
+
+    impl Lock {
+        pub fn new(p: &Path,
+                   wait: bool,
+                   create: bool,
+                   exclusive: bool)
+                   -> io::Result<Lock> {
+            debug!("Attempting to open lock file `{}`", p.display());
+            let file = open(p, create)?;
+
+            debug!("Attempting to acquire lock on lock file `{}`", p.display());
+            let locked = match (wait, exclusive) {
+                (true, true) => file.lock_exclusive().map(|_| true),
+                (true, false) => file.lock_shared().map(|_| true),
+                (false, true) => file.try_lock_exclusive(),
+                (false, false) => file.try_lock_shared(),
+            };
+            match locked {
+                Ok(true) => {
+                    debug!("Successfully acquired lock.");
+                    Ok(Lock { _file: file })
+                }
+                Ok(false) => {
+                    debug!("Lock file is already locked");
+                    Err(io::Error::new(io::ErrorKind::WouldBlock,
+                                       "lock file is already locked"))
+                }
+                Err(err) => {
+                    debug!("Failed acquiring file lock: {}", err);
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    fn open(p: &Path, create: bool) -> io::Result<File> {
+        use std::fs::OpenOptions;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(create)
+            .mode(0o700)
+            .open(p)
+    }
+
+    #[cfg(windows)]
+    fn open(p: &Path, create: bool) -> io::Result<File> {
+        use std::fs::OpenOptions;
+        use std::os::windows::fs::OpenOptionsExt;
+
+        const FILE_SHARE_DELETE: u32 = 0x4;
+        const FILE_SHARE_READ: u32 = 0x1;
+        const FILE_SHARE_WRITE: u32 = 0x2;
+
+        assert!(p.parent().unwrap().exists(),
+            "Parent directory of lock-file must exist: {}",
+            p.display());
+
+        let mut open_options = OpenOptions::new();
+        open_options.read(true)
+                    .share_mode(FILE_SHARE_DELETE | FILE_SHARE_READ | FILE_SHARE_WRITE);
+        if create {
+            open_options.create(true)
+                        .write(true);
+        }
+        open_options.open(p)
+    }
+
+    // Note that we don't need a Drop impl: the lock is released when the file
+    // is closed.
+}
+
 impl imp::Lock {
     pub fn panicking_new(p: &Path,
                          wait: bool,
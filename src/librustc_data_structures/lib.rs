@@ -34,8 +34,9 @@
 #![feature(underscore_lifetimes)]
 #![feature(macro_vis_matcher)]
 #![feature(allow_internal_unstable)]
+#![cfg_attr(not(stage0), feature(file_lock))]
 
-#![cfg_attr(unix, feature(libc))]
+#![cfg_attr(all(stage0, unix), feature(libc))]
 #![cfg_attr(test, feature(test))]
 
 extern crate core;
@@ -43,7 +44,7 @@ extern crate ena;
 #[macro_use]
 extern crate log;
 extern crate serialize as rustc_serialize; // used by deriving
-#[cfg(unix)]
+#[cfg(all(stage0, unix))]
 extern crate libc;
 extern crate parking_lot;
 #[macro_use]
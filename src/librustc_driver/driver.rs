@@ -671,6 +671,10 @@ pub fn phase_2_configure_and_expand_inner<'a, F>(sess: &'a Session,
         });
     }
 
+    time(time_passes, "load proc macro expansion cache", || {
+        rustc_incremental::load_proc_macro_cache(sess);
+    });
+
     // If necessary, compute the dependency graph (in the background).
     let future_dep_graph = if sess.opts.build_dep_graph() {
         Some(rustc_incremental::load_dep_graph(sess, time_passes))
@@ -785,6 +789,7 @@ pub fn phase_2_configure_and_expand_inner<'a, F>(sess: &'a Session,
             features: Some(&features),
             recursion_limit: sess.recursion_limit.get(),
             trace_mac: sess.opts.debugging_opts.trace_macros,
+            trace_matchers: sess.opts.debugging_opts.trace_macro_matchers,
             should_test: sess.opts.test,
             ..syntax::ext::expand::ExpansionConfig::default(crate_name.to_string())
         };
@@ -813,6 +818,10 @@ pub fn phase_2_configure_and_expand_inner<'a, F>(sess: &'a Session,
         krate
     });
 
+    time(time_passes, "persist proc macro expansion cache", || {
+        rustc_incremental::save_proc_macro_cache(sess);
+    });
+
     krate = time(time_passes, "maybe building test harness", || {
         syntax::test::modify_for_testing(&sess.parse_sess,
                                          &mut resolver,
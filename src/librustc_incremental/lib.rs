@@ -39,8 +39,10 @@ pub use assert_dep_graph::assert_dep_graph;
 pub use persist::dep_graph_tcx_init;
 pub use persist::load_dep_graph;
 pub use persist::load_query_result_cache;
+pub use persist::load_proc_macro_cache;
 pub use persist::save_dep_graph;
 pub use persist::save_trans_partition;
+pub use persist::save_proc_macro_cache;
 pub use persist::save_work_products;
 pub use persist::in_incr_comp_dir;
 pub use persist::prepare_session_directory;
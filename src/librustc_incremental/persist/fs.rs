@@ -132,6 +132,7 @@ const LOCK_FILE_EXT: &'static str = ".lock";
 const DEP_GRAPH_FILENAME: &'static str = "dep-graph.bin";
 const WORK_PRODUCTS_FILENAME: &'static str = "work-products.bin";
 const QUERY_CACHE_FILENAME: &'static str = "query-cache.bin";
+const PROC_MACRO_CACHE_FILENAME: &'static str = "proc-macro-cache.bin";
 
 // We encode integers using the following base, so they are shorter than decimal
 // or hexadecimal numbers (we want short file and directory names). Since these
@@ -154,6 +155,10 @@ pub fn query_cache_path(sess: &Session) -> PathBuf {
     in_incr_comp_dir_sess(sess, QUERY_CACHE_FILENAME)
 }
 
+pub fn proc_macro_cache_path(sess: &Session) -> PathBuf {
+    in_incr_comp_dir_sess(sess, PROC_MACRO_CACHE_FILENAME)
+}
+
 pub fn lock_file_path(session_dir: &Path) -> PathBuf {
     let crate_dir = session_dir.parent().unwrap();
 
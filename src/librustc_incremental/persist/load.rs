@@ -199,6 +199,29 @@ pub fn load_dep_graph(sess: &Session, time_passes: bool) ->
     }))
 }
 
+/// Makes the procedural macro expansions recorded by the previous session
+/// available to macro expansion.
+pub fn load_proc_macro_cache(sess: &Session) {
+    if sess.opts.incremental.is_none() {
+        return
+    }
+
+    let path = proc_macro_cache_path(sess);
+    match load_data(sess.opts.debugging_opts.incremental_info, &path) {
+        LoadResult::Ok { data: (bytes, start_pos) } => {
+            let mut decoder = Decoder::new(&bytes[..], start_pos);
+            let result = sess.parse_sess.proc_macro_cache.borrow_mut().load(&mut decoder);
+            if let Err(e) = result {
+                // The cache is only an optimization, so just start over.
+                sess.warn(&format!("could not decode proc macro expansion cache `{}`: {}",
+                                   path.display(), e));
+            }
+        }
+        LoadResult::DataOutOfDate => {}
+        LoadResult::Error { message } => sess.warn(&message),
+    }
+}
+
 pub fn load_query_result_cache<'sess>(sess: &'sess Session) -> OnDiskCache<'sess> {
     if sess.opts.incremental.is_none() ||
        !sess.opts.debugging_opts.incremental_queries {
@@ -51,6 +51,17 @@ pub fn save_dep_graph<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
     })
 }
 
+pub fn save_proc_macro_cache(sess: &Session) {
+    if sess.opts.incremental.is_none() {
+        return;
+    }
+
+    debug!("save_proc_macro_cache()");
+    save_in(sess,
+            proc_macro_cache_path(sess),
+            |e| sess.parse_sess.proc_macro_cache.borrow().save(e));
+}
+
 pub fn save_work_products(sess: &Session, dep_graph: &DepGraph) {
     if sess.opts.incremental.is_none() {
         return;
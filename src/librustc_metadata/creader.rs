@@ -541,7 +541,36 @@ impl<'a> CrateLoader<'a> {
             mem::transmute::<*mut u8, fn(&mut Registry)>(sym)
         };
 
-        struct MyRegistrar(Vec<(ast::Name, Lrc<SyntaxExtension>)>);
+        struct MyRegistrar {
+            extensions: Vec<(ast::Name, Lrc<SyntaxExtension>)>,
+            crate_hash: u64,
+        }
+
+        impl MyRegistrar {
+            fn attr_proc_macro(&mut self,
+                               name: &str,
+                               expand: fn(TokenStream, TokenStream) -> TokenStream,
+                               pure: bool) {
+                let name = Symbol::intern(name);
+                let pure = if pure { Some((self.crate_hash, name)) } else { None };
+                let expand = SyntaxExtension::AttrProcMacro(
+                    Box::new(AttrProcMacro { inner: expand, pure })
+                );
+                self.extensions.push((name, Lrc::new(expand)));
+            }
+
+            fn bang_proc_macro(&mut self,
+                               name: &str,
+                               expand: fn(TokenStream) -> TokenStream,
+                               pure: bool) {
+                let name = Symbol::intern(name);
+                let pure = if pure { Some((self.crate_hash, name)) } else { None };
+                let expand = SyntaxExtension::ProcMacro(
+                    Box::new(BangProcMacro { inner: expand, pure })
+                );
+                self.extensions.push((name, Lrc::new(expand)));
+            }
+        }
 
         impl Registry for MyRegistrar {
             fn register_custom_derive(&mut self,
@@ -551,35 +580,44 @@ impl<'a> CrateLoader<'a> {
                 let attrs = attributes.iter().cloned().map(Symbol::intern).collect::<Vec<_>>();
                 let derive = ProcMacroDerive::new(expand, attrs.clone());
                 let derive = SyntaxExtension::ProcMacroDerive(Box::new(derive), attrs);
-                self.0.push((Symbol::intern(trait_name), Lrc::new(derive)));
+                self.extensions.push((Symbol::intern(trait_name), Lrc::new(derive)));
             }
 
             fn register_attr_proc_macro(&mut self,
                                         name: &str,
                                         expand: fn(TokenStream, TokenStream) -> TokenStream) {
-                let expand = SyntaxExtension::AttrProcMacro(
-                    Box::new(AttrProcMacro { inner: expand })
-                );
-                self.0.push((Symbol::intern(name), Lrc::new(expand)));
+                self.attr_proc_macro(name, expand, false);
             }
 
             fn register_bang_proc_macro(&mut self,
                                         name: &str,
                                         expand: fn(TokenStream) -> TokenStream) {
-                let expand = SyntaxExtension::ProcMacro(
-                    Box::new(BangProcMacro { inner: expand })
-                );
-                self.0.push((Symbol::intern(name), Lrc::new(expand)));
+                self.bang_proc_macro(name, expand, false);
+            }
+
+            fn register_pure_attr_proc_macro(&mut self,
+                                             name: &str,
+                                             expand: fn(TokenStream, TokenStream) -> TokenStream) {
+                self.attr_proc_macro(name, expand, true);
+            }
+
+            fn register_pure_bang_proc_macro(&mut self,
+                                             name: &str,
+                                             expand: fn(TokenStream) -> TokenStream) {
+                self.bang_proc_macro(name, expand, true);
             }
         }
 
-        let mut my_registrar = MyRegistrar(Vec::new());
+        let mut my_registrar = MyRegistrar {
+            extensions: Vec::new(),
+            crate_hash: root.hash.as_u64(),
+        };
         registrar(&mut my_registrar);
 
         // Intentionally leak the dynamic library. We can't ever unload it
         // since the library can make things that will live arbitrarily long.
         mem::forget(lib);
-        my_registrar.0
+        my_registrar.extensions
     }
 
     /// Look for a plugin registrar. Returns library path, crate
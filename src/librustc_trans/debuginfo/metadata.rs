@@ -557,7 +557,9 @@ pub fn type_metadata<'a, 'tcx>(cx: &CodegenCx<'a, 'tcx>,
                 Err(metadata) => return metadata,
             }
         }
-        ty::TyAdt(def, _) if def.is_box() => {
+        // A box with an allocator that takes up space is described as the struct it is.
+        ty::TyAdt(def, _) if def.is_box() &&
+                             cx.layout_of(t).field(cx, 0).size == cx.size_of(t) => {
             match ptr_metadata(t.boxed_ty()) {
                 Ok(res) => res,
                 Err(metadata) => return metadata,
@@ -431,7 +431,20 @@ impl<'a, 'tcx> FunctionCx<'a, 'tcx> {
                 elem: mir::ProjectionElem::Deref
             }) => {
                 // Load the pointer from its location.
-                self.trans_consume(bx, base).deref(bx.cx)
+                let mut ptr = self.trans_consume(bx, base);
+
+                // A box whose allocator takes up space is more than a pointer,
+                // so dig the pointer out of its first field.
+                if ptr.layout.ty.is_box() && ptr.layout.field(bx.cx, 0).size != ptr.layout.size {
+                    if let OperandValue::Ref(llval, align) = ptr.val {
+                        ptr = PlaceRef::new_sized(llval, ptr.layout, align)
+                            .project_field(bx, 0).load(bx);
+                    }
+                    while !ptr.layout.ty.is_unsafe_ptr() {
+                        ptr = ptr.extract_field(bx, 0);
+                    }
+                }
+                ptr.deref(bx.cx)
             }
             mir::Place::Projection(ref projection) => {
                 let tr_base = self.trans_place(bx, &projection.base);
@@ -343,7 +343,7 @@ impl<'tcx> LayoutLlvmExt<'tcx> for TyLayout<'tcx> {
             ty::TyRawPtr(_) => {
                 return self.field(cx, index).llvm_type(cx);
             }
-            ty::TyAdt(def, _) if def.is_box() => {
+            ty::TyAdt(def, _) if def.is_box() && self.field(cx, 0).size == self.size => {
                 let ptr_ty = cx.tcx.mk_mut_ptr(self.ty.boxed_ty());
                 return cx.layout_of(ptr_ty).scalar_pair_element_llvm_type(cx, index);
             }
@@ -489,7 +489,7 @@ impl<'tcx> LayoutLlvmExt<'tcx> for TyLayout<'tcx> {
                 // FIXME(eddyb) This should be for `ptr::Unique<T>`, not `Box<T>`.
                 if let Some(ref mut pointee) = result {
                     if let ty::TyAdt(def, _) = self.ty.sty {
-                        if def.is_box() && offset.bytes() == 0 {
+                        if def.is_box() && offset == self.fields.offset(0) {
                             pointee.safe = Some(PointerKind::UniqueOwned);
                         }
                     }
@@ -11,6 +11,8 @@
 use self::Entry::*;
 use self::VacantEntryState::*;
 
+use alloc::heap::{Heap, Alloc};
+use alloc::allocator::CollectionAllocErr;
 use cell::Cell;
 use borrow::Borrow;
 use cmp::max;
@@ -42,21 +44,28 @@ impl DefaultResizePolicy {
     /// provide that capacity, accounting for maximum loading. The raw capacity
     /// is always zero or a power of two.
     #[inline]
-    fn raw_capacity(&self, len: usize) -> usize {
+    fn try_raw_capacity(&self, len: usize) -> Result<usize, CollectionAllocErr> {
         if len == 0 {
-            0
+            Ok(0)
         } else {
             // 1. Account for loading: `raw_capacity >= len * 1.1`.
             // 2. Ensure it is a power of two.
             // 3. Ensure it is at least the minimum size.
-            let mut raw_cap = len * 11 / 10;
-            assert!(raw_cap >= len, "raw_cap overflow");
-            raw_cap = raw_cap.checked_next_power_of_two().expect("raw_capacity overflow");
+            let mut raw_cap = len.checked_mul(11)
+                .map(|l| l / 10)
+                .and_then(|l| l.checked_next_power_of_two())
+                .ok_or(CollectionAllocErr::CapacityOverflow)?;
+
             raw_cap = max(MIN_NONZERO_RAW_CAPACITY, raw_cap);
-            raw_cap
+            Ok(raw_cap)
         }
     }
 
+    #[inline]
+    fn raw_capacity(&self, len: usize) -> usize {
+        self.try_raw_capacity(len).expect("raw_capacity overflow")
+    }
+
     /// The capacity of the given raw capacity.
     #[inline]
     fn capacity(&self, raw_cap: usize) -> usize {
@@ -775,17 +784,45 @@ impl<K, V, S> HashMap<K, V, S>
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn reserve(&mut self, additional: usize) {
+        match self.try_reserve(additional) {
+            Err(CollectionAllocErr::CapacityOverflow) => panic!("capacity overflow"),
+            Err(CollectionAllocErr::AllocErr(e)) => Heap.oom(e),
+            Ok(()) => { /* yay */ }
+        }
+    }
+
+    /// Tries to reserve capacity for at least `additional` more elements to be inserted
+    /// in the given `HashMap<K,V>`. The collection may reserve more space to avoid
+    /// frequent reallocations.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::HashMap;
+    /// let mut map: HashMap<&str, isize> = HashMap::new();
+    /// map.try_reserve(10).expect("why is the test harness OOMing on 10 bytes?");
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "0")]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), CollectionAllocErr> {
         let remaining = self.capacity() - self.len(); // this can't overflow
         if remaining < additional {
-            let min_cap = self.len().checked_add(additional).expect("reserve overflow");
-            let raw_cap = self.resize_policy.raw_capacity(min_cap);
-            self.resize(raw_cap);
+            let min_cap = self.len().checked_add(additional)
+                .ok_or(CollectionAllocErr::CapacityOverflow)?;
+            let raw_cap = self.resize_policy.try_raw_capacity(min_cap)?;
+            self.try_resize(raw_cap)?;
         } else if self.table.tag() && remaining <= self.len() {
             // Probe sequence is too long and table is half full,
             // resize early to reduce probing length.
             let new_capacity = self.table.capacity() * 2;
-            self.resize(new_capacity);
+            self.try_resize(new_capacity)?;
         }
+        Ok(())
     }
 
     /// Resizes the internal vectors to a new capacity. It's your
@@ -795,15 +832,15 @@ impl<K, V, S> HashMap<K, V, S>
     ///   2) Ensure `new_raw_cap` is a power of two or zero.
     #[inline(never)]
     #[cold]
-    fn resize(&mut self, new_raw_cap: usize) {
+    fn try_resize(&mut self, new_raw_cap: usize) -> Result<(), CollectionAllocErr> {
         assert!(self.table.size() <= new_raw_cap);
         assert!(new_raw_cap.is_power_of_two() || new_raw_cap == 0);
 
-        let mut old_table = replace(&mut self.table, RawTable::new(new_raw_cap));
+        let mut old_table = replace(&mut self.table, RawTable::try_new(new_raw_cap)?);
         let old_size = old_table.size();
 
         if old_table.size() == 0 {
-            return;
+            return Ok(());
         }
 
         let mut bucket = Bucket::head_bucket(&mut old_table);
@@ -838,6 +875,7 @@ impl<K, V, S> HashMap<K, V, S>
         }
 
         assert_eq!(self.table.size(), old_size);
+        Ok(())
     }
 
     /// Shrinks the capacity of the map as much as possible. It will drop
@@ -1345,6 +1383,44 @@ impl<K, V, S> HashMap<K, V, S>
             debug_assert!(elems_left == 0 || bucket.index() != start_index);
         }
     }
+
+    /// Creates an iterator which uses a closure to determine if an element should be removed.
+    ///
+    /// If the closure returns true, the element is removed from the map and yielded.
+    /// If the closure returns false, the element remains in the map and will not be yielded
+    /// by the iterator.
+    ///
+    /// If the iterator is dropped before being fully consumed, the rest of the elements are
+    /// still filtered. Elements are visited in an arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Splitting a map into even and odd keys:
+    ///
+    /// ```
+    /// #![feature(drain_filter)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<i32, i32> = (0..8).map(|x| (x, x)).collect();
+    /// let drained: HashMap<i32, i32> = map.drain_filter(|k, _v| k % 2 == 0).collect();
+    ///
+    /// let mut evens = drained.keys().cloned().collect::<Vec<_>>();
+    /// let mut odds = map.keys().cloned().collect::<Vec<_>>();
+    /// evens.sort();
+    /// odds.sort();
+    ///
+    /// assert_eq!(evens, vec![0, 2, 4, 6]);
+    /// assert_eq!(odds, vec![1, 3, 5, 7]);
+    /// ```
+    #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<K, V, F>
+        where F: FnMut(&K, &mut V) -> bool
+    {
+        DrainFilter {
+            pred,
+            inner: DrainFilterInner::new(self),
+        }
+    }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -1534,6 +1610,31 @@ pub struct Drain<'a, K: 'a, V: 'a> {
     pub(super) inner: table::Drain<'a, K, V>,
 }
 
+/// A draining, filtering iterator over the entries of a `HashMap`.
+///
+/// This `struct` is created by the [`drain_filter`] method on [`HashMap`]. See its
+/// documentation for more.
+///
+/// [`drain_filter`]: struct.HashMap.html#method.drain_filter
+/// [`HashMap`]: struct.HashMap.html
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+pub struct DrainFilter<'a, K: 'a, V: 'a, F>
+    where F: FnMut(&K, &mut V) -> bool
+{
+    pred: F,
+    inner: DrainFilterInner<'a, K, V>,
+}
+
+/// The part of `DrainFilter` which doesn't depend on the predicate, shared
+/// with `HashSet`.
+pub(super) struct DrainFilterInner<'a, K: 'a, V: 'a> {
+    // The next bucket to look at, walking backwards from the end of a
+    // cluster so that backward shifts only move elements already visited.
+    // `None` once the table is exhausted, or if the predicate panicked.
+    bucket: Option<Bucket<K, V, &'a mut RawTable<K, V>>>,
+    elems_left: usize,
+}
+
 /// A mutable iterator over the values of a `HashMap`.
 ///
 /// This `struct` is created by the [`values_mut`] method on [`HashMap`]. See its
@@ -1924,6 +2025,90 @@ impl<'a, K, V> ExactSizeIterator for Drain<'a, K, V> {
 #[unstable(feature = "fused", issue = "35602")]
 impl<'a, K, V> FusedIterator for Drain<'a, K, V> {}
 
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, K, V, F> Iterator for DrainFilter<'a, K, V, F>
+    where F: FnMut(&K, &mut V) -> bool
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        self.inner.next(&mut self.pred)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, K, V, F> Drop for DrainFilter<'a, K, V, F>
+    where F: FnMut(&K, &mut V) -> bool
+{
+    fn drop(&mut self) {
+        for _ in self.by_ref() { }
+    }
+}
+
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, K, V, F> fmt::Debug for DrainFilter<'a, K, V, F>
+    where F: FnMut(&K, &mut V) -> bool
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("DrainFilter { .. }")
+    }
+}
+
+impl<'a, K, V> DrainFilterInner<'a, K, V> {
+    pub(super) fn new<S>(map: &'a mut HashMap<K, V, S>) -> DrainFilterInner<'a, K, V> {
+        let elems_left = map.table.size();
+        let bucket = if elems_left == 0 {
+            None
+        } else {
+            let mut bucket = Bucket::head_bucket(&mut map.table);
+            bucket.prev();
+            Some(bucket)
+        };
+        DrainFilterInner { bucket, elems_left }
+    }
+
+    pub(super) fn next<F>(&mut self, pred: &mut F) -> Option<(K, V)>
+        where F: FnMut(&K, &mut V) -> bool
+    {
+        while self.elems_left != 0 {
+            let bucket = match self.bucket.take() {
+                Some(bucket) => bucket,
+                None => return None,
+            };
+            let (mut bucket, removed) = match bucket.peek() {
+                Full(mut full) => {
+                    self.elems_left -= 1;
+                    let should_remove = {
+                        let (k, v) = full.read_mut();
+                        pred(k, v)
+                    };
+                    if should_remove {
+                        let prev_raw = full.raw();
+                        let (k, v, t) = pop_internal(full);
+                        (Bucket::new_from(prev_raw, t), Some((k, v)))
+                    } else {
+                        (full.into_bucket(), None)
+                    }
+                },
+                Empty(b) => (b.into_bucket(), None),
+            };
+            bucket.prev();  // reverse iteration
+            self.bucket = Some(bucket);
+            if removed.is_some() {
+                return removed;
+            }
+        }
+        None
+    }
+
+    pub(super) fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.elems_left))
+    }
+}
+
 #[stable(feature = "std_debug", since = "1.16.0")]
 impl<'a, K, V> fmt::Debug for Drain<'a, K, V>
     where K: fmt::Debug,
@@ -3579,6 +3764,35 @@ mod test_map {
         assert_eq!(map[&6], 60);
     }
 
+    #[test]
+    fn test_drain_filter() {
+        let mut map: HashMap<isize, isize> = (0..100).map(|x|(x, x*10)).collect();
+
+        let mut drained = map.drain_filter(|&k, v| {
+            *v += 1;
+            k % 3 == 0
+        }).collect::<Vec<_>>();
+        drained.sort();
+        assert_eq!(drained.len(), 34);
+        assert_eq!(drained[0], (0, 1));
+        assert_eq!(drained[33], (99, 991));
+
+        assert_eq!(map.len(), 66);
+        assert_eq!(map[&1], 11);
+        assert_eq!(map[&98], 981);
+        for k in 0..100 {
+            assert_eq!(map.contains_key(&k), k % 3 != 0);
+        }
+
+        // Dropping the iterator early still removes the rest.
+        map.drain_filter(|&k, _| k < 50).next();
+        assert_eq!(map.len(), 33);
+        assert!(map.keys().all(|&k| k >= 50));
+
+        let mut empty: HashMap<isize, isize> = HashMap::new();
+        assert_eq!(empty.drain_filter(|_, _| true).next(), None);
+    }
+
     #[test]
     fn test_adaptive() {
         const TEST_LEN: usize = 5000;
@@ -3651,4 +3865,24 @@ mod test_map {
         let _ = panic::catch_unwind(panic::AssertUnwindSafe(|| { hm.entry(0) <- makepanic(); }));
         assert_eq!(hm.len(), 0);
     }
+
+    #[test]
+    fn test_try_reserve() {
+        use collections::CollectionAllocErr::*;
+
+        let mut empty_bytes: HashMap<u8, u8> = HashMap::new();
+        assert_eq!(empty_bytes.try_reserve(10), Ok(()));
+        assert!(empty_bytes.capacity() >= 10);
+
+        // The length plus `additional` overflows.
+        empty_bytes.insert(1, 1);
+        assert_eq!(empty_bytes.try_reserve(usize::max_value()), Err(CapacityOverflow));
+
+        // The raw capacity, with room for the load factor, overflows.
+        assert_eq!(empty_bytes.try_reserve(usize::max_value() / 8), Err(CapacityOverflow));
+
+        // A failed reservation leaves the map untouched.
+        assert_eq!(empty_bytes.len(), 1);
+        assert_eq!(empty_bytes.get(&1), Some(&1));
+    }
 }
@@ -734,6 +734,42 @@ impl<T, S> HashSet<T, S>
     {
         self.map.retain(|k, _| f(k));
     }
+
+    /// Creates an iterator which uses a closure to determine if a value should be removed.
+    ///
+    /// If the closure returns true, the value is removed from the set and yielded.
+    /// If the closure returns false, the value remains in the set and will not be yielded
+    /// by the iterator.
+    ///
+    /// If the iterator is dropped before being fully consumed, the rest of the values are
+    /// still filtered. Values are visited in an arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Splitting a set into even and odd values:
+    ///
+    /// ```
+    /// #![feature(drain_filter)]
+    /// use std::collections::HashSet;
+    ///
+    /// let mut set: HashSet<i32> = (0..8).collect();
+    /// let mut evens = set.drain_filter(|v| v % 2 == 0).collect::<Vec<_>>();
+    /// let mut odds = set.into_iter().collect::<Vec<_>>();
+    /// evens.sort();
+    /// odds.sort();
+    ///
+    /// assert_eq!(evens, vec![0, 2, 4, 6]);
+    /// assert_eq!(odds, vec![1, 3, 5, 7]);
+    /// ```
+    #[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<T, F>
+        where F: FnMut(&T) -> bool
+    {
+        DrainFilter {
+            pred,
+            inner: map::DrainFilterInner::new(&mut self.map),
+        }
+    }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -974,6 +1010,21 @@ pub struct Drain<'a, K: 'a> {
     iter: map::Drain<'a, K, ()>,
 }
 
+/// A draining, filtering iterator over the items of a `HashSet`.
+///
+/// This `struct` is created by the [`drain_filter`] method on [`HashSet`].
+/// See its documentation for more.
+///
+/// [`HashSet`]: struct.HashSet.html
+/// [`drain_filter`]: struct.HashSet.html#method.drain_filter
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+pub struct DrainFilter<'a, K: 'a, F>
+    where F: FnMut(&K) -> bool
+{
+    pred: F,
+    inner: map::DrainFilterInner<'a, K, ()>,
+}
+
 /// A lazy iterator producing elements in the intersection of `HashSet`s.
 ///
 /// This `struct` is created by the [`intersection`] method on [`HashSet`].
@@ -1158,6 +1209,39 @@ impl<'a, K> ExactSizeIterator for Drain<'a, K> {
 #[unstable(feature = "fused", issue = "35602")]
 impl<'a, K> FusedIterator for Drain<'a, K> {}
 
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, K, F> Iterator for DrainFilter<'a, K, F>
+    where F: FnMut(&K) -> bool
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<K> {
+        let pred = &mut self.pred;
+        self.inner.next(&mut |k: &K, _: &mut ()| pred(k)).map(|(k, _)| k)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, K, F> Drop for DrainFilter<'a, K, F>
+    where F: FnMut(&K) -> bool
+{
+    fn drop(&mut self) {
+        for _ in self.by_ref() { }
+    }
+}
+
+#[unstable(feature = "drain_filter", reason = "recently added", issue = "43244")]
+impl<'a, K, F> fmt::Debug for DrainFilter<'a, K, F>
+    where F: FnMut(&K) -> bool
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("DrainFilter { .. }")
+    }
+}
+
 #[stable(feature = "std_debug", since = "1.16.0")]
 impl<'a, K: fmt::Debug> fmt::Debug for Drain<'a, K> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -1752,4 +1836,14 @@ mod test_set {
         assert!(set.contains(&4));
         assert!(set.contains(&6));
     }
+
+    #[test]
+    fn test_drain_filter() {
+        let mut set: HashSet<isize> = (0..10).collect();
+        let mut drained = set.drain_filter(|&k| k % 2 == 0).collect::<Vec<_>>();
+        drained.sort();
+        assert_eq!(drained, [0, 2, 4, 6, 8]);
+        assert_eq!(set.len(), 5);
+        assert!(set.iter().all(|&k| k % 2 == 1));
+    }
 }
@@ -17,13 +17,15 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
+use cmp;
 use fmt;
-use ffi::OsString;
-use io::{self, SeekFrom, Seek, Read, Initializer, Write};
+use ffi::{OsStr, OsString};
+use io::{self, SeekFrom, Seek, Read, Initializer, Write, IoVec, IoVecMut};
 use path::{Path, PathBuf};
 use sys::fs as fs_imp;
 use sys_common::{AsInnerMut, FromInner, AsInner, IntoInner};
 use time::SystemTime;
+use vec;
 
 /// A reference to an open file on the filesystem.
 ///
@@ -339,6 +341,202 @@ pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result
     File::create(path)?.write_all(contents.as_ref())
 }
 
+/// Atomically replace the entire contents of a file with a slice.
+///
+/// The contents are written to a temporary file in the same directory, which
+/// is flushed to disk and then renamed over `path`. Other processes see
+/// either the old contents or the new ones, never a mixture of the two, and
+/// if the system crashes part way through then `path` is left as it was.
+///
+/// This is a convenience function for using [`AtomicFile`] and
+/// [`write_all`]. See the former for details of how the replacement is made
+/// durable and which attributes of the old file are preserved.
+///
+/// [`AtomicFile`]: struct.AtomicFile.html
+/// [`write_all`]: ../io/trait.Write.html#method.write_all
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_atomic_write)]
+///
+/// use std::fs;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// fs::write_atomic("config.toml", b"verbose = true\n")?;
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "fs_atomic_write", issue = "0")]
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    let mut file = AtomicFile::create(path)?;
+    file.write_all(contents.as_ref())?;
+    file.commit()
+}
+
+/// A file whose contents atomically replace another file's when committed.
+///
+/// An `AtomicFile` is a new, uniquely named temporary file in the same
+/// directory as its destination. Once it has been written,
+/// [`commit`] flushes it to disk, renames it over the destination and then
+/// flushes the directory, so that the replacement survives a crash. If the
+/// `AtomicFile` is dropped without being committed, the temporary file is
+/// removed and the destination is untouched.
+///
+/// If the destination already exists, the temporary file is given its
+/// permissions. Other attributes, such as the owner on Unix or alternate
+/// data streams on Windows, are not preserved. If the destination is a
+/// symbolic link then the link itself is replaced by a regular file with
+/// the default permissions; the link's target is neither modified nor
+/// consulted.
+///
+/// [`commit`]: #method.commit
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_atomic_write)]
+///
+/// use std::fs::AtomicFile;
+/// use std::io::prelude::*;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// let mut file = AtomicFile::create("state.json")?;
+/// file.write_all(b"{\"version\": ")?;
+/// file.write_all(b"2}")?;
+/// file.commit()?;
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "fs_atomic_write", issue = "0")]
+pub struct AtomicFile {
+    file: File,
+    path: PathBuf,
+    temp_path: PathBuf,
+    committed: bool,
+}
+
+impl AtomicFile {
+    /// Creates a temporary file which will replace `path` when committed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `path` has no file name, if the
+    /// directory containing `path` does not exist or cannot be written to,
+    /// or if the permissions of an existing file at `path` cannot be read.
+    #[unstable(feature = "fs_atomic_write", issue = "0")]
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<AtomicFile> {
+        let path = path.as_ref();
+        let name = match path.file_name() {
+            Some(name) => name.to_string_lossy().into_owned(),
+            None => return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                              "path has no file name")),
+        };
+        // The link itself is what gets replaced, so don't follow it to copy
+        // the target's permissions onto the new file.
+        let permissions = match symlink_metadata(path) {
+            Ok(ref metadata) if metadata.file_type().is_symlink() => None,
+            Ok(metadata) => Some(metadata.permissions()),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+
+        let mut tries = 0;
+        loop {
+            let (key, _) = ::sys::hashmap_random_keys();
+            let temp_path = path.with_file_name(format!(".{}.{:016x}.tmp", name, key));
+            match OpenOptions::new().write(true).create_new(true).open(&temp_path) {
+                Ok(file) => {
+                    let file = AtomicFile {
+                        file,
+                        path: path.to_path_buf(),
+                        temp_path,
+                        committed: false,
+                    };
+                    if let Some(permissions) = permissions {
+                        file.file.set_permissions(permissions)?;
+                    }
+                    return Ok(file)
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists && tries < 16 => {
+                    tries += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Replaces the destination with the contents written so far.
+    ///
+    /// This flushes the temporary file to disk, renames it over the
+    /// destination and then flushes the containing directory with
+    /// [`File::sync_dir`]. When this returns `Ok` the new contents are durable.
+    ///
+    /// If an error is returned before the rename has happened, the temporary
+    /// file is removed and the destination is untouched. If the error comes
+    /// from flushing the directory, the destination has already been
+    /// replaced but the replacement may not survive a crash.
+    ///
+    /// [`File::sync_dir`]: struct.File.html#method.sync_dir
+    #[unstable(feature = "fs_atomic_write", issue = "0")]
+    pub fn commit(mut self) -> io::Result<()> {
+        self.file.sync_all()?;
+        rename(&self.temp_path, &self.path)?;
+        self.committed = true;
+        match self.path.parent() {
+            Some(parent) if parent != Path::new("") => File::sync_dir(parent),
+            _ => File::sync_dir("."),
+        }
+    }
+
+    /// Returns the path which this file will replace when committed.
+    #[unstable(feature = "fs_atomic_write", issue = "0")]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns a reference to the underlying temporary file.
+    #[unstable(feature = "fs_atomic_write", issue = "0")]
+    pub fn as_file(&self) -> &File {
+        &self.file
+    }
+}
+
+#[unstable(feature = "fs_atomic_write", issue = "0")]
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = remove_file(&self.temp_path);
+        }
+    }
+}
+
+#[unstable(feature = "fs_atomic_write", issue = "0")]
+impl fmt::Debug for AtomicFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AtomicFile")
+            .field("path", &self.path)
+            .field("temp_path", &self.temp_path)
+            .field("file", &self.file)
+            .finish()
+    }
+}
+
+#[unstable(feature = "fs_atomic_write", issue = "0")]
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+    fn flush(&mut self) -> io::Result<()> { self.file.flush() }
+}
+
+#[unstable(feature = "fs_atomic_write", issue = "0")]
+impl Seek for AtomicFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.file.seek(pos)
+    }
+}
+
 impl File {
     /// Attempts to open a file in read-only mode.
     ///
@@ -445,6 +643,395 @@ impl File {
         self.inner.datasync()
     }
 
+    /// Makes changes to the entries of the directory at `path` durable.
+    ///
+    /// [`sync_all`] makes a file's contents durable, but not the directory
+    /// entry through which it is reached. After creating, renaming or
+    /// removing a file, the change can be lost in a crash until the directory
+    /// containing it has been synced with this function.
+    ///
+    /// This is an associated function rather than a method because a
+    /// directory cannot be opened as a `File` on every platform.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to opening the directory and calling
+    /// `fsync` on it on Unix. On Windows, where NTFS journals changes to
+    /// directory entries and a directory handle cannot be flushed, it only
+    /// checks that `path` is a directory.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: ../io/index.html#platform-specific-behavior
+    /// [`sync_all`]: struct.File.html#method.sync_all
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `path` does not exist or is not a
+    /// directory, or if the flush fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_atomic_write)]
+    ///
+    /// use std::fs::{self, File};
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// fs::rename("data.tmp", "data")?;
+    /// File::sync_dir(".")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "fs_atomic_write", issue = "0")]
+    pub fn sync_dir<P: AsRef<Path>>(path: P) -> io::Result<()> {
+        fs_imp::sync_dir(path.as_ref())
+    }
+
+    /// Acquires a shared lock on the file, blocking until it is available.
+    ///
+    /// Any number of shared locks may be held on a file at once, but none
+    /// while an exclusive lock is held. Whether a lock also restricts reads
+    /// and writes depends on the platform, see below.
+    ///
+    /// A lock is held by this `File` and any handles created with
+    /// [`try_clone`], and is released by [`unlock`] or when the last of them
+    /// is closed.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to `flock(fd, LOCK_SH)` on Unix
+    /// and `LockFileEx` on Windows.
+    ///
+    /// On Unix locks are advisory: they only constrain other callers which
+    /// also lock the file, and do not prevent the file from being read or
+    /// written. On Windows locks are mandatory: while a shared lock is held
+    /// no handle, including this one, may write to the file, and while an
+    /// exclusive lock is held no other handle may read or write it. Such
+    /// reads and writes fail with an error rather than blocking.
+    ///
+    /// On Unix, locking a file which this handle already has locked
+    /// converts the existing lock, while on Windows the existing lock must be
+    /// released first. On Solaris, which has no `flock`, a whole-file `fcntl`
+    /// record lock is used, which requires the file to be open for reading
+    /// and is released when the process closes *any* handle to the file.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: ../io/index.html#platform-specific-behavior
+    /// [`try_clone`]: #method.try_clone
+    /// [`unlock`]: #method.unlock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    ///
+    /// use std::fs::File;
+    /// use std::io::prelude::*;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let mut f = File::open("foo.txt")?;
+    /// f.lock_shared()?;
+    /// let mut contents = String::new();
+    /// f.read_to_string(&mut contents)?;
+    /// f.unlock()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "0")]
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.inner.lock(false, true).map(|_| ())
+    }
+
+    /// Acquires an exclusive lock on the file, blocking until it is
+    /// available.
+    ///
+    /// No other lock, shared or exclusive, may be held on the file while an
+    /// exclusive lock is held. See [`lock_shared`] for how locks behave.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to `flock(fd, LOCK_EX)` on Unix
+    /// and `LockFileEx` with `LOCKFILE_EXCLUSIVE_LOCK` on Windows. On
+    /// Solaris the file must be open for writing.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: ../io/index.html#platform-specific-behavior
+    /// [`lock_shared`]: #method.lock_shared
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    ///
+    /// use std::fs::File;
+    /// use std::io::prelude::*;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let mut f = File::create("foo.txt")?;
+    /// f.lock_exclusive()?;
+    /// f.write_all(b"Hello, world!")?;
+    /// f.unlock()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "0")]
+    pub fn lock_exclusive(&self) -> io::Result<()> {
+        self.inner.lock(true, true).map(|_| ())
+    }
+
+    /// Attempts to acquire a shared lock on the file without blocking.
+    ///
+    /// Returns `Ok(true)` if the lock was acquired and `Ok(false)` if an
+    /// exclusive lock is held elsewhere. See [`lock_shared`] for how locks
+    /// behave.
+    ///
+    /// [`lock_shared`]: #method.lock_shared
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    ///
+    /// use std::fs::File;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let f = File::open("foo.txt")?;
+    /// if !f.try_lock_shared()? {
+    ///     println!("foo.txt is being written, try again later");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "0")]
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.inner.lock(false, false)
+    }
+
+    /// Attempts to acquire an exclusive lock on the file without blocking.
+    ///
+    /// Returns `Ok(true)` if the lock was acquired and `Ok(false)` if any
+    /// other lock is held on the file. See [`lock_shared`] for how locks
+    /// behave.
+    ///
+    /// [`lock_shared`]: #method.lock_shared
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    ///
+    /// use std::fs::File;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let f = File::create("build.lock")?;
+    /// if !f.try_lock_exclusive()? {
+    ///     println!("waiting for another build to finish");
+    ///     f.lock_exclusive()?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "0")]
+    pub fn try_lock_exclusive(&self) -> io::Result<bool> {
+        self.inner.lock(true, false)
+    }
+
+    /// Releases the lock held on the file by this handle, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    ///
+    /// use std::fs::File;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let f = File::create("build.lock")?;
+    /// f.lock_exclusive()?;
+    /// f.unlock()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "0")]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.inner.unlock()
+    }
+
+    /// Reads a number of bytes starting from a given offset in the file.
+    ///
+    /// Returns the number of bytes read. This is like [`Read::read`] except
+    /// that the read starts at `offset` rather than at the current position,
+    /// so several threads can read from different parts of a file through
+    /// one handle.
+    ///
+    /// Reading past the end of the file returns `Ok(0)`.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This corresponds to `pread` on Unix, which leaves the current position
+    /// unchanged, and to `ReadFile` with an offset on Windows, which moves
+    /// the current position to the end of the bytes read. Portable code
+    /// shouldn't mix these methods with [`Seek`] and [`Read`] on the same
+    /// handle.
+    ///
+    /// [`Read::read`]: ../io/trait.Read.html#tymethod.read
+    /// [`Read`]: ../io/trait.Read.html
+    /// [`Seek`]: ../io/trait.Seek.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_offset_io)]
+    ///
+    /// use std::fs::File;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let file = File::open("foo.txt")?;
+    /// let mut buf = [0; 8];
+    ///
+    /// // Read up to 8 bytes from offset 10.
+    /// let n = file.read_at(&mut buf, 10)?;
+    /// println!("{:?}", &buf[..n]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "file_offset_io", issue = "0")]
+    pub fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        self.inner.read_at(buf, offset)
+    }
+
+    /// Reads the exact number of bytes required to fill `buf` starting from
+    /// a given offset in the file.
+    ///
+    /// This calls [`read_at`] until `buf` is full, retrying on
+    /// [`ErrorKind::Interrupted`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::UnexpectedEof`] if the end of
+    /// the file is reached before `buf` is filled, in which case the contents
+    /// of `buf` are unspecified.
+    ///
+    /// [`read_at`]: #method.read_at
+    /// [`ErrorKind::Interrupted`]: ../io/enum.ErrorKind.html#variant.Interrupted
+    /// [`ErrorKind::UnexpectedEof`]: ../io/enum.ErrorKind.html#variant.UnexpectedEof
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_offset_io)]
+    ///
+    /// use std::fs::File;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let file = File::open("foo.txt")?;
+    /// let mut header = [0; 16];
+    /// file.read_exact_at(&mut header, 0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "file_offset_io", issue = "0")]
+    pub fn read_exact_at(&self, mut buf: &mut [u8], mut offset: u64) -> io::Result<()> {
+        while !buf.is_empty() {
+            match self.read_at(buf, offset) {
+                Ok(0) => break,
+                Ok(n) => {
+                    let tmp = buf;
+                    buf = &mut tmp[n..];
+                    offset += n as u64;
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        if !buf.is_empty() {
+            Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                               "failed to fill whole buffer"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Writes a number of bytes starting from a given offset in the file.
+    ///
+    /// Returns the number of bytes written. This is like [`Write::write`]
+    /// except that the write starts at `offset` rather than at the current
+    /// position. Writing past the end of the file extends it.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This corresponds to `pwrite` on Unix and to `WriteFile` with an offset
+    /// on Windows; see [`read_at`] for how they affect the current position.
+    /// On Linux, if the file was opened in append mode the data is appended
+    /// to the end of the file regardless of `offset`.
+    ///
+    /// [`Write::write`]: ../io/trait.Write.html#tymethod.write
+    /// [`read_at`]: #method.read_at
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_offset_io)]
+    ///
+    /// use std::fs::File;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let file = File::create("foo.txt")?;
+    ///
+    /// // Write a byte string starting at offset 10.
+    /// file.write_at(b"sushi", 10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "file_offset_io", issue = "0")]
+    pub fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
+        self.inner.write_at(buf, offset)
+    }
+
+    /// Writes an entire buffer starting from a given offset in the file.
+    ///
+    /// This calls [`write_at`] until all of `buf` has been written, retrying
+    /// on [`ErrorKind::Interrupted`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::WriteZero`] if [`write_at`]
+    /// returns `Ok(0)` before the whole buffer has been written.
+    ///
+    /// [`write_at`]: #method.write_at
+    /// [`ErrorKind::Interrupted`]: ../io/enum.ErrorKind.html#variant.Interrupted
+    /// [`ErrorKind::WriteZero`]: ../io/enum.ErrorKind.html#variant.WriteZero
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_offset_io)]
+    ///
+    /// use std::fs::File;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let file = File::create("foo.txt")?;
+    /// file.write_all_at(b"sushi", 10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "file_offset_io", issue = "0")]
+    pub fn write_all_at(&self, mut buf: &[u8], mut offset: u64) -> io::Result<()> {
+        while !buf.is_empty() {
+            match self.write_at(buf, offset) {
+                Ok(0) => return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                                   "failed to write whole buffer")),
+                Ok(n) => {
+                    buf = &buf[n..];
+                    offset += n as u64;
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
     /// Truncates or extends the underlying file, updating the size of
     /// this file to become `size`.
     ///
@@ -605,6 +1192,10 @@ impl Read for File {
         self.inner.read(buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -615,6 +1206,9 @@ impl Write for File {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.inner.write(buf)
     }
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -629,6 +1223,10 @@ impl<'a> Read for &'a File {
         self.inner.read(buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -639,6 +1237,9 @@ impl<'a> Write for &'a File {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.inner.write(buf)
     }
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -1893,6 +2494,417 @@ pub fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<ReadDir> {
     fs_imp::readdir(path.as_ref()).map(ReadDir)
 }
 
+/// Returns an iterator which recursively walks the directory tree rooted at
+/// `path`.
+///
+/// The iterator yields the root itself first, at depth 0, and then every
+/// entry below it in depth-first order, each directory being yielded before
+/// its contents. The walk is lazy: no directory is read until the iterator
+/// reaches it, and at most one directory handle is kept open per level of
+/// the tree.
+///
+/// The returned [`WalkDir`] can be configured before iterating to limit the
+/// depth of the walk, to follow symbolic links, or to visit the entries of
+/// each directory in sorted order. While iterating, [`WalkDir::prune`]
+/// skips the contents of the directory which was just yielded.
+///
+/// # Platform-specific behavior
+///
+/// The type of each entry is taken from [`DirEntry::file_type`], so on most
+/// Unix platforms walking a tree without following symbolic links needs no
+/// `stat` calls at all.
+///
+/// # Errors
+///
+/// Errors are reported through the iterator rather than ending the walk. A
+/// directory which cannot be read is still yielded as an [`Ok`] entry, and
+/// the [`Err`] from reading it comes next, where its contents would have
+/// been. A symbolic link which cannot be followed is yielded as an [`Err`]
+/// in place of the entry. Either way the walk then carries on with the
+/// siblings.
+///
+/// [`WalkDir`]: struct.WalkDir.html
+/// [`WalkDir::prune`]: struct.WalkDir.html#method.prune
+/// [`DirEntry::file_type`]: struct.DirEntry.html#method.file_type
+/// [`Ok`]: ../result/enum.Result.html#variant.Ok
+/// [`Err`]: ../result/enum.Result.html#variant.Err
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_walk_dir)]
+///
+/// use std::fs;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// for entry in fs::walk_dir("src").max_depth(3) {
+///     let entry = entry?;
+///     if entry.file_type().is_file() {
+///         println!("{}", entry.path().display());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "fs_walk_dir", issue = "0")]
+pub fn walk_dir<P: AsRef<Path>>(path: P) -> WalkDir {
+    WalkDir {
+        root: Some(path.as_ref().to_path_buf()),
+        min_depth: 0,
+        max_depth: usize::max_value(),
+        follow_links: false,
+        sorter: None,
+        stack: Vec::new(),
+        pending: None,
+    }
+}
+
+/// An iterator over the entries of a directory tree, created with
+/// [`walk_dir`].
+///
+/// [`walk_dir`]: fn.walk_dir.html
+#[unstable(feature = "fs_walk_dir", issue = "0")]
+pub struct WalkDir {
+    root: Option<PathBuf>,
+    min_depth: usize,
+    max_depth: usize,
+    follow_links: bool,
+    sorter: Option<Box<FnMut(&WalkDirEntry, &WalkDirEntry) -> cmp::Ordering + Send + Sync>>,
+    // One open directory per level of the walk that is currently being
+    // listed, the root's listing being at the bottom of the stack.
+    stack: Vec<DirList>,
+    // The directory most recently yielded, which will be descended into on
+    // the next call to `next` unless it is pruned first.
+    pending: Option<PathBuf>,
+}
+
+struct DirList {
+    entries: DirListEntries,
+    // The canonical path of this directory, recorded only when following
+    // symbolic links so that loops back to an ancestor can be detected.
+    canonical: Option<PathBuf>,
+}
+
+enum DirListEntries {
+    Unsorted(ReadDir),
+    Sorted(vec::IntoIter<io::Result<WalkDirEntry>>),
+}
+
+/// An entry yielded by the [`WalkDir`] iterator.
+///
+/// [`WalkDir`]: struct.WalkDir.html
+#[unstable(feature = "fs_walk_dir", issue = "0")]
+#[derive(Clone)]
+pub struct WalkDirEntry {
+    path: PathBuf,
+    file_type: FileType,
+    depth: usize,
+    is_symlink: bool,
+    // Whether `metadata` should traverse a symbolic link at `path`.
+    follow: bool,
+}
+
+impl WalkDir {
+    /// Sets the minimum depth of the entries which are yielded.
+    ///
+    /// The root is at depth 0 and its direct children are at depth 1, so a
+    /// minimum depth of 1 leaves out the root. Directories above the
+    /// minimum depth are still descended into. The default is 0.
+    #[unstable(feature = "fs_walk_dir", issue = "0")]
+    pub fn min_depth(mut self, depth: usize) -> WalkDir {
+        self.min_depth = depth;
+        self
+    }
+
+    /// Sets the maximum depth of the entries which are yielded.
+    ///
+    /// Directories at the maximum depth are yielded but never read, so a
+    /// maximum depth of 1 lists only the root and its direct children. The
+    /// default is unlimited.
+    #[unstable(feature = "fs_walk_dir", issue = "0")]
+    pub fn max_depth(mut self, depth: usize) -> WalkDir {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets whether symbolic links are followed.
+    ///
+    /// When enabled, a symbolic link is reported with the type of its target
+    /// and a link to a directory is descended into. A link which leads back
+    /// to one of its own ancestors is reported as an error instead of being
+    /// followed, so the walk always terminates. The root is always followed
+    /// if it is a symbolic link. The default is `false`.
+    #[unstable(feature = "fs_walk_dir", issue = "0")]
+    pub fn follow_links(mut self, follow: bool) -> WalkDir {
+        self.follow_links = follow;
+        self
+    }
+
+    /// Visits the entries of each directory in the order given by `compare`.
+    ///
+    /// Sorting requires reading each directory in full before yielding any
+    /// of its entries. By default entries are visited in whatever order the
+    /// operating system returns them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_walk_dir)]
+    ///
+    /// use std::fs;
+    ///
+    /// let walk = fs::walk_dir("src").sort_by(|a, b| a.file_name().cmp(b.file_name()));
+    /// for entry in walk {
+    ///     println!("{}", entry.unwrap().path().display());
+    /// }
+    /// ```
+    #[unstable(feature = "fs_walk_dir", issue = "0")]
+    pub fn sort_by<F>(mut self, compare: F) -> WalkDir
+        where F: FnMut(&WalkDirEntry, &WalkDirEntry) -> cmp::Ordering + Send + Sync + 'static
+    {
+        self.sorter = Some(Box::new(compare));
+        self
+    }
+
+    /// Skips the contents of the directory which was most recently yielded.
+    ///
+    /// Calling this after the iterator yields a directory prevents the walk
+    /// from descending into it. If the most recently yielded entry is not a
+    /// directory, or is at the maximum depth, this does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_walk_dir)]
+    ///
+    /// use std::fs;
+    ///
+    /// let mut walk = fs::walk_dir(".");
+    /// while let Some(entry) = walk.next() {
+    ///     let entry = entry.unwrap();
+    ///     if entry.file_name() == ".git" {
+    ///         walk.prune();
+    ///         continue
+    ///     }
+    ///     println!("{}", entry.path().display());
+    /// }
+    /// ```
+    #[unstable(feature = "fs_walk_dir", issue = "0")]
+    pub fn prune(&mut self) {
+        self.pending = None;
+    }
+
+    fn push(&mut self, path: &Path) -> io::Result<()> {
+        let canonical = if self.follow_links {
+            let canonical = canonicalize(path)?;
+            if self.stack.iter().any(|dir| dir.canonical.as_ref() == Some(&canonical)) {
+                return Err(io::Error::new(io::ErrorKind::Other,
+                                          "filesystem loop detected"))
+            }
+            Some(canonical)
+        } else {
+            None
+        };
+
+        let depth = self.stack.len() + 1;
+        let follow_links = self.follow_links;
+        let read_dir = read_dir(path)?;
+        let entries = match self.sorter {
+            Some(ref mut compare) => {
+                let mut entries = read_dir.map(|entry| {
+                    entry.and_then(|entry| WalkDirEntry::from_entry(entry, depth, follow_links))
+                }).collect::<Vec<_>>();
+                // Errors have nothing to compare by, so they go first.
+                entries.sort_by(|a, b| {
+                    match (a, b) {
+                        (&Ok(ref a), &Ok(ref b)) => compare(a, b),
+                        (&Err(_), &Ok(_)) => cmp::Ordering::Less,
+                        (&Ok(_), &Err(_)) => cmp::Ordering::Greater,
+                        (&Err(_), &Err(_)) => cmp::Ordering::Equal,
+                    }
+                });
+                DirListEntries::Sorted(entries.into_iter())
+            }
+            None => DirListEntries::Unsorted(read_dir),
+        };
+        self.stack.push(DirList { entries, canonical });
+        Ok(())
+    }
+
+    // Yields `entry` unless it is above the minimum depth, arranging for it
+    // to be descended into if it's a directory.
+    fn visit(&mut self, entry: WalkDirEntry) -> Option<WalkDirEntry> {
+        if entry.file_type.is_dir() && entry.depth < self.max_depth {
+            self.pending = Some(entry.path.clone());
+        }
+        if entry.depth >= self.min_depth {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "0")]
+impl Iterator for WalkDir {
+    type Item = io::Result<WalkDirEntry>;
+
+    fn next(&mut self) -> Option<io::Result<WalkDirEntry>> {
+        if let Some(root) = self.root.take() {
+            let entry = match WalkDirEntry::from_root(root) {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e)),
+            };
+            if let Some(entry) = self.visit(entry) {
+                return Some(Ok(entry))
+            }
+        }
+
+        loop {
+            if let Some(dir) = self.pending.take() {
+                if let Err(e) = self.push(&dir) {
+                    return Some(Err(e))
+                }
+            }
+
+            let depth = self.stack.len();
+            let follow_links = self.follow_links;
+            let next = match self.stack.last_mut() {
+                Some(dir) => match dir.entries {
+                    DirListEntries::Unsorted(ref mut read_dir) => {
+                        read_dir.next().map(|entry| {
+                            entry.and_then(|entry| {
+                                WalkDirEntry::from_entry(entry, depth, follow_links)
+                            })
+                        })
+                    }
+                    DirListEntries::Sorted(ref mut entries) => entries.next(),
+                },
+                None => return None,
+            };
+            match next {
+                Some(Ok(entry)) => {
+                    if let Some(entry) = self.visit(entry) {
+                        return Some(Ok(entry))
+                    }
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "0")]
+impl fmt::Debug for WalkDir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WalkDir")
+            .field("min_depth", &self.min_depth)
+            .field("max_depth", &self.max_depth)
+            .field("follow_links", &self.follow_links)
+            .field("sorted", &self.sorter.is_some())
+            .field("depth", &self.stack.len())
+            .finish()
+    }
+}
+
+impl WalkDirEntry {
+    fn from_root(path: PathBuf) -> io::Result<WalkDirEntry> {
+        let mut file_type = symlink_metadata(&path)?.file_type();
+        let is_symlink = file_type.is_symlink();
+        if is_symlink {
+            file_type = metadata(&path)?.file_type();
+        }
+        Ok(WalkDirEntry { path, file_type, depth: 0, is_symlink, follow: true })
+    }
+
+    fn from_entry(entry: DirEntry, depth: usize, follow: bool)
+                  -> io::Result<WalkDirEntry> {
+        let mut file_type = entry.file_type()?;
+        let path = entry.path();
+        let is_symlink = file_type.is_symlink();
+        if is_symlink && follow {
+            file_type = metadata(&path)?.file_type();
+        }
+        Ok(WalkDirEntry { path, file_type, depth, is_symlink, follow })
+    }
+
+    /// Returns the full path to this entry.
+    ///
+    /// The path is the root passed to [`walk_dir`] joined with the names of
+    /// each directory leading down to this entry.
+    ///
+    /// [`walk_dir`]: fn.walk_dir.html
+    #[unstable(feature = "fs_walk_dir", issue = "0")]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Consumes this entry, returning its full path.
+    #[unstable(feature = "fs_walk_dir", issue = "0")]
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+
+    /// Returns the bare file name of this entry.
+    ///
+    /// For the root this is the last component of the path it was given
+    /// as, or the whole path if it has no final component, such as `..`.
+    #[unstable(feature = "fs_walk_dir", issue = "0")]
+    pub fn file_name(&self) -> &OsStr {
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+
+    /// Returns the type of this entry.
+    ///
+    /// If the walk follows symbolic links then this is the type of the
+    /// link's target, otherwise it is the type of the link itself.
+    #[unstable(feature = "fs_walk_dir", issue = "0")]
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Returns the depth of this entry, the root being at depth 0.
+    #[unstable(feature = "fs_walk_dir", issue = "0")]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns whether the path of this entry is a symbolic link, whether or
+    /// not the walk followed it.
+    #[unstable(feature = "fs_walk_dir", issue = "0")]
+    pub fn path_is_symlink(&self) -> bool {
+        self.is_symlink
+    }
+
+    /// Queries the metadata of this entry.
+    ///
+    /// Symbolic links are traversed if and only if the walk follows them.
+    /// Unlike [`file_type`], this always requires a system call.
+    ///
+    /// [`file_type`]: #method.file_type
+    #[unstable(feature = "fs_walk_dir", issue = "0")]
+    pub fn metadata(&self) -> io::Result<Metadata> {
+        if self.follow {
+            metadata(&self.path)
+        } else {
+            symlink_metadata(&self.path)
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "0")]
+impl fmt::Debug for WalkDirEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WalkDirEntry")
+            .field("path", &self.path)
+            .field("depth", &self.depth)
+            .finish()
+    }
+}
+
 /// Changes the permissions found on a file or a directory.
 ///
 /// # Platform-specific behavior
@@ -2033,7 +3045,7 @@ mod tests {
     use io::prelude::*;
 
     use fs::{self, File, OpenOptions};
-    use io::{ErrorKind, SeekFrom};
+    use io::{self, ErrorKind, SeekFrom};
     use path::Path;
     use rand::{StdRng, Rng};
     use str;
@@ -2117,6 +3129,35 @@ mod tests {
         check!(fs::remove_file(filename));
     }
 
+    #[test]
+    fn file_test_io_vectored() {
+        use io::{IoVec, IoVecMut};
+
+        let tmpdir = tmpdir();
+        let filename = &tmpdir.join("file_rt_io_vectored.txt");
+        let written = {
+            let mut f = check!(File::create(filename));
+            check!(f.write_vectored(&[IoVec::new(&[]),
+                                      IoVec::new(b"hello "),
+                                      IoVec::new(b"world")]))
+        };
+        assert!(written > 0);
+        let mut contents = vec![];
+        check!(check!(File::open(filename)).read_to_end(&mut contents));
+        assert_eq!(&contents[..], &b"hello world"[..written]);
+
+        let mut f = check!(File::open(filename));
+        let mut a = [0; 3];
+        let mut b = [0; 16];
+        let read = check!(f.read_vectored(&mut [IoVecMut::new(&mut []),
+                                                IoVecMut::new(&mut a),
+                                                IoVecMut::new(&mut b)]));
+        assert!(read > 0 && read <= written);
+        let mut out = a.to_vec();
+        out.extend_from_slice(&b);
+        assert_eq!(&out[..read], &contents[..read]);
+    }
+
     #[test]
     fn invalid_path_raises() {
         let tmpdir = tmpdir();
@@ -2396,6 +3437,64 @@ mod tests {
         check!(fs::remove_file(&filename));
     }
 
+    #[test]
+    fn file_test_io_read_write_at_portable() {
+        let tmpdir = tmpdir();
+        let filename = tmpdir.join("file_rt_io_file_test_read_write_at_portable.txt");
+        let mut buf = [0; 8];
+        let rw = check!(OpenOptions::new().create_new(true).write(true).read(true)
+                                          .open(&filename));
+        assert_eq!(check!(rw.write_at(b"asdf", 5)), 4);
+        check!(rw.write_all_at(b"qwer-", 0));
+        check!(rw.write_all_at(b"-zxcv", 9));
+
+        check!(rw.read_exact_at(&mut buf[..4], 5));
+        assert_eq!(&buf[..4], b"asdf");
+        assert_eq!(check!(rw.read_at(&mut buf, 10)), 4);
+        assert_eq!(&buf[..4], b"zxcv");
+        assert_eq!(check!(rw.read_at(&mut buf, 14)), 0);
+        assert_eq!(check!(rw.read_at(&mut buf, 20)), 0);
+        assert_eq!(rw.read_exact_at(&mut buf, 10).unwrap_err().kind(),
+                   ErrorKind::UnexpectedEof);
+
+        // Threads can read disjoint parts of the file through one handle.
+        let rw = ::sync::Arc::new(rw);
+        let threads = (0..3).map(|i| {
+            let rw = rw.clone();
+            thread::spawn(move || {
+                let mut buf = [0; 4];
+                rw.read_exact_at(&mut buf, i * 5).unwrap();
+                buf
+            })
+        }).collect::<Vec<_>>();
+        let parts = threads.into_iter().map(|t| t.join().unwrap()).collect::<Vec<_>>();
+        assert_eq!(&parts[0], b"qwer");
+        assert_eq!(&parts[1], b"asdf");
+        assert_eq!(&parts[2], b"zxcv");
+    }
+
+    #[test]
+    fn copy_between_files_keeps_positions() {
+        let tmpdir = tmpdir();
+        let src_path = tmpdir.join("copy_src.txt");
+        let dst_path = tmpdir.join("copy_dst.txt");
+        let data = (0..100_000u32).map(|i| i as u8).collect::<Vec<_>>();
+        check!(check!(File::create(&src_path)).write_all(&data));
+
+        let mut src = check!(File::open(&src_path));
+        check!(src.seek(SeekFrom::Start(10)));
+        let mut dst = check!(File::create(&dst_path));
+        check!(dst.write_all(b"header"));
+
+        assert_eq!(check!(io::copy(&mut src, &mut dst)), data.len() as u64 - 10);
+        assert_eq!(check!(src.seek(SeekFrom::Current(0))), data.len() as u64);
+        assert_eq!(check!(dst.seek(SeekFrom::Current(0))), data.len() as u64 - 4);
+
+        let copied = check!(fs::read(&dst_path));
+        assert_eq!(&copied[..6], b"header");
+        assert_eq!(&copied[6..], &data[10..]);
+    }
+
     #[test]
     fn file_test_stat_is_correct_on_is_file() {
         let tmpdir = tmpdir();
@@ -3290,4 +4389,295 @@ mod tests {
             check!(b.created());
         }
     }
+
+    fn walk_names(walk: fs::WalkDir, root: &Path) -> Vec<(usize, String)> {
+        walk.sort_by(|a, b| a.file_name().cmp(b.file_name())).map(|entry| {
+            let entry = check!(entry);
+            let path = entry.path().strip_prefix(root).unwrap();
+            (entry.depth(), path.to_str().unwrap().replace("\\", "/"))
+        }).collect()
+    }
+
+    fn make_walk_tree(tmpdir: &TempDir) {
+        check!(fs::create_dir_all(tmpdir.join("a/b/c")));
+        check!(fs::create_dir_all(tmpdir.join("d")));
+        check!(File::create(tmpdir.join("a/x")));
+        check!(File::create(tmpdir.join("a/b/y")));
+        check!(File::create(tmpdir.join("a/b/c/z")));
+        check!(File::create(tmpdir.join("e")));
+    }
+
+    #[test]
+    fn walk_dir_sorted() {
+        let tmpdir = tmpdir();
+        make_walk_tree(&tmpdir);
+
+        let names = walk_names(fs::walk_dir(tmpdir.path()), tmpdir.path());
+        let expected = [(0, ""), (1, "a"), (2, "a/b"), (3, "a/b/c"), (4, "a/b/c/z"),
+                        (3, "a/b/y"), (2, "a/x"), (1, "d"), (1, "e")];
+        assert_eq!(names.len(), expected.len());
+        for (name, &(depth, path)) in names.iter().zip(expected.iter()) {
+            assert_eq!(name.0, depth);
+            assert_eq!(name.1, path);
+        }
+
+        let root = check!(fs::walk_dir(tmpdir.path()).next().unwrap());
+        assert!(root.file_type().is_dir());
+        assert!(!root.path_is_symlink());
+        assert_eq!(root.path(), tmpdir.path());
+    }
+
+    #[test]
+    fn walk_dir_unsorted() {
+        let tmpdir = tmpdir();
+        make_walk_tree(&tmpdir);
+
+        let mut paths = fs::walk_dir(tmpdir.path()).map(|entry| {
+            check!(entry).into_path()
+        }).collect::<Vec<_>>();
+        // Every directory comes before its contents.
+        for (i, path) in paths.iter().enumerate() {
+            if let Some(parent) = path.parent() {
+                if let Some(j) = paths.iter().position(|p| p == parent) {
+                    assert!(j < i);
+                }
+            }
+        }
+        paths.sort();
+        assert_eq!(paths.len(), 9);
+        assert!(paths.contains(&tmpdir.join("a/b/c/z")));
+    }
+
+    #[test]
+    fn walk_dir_depth_limits() {
+        let tmpdir = tmpdir();
+        make_walk_tree(&tmpdir);
+
+        let names = walk_names(fs::walk_dir(tmpdir.path()).max_depth(1), tmpdir.path());
+        let names = names.iter().map(|n| &n.1[..]).collect::<Vec<_>>();
+        assert_eq!(names, ["", "a", "d", "e"]);
+
+        let walk = fs::walk_dir(tmpdir.path()).min_depth(2).max_depth(3);
+        let names = walk_names(walk, tmpdir.path());
+        let names = names.iter().map(|n| &n.1[..]).collect::<Vec<_>>();
+        assert_eq!(names, ["a/b", "a/b/c", "a/b/y", "a/x"]);
+
+        let names = walk_names(fs::walk_dir(tmpdir.path()).max_depth(0), tmpdir.path());
+        assert_eq!(names.len(), 1);
+    }
+
+    #[test]
+    fn walk_dir_prune() {
+        let tmpdir = tmpdir();
+        make_walk_tree(&tmpdir);
+
+        let mut walk = fs::walk_dir(tmpdir.path())
+            .sort_by(|a, b| a.file_name().cmp(b.file_name()));
+        let mut seen = Vec::new();
+        while let Some(entry) = walk.next() {
+            let entry = check!(entry);
+            if entry.file_name() == "b" {
+                walk.prune();
+            }
+            seen.push(entry.file_name().to_str().unwrap().to_string());
+        }
+        assert_eq!(&seen[1..], ["a", "b", "x", "d", "e"]);
+    }
+
+    #[test]
+    fn walk_dir_of_file() {
+        let tmpdir = tmpdir();
+        let file = tmpdir.join("file");
+        check!(File::create(&file));
+
+        let entries = fs::walk_dir(&file).map(|e| check!(e)).collect::<Vec<_>>();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].file_type().is_file());
+        assert_eq!(entries[0].file_name(), "file");
+
+        assert!(fs::walk_dir(tmpdir.join("missing")).next().unwrap().is_err());
+    }
+
+    #[test]
+    fn walk_dir_symlinks() {
+        let tmpdir = tmpdir();
+        if !got_symlink_permission(&tmpdir) { return };
+        make_walk_tree(&tmpdir);
+        check!(symlink_dir(&tmpdir.join("a/b"), &tmpdir.join("d/link")));
+        // A link back to an ancestor would loop forever if followed blindly.
+        check!(symlink_dir(&tmpdir.join("a"), &tmpdir.join("a/b/c/up")));
+
+        // Not following links, they are reported as links and not entered.
+        let mut links = 0;
+        for entry in fs::walk_dir(tmpdir.path()) {
+            let entry = check!(entry);
+            assert!(!entry.path().starts_with(tmpdir.join("d/link/")));
+            if entry.file_type().is_symlink() {
+                assert!(entry.path_is_symlink());
+                links += 1;
+            }
+        }
+        assert_eq!(links, 2);
+
+        // Following links, the link in `d` is entered and the loop is an
+        // error which doesn't stop the walk.
+        let mut errors = 0;
+        let mut paths = Vec::new();
+        for entry in fs::walk_dir(tmpdir.path()).follow_links(true) {
+            match entry {
+                Ok(entry) => {
+                    assert!(!entry.file_type().is_symlink());
+                    paths.push(entry.into_path());
+                }
+                Err(_) => errors += 1,
+            }
+        }
+        assert!(paths.contains(&tmpdir.join("d/link/y")));
+        assert!(paths.contains(&tmpdir.join("a/b/c/up")));
+        assert!(errors >= 1);
+    }
+
+    #[test]
+    fn write_atomic_replaces_contents() {
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("config");
+
+        check!(fs::write_atomic(&path, b"first"));
+        assert_eq!(check!(fs::read(&path)), b"first");
+        check!(fs::write_atomic(&path, b"second"));
+        assert_eq!(check!(fs::read(&path)), b"second");
+
+        // No temporary files are left behind.
+        assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 1);
+    }
+
+    #[test]
+    fn atomic_file_dropped_without_commit() {
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("config");
+        check!(fs::write(&path, b"original"));
+
+        {
+            let mut file = check!(fs::AtomicFile::create(&path));
+            check!(file.write_all(b"discarded"));
+            assert_eq!(file.path(), &*path);
+            assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 2);
+        }
+
+        assert_eq!(check!(fs::read(&path)), b"original");
+        assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 1);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn atomic_file_preserves_permissions() {
+        use os::unix::fs::PermissionsExt;
+
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("config");
+        check!(fs::write(&path, b"original"));
+        check!(fs::set_permissions(&path, fs::Permissions::from_mode(0o640)));
+
+        check!(fs::write_atomic(&path, b"replaced"));
+        assert_eq!(check!(fs::read(&path)), b"replaced");
+        let perm = check!(fs::metadata(&path)).permissions();
+        assert_eq!(perm.mode() & 0o777, 0o640);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn atomic_file_replaces_symlink() {
+        use os::unix::fs::{symlink, PermissionsExt};
+
+        let tmpdir = tmpdir();
+        let target = tmpdir.join("target");
+        let link = tmpdir.join("link");
+        check!(fs::write(&target, b"original"));
+        check!(fs::set_permissions(&target, fs::Permissions::from_mode(0o604)));
+        check!(symlink(&target, &link));
+
+        check!(fs::write_atomic(&link, b"replaced"));
+        assert!(!check!(fs::symlink_metadata(&link)).file_type().is_symlink());
+        assert_eq!(check!(fs::read(&link)), b"replaced");
+        assert_eq!(check!(fs::read(&target)), b"original");
+        // The target's permissions aren't copied: the new file gets the same
+        // mode as any freshly created file, whatever the umask is.
+        let fresh = tmpdir.join("fresh");
+        check!(File::create(&fresh));
+        let expected = check!(fs::metadata(&fresh)).permissions().mode() & 0o777;
+        let perm = check!(fs::metadata(&link)).permissions();
+        assert_eq!(perm.mode() & 0o777, expected);
+    }
+
+    #[test]
+    fn atomic_file_invalid_paths() {
+        let tmpdir = tmpdir();
+        assert!(fs::AtomicFile::create(tmpdir.join("missing/config")).is_err());
+        assert!(fs::AtomicFile::create("..").is_err());
+    }
+
+    #[test]
+    fn sync_dir_works() {
+        let tmpdir = tmpdir();
+        check!(File::sync_dir(tmpdir.path()));
+
+        let file = tmpdir.join("file");
+        check!(File::create(&file));
+        assert!(File::sync_dir(&file).is_err());
+        assert!(File::sync_dir(tmpdir.join("missing")).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "solaris", ignore)] // fcntl locks never conflict within a process
+    fn file_lock_works() {
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("lock");
+        let a = check!(File::create(&path));
+        let b = check!(File::open(&path));
+        let c = check!(File::open(&path));
+
+        // Shared locks coexist, but keep out an exclusive lock.
+        check!(a.lock_shared());
+        assert!(check!(b.try_lock_shared()));
+        assert!(!check!(c.try_lock_exclusive()));
+        check!(a.unlock());
+        assert!(!check!(c.try_lock_exclusive()));
+        check!(b.unlock());
+        assert!(check!(c.try_lock_exclusive()));
+
+        // An exclusive lock keeps out everyone else.
+        assert!(!check!(a.try_lock_shared()));
+        assert!(!check!(b.try_lock_exclusive()));
+
+        // Closing the handle releases its lock.
+        drop(c);
+        assert!(check!(a.try_lock_exclusive()));
+        check!(a.unlock());
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "solaris", ignore)] // fcntl locks never conflict within a process
+    fn file_lock_blocks() {
+        use sync::mpsc::channel;
+        use time::Duration;
+
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("lock");
+        let a = check!(File::create(&path));
+        check!(a.lock_exclusive());
+
+        let (tx, rx) = channel();
+        let path2 = path.clone();
+        let t = thread::spawn(move || {
+            let b = check!(File::open(&path2));
+            check!(b.lock_shared());
+            tx.send(()).unwrap();
+        });
+
+        thread::sleep(Duration::from_millis(100));
+        assert!(rx.try_recv().is_err());
+        check!(a.unlock());
+        rx.recv().unwrap();
+        t.join().unwrap();
+    }
 }
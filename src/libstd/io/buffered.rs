@@ -15,7 +15,7 @@ use io::prelude::*;
 use cmp;
 use error;
 use fmt;
-use io::{self, Initializer, DEFAULT_BUF_SIZE, Error, ErrorKind, SeekFrom};
+use io::{self, Initializer, DEFAULT_BUF_SIZE, Error, ErrorKind, SeekFrom, IoVec};
 use memchr;
 
 /// The `BufReader` struct adds buffering to any reader.
@@ -554,6 +554,23 @@ impl<W: Write> Write for BufWriter<W> {
             Write::write(&mut self.buf, buf)
         }
     }
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        let total_len = bufs.iter().fold(0usize, |len, buf| len.saturating_add(buf.len()));
+        if self.buf.len().saturating_add(total_len) > self.buf.capacity() {
+            self.flush_buf()?;
+        }
+        if total_len >= self.buf.capacity() {
+            self.panicked = true;
+            let r = self.inner.as_mut().unwrap().write_vectored(bufs);
+            self.panicked = false;
+            r
+        } else {
+            for buf in bufs {
+                self.buf.extend_from_slice(buf);
+            }
+            Ok(total_len)
+        }
+    }
     fn flush(&mut self) -> io::Result<()> {
         self.flush_buf().and_then(|()| self.get_mut().flush())
     }
@@ -1045,6 +1062,27 @@ mod tests {
         assert_eq!(reader.get_ref().pos, expected);
     }
 
+    #[test]
+    fn test_buffered_writer_vectored() {
+        use io::IoVec;
+
+        let mut writer = BufWriter::with_capacity(4, Vec::new());
+
+        assert_eq!(writer.write_vectored(&[IoVec::new(&[0]), IoVec::new(&[1, 2])]).unwrap(), 3);
+        assert_eq!(*writer.get_ref(), []);
+
+        assert_eq!(writer.write_vectored(&[IoVec::new(&[3, 4])]).unwrap(), 2);
+        assert_eq!(*writer.get_ref(), [0, 1, 2]);
+
+        assert_eq!(writer.write_vectored(&[IoVec::new(&[5, 6, 7]),
+                                           IoVec::new(&[8, 9])]).unwrap(), 5);
+        assert_eq!(*writer.get_ref(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        assert_eq!(writer.write_vectored(&[]).unwrap(), 0);
+        writer.flush().unwrap();
+        assert_eq!(*writer.get_ref(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
     #[test]
     fn test_buffered_writer() {
         let inner = Vec::new();
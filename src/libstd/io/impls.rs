@@ -9,8 +9,10 @@
 // except according to those terms.
 
 use cmp;
-use io::{self, SeekFrom, Read, Initializer, Write, Seek, BufRead, Error, ErrorKind};
+use io::{self, SeekFrom, Read, Initializer, Write, Seek, BufRead, Error, ErrorKind, IoVec,
+         IoVecMut};
 use fmt;
+use heap::Alloc;
 use mem;
 
 // =============================================================================
@@ -23,6 +25,11 @@ impl<'a, R: Read + ?Sized> Read for &'a mut R {
         (**self).read(buf)
     }
 
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        (**self).read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         (**self).initializer()
@@ -48,6 +55,11 @@ impl<'a, W: Write + ?Sized> Write for &'a mut W {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { (**self).write(buf) }
 
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        (**self).write_vectored(bufs)
+    }
+
     #[inline]
     fn flush(&mut self) -> io::Result<()> { (**self).flush() }
 
@@ -92,6 +104,11 @@ impl<R: Read + ?Sized> Read for Box<R> {
         (**self).read(buf)
     }
 
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        (**self).read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         (**self).initializer()
@@ -117,6 +134,11 @@ impl<W: Write + ?Sized> Write for Box<W> {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { (**self).write(buf) }
 
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        (**self).write_vectored(bufs)
+    }
+
     #[inline]
     fn flush(&mut self) -> io::Result<()> { (**self).flush() }
 
@@ -257,13 +279,23 @@ impl<'a> Write for &'a mut [u8] {
 /// Write is implemented for `Vec<u8>` by appending to the vector.
 /// The vector will grow as needed.
 #[stable(feature = "rust1", since = "1.0.0")]
-impl Write for Vec<u8> {
+impl<A: Alloc> Write for Vec<u8, A> {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.extend_from_slice(buf);
         Ok(buf.len())
     }
 
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        let len = bufs.iter().map(|b| b.len()).sum();
+        self.reserve(len);
+        for buf in bufs {
+            self.extend_from_slice(buf);
+        }
+        Ok(len)
+    }
+
     #[inline]
     fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
         self.extend_from_slice(buf);
@@ -277,7 +277,9 @@ use fmt;
 use result;
 use str;
 use memchr;
+use ops::{Deref, DerefMut};
 use ptr;
+use sys;
 
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::buffered::{BufReader, BufWriter, LineWriter};
@@ -526,6 +528,44 @@ pub trait Read {
     #[stable(feature = "rust1", since = "1.0.0")]
     fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
 
+    /// Like [`read`], except that it reads into a slice of buffers.
+    ///
+    /// Data is copied to fill each buffer in order, with the final buffer
+    /// written to possibly being only partially filled. This method must
+    /// behave as a single call to [`read`] with the buffers concatenated
+    /// would.
+    ///
+    /// The default implementation calls [`read`] with the first nonempty
+    /// buffer, or an empty one if there is none. Readers such as files and
+    /// sockets override it to perform a single `readv`-like system call.
+    ///
+    /// [`read`]: #tymethod.read
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(iovec)]
+    ///
+    /// use std::io::{self, IoVecMut};
+    /// use std::io::prelude::*;
+    /// use std::fs::File;
+    ///
+    /// # fn foo() -> io::Result<()> {
+    /// let mut f = File::open("foo.txt")?;
+    /// let mut header = [0; 4];
+    /// let mut body = [0; 512];
+    ///
+    /// let n = f.read_vectored(&mut [IoVecMut::new(&mut header),
+    ///                               IoVecMut::new(&mut body)])?;
+    /// println!("read {} bytes", n);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "iovec", issue = "0")]
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> Result<usize> {
+        default_read_vectored(|b| self.read(b), bufs)
+    }
+
     /// Determines if this `Read`er can work with buffers of uninitialized
     /// memory.
     ///
@@ -951,6 +991,112 @@ impl Initializer {
     }
 }
 
+/// A buffer type used with `Write::write_vectored`.
+///
+/// It is semantically a wrapper around a `&[u8]`, but is guaranteed to be
+/// ABI compatible with the `iovec` type on Unix platforms and `WSABUF` on
+/// Windows, so that slices of them can be passed to the operating system
+/// directly.
+#[unstable(feature = "iovec", issue = "0")]
+#[repr(C)]
+pub struct IoVec<'a>(sys::io::IoVec<'a>);
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> fmt::Debug for IoVec<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.0.as_slice(), fmt)
+    }
+}
+
+impl<'a> IoVec<'a> {
+    /// Creates a new `IoVec` wrapping a byte slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics on Windows if the slice is larger than 4GB.
+    #[unstable(feature = "iovec", issue = "0")]
+    #[inline]
+    pub fn new(buf: &'a [u8]) -> IoVec<'a> {
+        IoVec(sys::io::IoVec::new(buf))
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> Deref for IoVec<'a> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+/// A buffer type used with `Read::read_vectored`.
+///
+/// It is semantically a wrapper around a `&mut [u8]`, but is guaranteed to
+/// be ABI compatible with the `iovec` type on Unix platforms and `WSABUF` on
+/// Windows, so that slices of them can be passed to the operating system
+/// directly.
+#[unstable(feature = "iovec", issue = "0")]
+#[repr(C)]
+pub struct IoVecMut<'a>(sys::io::IoVecMut<'a>);
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> fmt::Debug for IoVecMut<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.0.as_slice(), fmt)
+    }
+}
+
+impl<'a> IoVecMut<'a> {
+    /// Creates a new `IoVecMut` wrapping a byte slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics on Windows if the slice is larger than 4GB.
+    #[unstable(feature = "iovec", issue = "0")]
+    #[inline]
+    pub fn new(buf: &'a mut [u8]) -> IoVecMut<'a> {
+        IoVecMut(sys::io::IoVecMut::new(buf))
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> Deref for IoVecMut<'a> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> DerefMut for IoVecMut<'a> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.0.as_mut_slice()
+    }
+}
+
+pub(crate) fn default_read_vectored<F>(read: F, bufs: &mut [IoVecMut]) -> Result<usize>
+    where F: FnOnce(&mut [u8]) -> Result<usize>
+{
+    let buf = bufs.iter_mut()
+        .find(|b| !b.is_empty())
+        .map_or(&mut [][..], |b| &mut **b);
+    read(buf)
+}
+
+pub(crate) fn default_write_vectored<F>(write: F, bufs: &[IoVec]) -> Result<usize>
+    where F: FnOnce(&[u8]) -> Result<usize>
+{
+    let buf = bufs.iter()
+        .find(|b| !b.is_empty())
+        .map_or(&[][..], |b| &**b);
+    write(buf)
+}
+
 /// A trait for objects which are byte-oriented sinks.
 ///
 /// Implementors of the `Write` trait are sometimes called 'writers'.
@@ -1037,6 +1183,43 @@ pub trait Write {
     #[stable(feature = "rust1", since = "1.0.0")]
     fn write(&mut self, buf: &[u8]) -> Result<usize>;
 
+    /// Like [`write`], except that it writes from a slice of buffers.
+    ///
+    /// Data is copied from each buffer in order, with the final buffer read
+    /// from possibly being only partially consumed. This method must behave
+    /// as a single call to [`write`] with the buffers concatenated would.
+    ///
+    /// The default implementation calls [`write`] with the first nonempty
+    /// buffer, or an empty one if there is none. Writers such as files and
+    /// sockets override it to perform a single `writev`-like system call.
+    ///
+    /// [`write`]: #tymethod.write
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(iovec)]
+    ///
+    /// use std::io::IoVec;
+    /// use std::io::prelude::*;
+    /// use std::net::TcpStream;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let mut stream = TcpStream::connect("127.0.0.1:8080")?;
+    /// let payload = b"hello";
+    /// let header = [0, payload.len() as u8];
+    ///
+    /// // Writes some prefix of the header followed by the payload, without
+    /// // copying them into one buffer first.
+    /// stream.write_vectored(&[IoVec::new(&header), IoVec::new(payload)])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "iovec", issue = "0")]
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> Result<usize> {
+        default_write_vectored(|b| self.write(b), bufs)
+    }
+
     /// Flush this output stream, ensuring that all intermediately buffered
     /// contents reach their destination.
     ///
@@ -2347,6 +2530,36 @@ mod tests {
         assert_eq!(&cat[..], &exp[..])
     }
 
+    #[test]
+    fn default_read_vectored() {
+        use super::IoVecMut;
+
+        let mut c = Cursor::new(&b"hello"[..]);
+        let mut empty: [u8; 0] = [];
+        let mut a = [0; 2];
+        let mut b = [0; 8];
+        let n = c.read_vectored(&mut [IoVecMut::new(&mut empty),
+                                     IoVecMut::new(&mut a),
+                                     IoVecMut::new(&mut b)]).unwrap();
+        assert_eq!(n, 2);
+        assert_eq!(&a, b"he");
+        assert_eq!(c.read_vectored(&mut []).unwrap(), 0);
+    }
+
+    #[test]
+    fn default_write_vectored() {
+        use super::IoVec;
+
+        let mut buf = [0; 8];
+        let mut c = Cursor::new(&mut buf[..]);
+        let n = c.write_vectored(&[IoVec::new(&[]),
+                                   IoVec::new(b"abc"),
+                                   IoVec::new(b"def")]).unwrap();
+        assert_eq!(n, 3);
+        assert_eq!(c.position(), 3);
+        assert_eq!(c.write_vectored(&[]).unwrap(), 0);
+    }
+
     #[test]
     fn chain_bufread() {
         let testdata = b"ABCDEFGHIJKL";
@@ -29,6 +29,18 @@ use mem;
 /// `write` returns an error. All instances of `ErrorKind::Interrupted` are
 /// handled by this function and the underlying operation is retried.
 ///
+/// # Platform-specific behavior
+///
+/// On Linux, when both ends are unbuffered file descriptors such as a
+/// [`File`], [`TcpStream`] or the pipes of a child process, the data is moved
+/// inside the kernel with `copy_file_range`, `sendfile` or `splice` where
+/// possible, rather than through a buffer in userspace. Note that this
+/// [may change in the future][changes].
+///
+/// [`File`]: ../fs/struct.File.html
+/// [`TcpStream`]: ../net/struct.TcpStream.html
+/// [changes]: index.html#platform-specific-behavior
+///
 /// # Examples
 ///
 /// ```
@@ -49,13 +61,23 @@ use mem;
 pub fn copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W) -> io::Result<u64>
     where R: Read, W: Write
 {
+    #[cfg(target_os = "linux")]
+    let mut written = {
+        use sys::kernel_copy::{copy_spec, CopyResult};
+        match copy_spec(reader, writer)? {
+            CopyResult::Ended(written) => return Ok(written),
+            CopyResult::Fallback(written) => written,
+        }
+    };
+    #[cfg(not(target_os = "linux"))]
+    let mut written = 0;
+
     let mut buf = unsafe {
         let mut buf: [u8; super::DEFAULT_BUF_SIZE] = mem::uninitialized();
         reader.initializer().initialize(&mut buf);
         buf
     };
 
-    let mut written = 0;
     loop {
         let len = match reader.read(&mut buf) {
             Ok(0) => return Ok(written),
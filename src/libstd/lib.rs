@@ -305,11 +305,13 @@
 #![feature(slice_concat_ext)]
 #![feature(slice_internals)]
 #![feature(slice_patterns)]
+#![feature(specialization)]
 #![feature(staged_api)]
 #![feature(stmt_expr_attributes)]
 #![feature(str_char)]
 #![feature(str_internals)]
 #![feature(str_utf16)]
+#![feature(target_feature)]
 #![feature(termination_trait)]
 #![feature(test, rustc_private)]
 #![feature(thread_local)]
@@ -55,10 +55,217 @@ pub fn memrchr(needle: u8, haystack: &[u8]) -> Option<usize> {
     ::sys::memchr::memrchr(needle, haystack)
 }
 
+/// Returns the index of the first occurrence of either `n1` or `n2` in
+/// `haystack`, or `None` if neither is found.
+///
+/// Uses SSE2 or AVX2 when the CPU supports them.
+///
+/// # Examples
+///
+/// ```ignore (cannot-doctest-private-modules)
+/// use memchr::memchr2;
+///
+/// let haystack = b"the quick brown fox";
+/// assert_eq!(memchr2(b'k', b'q', haystack), Some(4));
+/// ```
+#[inline]
+pub fn memchr2(n1: u8, n2: u8, haystack: &[u8]) -> Option<usize> {
+    #[cfg(all(not(stage0), any(target_arch = "x86", target_arch = "x86_64")))]
+    {
+        if let Some(find) = x86::find_fn() {
+            return unsafe { find([n1, n2, n2], haystack) };
+        }
+    }
+    ::core::slice::memchr::memchr2(n1, n2, haystack)
+}
+
+/// Returns the index of the first occurrence of any of `n1`, `n2` and `n3`
+/// in `haystack`, or `None` if none of them is found.
+///
+/// Uses SSE2 or AVX2 when the CPU supports them.
+#[inline]
+pub fn memchr3(n1: u8, n2: u8, n3: u8, haystack: &[u8]) -> Option<usize> {
+    #[cfg(all(not(stage0), any(target_arch = "x86", target_arch = "x86_64")))]
+    {
+        if let Some(find) = x86::find_fn() {
+            return unsafe { find([n1, n2, n3], haystack) };
+        }
+    }
+    ::core::slice::memchr::memchr3(n1, n2, n3, haystack)
+}
+
+/// Returns the index of the last occurrence of either `n1` or `n2` in
+/// `haystack`, or `None` if neither is found.
+///
+/// Uses SSE2 or AVX2 when the CPU supports them.
+#[inline]
+pub fn memrchr2(n1: u8, n2: u8, haystack: &[u8]) -> Option<usize> {
+    #[cfg(all(not(stage0), any(target_arch = "x86", target_arch = "x86_64")))]
+    {
+        if let Some(rfind) = x86::rfind_fn() {
+            return unsafe { rfind([n1, n2, n2], haystack) };
+        }
+    }
+    ::core::slice::memchr::memrchr2(n1, n2, haystack)
+}
+
+/// Returns the index of the last occurrence of any of `n1`, `n2` and `n3`
+/// in `haystack`, or `None` if none of them is found.
+///
+/// Uses SSE2 or AVX2 when the CPU supports them.
+#[inline]
+pub fn memrchr3(n1: u8, n2: u8, n3: u8, haystack: &[u8]) -> Option<usize> {
+    #[cfg(all(not(stage0), any(target_arch = "x86", target_arch = "x86_64")))]
+    {
+        if let Some(rfind) = x86::rfind_fn() {
+            return unsafe { rfind([n1, n2, n3], haystack) };
+        }
+    }
+    ::core::slice::memchr::memrchr3(n1, n2, n3, haystack)
+}
+
+/// Vectorised searches for up to three bytes at once, picked at runtime
+/// according to what the CPU supports.
+#[cfg(all(not(stage0), any(target_arch = "x86", target_arch = "x86_64")))]
+mod x86 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+    use sync::atomic::{AtomicUsize, Ordering};
+
+    /// Searches `haystack` for any of the three bytes, which need not be
+    /// distinct.
+    pub type SearchFn = unsafe fn([u8; 3], &[u8]) -> Option<usize>;
+
+    const UNKNOWN: usize = 0;
+    const NONE: usize = 1;
+    const SSE2: usize = 2;
+    const AVX2: usize = 3;
+
+    /// The best instruction set extension available, detected on first use.
+    static LEVEL: AtomicUsize = AtomicUsize::new(UNKNOWN);
+
+    #[inline]
+    fn level() -> usize {
+        match LEVEL.load(Ordering::Relaxed) {
+            UNKNOWN => {
+                // Racing threads all arrive at the same answer.
+                let level = unsafe { detect() };
+                LEVEL.store(level, Ordering::Relaxed);
+                level
+            }
+            level => level,
+        }
+    }
+
+    unsafe fn detect() -> usize {
+        #[cfg(target_arch = "x86")]
+        {
+            if !has_cpuid() {
+                return NONE;
+            }
+        }
+        let max_leaf = __cpuid(0).eax;
+        let leaf1 = __cpuid(1);
+        if leaf1.edx & (1 << 26) == 0 {
+            return NONE;
+        }
+        // AVX2 also needs the OS to preserve the upper halves of the vector
+        // registers, which it signals through OSXSAVE and XCR0.
+        let osxsave_avx = (1 << 27) | (1 << 28);
+        if max_leaf >= 7 &&
+           leaf1.ecx & osxsave_avx == osxsave_avx &&
+           _xgetbv(0) & 0b110 == 0b110 &&
+           __cpuid_count(7, 0).ebx & (1 << 5) != 0 {
+            AVX2
+        } else {
+            SSE2
+        }
+    }
+
+    #[inline]
+    pub fn find_fn() -> Option<SearchFn> {
+        match level() {
+            AVX2 => Some(find_avx2 as SearchFn),
+            SSE2 => Some(find_sse2 as SearchFn),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn rfind_fn() -> Option<SearchFn> {
+        match level() {
+            AVX2 => Some(rfind_avx2 as SearchFn),
+            SSE2 => Some(rfind_sse2 as SearchFn),
+            _ => None,
+        }
+    }
+
+    // Closures aren't compiled with the enclosing function's target
+    // features, so the vector code is spelled out in the function bodies.
+    macro_rules! search_fns {
+        ($feature:tt, $find:ident, $rfind:ident, $vec:ident, $width:expr,
+         $set1:ident, $loadu:ident, $cmpeq:ident, $or:ident, $movemask:ident) => {
+            #[target_feature(enable = $feature)]
+            pub unsafe fn $find(needles: [u8; 3], haystack: &[u8]) -> Option<usize> {
+                let n1 = $set1(needles[0] as i8);
+                let n2 = $set1(needles[1] as i8);
+                let n3 = $set1(needles[2] as i8);
+                let ptr = haystack.as_ptr();
+
+                let mut offset = 0;
+                while offset + $width <= haystack.len() {
+                    let chunk = $loadu(ptr.offset(offset as isize) as *const $vec);
+                    let eq = $or($or($cmpeq(chunk, n1), $cmpeq(chunk, n2)), $cmpeq(chunk, n3));
+                    let mask = $movemask(eq) as u32;
+                    if mask != 0 {
+                        return Some(offset + mask.trailing_zeros() as usize);
+                    }
+                    offset += $width;
+                }
+
+                haystack[offset..].iter()
+                    .position(|&b| b == needles[0] || b == needles[1] || b == needles[2])
+                    .map(|i| offset + i)
+            }
+
+            #[target_feature(enable = $feature)]
+            pub unsafe fn $rfind(needles: [u8; 3], haystack: &[u8]) -> Option<usize> {
+                let n1 = $set1(needles[0] as i8);
+                let n2 = $set1(needles[1] as i8);
+                let n3 = $set1(needles[2] as i8);
+                let ptr = haystack.as_ptr();
+
+                let mut end = haystack.len();
+                while end >= $width {
+                    let chunk = $loadu(ptr.offset((end - $width) as isize) as *const $vec);
+                    let eq = $or($or($cmpeq(chunk, n1), $cmpeq(chunk, n2)), $cmpeq(chunk, n3));
+                    let mask = $movemask(eq) as u32;
+                    if mask != 0 {
+                        return Some(end - $width + (31 - mask.leading_zeros()) as usize);
+                    }
+                    end -= $width;
+                }
+
+                haystack[..end].iter()
+                    .rposition(|&b| b == needles[0] || b == needles[1] || b == needles[2])
+            }
+        }
+    }
+
+    search_fns!("sse2", find_sse2, rfind_sse2, __m128i, 16,
+                _mm_set1_epi8, _mm_loadu_si128, _mm_cmpeq_epi8, _mm_or_si128,
+                _mm_movemask_epi8);
+    search_fns!("avx2", find_avx2, rfind_avx2, __m256i, 32,
+                _mm256_set1_epi8, _mm256_loadu_si256, _mm256_cmpeq_epi8, _mm256_or_si256,
+                _mm256_movemask_epi8);
+}
+
 #[cfg(test)]
 mod tests {
     // test the implementations for the current plattform
-    use super::{memchr, memrchr};
+    use super::{memchr, memrchr, memchr2, memchr3, memrchr2, memrchr3};
 
     #[test]
     fn matches_one() {
@@ -140,4 +347,36 @@ mod tests {
             assert_eq!(Some(pos - start), memchr(needle, &data[start..]));
         }
     }
+
+    #[test]
+    fn matches_two_and_three() {
+        let haystack = b"the quick brown fox jumps over the lazy dog";
+        assert_eq!(Some(4), memchr2(b'q', b'z', haystack));
+        assert_eq!(Some(37), memrchr2(b'q', b'z', haystack));
+        assert_eq!(Some(10), memchr3(b'b', b'x', b'y', haystack));
+        assert_eq!(Some(38), memrchr3(b'b', b'x', b'y', haystack));
+        assert_eq!(None, memchr3(b'1', b'2', b'3', haystack));
+        assert_eq!(None, memrchr3(b'1', b'2', b'3', haystack));
+        assert_eq!(None, memchr2(b'a', b'b', b""));
+        assert_eq!(None, memrchr2(b'a', b'b', b""));
+    }
+
+    #[test]
+    fn each_position_two_and_three() {
+        // long enough to go through the vector loops, with every kind of tail
+        let mut data = [b'.'; 100];
+        for pos in 0..data.len() {
+            data[pos] = b'x';
+            for start in 0..pos + 1 {
+                let haystack = &data[start..];
+                assert_eq!(Some(pos - start), memchr2(b'x', b'y', haystack));
+                assert_eq!(Some(pos - start), memrchr2(b'y', b'x', haystack));
+                assert_eq!(Some(pos - start), memchr3(b'y', b'z', b'x', haystack));
+                assert_eq!(Some(pos - start), memrchr3(b'x', b'y', b'z', haystack));
+                assert_eq!(Some(pos - start), memchr(b'x', haystack));
+                assert_eq!(Some(pos - start), memrchr(b'x', haystack));
+            }
+            data[pos] = b'.';
+        }
+    }
 }
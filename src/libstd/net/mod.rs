@@ -48,16 +48,23 @@ pub use self::ip::{IpAddr, Ipv4Addr, Ipv6Addr, Ipv6MulticastScope};
 pub use self::addr::{SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::tcp::{TcpStream, TcpListener, Incoming};
+#[unstable(feature = "tcp_builder", issue = "0")]
+pub use self::tcp::TcpBuilder;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::udp::UdpSocket;
+#[unstable(feature = "udp_recv_msg", issue = "0")]
+pub use self::udp::{PacketInfo, RecvMeta};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::parser::AddrParseError;
+#[unstable(feature = "net_poller", issue = "0")]
+pub use self::poll::{Poller, Interest, Event, Events, EventsIter, Source};
 
 mod ip;
 mod addr;
 mod tcp;
 mod udp;
 mod parser;
+mod poll;
 #[cfg(test)]
 mod test;
 
@@ -0,0 +1,525 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use fmt;
+use io;
+use ops::BitOr;
+use sys::poll as poll_imp;
+use time::Duration;
+
+/// A readiness notification queue for nonblocking sockets and pipes.
+///
+/// Sources such as [`TcpStream`], [`TcpListener`], [`UdpSocket`] and the
+/// pipes of a child process are [`register`]ed with a token of the caller's
+/// choosing and an [`Interest`]. [`poll`] then blocks until at least one of
+/// them becomes ready, or the timeout elapses, and reports the tokens of the
+/// ready sources.
+///
+/// Registrations are edge-triggered: an event is reported when a source
+/// *becomes* ready, not for as long as it stays ready. After an event a
+/// source should be read from or written to until the operation fails with
+/// [`WouldBlock`], otherwise no further event may arrive for it. For the
+/// same reason, sources should be put in nonblocking mode, for example with
+/// [`TcpStream::set_nonblocking`], before they are registered.
+///
+/// This is implemented with epoll on Linux and Android and kqueue on macOS,
+/// iOS and the BSDs. On other platforms [`Poller::new`] returns an error.
+///
+/// [`TcpStream`]: struct.TcpStream.html
+/// [`TcpListener`]: struct.TcpListener.html
+/// [`UdpSocket`]: struct.UdpSocket.html
+/// [`register`]: #method.register
+/// [`Interest`]: struct.Interest.html
+/// [`poll`]: #method.poll
+/// [`WouldBlock`]: ../io/enum.ErrorKind.html#variant.WouldBlock
+/// [`TcpStream::set_nonblocking`]: struct.TcpStream.html#method.set_nonblocking
+/// [`Poller::new`]: #method.new
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(net_poller)]
+///
+/// use std::io::{self, Read};
+/// use std::net::{Events, Interest, Poller, TcpStream};
+///
+/// # fn foo() -> io::Result<()> {
+/// let mut stream = TcpStream::connect("127.0.0.1:8080")?;
+/// stream.set_nonblocking(true)?;
+///
+/// let poller = Poller::new()?;
+/// poller.register(&stream, 0, Interest::READABLE)?;
+///
+/// let mut events = Events::with_capacity(16);
+/// let mut buf = [0; 1024];
+/// loop {
+///     poller.poll(&mut events, None)?;
+///     for event in &events {
+///         assert_eq!(event.token(), 0);
+///         loop {
+///             match stream.read(&mut buf) {
+///                 Ok(0) => return Ok(()),
+///                 Ok(n) => println!("read {} bytes", n),
+///                 Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => break,
+///                 Err(e) => return Err(e),
+///             }
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[unstable(feature = "net_poller", issue = "0")]
+pub struct Poller(poll_imp::Poller);
+
+/// The kinds of readiness a source is registered for with a [`Poller`].
+///
+/// Interests are combined with `|`.
+///
+/// [`Poller`]: struct.Poller.html
+#[unstable(feature = "net_poller", issue = "0")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Interest {
+    readable: bool,
+    writable: bool,
+}
+
+/// A readiness event reported by [`Poller::poll`].
+///
+/// [`Poller::poll`]: struct.Poller.html#method.poll
+#[unstable(feature = "net_poller", issue = "0")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Event {
+    token: usize,
+    readable: bool,
+    writable: bool,
+    error: bool,
+    hup: bool,
+}
+
+/// A buffer of events filled in by [`Poller::poll`].
+///
+/// [`Poller::poll`]: struct.Poller.html#method.poll
+#[unstable(feature = "net_poller", issue = "0")]
+pub struct Events(poll_imp::Events);
+
+/// An iterator over the [`Event`]s in an [`Events`] buffer.
+///
+/// This struct is created by the [`iter`] method on [`Events`].
+///
+/// [`Event`]: struct.Event.html
+/// [`Events`]: struct.Events.html
+/// [`iter`]: struct.Events.html#method.iter
+#[unstable(feature = "net_poller", issue = "0")]
+#[derive(Debug)]
+pub struct EventsIter<'a> {
+    events: &'a Events,
+    pos: usize,
+}
+
+/// A source of readiness events which can be registered with a [`Poller`].
+///
+/// This trait is sealed and cannot be implemented outside of the standard
+/// library. On Unix it is implemented for the sockets in this module and in
+/// `std::os::unix::net`, and for the pipes of a child process.
+///
+/// [`Poller`]: struct.Poller.html
+#[unstable(feature = "net_poller", issue = "0")]
+pub trait Source: private::Sealed {
+    #[doc(hidden)]
+    fn raw_source(&self) -> poll_imp::RawSource;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+impl Poller {
+    /// Creates a new poller with no registered sources.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on platforms without a supported readiness
+    /// mechanism, or if the underlying queue cannot be created.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn new() -> io::Result<Poller> {
+        poll_imp::Poller::new().map(Poller)
+    }
+
+    /// Registers `source` for the readiness given by `interest`.
+    ///
+    /// Events for the source carry `token`. A source may only be registered
+    /// with one poller once; use [`reregister`] to change its token or
+    /// interest.
+    ///
+    /// [`reregister`]: #method.reregister
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn register<S: Source + ?Sized>(&self, source: &S, token: usize, interest: Interest)
+                                        -> io::Result<()> {
+        self.0.register(source.raw_source(), token, interest)
+    }
+
+    /// Changes the token and interest of a source which was previously
+    /// [`register`]ed.
+    ///
+    /// [`register`]: #method.register
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn reregister<S: Source + ?Sized>(&self, source: &S, token: usize, interest: Interest)
+                                          -> io::Result<()> {
+        self.0.reregister(source.raw_source(), token, interest)
+    }
+
+    /// Stops reporting events for `source`.
+    ///
+    /// Sources are also removed automatically when they are closed.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn deregister<S: Source + ?Sized>(&self, source: &S) -> io::Result<()> {
+        self.0.deregister(source.raw_source())
+    }
+
+    /// Waits for readiness events, filling `events` with them and returning
+    /// how many there are.
+    ///
+    /// Any events previously in `events` are cleared. At most
+    /// [`Events::capacity`] events are returned; the rest are reported by
+    /// later calls.
+    ///
+    /// If `timeout` is `None` this blocks until an event arrives. Otherwise
+    /// it returns `Ok(0)` once the timeout has elapsed without any. The call
+    /// may also return early with an error of kind [`Interrupted`] if a
+    /// signal arrives.
+    ///
+    /// [`Events::capacity`]: struct.Events.html#method.capacity
+    /// [`Interrupted`]: ../io/enum.ErrorKind.html#variant.Interrupted
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn poll(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<usize> {
+        self.0.poll(&mut events.0, timeout)
+    }
+}
+
+#[unstable(feature = "net_poller", issue = "0")]
+impl fmt::Debug for Poller {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("Poller { .. }")
+    }
+}
+
+impl Interest {
+    /// Interest in the source becoming readable, or being closed by the
+    /// peer.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub const READABLE: Interest = Interest { readable: true, writable: false };
+
+    /// Interest in the source becoming writable.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub const WRITABLE: Interest = Interest { readable: false, writable: true };
+
+    /// Returns whether this includes [`READABLE`].
+    ///
+    /// [`READABLE`]: #associatedconstant.READABLE
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn is_readable(&self) -> bool {
+        self.readable
+    }
+
+    /// Returns whether this includes [`WRITABLE`].
+    ///
+    /// [`WRITABLE`]: #associatedconstant.WRITABLE
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn is_writable(&self) -> bool {
+        self.writable
+    }
+}
+
+#[unstable(feature = "net_poller", issue = "0")]
+impl BitOr for Interest {
+    type Output = Interest;
+
+    fn bitor(self, other: Interest) -> Interest {
+        Interest {
+            readable: self.readable || other.readable,
+            writable: self.writable || other.writable,
+        }
+    }
+}
+
+impl Event {
+    pub(crate) fn new(token: usize,
+                      readable: bool,
+                      writable: bool,
+                      error: bool,
+                      hup: bool) -> Event {
+        Event { token, readable, writable, error, hup }
+    }
+
+    /// Returns the token the source was registered with.
+    ///
+    /// On some platforms readable and writable readiness of the same source
+    /// are reported as two separate events with the same token.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn token(&self) -> usize {
+        self.token
+    }
+
+    /// Returns whether the source has become readable.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn is_readable(&self) -> bool {
+        self.readable
+    }
+
+    /// Returns whether the source has become writable.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn is_writable(&self) -> bool {
+        self.writable
+    }
+
+    /// Returns whether an error is pending on the source.
+    ///
+    /// The error itself can be retrieved with methods such as
+    /// [`TcpStream::take_error`].
+    ///
+    /// [`TcpStream::take_error`]: struct.TcpStream.html#method.take_error
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn is_error(&self) -> bool {
+        self.error
+    }
+
+    /// Returns whether the peer has closed its end of the source, or the
+    /// source has otherwise hung up.
+    ///
+    /// Data may still be buffered for reading after a hang up.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn is_hup(&self) -> bool {
+        self.hup
+    }
+}
+
+impl Events {
+    /// Creates an empty buffer with room for `capacity` events.
+    ///
+    /// The capacity is rounded up to at least one on platforms which can't
+    /// wait with an empty buffer.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn with_capacity(capacity: usize) -> Events {
+        Events(poll_imp::Events::with_capacity(capacity))
+    }
+
+    /// Returns the maximum number of events returned by one call to
+    /// [`Poller::poll`].
+    ///
+    /// [`Poller::poll`]: struct.Poller.html#method.poll
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Returns the number of events in the buffer.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the buffer holds no events.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all events from the buffer, keeping its capacity.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Returns an iterator over the events in the buffer.
+    #[unstable(feature = "net_poller", issue = "0")]
+    pub fn iter(&self) -> EventsIter {
+        EventsIter { events: self, pos: 0 }
+    }
+}
+
+#[unstable(feature = "net_poller", issue = "0")]
+impl fmt::Debug for Events {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[unstable(feature = "net_poller", issue = "0")]
+impl<'a> IntoIterator for &'a Events {
+    type Item = Event;
+    type IntoIter = EventsIter<'a>;
+
+    fn into_iter(self) -> EventsIter<'a> {
+        self.iter()
+    }
+}
+
+#[unstable(feature = "net_poller", issue = "0")]
+impl<'a> Iterator for EventsIter<'a> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        let event = self.events.0.get(self.pos);
+        if event.is_some() {
+            self.pos += 1;
+        }
+        event
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.events.len() - self.pos;
+        (remaining, Some(remaining))
+    }
+}
+
+#[unstable(feature = "net_poller", issue = "0")]
+impl<'a> ExactSizeIterator for EventsIter<'a> {}
+
+macro_rules! unix_sources {
+    ($($t:ty)*) => ($(
+        #[cfg(unix)]
+        impl private::Sealed for $t {}
+
+        #[cfg(unix)]
+        #[unstable(feature = "net_poller", issue = "0")]
+        impl Source for $t {
+            fn raw_source(&self) -> poll_imp::RawSource {
+                ::os::unix::io::AsRawFd::as_raw_fd(self)
+            }
+        }
+    )*)
+}
+
+unix_sources! {
+    ::net::TcpStream
+    ::net::TcpListener
+    ::net::UdpSocket
+    ::process::ChildStdin
+    ::process::ChildStdout
+    ::process::ChildStderr
+}
+
+unix_sources! {
+    ::os::unix::net::UnixStream
+    ::os::unix::net::UnixListener
+    ::os::unix::net::UnixDatagram
+}
+
+#[cfg(all(test, any(target_os = "linux", target_os = "macos")))]
+mod tests {
+    use io::ErrorKind;
+    use io::prelude::*;
+    use net::*;
+    use net::test::next_test_ip4;
+    use process::{Command, Stdio};
+    use time::{Duration, Instant};
+
+    macro_rules! t {
+        ($e:expr) => {
+            match $e {
+                Ok(t) => t,
+                Err(e) => panic!("received error for `{}`: {}", stringify!($e), e),
+            }
+        }
+    }
+
+    fn wait_for(poller: &Poller, token: usize, readable: bool) -> Event {
+        let mut events = Events::with_capacity(8);
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            t!(poller.poll(&mut events, Some(Duration::from_millis(100))));
+            if let Some(e) = events.iter().find(|e| {
+                e.token() == token && (if readable { e.is_readable() } else { e.is_writable() })
+            }) {
+                return e;
+            }
+            assert!(Instant::now() < deadline, "timed out waiting for token {}", token);
+        }
+    }
+
+    #[test]
+    fn timeout() {
+        let poller = t!(Poller::new());
+        let mut events = Events::with_capacity(8);
+        let start = Instant::now();
+        assert_eq!(t!(poller.poll(&mut events, Some(Duration::from_millis(50)))), 0);
+        assert!(events.is_empty());
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[test]
+    fn tcp_loopback() {
+        let addr = next_test_ip4();
+        let listener = t!(TcpListener::bind(&addr));
+        t!(listener.set_nonblocking(true));
+
+        let poller = t!(Poller::new());
+        t!(poller.register(&listener, 1, Interest::READABLE));
+
+        let mut client = t!(TcpStream::connect(&addr));
+        wait_for(&poller, 1, true);
+
+        let (server, _) = t!(listener.accept());
+        t!(server.set_nonblocking(true));
+        t!(poller.register(&server, 2, Interest::READABLE | Interest::WRITABLE));
+        wait_for(&poller, 2, false);
+
+        t!(client.write_all(b"hello"));
+        wait_for(&poller, 2, true);
+        let mut buf = [0; 16];
+        assert_eq!(t!((&server).read(&mut buf)), 5);
+        assert_eq!(&buf[..5], b"hello");
+        assert_eq!((&server).read(&mut buf).unwrap_err().kind(), ErrorKind::WouldBlock);
+
+        drop(client);
+        let event = wait_for(&poller, 2, true);
+        assert!(event.is_hup());
+        assert_eq!(t!((&server).read(&mut buf)), 0);
+
+        t!(poller.deregister(&server));
+        t!(poller.deregister(&listener));
+    }
+
+    #[test]
+    fn udp_reregister() {
+        let a = t!(UdpSocket::bind(&next_test_ip4()));
+        let b = t!(UdpSocket::bind(&next_test_ip4()));
+        t!(a.set_nonblocking(true));
+
+        let poller = t!(Poller::new());
+        t!(poller.register(&a, 1, Interest::WRITABLE));
+        wait_for(&poller, 1, false);
+
+        t!(poller.reregister(&a, 7, Interest::READABLE));
+        t!(b.send_to(b"ping", &t!(a.local_addr())));
+        wait_for(&poller, 7, true);
+
+        let mut buf = [0; 8];
+        let (n, _) = t!(a.recv_from(&mut buf));
+        assert_eq!(&buf[..n], b"ping");
+    }
+
+    #[test]
+    fn child_stdout() {
+        let mut child = t!(Command::new("echo").arg("hi")
+                                               .stdout(Stdio::piped())
+                                               .spawn());
+        let mut stdout = child.stdout.take().unwrap();
+
+        let poller = t!(Poller::new());
+        t!(poller.register(&stdout, 3, Interest::READABLE));
+        wait_for(&poller, 3, true);
+
+        let mut s = String::new();
+        t!(stdout.read_to_string(&mut s));
+        assert_eq!(s, "hi\n");
+        t!(child.wait());
+    }
+}
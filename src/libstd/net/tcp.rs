@@ -11,7 +11,7 @@
 use io::prelude::*;
 
 use fmt;
-use io::{self, Initializer};
+use io::{self, Initializer, IoVec, IoVecMut};
 use net::{ToSocketAddrs, SocketAddr, Shutdown};
 use sys_common::net as net_imp;
 use sys_common::{AsInner, FromInner, IntoInner};
@@ -93,6 +93,39 @@ pub struct TcpStream(net_imp::TcpStream);
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct TcpListener(net_imp::TcpListener);
 
+/// A TCP socket which has not been connected or bound to listen yet.
+///
+/// This allows options such as `SO_REUSEADDR`, `IPV6_V6ONLY` or the buffer
+/// sizes to be set before the socket is [`bind`]ed, and the listen backlog
+/// to be chosen. The builder is then turned into a [`TcpListener`] with
+/// [`listen`], or into a [`TcpStream`] with [`connect`].
+///
+/// Unlike [`TcpListener::bind`], the builder does not set `SO_REUSEADDR`
+/// by default.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(tcp_builder)]
+///
+/// use std::net::TcpBuilder;
+///
+/// let builder = TcpBuilder::new_v6().unwrap();
+/// builder.set_only_v6(false).unwrap();
+/// builder.set_reuse_address(true).unwrap();
+/// builder.bind("[::]:8080").unwrap();
+/// let listener = builder.listen(1024).unwrap();
+/// ```
+///
+/// [`bind`]: #method.bind
+/// [`connect`]: #method.connect
+/// [`listen`]: #method.listen
+/// [`TcpListener`]: ../../std/net/struct.TcpListener.html
+/// [`TcpListener::bind`]: ../../std/net/struct.TcpListener.html#method.bind
+/// [`TcpStream`]: ../../std/net/struct.TcpStream.html
+#[unstable(feature = "tcp_builder", issue = "0")]
+pub struct TcpBuilder(net_imp::TcpBuilder);
+
 /// An iterator that infinitely [`accept`]s connections on a [`TcpListener`].
 ///
 /// This `struct` is created by the [`incoming`] method on [`TcpListener`].
@@ -507,6 +540,126 @@ impl TcpStream {
         self.0.ttl()
     }
 
+    /// Sets whether keepalive messages are enabled for this socket, and how
+    /// long the connection has to be idle before they are sent.
+    ///
+    /// If `None` is specified, keepalive messages are disabled. Otherwise,
+    /// this sets the `SO_KEEPALIVE` option, together with `TCP_KEEPIDLE` or
+    /// its equivalent. The time is rounded to whole seconds.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Windows, this uses `SIO_KEEPALIVE_VALS`, which also resets the
+    /// interval between probes to one second. Some other platforms, such
+    /// as OpenBSD, do not allow setting the idle time per socket, and return
+    /// an error if `Some` is given.
+    ///
+    /// # Errors
+    ///
+    /// An [`Err`] is returned if the zero [`Duration`] is passed to this
+    /// method.
+    ///
+    /// [`Err`]: ../../std/result/enum.Result.html#variant.Err
+    /// [`Duration`]: ../../std/time/struct.Duration.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_keepalive)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(Some(Duration::from_secs(60)))
+    ///       .expect("set_keepalive call failed");
+    /// ```
+    #[unstable(feature = "tcp_keepalive", issue = "0")]
+    pub fn set_keepalive(&self, keepalive: Option<Duration>) -> io::Result<()> {
+        self.0.set_keepalive(keepalive)
+    }
+
+    /// Returns the idle time before keepalive messages are sent on this
+    /// socket, or `None` if they are disabled.
+    ///
+    /// For more information about this option, see [`set_keepalive`][link].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Windows and on platforms where the idle time cannot be set per
+    /// socket, an error is returned if keepalive messages are enabled.
+    ///
+    /// [link]: #method.set_keepalive
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_keepalive)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(Some(Duration::from_secs(60)))
+    ///       .expect("set_keepalive call failed");
+    /// assert_eq!(stream.keepalive().unwrap(), Some(Duration::from_secs(60)));
+    /// ```
+    #[unstable(feature = "tcp_keepalive", issue = "0")]
+    pub fn keepalive(&self) -> io::Result<Option<Duration>> {
+        self.0.keepalive()
+    }
+
+    /// Sets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// If set to `Some`, dropping the stream blocks until all pending data
+    /// has been sent or the given time has elapsed. A time of zero makes the
+    /// connection be reset instead of closed gracefully. If `None`, closing
+    /// returns immediately and the data is sent in the background. The time
+    /// is rounded down to whole seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_linger)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_linger(Some(Duration::from_secs(5))).expect("set_linger call failed");
+    /// ```
+    #[unstable(feature = "tcp_linger", issue = "0")]
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.0.set_linger(linger)
+    }
+
+    /// Gets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// For more information about this option, see [`set_linger`][link].
+    ///
+    /// [link]: #method.set_linger
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_linger)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_linger(Some(Duration::from_secs(5))).expect("set_linger call failed");
+    /// assert_eq!(stream.linger().unwrap(), Some(Duration::from_secs(5)));
+    /// ```
+    #[unstable(feature = "tcp_linger", issue = "0")]
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.0.linger()
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -579,6 +732,10 @@ impl TcpStream {
 impl Read for TcpStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.0.read(buf) }
 
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -587,12 +744,19 @@ impl Read for TcpStream {
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Write for TcpStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.write(buf) }
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a> Read for &'a TcpStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.0.read(buf) }
 
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -601,6 +765,9 @@ impl<'a> Read for &'a TcpStream {
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a> Write for &'a TcpStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.write(buf) }
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 
@@ -916,6 +1083,296 @@ impl fmt::Debug for TcpListener {
     }
 }
 
+impl TcpBuilder {
+    /// Creates a new IPv4 TCP socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_builder)]
+    ///
+    /// use std::net::TcpBuilder;
+    ///
+    /// let builder = TcpBuilder::new_v4().expect("couldn't create socket");
+    /// ```
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn new_v4() -> io::Result<TcpBuilder> {
+        net_imp::TcpBuilder::new_v4().map(TcpBuilder)
+    }
+
+    /// Creates a new IPv6 TCP socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_builder)]
+    ///
+    /// use std::net::TcpBuilder;
+    ///
+    /// let builder = TcpBuilder::new_v6().expect("couldn't create socket");
+    /// ```
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn new_v6() -> io::Result<TcpBuilder> {
+        net_imp::TcpBuilder::new_v6().map(TcpBuilder)
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On Unix, this allows a listener to be bound to an address while
+    /// connections to a previous listener on it are still in the
+    /// `TIME_WAIT` state. On Windows, it allows binding to an address which
+    /// another socket is actively listening on.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_builder)]
+    ///
+    /// use std::net::TcpBuilder;
+    ///
+    /// let builder = TcpBuilder::new_v4().unwrap();
+    /// builder.set_reuse_address(true).expect("set_reuse_address call failed");
+    /// ```
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+        self.0.set_reuse_address(reuse)
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_reuse_address`][link].
+    ///
+    /// [link]: #method.set_reuse_address
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        self.0.reuse_address()
+    }
+
+    /// Sets the value of the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// If this is set to `true` then the socket is restricted to sending and
+    /// receiving IPv6 packets only. In this case two IPv4 and IPv6
+    /// applications can bind the same port at the same time.
+    ///
+    /// If this is set to `false` then the socket can be used to send and
+    /// receive packets from an IPv4-mapped IPv6 address.
+    ///
+    /// This only applies to sockets created with [`new_v6`], and has to be
+    /// set before the socket is bound.
+    ///
+    /// [`new_v6`]: #method.new_v6
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_builder)]
+    ///
+    /// use std::net::TcpBuilder;
+    ///
+    /// let builder = TcpBuilder::new_v6().unwrap();
+    /// builder.set_only_v6(true).expect("set_only_v6 call failed");
+    /// ```
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        self.0.set_only_v6(only_v6)
+    }
+
+    /// Gets the value of the `IPV6_V6ONLY` option for this socket.
+    ///
+    /// For more information about this option, see [`set_only_v6`][link].
+    ///
+    /// [link]: #method.set_only_v6
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.0.only_v6()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This is the size of the buffer the operating system reserves for
+    /// received data. The operating system may round the value or adjust it
+    /// for its own bookkeeping; Linux, for instance, doubles it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_builder)]
+    ///
+    /// use std::net::TcpBuilder;
+    ///
+    /// let builder = TcpBuilder::new_v4().unwrap();
+    /// builder.set_recv_buffer_size(1 << 20).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_recv_buffer_size`][link].
+    ///
+    /// [link]: #method.set_recv_buffer_size
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This is the size of the buffer the operating system reserves for data
+    /// waiting to be sent. As with [`set_recv_buffer_size`], the value may be
+    /// adjusted by the operating system.
+    ///
+    /// [`set_recv_buffer_size`]: #method.set_recv_buffer_size
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_send_buffer_size`][link].
+    ///
+    /// [link]: #method.set_send_buffer_size
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Sets the value for the `IP_TTL` option on this socket.
+    ///
+    /// This value sets the time-to-live field that is used in every packet sent
+    /// from this socket.
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.0.set_ttl(ttl)
+    }
+
+    /// Gets the value of the `IP_TTL` option for this socket.
+    ///
+    /// For more information about this option, see [`set_ttl`][link].
+    ///
+    /// [link]: #method.set_ttl
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.0.ttl()
+    }
+
+    /// Binds the socket to the given local address.
+    ///
+    /// The address type can be any implementor of [`ToSocketAddrs`] trait.
+    /// If `addr` yields multiple addresses, `bind` will be attempted with
+    /// each of the addresses until one succeeds. Binding with port 0 asks
+    /// the OS to assign a port, which can be queried with [`local_addr`].
+    ///
+    /// [`ToSocketAddrs`]: ../../std/net/trait.ToSocketAddrs.html
+    /// [`local_addr`]: #method.local_addr
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_builder)]
+    ///
+    /// use std::net::TcpBuilder;
+    ///
+    /// let builder = TcpBuilder::new_v4().unwrap();
+    /// builder.bind("127.0.0.1:0").expect("couldn't bind to address");
+    /// ```
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn bind<A: ToSocketAddrs>(&self, addr: A) -> io::Result<()> {
+        super::each_addr(addr, |addr| self.0.bind(addr))
+    }
+
+    /// Starts listening for incoming connections, returning a
+    /// [`TcpListener`].
+    ///
+    /// The socket should have been bound with [`bind`] first; otherwise the
+    /// OS chooses the address. `backlog` is the maximum number of pending
+    /// connections, which the OS may silently cap.
+    ///
+    /// [`bind`]: #method.bind
+    /// [`TcpListener`]: ../../std/net/struct.TcpListener.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_builder)]
+    ///
+    /// use std::net::TcpBuilder;
+    ///
+    /// let builder = TcpBuilder::new_v4().unwrap();
+    /// builder.bind("127.0.0.1:80").unwrap();
+    /// let listener = builder.listen(1024).expect("couldn't listen");
+    /// ```
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        self.0.listen(backlog).map(TcpListener)
+    }
+
+    /// Connects the socket to a remote host, returning a [`TcpStream`].
+    ///
+    /// If the socket was bound with [`bind`], that address is used as the
+    /// local end of the connection.
+    ///
+    /// [`bind`]: #method.bind
+    /// [`TcpStream`]: ../../std/net/struct.TcpStream.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_builder)]
+    ///
+    /// use std::net::{SocketAddr, TcpBuilder};
+    ///
+    /// let builder = TcpBuilder::new_v4().unwrap();
+    /// builder.set_send_buffer_size(1 << 20).unwrap();
+    /// let addr: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+    /// let stream = builder.connect(&addr).expect("couldn't connect");
+    /// ```
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<TcpStream> {
+        self.0.connect(addr).map(TcpStream)
+    }
+
+    /// Returns the local address that this socket is bound to.
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.socket_addr()
+    }
+
+    /// Get the value of the `SO_ERROR` option on this socket.
+    ///
+    /// This will retrieve the stored error in the underlying socket, clearing
+    /// the field in the process.
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.0.take_error()
+    }
+}
+
+impl AsInner<net_imp::TcpBuilder> for TcpBuilder {
+    fn as_inner(&self) -> &net_imp::TcpBuilder { &self.0 }
+}
+
+impl FromInner<net_imp::TcpBuilder> for TcpBuilder {
+    fn from_inner(inner: net_imp::TcpBuilder) -> TcpBuilder { TcpBuilder(inner) }
+}
+
+impl IntoInner<net_imp::TcpBuilder> for TcpBuilder {
+    fn into_inner(self) -> net_imp::TcpBuilder { self.0 }
+}
+
+#[unstable(feature = "tcp_builder", issue = "0")]
+impl fmt::Debug for TcpBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 #[cfg(all(test, not(any(target_os = "cloudabi", target_os = "emscripten"))))]
 mod tests {
     use io::ErrorKind;
@@ -979,6 +1436,36 @@ mod tests {
         assert!(buf[0] == 144);
     }
 
+    #[test]
+    fn vectored() {
+        use io::{IoVec, IoVecMut};
+
+        each_ip(&mut |addr| {
+            let acceptor = t!(TcpListener::bind(&addr));
+
+            let _t = thread::spawn(move|| {
+                let mut stream = t!(TcpStream::connect(&addr));
+                let mut written = 0;
+                let data = b"hello world";
+                while written < data.len() {
+                    let (a, b) = data[written..].split_at((data.len() - written) / 2);
+                    written += t!(stream.write_vectored(&[IoVec::new(a), IoVec::new(b)]));
+                }
+            });
+
+            let mut stream = t!(acceptor.accept()).0;
+            let mut buf = [0; 11];
+            let mut read = 0;
+            while read < 11 {
+                let (a, b) = buf[read..].split_at_mut((11 - read) / 2);
+                let n = t!(stream.read_vectored(&mut [IoVecMut::new(a), IoVecMut::new(b)]));
+                assert!(n > 0);
+                read += n;
+            }
+            assert_eq!(&buf, b"hello world");
+        })
+    }
+
     #[test]
     fn connect_loopback() {
         each_ip(&mut |addr| {
@@ -1626,6 +2113,83 @@ mod tests {
         assert_eq!(ttl, t!(stream.ttl()));
     }
 
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn keepalive() {
+        let addr = next_test_ip4();
+        let _listener = t!(TcpListener::bind(&addr));
+
+        let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+        assert_eq!(None, t!(stream.keepalive()));
+        t!(stream.set_keepalive(Some(Duration::from_secs(30))));
+        assert_eq!(Some(Duration::from_secs(30)), t!(stream.keepalive()));
+        t!(stream.set_keepalive(None));
+        assert_eq!(None, t!(stream.keepalive()));
+
+        let err = stream.set_keepalive(Some(Duration::new(0, 0))).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn linger() {
+        let addr = next_test_ip4();
+        let _listener = t!(TcpListener::bind(&addr));
+
+        let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+        assert_eq!(None, t!(stream.linger()));
+        t!(stream.set_linger(Some(Duration::from_secs(5))));
+        assert_eq!(Some(Duration::from_secs(5)), t!(stream.linger()));
+        t!(stream.set_linger(None));
+        assert_eq!(None, t!(stream.linger()));
+    }
+
+    #[test]
+    fn builder() {
+        each_ip(&mut |addr| {
+            fn new(addr: SocketAddr) -> io::Result<TcpBuilder> {
+                if addr.is_ipv4() { TcpBuilder::new_v4() } else { TcpBuilder::new_v6() }
+            }
+
+            let builder = t!(new(addr));
+            t!(builder.set_reuse_address(true));
+            assert!(t!(builder.reuse_address()));
+            t!(builder.bind(&addr));
+            assert_eq!(t!(builder.local_addr()), addr);
+            let listener = t!(builder.listen(16));
+
+            let _t = thread::spawn(move|| {
+                let builder = t!(new(addr));
+                let mut stream = t!(builder.connect(&addr));
+                t!(stream.write(&[99]));
+            });
+
+            let mut stream = t!(listener.accept()).0;
+            let mut buf = [0];
+            t!(stream.read(&mut buf));
+            assert_eq!(buf[0], 99);
+        })
+    }
+
+    #[test]
+    fn builder_options() {
+        let builder = t!(TcpBuilder::new_v6());
+        t!(builder.set_only_v6(true));
+        assert!(t!(builder.only_v6()));
+        t!(builder.set_only_v6(false));
+        assert!(!t!(builder.only_v6()));
+
+        // The OS may round buffer sizes up, or double them as Linux does.
+        t!(builder.set_recv_buffer_size(64 * 1024));
+        assert!(t!(builder.recv_buffer_size()) >= 64 * 1024);
+        t!(builder.set_send_buffer_size(64 * 1024));
+        assert!(t!(builder.send_buffer_size()) >= 64 * 1024);
+
+        t!(builder.set_ttl(100));
+        assert_eq!(100, t!(builder.ttl()));
+    }
+
     #[test]
     fn set_nonblocking() {
         let addr = next_test_ip4();
@@ -1710,4 +2274,30 @@ mod tests {
         let addr = listener.local_addr().unwrap();
         TcpStream::connect_timeout(&addr, Duration::from_secs(2)).unwrap();
     }
+
+    #[test]
+    fn copy_file_to_stream() {
+        use fs::File;
+        use io;
+        use sys_common::io::test::tmpdir;
+
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("copy_to_stream");
+        let data = (0..200_000u32).map(|i| i as u8).collect::<Vec<_>>();
+        t!(t!(File::create(&path)).write_all(&data));
+
+        let addr = next_test_ip4();
+        let listener = t!(TcpListener::bind(&addr));
+        let reader = thread::spawn(move || {
+            let mut received = Vec::new();
+            t!(t!(listener.accept()).0.read_to_end(&mut received));
+            received
+        });
+
+        let mut file = t!(File::open(&path));
+        let mut stream = t!(TcpStream::connect(&addr));
+        assert_eq!(t!(io::copy(&mut file, &mut stream)), data.len() as u64);
+        drop(stream);
+        assert!(reader.join().unwrap() == data);
+    }
 }
@@ -10,10 +10,10 @@
 
 use fmt;
 use io::{self, Error, ErrorKind};
-use net::{ToSocketAddrs, SocketAddr, Ipv4Addr, Ipv6Addr};
+use net::{ToSocketAddrs, SocketAddr, IpAddr, Ipv4Addr, Ipv6Addr};
 use sys_common::net as net_imp;
 use sys_common::{AsInner, FromInner, IntoInner};
-use time::Duration;
+use time::{Duration, SystemTime};
 
 /// A UDP socket.
 ///
@@ -64,6 +64,105 @@ use time::Duration;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct UdpSocket(net_imp::UdpSocket);
 
+/// The local end of a datagram: the local address and the index of the
+/// network interface it arrives on or is sent from.
+///
+/// This is returned for received datagrams by [`RecvMeta::packet_info`] once
+/// [`set_recv_packet_info`] has been enabled, and can be passed to
+/// [`send_msg`] to choose the source address and interface of a reply.
+///
+/// [`RecvMeta::packet_info`]: struct.RecvMeta.html#method.packet_info
+/// [`set_recv_packet_info`]: struct.UdpSocket.html#method.set_recv_packet_info
+/// [`send_msg`]: struct.UdpSocket.html#method.send_msg
+#[unstable(feature = "udp_recv_msg", issue = "0")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PacketInfo {
+    addr: IpAddr,
+    interface: u32,
+}
+
+impl PacketInfo {
+    /// Creates packet information from a local address and an interface
+    /// index.
+    ///
+    /// An interface index of 0 leaves the choice of interface to the routing
+    /// table. An unspecified address leaves the choice of source address to
+    /// the system.
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn new(addr: IpAddr, interface: u32) -> PacketInfo {
+        PacketInfo { addr, interface }
+    }
+
+    /// Returns the local address: the destination address of a received
+    /// datagram, or the source address of a sent one.
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the index of the network interface.
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn interface(&self) -> u32 {
+        self.interface
+    }
+}
+
+/// Metadata about a datagram received with [`UdpSocket::recv_msg`].
+///
+/// [`UdpSocket::recv_msg`]: struct.UdpSocket.html#method.recv_msg
+#[unstable(feature = "udp_recv_msg", issue = "0")]
+#[derive(Clone, Debug)]
+pub struct RecvMeta {
+    source: SocketAddr,
+    packet_info: Option<PacketInfo>,
+    timestamp: Option<SystemTime>,
+    truncated: bool,
+}
+
+impl RecvMeta {
+    pub(crate) fn new(source: SocketAddr,
+                      packet_info: Option<PacketInfo>,
+                      timestamp: Option<SystemTime>,
+                      truncated: bool) -> RecvMeta {
+        RecvMeta { source, packet_info, timestamp, truncated }
+    }
+
+    /// Returns the address the datagram was sent from.
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn source(&self) -> SocketAddr {
+        self.source
+    }
+
+    /// Returns the local address and interface the datagram arrived on.
+    ///
+    /// This is `None` unless [`set_recv_packet_info`] has been enabled on
+    /// the socket.
+    ///
+    /// [`set_recv_packet_info`]: struct.UdpSocket.html#method.set_recv_packet_info
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn packet_info(&self) -> Option<PacketInfo> {
+        self.packet_info
+    }
+
+    /// Returns the time at which the kernel received the datagram.
+    ///
+    /// This is `None` unless [`set_recv_timestamp`] has been enabled on the
+    /// socket.
+    ///
+    /// [`set_recv_timestamp`]: struct.UdpSocket.html#method.set_recv_timestamp
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        self.timestamp
+    }
+
+    /// Returns whether the datagram was longer than the buffer, in which
+    /// case the rest of it was discarded.
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
 impl UdpSocket {
     /// Creates a UDP socket from the given address.
     ///
@@ -600,6 +699,137 @@ impl UdpSocket {
         self.0.leave_multicast_v6(multiaddr, interface)
     }
 
+    /// Sets whether the local address and interface of received datagrams
+    /// are reported by [`recv_msg`].
+    ///
+    /// This sets the `IP_PKTINFO` or `IPV6_RECVPKTINFO` option, depending on
+    /// the address family the socket is bound to. It is useful for sockets
+    /// bound to an unspecified address, which receive datagrams on every
+    /// interface.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This is currently only supported on Linux, Android, macOS and iOS,
+    /// and returns an error elsewhere. Note that this [may change in the
+    /// future][changes].
+    ///
+    /// [`recv_msg`]: #method.recv_msg
+    /// [changes]: ../io/index.html#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_recv_msg)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("0.0.0.0:34254").expect("couldn't bind to address");
+    /// socket.set_recv_packet_info(true).expect("set_recv_packet_info call failed");
+    /// ```
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn set_recv_packet_info(&self, recv_packet_info: bool) -> io::Result<()> {
+        self.0.set_recv_packet_info(recv_packet_info)
+    }
+
+    /// Gets whether the local address and interface of received datagrams
+    /// are reported.
+    ///
+    /// For more information about this option, see
+    /// [`set_recv_packet_info`][link].
+    ///
+    /// [link]: #method.set_recv_packet_info
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn recv_packet_info(&self) -> io::Result<bool> {
+        self.0.recv_packet_info()
+    }
+
+    /// Sets whether the time at which datagrams were received is reported
+    /// by [`recv_msg`].
+    ///
+    /// This sets the `SO_TIMESTAMP` option. The same platform restrictions
+    /// as for [`set_recv_packet_info`] apply.
+    ///
+    /// [`recv_msg`]: #method.recv_msg
+    /// [`set_recv_packet_info`]: #method.set_recv_packet_info
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn set_recv_timestamp(&self, recv_timestamp: bool) -> io::Result<()> {
+        self.0.set_recv_timestamp(recv_timestamp)
+    }
+
+    /// Gets whether the time at which datagrams were received is reported.
+    ///
+    /// For more information about this option, see
+    /// [`set_recv_timestamp`][link].
+    ///
+    /// [link]: #method.set_recv_timestamp
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn recv_timestamp(&self) -> io::Result<bool> {
+        self.0.recv_timestamp()
+    }
+
+    /// Receives a single datagram message on the socket, together with its
+    /// metadata.
+    ///
+    /// Like [`recv_from`], this returns the number of bytes read, and the
+    /// sender's address is available as [`RecvMeta::source`]. Depending on
+    /// the options enabled on the socket, the metadata also contains the
+    /// local address and interface the datagram arrived on, and the time at
+    /// which it was received.
+    ///
+    /// The same platform restrictions as for [`set_recv_packet_info`] apply.
+    ///
+    /// [`recv_from`]: #method.recv_from
+    /// [`RecvMeta::source`]: struct.RecvMeta.html#method.source
+    /// [`set_recv_packet_info`]: #method.set_recv_packet_info
+    ///
+    /// # Examples
+    ///
+    /// Reply to each datagram from the address and interface it was sent
+    /// to:
+    ///
+    /// ```no_run
+    /// #![feature(udp_recv_msg)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("0.0.0.0:34254").expect("couldn't bind to address");
+    /// socket.set_recv_packet_info(true).expect("set_recv_packet_info call failed");
+    /// let mut buf = [0; 1500];
+    /// loop {
+    ///     let (len, meta) = socket.recv_msg(&mut buf).expect("recv_msg call failed");
+    ///     let info = meta.packet_info().unwrap();
+    ///     socket.send_msg(&buf[..len], meta.source(), &info).expect("send_msg call failed");
+    /// }
+    /// ```
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn recv_msg(&self, buf: &mut [u8]) -> io::Result<(usize, RecvMeta)> {
+        self.0.recv_msg(buf)
+    }
+
+    /// Sends data on the socket to the given address, from the local address
+    /// and interface given by `info`.
+    ///
+    /// This is like [`send_to`], but overrides the address the socket is
+    /// bound to as the source of the datagram. To reply to a datagram from
+    /// the address it was sent to, pass the [`PacketInfo`] reported by
+    /// [`recv_msg`].
+    ///
+    /// The same platform restrictions as for [`set_recv_packet_info`] apply.
+    ///
+    /// [`send_to`]: #method.send_to
+    /// [`PacketInfo`]: struct.PacketInfo.html
+    /// [`recv_msg`]: #method.recv_msg
+    /// [`set_recv_packet_info`]: #method.set_recv_packet_info
+    #[unstable(feature = "udp_recv_msg", issue = "0")]
+    pub fn send_msg<A: ToSocketAddrs>(&self, buf: &[u8], addr: A, info: &PacketInfo)
+                                      -> io::Result<usize> {
+        match addr.to_socket_addrs()?.next() {
+            Some(addr) => self.0.send_msg(buf, &addr, info),
+            None => Err(Error::new(ErrorKind::InvalidInput,
+                                   "no addresses to send data to")),
+        }
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -1160,4 +1390,36 @@ mod tests {
             }
         })
     }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn recv_msg() {
+        each_ip(&mut |server_ip, client_ip| {
+            let server = t!(UdpSocket::bind(&server_ip));
+            let client = t!(UdpSocket::bind(&client_ip));
+
+            t!(server.set_recv_packet_info(true));
+            assert!(t!(server.recv_packet_info()));
+            t!(server.set_recv_timestamp(true));
+            assert!(t!(server.recv_timestamp()));
+
+            t!(client.send_to(&[1, 2, 3], &server_ip));
+            let mut buf = [0; 2];
+            let (nread, meta) = t!(server.recv_msg(&mut buf));
+            assert_eq!(nread, 2);
+            assert!(meta.truncated());
+            assert_eq!(meta.source(), client_ip);
+            assert!(meta.timestamp().is_some());
+            let info = meta.packet_info().unwrap();
+            assert_eq!(info.addr(), server_ip.ip());
+            assert!(info.interface() != 0);
+
+            // Reply from the address and interface the datagram arrived on.
+            t!(server.send_msg(&[4], meta.source(), &info));
+            let (nread, src) = t!(client.recv_from(&mut buf));
+            assert_eq!(nread, 1);
+            assert_eq!(buf[0], 4);
+            assert_eq!(src, server_ip);
+        })
+    }
 }
@@ -121,12 +121,13 @@ use io::prelude::*;
 use ffi::OsStr;
 use fmt;
 use fs;
-use io::{self, Initializer};
+use io::{self, Initializer, IoVec, IoVecMut};
 use path::Path;
 use str;
 use sys::pipe::{read2, AnonPipe};
 use sys::process as imp;
 use sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+use time::Duration;
 
 /// Representation of a running or exited child process.
 ///
@@ -235,6 +236,10 @@ impl Write for ChildStdin {
         self.inner.write(buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
@@ -281,6 +286,9 @@ impl Read for ChildStdout {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -328,6 +336,9 @@ impl Read for ChildStderr {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -1202,6 +1213,55 @@ impl Child {
         Ok(self.handle.try_wait()?.map(ExitStatus))
     }
 
+    /// Waits for the child to exit for at most `dur`, returning the status
+    /// that it exited with if it did so in time.
+    ///
+    /// If the child exits before the timeout elapses then `Ok(Some(status))`
+    /// is returned, and on Unix the process id is reaped. If the timeout
+    /// elapses first then `Ok(None)` is returned and the child is left
+    /// running. Like [`try_wait`], this function will repeatedly return a
+    /// successful exit status once the child has exited.
+    ///
+    /// The stdin handle to the child process, if any, will be closed
+    /// before waiting, for the same reasons as in [`wait`].
+    ///
+    /// ## Platform-specific behavior
+    ///
+    /// **Unix**: the first call installs a `SIGCHLD` handler which chains to
+    /// any previously installed handler. If `SIGCHLD` is ignored by the
+    /// process then children are reaped automatically by the kernel and
+    /// their exit status cannot be collected. No signal is delivered in that
+    /// case either, so the child is checked on every 50 milliseconds instead.
+    ///
+    /// [`try_wait`]: #method.try_wait
+    /// [`wait`]: #method.wait
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    ///
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+    ///
+    /// match child.wait_timeout(Duration::from_secs(1)) {
+    ///     Ok(Some(status)) => println!("exited with: {}", status),
+    ///     Ok(None) => {
+    ///         println!("still running after a second, killing it");
+    ///         child.kill().unwrap();
+    ///         child.wait().unwrap();
+    ///     }
+    ///     Err(e) => println!("error attempting to wait: {}", e),
+    /// }
+    /// ```
+    #[unstable(feature = "child_wait_timeout", issue = "0")]
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<Option<ExitStatus>> {
+        drop(self.stdin.take());
+        Ok(self.handle.wait_timeout(dur)?.map(ExitStatus))
+    }
+
     /// Simultaneously waits for the child to exit and collect all remaining
     /// output on the stdout/stderr handles, returning an `Output`
     /// instance.
@@ -17,6 +17,7 @@ pub mod net;
 #[path = "../../unix/path.rs"]
 pub mod path;
 pub mod pipe;
+pub mod poll;
 pub mod process;
 pub mod os;
 
@@ -0,0 +1,65 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use io;
+use net::{Event, Interest};
+use time::Duration;
+use sys::{unsupported, Void};
+
+pub type RawSource = Void;
+
+pub struct Poller(Void);
+
+pub struct Events(usize);
+
+impl Poller {
+    pub fn new() -> io::Result<Poller> {
+        unsupported()
+    }
+
+    pub fn register(&self, _source: RawSource, _token: usize, _interest: Interest)
+                    -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn reregister(&self, _source: RawSource, _token: usize, _interest: Interest)
+                      -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn deregister(&self, _source: RawSource) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn poll(&self, _events: &mut Events, _timeout: Option<Duration>)
+                -> io::Result<usize> {
+        match self.0 {}
+    }
+}
+
+impl Events {
+    pub fn with_capacity(capacity: usize) -> Events {
+        Events(capacity)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        0
+    }
+
+    pub fn clear(&mut self) {}
+
+    pub fn get(&self, _idx: usize) -> Option<Event> {
+        None
+    }
+}
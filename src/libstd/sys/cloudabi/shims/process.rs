@@ -15,6 +15,7 @@ use sys::fs::File;
 use sys::pipe::AnonPipe;
 use sys::{unsupported, Void};
 use sys_common::process::{CommandEnv, DefaultEnvKey};
+use time::Duration;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Command
@@ -144,4 +145,8 @@ impl Process {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         match self.0 {}
     }
+
+    pub fn wait_timeout(&mut self, _dur: Duration) -> io::Result<Option<ExitStatus>> {
+        match self.0 {}
+    }
 }
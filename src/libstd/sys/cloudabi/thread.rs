@@ -109,6 +109,15 @@ impl Drop for Thread {
     }
 }
 
+pub fn available_concurrency() -> io::Result<usize> {
+    let count = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) };
+    if count < 1 {
+        return Err(io::Error::new(io::ErrorKind::Other,
+                                  "the number of hardware threads is not known"))
+    }
+    Ok(count as usize)
+}
+
 #[cfg_attr(test, allow(dead_code))]
 pub mod guard {
     pub type Guard = !;
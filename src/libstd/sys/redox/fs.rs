@@ -12,7 +12,7 @@ use os::unix::prelude::*;
 
 use ffi::{OsString, OsStr};
 use fmt;
-use io::{self, Error, ErrorKind, SeekFrom};
+use io::{self, Error, ErrorKind, SeekFrom, IoVec, IoVecMut};
 use path::{Path, PathBuf};
 use sync::Arc;
 use sys::fd::FileDesc;
@@ -276,6 +276,14 @@ impl File {
         self.fsync()
     }
 
+    pub fn lock(&self, _exclusive: bool, _wait: bool) -> io::Result<bool> {
+        Err(Error::new(ErrorKind::Other, "file locking is not supported on Redox"))
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        Err(Error::new(ErrorKind::Other, "file locking is not supported on Redox"))
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         cvt(syscall::ftruncate(self.0.raw(), size as usize))?;
         Ok(())
@@ -285,10 +293,26 @@ impl File {
         self.0.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        io::default_read_vectored(|buf| self.read(buf), bufs)
+    }
+
+    pub fn read_at(&self, _buf: &mut [u8], _offset: u64) -> io::Result<usize> {
+        Err(Error::new(ErrorKind::Other, "positional reads are not supported on Redox"))
+    }
+
     pub fn write(&self, buf: &[u8]) -> io::Result<usize> {
         self.0.write(buf)
     }
 
+    pub fn write_at(&self, _buf: &[u8], _offset: u64) -> io::Result<usize> {
+        Err(Error::new(ErrorKind::Other, "positional writes are not supported on Redox"))
+    }
+
+    pub fn write_vectored(&self, bufs: &[IoVec]) -> io::Result<usize> {
+        io::default_write_vectored(|buf| self.write(buf), bufs)
+    }
+
     pub fn flush(&self) -> io::Result<()> { Ok(()) }
 
     pub fn seek(&self, pos: SeekFrom) -> io::Result<u64> {
@@ -392,6 +416,16 @@ pub fn rename(old: &Path, new: &Path) -> io::Result<()> {
     Ok(())
 }
 
+pub fn sync_dir(p: &Path) -> io::Result<()> {
+    let mut opts = OpenOptions::new();
+    opts.read(true);
+    let dir = File::open(p, &opts)?;
+    if !dir.file_attr()?.file_type().is_dir() {
+        return Err(Error::from_raw_os_error(syscall::ENOTDIR))
+    }
+    dir.fsync()
+}
+
 pub fn set_perm(p: &Path, perm: FilePermissions) -> io::Result<()> {
     cvt(syscall::chmod(p.to_str().unwrap(), perm.mode as usize))?;
     Ok(())
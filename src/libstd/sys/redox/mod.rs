@@ -25,6 +25,7 @@ pub mod ext;
 pub mod fast_thread_local;
 pub mod fd;
 pub mod fs;
+pub mod io;
 pub mod memchr;
 pub mod mutex;
 pub mod net;
@@ -32,6 +33,7 @@ pub mod os;
 pub mod os_str;
 pub mod path;
 pub mod pipe;
+pub mod poll;
 pub mod process;
 pub mod rand;
 pub mod rwlock;
@@ -20,7 +20,7 @@ use vec::{IntoIter, Vec};
 
 use self::dns::{Dns, DnsQuery};
 
-pub use self::tcp::{TcpStream, TcpListener};
+pub use self::tcp::{TcpStream, TcpListener, TcpBuilder};
 pub use self::udp::UdpSocket;
 
 pub mod netc;
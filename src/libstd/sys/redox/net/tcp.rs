@@ -9,7 +9,7 @@
 // except according to those terms.
 
 use cmp;
-use io::{self, Error, ErrorKind, Result};
+use io::{self, Error, ErrorKind, Result, IoVec, IoVecMut};
 use mem;
 use net::{SocketAddr, Shutdown};
 use path::Path;
@@ -44,10 +44,18 @@ impl TcpStream {
         self.0.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoVecMut]) -> Result<usize> {
+        io::default_read_vectored(|buf| self.read(buf), bufs)
+    }
+
     pub fn write(&self, buf: &[u8]) -> Result<usize> {
         self.0.write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoVec]) -> Result<usize> {
+        io::default_write_vectored(|buf| self.write(buf), bufs)
+    }
+
     pub fn take_error(&self) -> Result<Option<Error>> {
         Ok(None)
     }
@@ -70,6 +78,14 @@ impl TcpStream {
         Err(Error::new(ErrorKind::Other, "TcpStream::shutdown not implemented"))
     }
 
+    pub fn keepalive(&self) -> Result<Option<Duration>> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::keepalive not implemented"))
+    }
+
+    pub fn linger(&self) -> Result<Option<Duration>> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::linger not implemented"))
+    }
+
     pub fn nodelay(&self) -> Result<bool> {
         Err(Error::new(ErrorKind::Other, "TcpStream::nodelay not implemented"))
     }
@@ -109,6 +125,14 @@ impl TcpStream {
         }
     }
 
+    pub fn set_keepalive(&self, _keepalive: Option<Duration>) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::set_keepalive not implemented"))
+    }
+
+    pub fn set_linger(&self, _linger: Option<Duration>) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::set_linger not implemented"))
+    }
+
     pub fn set_nodelay(&self, _nodelay: bool) -> Result<()> {
         Err(Error::new(ErrorKind::Other, "TcpStream::set_nodelay not implemented"))
     }
@@ -251,3 +275,78 @@ impl FromInner<File> for TcpListener {
 impl IntoInner<File> for TcpListener {
     fn into_inner(self) -> File { self.0 }
 }
+
+// Redox opens sockets through paths which already name the addresses, so
+// there is no unbound socket on which options could be set beforehand.
+#[derive(Debug)]
+pub struct TcpBuilder(());
+
+impl TcpBuilder {
+    pub fn new_v4() -> Result<TcpBuilder> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::new_v4 not implemented"))
+    }
+
+    pub fn new_v6() -> Result<TcpBuilder> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::new_v6 not implemented"))
+    }
+
+    pub fn set_reuse_address(&self, _reuse: bool) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::set_reuse_address not implemented"))
+    }
+
+    pub fn reuse_address(&self) -> Result<bool> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::reuse_address not implemented"))
+    }
+
+    pub fn set_only_v6(&self, _only_v6: bool) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::set_only_v6 not implemented"))
+    }
+
+    pub fn only_v6(&self) -> Result<bool> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::only_v6 not implemented"))
+    }
+
+    pub fn set_recv_buffer_size(&self, _size: usize) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::set_recv_buffer_size not implemented"))
+    }
+
+    pub fn recv_buffer_size(&self) -> Result<usize> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::recv_buffer_size not implemented"))
+    }
+
+    pub fn set_send_buffer_size(&self, _size: usize) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::set_send_buffer_size not implemented"))
+    }
+
+    pub fn send_buffer_size(&self) -> Result<usize> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::send_buffer_size not implemented"))
+    }
+
+    pub fn set_ttl(&self, _ttl: u32) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::set_ttl not implemented"))
+    }
+
+    pub fn ttl(&self) -> Result<u32> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::ttl not implemented"))
+    }
+
+    pub fn bind(&self, _addr: &SocketAddr) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::bind not implemented"))
+    }
+
+    pub fn listen(self, _backlog: u32) -> Result<TcpListener> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::listen not implemented"))
+    }
+
+    pub fn connect(self, _addr: &SocketAddr) -> Result<TcpStream> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::connect not implemented"))
+    }
+
+    pub fn socket_addr(&self) -> Result<SocketAddr> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::socket_addr not implemented"))
+    }
+
+    pub fn take_error(&self) -> Result<Option<Error>> {
+        Err(Error::new(ErrorKind::Other, "TcpBuilder::take_error not implemented"))
+    }
+}
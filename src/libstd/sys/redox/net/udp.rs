@@ -12,7 +12,7 @@ use cell::UnsafeCell;
 use cmp;
 use io::{self, Error, ErrorKind, Result};
 use mem;
-use net::{SocketAddr, Ipv4Addr, Ipv6Addr};
+use net::{SocketAddr, Ipv4Addr, Ipv6Addr, PacketInfo, RecvMeta};
 use path::Path;
 use sys::fs::{File, OpenOptions};
 use sys::syscall::TimeSpec;
@@ -95,6 +95,30 @@ impl UdpSocket {
         Err(Error::new(ErrorKind::Other, "UdpSocket::peek_from not implemented"))
     }
 
+    pub fn recv_msg(&self, _buf: &mut [u8]) -> Result<(usize, RecvMeta)> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::recv_msg not implemented"))
+    }
+
+    pub fn recv_packet_info(&self) -> Result<bool> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::recv_packet_info not implemented"))
+    }
+
+    pub fn recv_timestamp(&self) -> Result<bool> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::recv_timestamp not implemented"))
+    }
+
+    pub fn send_msg(&self, _buf: &[u8], _addr: &SocketAddr, _info: &PacketInfo) -> Result<usize> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::send_msg not implemented"))
+    }
+
+    pub fn set_recv_packet_info(&self, _recv_packet_info: bool) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::set_recv_packet_info not implemented"))
+    }
+
+    pub fn set_recv_timestamp(&self, _recv_timestamp: bool) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::set_recv_timestamp not implemented"))
+    }
+
     pub fn broadcast(&self) -> Result<bool> {
         Err(Error::new(ErrorKind::Other, "UdpSocket::broadcast not implemented"))
     }
@@ -8,6 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use cmp;
 use env::{split_paths};
 use ffi::OsStr;
 use os::unix::ffi::OsStrExt;
@@ -19,6 +20,8 @@ use sys::fs::{File, OpenOptions};
 use sys::pipe::{self, AnonPipe};
 use sys::{cvt, syscall};
 use sys_common::process::{CommandEnv, DefaultEnvKey};
+use thread;
+use time::{Duration, Instant};
 
 ////////////////////////////////////////////////////////////////////////////////
 // Command
@@ -527,4 +530,22 @@ impl Process {
             Ok(Some(ExitStatus(status as i32)))
         }
     }
+
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<Option<ExitStatus>> {
+        // Redox has no way to be notified of a child exiting short of
+        // blocking in `waitpid`, so poll with an exponential backoff.
+        let start = Instant::now();
+        let mut sleep = Duration::from_millis(1);
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status))
+            }
+            let remaining = match dur.checked_sub(start.elapsed()) {
+                Some(remaining) => remaining,
+                None => return Ok(None),
+            };
+            thread::sleep(cmp::min(sleep, remaining));
+            sleep = cmp::min(sleep * 2, Duration::from_millis(50));
+        }
+    }
 }
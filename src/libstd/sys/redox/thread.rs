@@ -87,6 +87,11 @@ impl Thread {
     }
 }
 
+pub fn available_concurrency() -> io::Result<usize> {
+    Err(io::Error::new(io::ErrorKind::Other,
+                       "the number of hardware threads is not known for this platform"))
+}
+
 pub mod guard {
     pub type Guard = !;
     pub unsafe fn current() -> Option<Guard> { None }
@@ -9,8 +9,8 @@
 // except according to those terms.
 
 use fmt;
-use io;
-use net::{SocketAddr, Shutdown, Ipv4Addr, Ipv6Addr};
+use io::{self, IoVec, IoVecMut};
+use net::{SocketAddr, Shutdown, Ipv4Addr, Ipv6Addr, PacketInfo, RecvMeta};
 use time::Duration;
 use sys::{unsupported, Void};
 
@@ -48,10 +48,18 @@ impl TcpStream { pub fn connect(_: &SocketAddr) -> io::Result<TcpStream> {
         match self.0 {}
     }
 
+    pub fn read_vectored(&self, _bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        match self.0 {}
+    }
+
     pub fn write(&self, _: &[u8]) -> io::Result<usize> {
         match self.0 {}
     }
 
+    pub fn write_vectored(&self, _bufs: &[IoVec]) -> io::Result<usize> {
+        match self.0 {}
+    }
+
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         match self.0 {}
     }
@@ -84,6 +92,22 @@ impl TcpStream { pub fn connect(_: &SocketAddr) -> io::Result<TcpStream> {
         match self.0 {}
     }
 
+    pub fn set_keepalive(&self, _: Option<Duration>) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn keepalive(&self) -> io::Result<Option<Duration>> {
+        match self.0 {}
+    }
+
+    pub fn set_linger(&self, _: Option<Duration>) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        match self.0 {}
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         match self.0 {}
     }
@@ -149,6 +173,84 @@ impl fmt::Debug for TcpListener {
     }
 }
 
+pub struct TcpBuilder(Void);
+
+impl TcpBuilder {
+    pub fn new_v4() -> io::Result<TcpBuilder> {
+        unsupported()
+    }
+
+    pub fn new_v6() -> io::Result<TcpBuilder> {
+        unsupported()
+    }
+
+    pub fn set_reuse_address(&self, _: bool) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn set_only_v6(&self, _: bool) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn only_v6(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        match self.0 {}
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        match self.0 {}
+    }
+
+    pub fn set_ttl(&self, _: u32) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn ttl(&self) -> io::Result<u32> {
+        match self.0 {}
+    }
+
+    pub fn bind(&self, _: &SocketAddr) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn listen(self, _: u32) -> io::Result<TcpListener> {
+        match self.0 {}
+    }
+
+    pub fn connect(self, _: &SocketAddr) -> io::Result<TcpStream> {
+        match self.0 {}
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        match self.0 {}
+    }
+
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        match self.0 {}
+    }
+}
+
+impl fmt::Debug for TcpBuilder {
+    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {}
+    }
+}
+
 pub struct UdpSocket(Void);
 
 impl UdpSocket {
@@ -252,6 +354,30 @@ impl UdpSocket {
         match self.0 {}
     }
 
+    pub fn set_recv_packet_info(&self, _: bool) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn recv_packet_info(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn set_recv_timestamp(&self, _: bool) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn recv_timestamp(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn recv_msg(&self, _: &mut [u8]) -> io::Result<(usize, RecvMeta)> {
+        match self.0 {}
+    }
+
+    pub fn send_msg(&self, _: &[u8], _: &SocketAddr, _: &PacketInfo) -> io::Result<usize> {
+        match self.0 {}
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         match self.0 {}
     }
@@ -42,6 +42,10 @@ impl Thread {
     }
 }
 
+pub fn available_concurrency() -> io::Result<usize> {
+    unsupported()
+}
+
 pub mod guard {
     pub type Guard = !;
     pub unsafe fn current() -> Option<Guard> { None }
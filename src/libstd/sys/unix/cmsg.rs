@@ -0,0 +1,94 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Helpers mirroring the `CMSG_*` macros from `<sys/socket.h>`, which the
+//! `libc` crate does not provide, for the control messages passed to
+//! `sendmsg` and `recvmsg`.
+
+use cmp;
+use libc::{self, c_int};
+use mem;
+use ptr;
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+pub fn align(len: usize) -> usize {
+    let align = mem::size_of::<usize>();
+    (len + align - 1) & !(align - 1)
+}
+
+/// The space taken by a message header, including padding.
+pub fn header_space() -> usize {
+    align(mem::size_of::<libc::cmsghdr>())
+}
+
+/// The space taken by a message with `data_len` bytes of data.
+pub fn space(data_len: usize) -> usize {
+    header_space() + align(data_len)
+}
+
+/// Writes a message with the given level, type and data to the start of
+/// `buf`, zeroing any padding, and returns the space it takes.
+///
+/// `buf` must be at least `space(mem::size_of_val(data))` bytes long, and
+/// `T` must be plain old data.
+pub unsafe fn write<T>(buf: &mut [u8], level: c_int, ty: c_int, data: &[T]) -> usize {
+    let data_len = mem::size_of_val(data);
+    let len = space(data_len);
+    assert!(buf.len() >= len);
+    ptr::write_bytes(buf.as_mut_ptr(), 0, len);
+    let mut header: libc::cmsghdr = mem::zeroed();
+    header.cmsg_level = level;
+    header.cmsg_type = ty;
+    header.cmsg_len = (header_space() + data_len) as _;
+    ptr::write_unaligned(buf.as_mut_ptr() as *mut libc::cmsghdr, header);
+    ptr::copy_nonoverlapping(data.as_ptr() as *const u8,
+                             buf[header_space()..].as_mut_ptr(),
+                             data_len);
+    len
+}
+
+/// An iterator over the level, type and data of the messages in a control
+/// buffer filled in by `recvmsg`.
+#[derive(Debug)]
+pub struct Messages<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Messages<'a> {
+    pub fn new(buf: &'a [u8]) -> Messages<'a> {
+        Messages { buf }
+    }
+}
+
+impl<'a> Iterator for Messages<'a> {
+    type Item = (c_int, c_int, &'a [u8]);
+
+    fn next(&mut self) -> Option<(c_int, c_int, &'a [u8])> {
+        if self.buf.len() < mem::size_of::<libc::cmsghdr>() {
+            return None
+        }
+        let header = unsafe {
+            ptr::read_unaligned(self.buf.as_ptr() as *const libc::cmsghdr)
+        };
+        let len = header.cmsg_len as usize;
+        if len < header_space() || len > self.buf.len() {
+            return None
+        }
+        let data = &self.buf[header_space()..len];
+        let next = cmp::min(align(len), self.buf.len());
+        self.buf = &self.buf[next..];
+        Some((header.cmsg_level, header.cmsg_type, data))
+    }
+}
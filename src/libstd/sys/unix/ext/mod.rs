@@ -61,6 +61,8 @@ pub mod prelude {
     pub use super::fs::FileExt;
     #[doc(no_inline)] #[stable(feature = "rust1", since = "1.0.0")]
     pub use super::thread::JoinHandleExt;
+    #[doc(no_inline)] #[unstable(feature = "thread_builder_ext", issue = "0")]
+    pub use super::thread::BuilderExt;
     #[doc(no_inline)] #[stable(feature = "rust1", since = "1.0.0")]
     pub use super::process::{CommandExt, ExitStatusExt};
 }
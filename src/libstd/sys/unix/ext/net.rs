@@ -28,12 +28,13 @@ mod libc {
 use ascii;
 use ffi::OsStr;
 use fmt;
-use io::{self, Initializer};
+use io::{self, Initializer, IoVec, IoVecMut};
 use mem;
 use net::{self, Shutdown};
 use os::unix::ffi::OsStrExt;
 use os::unix::io::{RawFd, AsRawFd, FromRawFd, IntoRawFd};
 use path::Path;
+use ptr;
 use time::Duration;
 use sys::{self, cvt};
 use sys::net::Socket;
@@ -549,6 +550,123 @@ impl UnixStream {
     pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
         self.0.shutdown(how)
     }
+
+    /// Sends data and ancillary data on the socket.
+    ///
+    /// On success, returns the number of bytes of `buf` which were written.
+    /// The ancillary data is sent along with the first byte of `buf`, which
+    /// therefore must not be empty.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data)]
+    ///
+    /// use std::os::unix::net::{UnixStream, SocketAncillary};
+    /// use std::os::unix::io::AsRawFd;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let socket = UnixStream::connect("/tmp/sock")?;
+    /// let mut buf = [0; 128];
+    /// let mut ancillary = SocketAncillary::new(&mut buf);
+    /// ancillary.add_fds(&[0, 1, 2]);
+    /// socket.send_with_ancillary(b"stdio", &mut ancillary)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn send_with_ancillary(&self, buf: &[u8], ancillary: &mut SocketAncillary)
+                               -> io::Result<usize> {
+        send_with_ancillary(&self.0, buf, ancillary, None)
+    }
+
+    /// Receives data and ancillary data from the socket.
+    ///
+    /// On success, returns the number of bytes read into `buf`. Any
+    /// ancillary data which was received is stored in `ancillary`,
+    /// replacing its previous contents.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data)]
+    ///
+    /// use std::os::unix::net::{UnixStream, SocketAncillary, AncillaryData};
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let socket = UnixStream::connect("/tmp/sock")?;
+    /// let mut data = [0; 32];
+    /// let mut buf = [0; 128];
+    /// let mut ancillary = SocketAncillary::new(&mut buf);
+    /// let n = socket.recv_with_ancillary(&mut data, &mut ancillary)?;
+    /// println!("received {} bytes", n);
+    /// for message in ancillary.messages() {
+    ///     if let AncillaryData::ScmRights(fds) = message {
+    ///         for fd in fds {
+    ///             println!("received fd {}", fd);
+    ///         }
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn recv_with_ancillary(&self, buf: &mut [u8], ancillary: &mut SocketAncillary)
+                               -> io::Result<usize> {
+        recv_with_ancillary(&self.0, buf, ancillary).map(|(count, _, _)| count)
+    }
+
+    /// Returns the credentials of the process which created the socket at
+    /// the other end of this connection, as they were when it connected.
+    ///
+    /// This corresponds to the `SO_PEERCRED` socket option.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data)]
+    ///
+    /// use std::os::unix::net::UnixStream;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let socket = UnixStream::connect("/tmp/sock")?;
+    /// let cred = socket.peer_cred()?;
+    /// println!("connected to pid {} running as uid {}", cred.pid(), cred.uid());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn peer_cred(&self) -> io::Result<SocketCred> {
+        sys_common::net::getsockopt(&self.0, libc::SOL_SOCKET, libc::SO_PEERCRED)
+            .map(SocketCred)
+    }
+
+    /// Enables or disables the receipt of `SCM_CREDENTIALS` messages.
+    ///
+    /// While enabled, every message received on this socket carries the
+    /// credentials of its sender, even if it did not add any itself.
+    ///
+    /// This corresponds to the `SO_PASSCRED` socket option.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
+        sys_common::net::setsockopt(&self.0, libc::SOL_SOCKET, libc::SO_PASSCRED,
+                                    passcred as libc::c_int)
+    }
+
+    /// Returns whether the receipt of `SCM_CREDENTIALS` messages is enabled.
+    ///
+    /// See [`set_passcred`] for more information.
+    ///
+    /// [`set_passcred`]: #method.set_passcred
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn passcred(&self) -> io::Result<bool> {
+        let passcred: libc::c_int = sys_common::net::getsockopt(&self.0, libc::SOL_SOCKET,
+                                                                libc::SO_PASSCRED)?;
+        Ok(passcred != 0)
+    }
 }
 
 #[stable(feature = "unix_socket", since = "1.10.0")]
@@ -557,6 +675,10 @@ impl io::Read for UnixStream {
         io::Read::read(&mut &*self, buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        io::Read::read_vectored(&mut &*self, bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -569,6 +691,10 @@ impl<'a> io::Read for &'a UnixStream {
         self.0.read(buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -581,6 +707,10 @@ impl io::Write for UnixStream {
         io::Write::write(&mut &*self, buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        io::Write::write_vectored(&mut &*self, bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         io::Write::flush(&mut &*self)
     }
@@ -592,6 +722,10 @@ impl<'a> io::Write for &'a UnixStream {
         self.0.write(buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
@@ -676,6 +810,73 @@ impl IntoRawFd for net::UdpSocket {
     }
 }
 
+#[unstable(feature = "tcp_builder", issue = "0")]
+impl AsRawFd for net::TcpBuilder {
+    fn as_raw_fd(&self) -> RawFd { *self.as_inner().socket().as_inner() }
+}
+
+#[unstable(feature = "tcp_builder", issue = "0")]
+impl FromRawFd for net::TcpBuilder {
+    unsafe fn from_raw_fd(fd: RawFd) -> net::TcpBuilder {
+        let socket = sys::net::Socket::from_inner(fd);
+        net::TcpBuilder::from_inner(sys_common::net::TcpBuilder::from_inner(socket))
+    }
+}
+
+#[unstable(feature = "tcp_builder", issue = "0")]
+impl IntoRawFd for net::TcpBuilder {
+    fn into_raw_fd(self) -> RawFd {
+        self.into_inner().into_socket().into_inner()
+    }
+}
+
+/// Unix-specific extensions to [`net::TcpBuilder`].
+///
+/// [`net::TcpBuilder`]: ../../../../std/net/struct.TcpBuilder.html
+#[unstable(feature = "tcp_builder", issue = "0")]
+pub trait TcpBuilderExt {
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// This allows several sockets to be bound to the same address. On
+    /// Linux, incoming connections are then distributed between listeners
+    /// which all set this option.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_builder)]
+    ///
+    /// use std::net::TcpBuilder;
+    /// use std::os::unix::net::TcpBuilderExt;
+    ///
+    /// let builder = TcpBuilder::new_v4().unwrap();
+    /// builder.set_reuse_port(true).expect("set_reuse_port call failed");
+    /// builder.bind("0.0.0.0:8080").unwrap();
+    /// let listener = builder.listen(128).unwrap();
+    /// ```
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    fn set_reuse_port(&self, reuse: bool) -> io::Result<()>;
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_reuse_port`][link].
+    ///
+    /// [link]: #tymethod.set_reuse_port
+    #[unstable(feature = "tcp_builder", issue = "0")]
+    fn reuse_port(&self) -> io::Result<bool>;
+}
+
+#[unstable(feature = "tcp_builder", issue = "0")]
+impl TcpBuilderExt for net::TcpBuilder {
+    fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        self.as_inner().set_reuse_port(reuse)
+    }
+
+    fn reuse_port(&self) -> io::Result<bool> {
+        self.as_inner().reuse_port()
+    }
+}
+
 /// A structure representing a Unix domain socket server.
 ///
 /// # Examples
@@ -1443,6 +1644,106 @@ impl UnixDatagram {
     pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
         self.0.shutdown(how)
     }
+
+    /// Sends data and ancillary data on the socket to its connected peer.
+    ///
+    /// On success, returns the number of bytes written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data)]
+    ///
+    /// use std::os::unix::net::{UnixDatagram, SocketAncillary};
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let socket = UnixDatagram::unbound()?;
+    /// socket.connect("/tmp/sock")?;
+    /// let mut buf = [0; 128];
+    /// let mut ancillary = SocketAncillary::new(&mut buf);
+    /// ancillary.add_fds(&[0]);
+    /// socket.send_with_ancillary(b"stdin", &mut ancillary)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn send_with_ancillary(&self, buf: &[u8], ancillary: &mut SocketAncillary)
+                               -> io::Result<usize> {
+        send_with_ancillary(&self.0, buf, ancillary, None)
+    }
+
+    /// Sends data and ancillary data on the socket to the specified address.
+    ///
+    /// On success, returns the number of bytes written.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn send_to_with_ancillary<P: AsRef<Path>>(&self,
+                                                  buf: &[u8],
+                                                  ancillary: &mut SocketAncillary,
+                                                  path: P) -> io::Result<usize> {
+        send_with_ancillary(&self.0, buf, ancillary, Some(path.as_ref()))
+    }
+
+    /// Receives data and ancillary data from the socket.
+    ///
+    /// On success, returns the number of bytes read into `buf`. Any
+    /// ancillary data which was received is stored in `ancillary`,
+    /// replacing its previous contents.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn recv_with_ancillary(&self, buf: &mut [u8], ancillary: &mut SocketAncillary)
+                               -> io::Result<usize> {
+        recv_with_ancillary(&self.0, buf, ancillary).map(|(count, _, _)| count)
+    }
+
+    /// Receives data and ancillary data from the socket.
+    ///
+    /// On success, returns the number of bytes read into `buf`, whether the
+    /// datagram was truncated because `buf` was too small, and the address
+    /// it came from.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_socket_ancillary_data)]
+    ///
+    /// use std::os::unix::net::{UnixDatagram, SocketAncillary};
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let socket = UnixDatagram::bind("/tmp/sock")?;
+    /// let mut data = [0; 32];
+    /// let mut buf = [0; 128];
+    /// let mut ancillary = SocketAncillary::new(&mut buf);
+    /// let (n, truncated, sender) = socket.recv_from_with_ancillary(&mut data,
+    ///                                                              &mut ancillary)?;
+    /// println!("received {} bytes from {:?}, truncated: {}", n, sender, truncated);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn recv_from_with_ancillary(&self, buf: &mut [u8], ancillary: &mut SocketAncillary)
+                                    -> io::Result<(usize, bool, SocketAddr)> {
+        recv_with_ancillary(&self.0, buf, ancillary)
+    }
+
+    /// Enables or disables the receipt of `SCM_CREDENTIALS` messages.
+    ///
+    /// See [`UnixStream::set_passcred`] for more information.
+    ///
+    /// [`UnixStream::set_passcred`]: struct.UnixStream.html#method.set_passcred
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
+        sys_common::net::setsockopt(&self.0, libc::SOL_SOCKET, libc::SO_PASSCRED,
+                                    passcred as libc::c_int)
+    }
+
+    /// Returns whether the receipt of `SCM_CREDENTIALS` messages is enabled.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn passcred(&self) -> io::Result<bool> {
+        let passcred: libc::c_int = sys_common::net::getsockopt(&self.0, libc::SOL_SOCKET,
+                                                                libc::SO_PASSCRED)?;
+        Ok(passcred != 0)
+    }
 }
 
 #[stable(feature = "unix_socket", since = "1.10.0")]
@@ -1466,11 +1767,385 @@ impl IntoRawFd for UnixDatagram {
     }
 }
 
+/// A buffer for the ancillary data, or control messages, sent and received
+/// alongside the normal data on a Unix socket.
+///
+/// Ancillary data is used to pass file descriptors between processes with
+/// [`add_fds`] and, on Linux, to send and receive process credentials with
+/// [`add_creds`]. A `SocketAncillary` wraps a caller-provided byte buffer;
+/// messages are appended to it before sending, and it is filled in by the
+/// receiving methods such as [`UnixStream::recv_with_ancillary`].
+///
+/// File descriptors received this way are owned by the receiving process and
+/// must be closed by it, for example by wrapping them with
+/// [`FromRawFd::from_raw_fd`]. On Linux they are received with the
+/// close-on-exec flag set.
+///
+/// [`add_fds`]: #method.add_fds
+/// [`add_creds`]: #method.add_creds
+/// [`UnixStream::recv_with_ancillary`]: struct.UnixStream.html#method.recv_with_ancillary
+/// [`FromRawFd::from_raw_fd`]: ../io/trait.FromRawFd.html#tymethod.from_raw_fd
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_socket_ancillary_data)]
+///
+/// use std::fs::File;
+/// use std::os::unix::net::{UnixStream, SocketAncillary, AncillaryData};
+/// use std::os::unix::io::{AsRawFd, FromRawFd};
+///
+/// # fn foo() -> std::io::Result<()> {
+/// let (a, b) = UnixStream::pair()?;
+///
+/// let file = File::open("/etc/hosts")?;
+/// let mut buf = [0; 128];
+/// let mut ancillary = SocketAncillary::new(&mut buf);
+/// ancillary.add_fds(&[file.as_raw_fd()]);
+/// a.send_with_ancillary(b"here you go", &mut ancillary)?;
+///
+/// let mut data = [0; 32];
+/// let mut buf = [0; 128];
+/// let mut ancillary = SocketAncillary::new(&mut buf);
+/// let n = b.recv_with_ancillary(&mut data, &mut ancillary)?;
+/// assert_eq!(&data[..n], b"here you go");
+/// for message in ancillary.messages() {
+///     if let AncillaryData::ScmRights(fds) = message {
+///         for fd in fds {
+///             let file = unsafe { File::from_raw_fd(fd) };
+///             println!("received {:?}", file);
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Debug)]
+pub struct SocketAncillary<'a> {
+    buffer: &'a mut [u8],
+    length: usize,
+    truncated: bool,
+}
+
+impl<'a> SocketAncillary<'a> {
+    /// Creates an empty ancillary data buffer backed by `buffer`.
+    ///
+    /// The space needed for a message depends on the platform, but 128
+    /// bytes is enough for a few file descriptors or one set of
+    /// credentials.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn new(buffer: &'a mut [u8]) -> SocketAncillary<'a> {
+        SocketAncillary { buffer, length: 0, truncated: false }
+    }
+
+    /// Returns the size of the underlying buffer.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the number of bytes of the buffer which are in use.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns true if the buffer holds no messages.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns true if the last receive had to discard ancillary data
+    /// because the buffer was too small.
+    ///
+    /// Any file descriptors which did not fit have been closed by the
+    /// operating system.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Removes all messages from the buffer.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn clear(&mut self) {
+        self.length = 0;
+        self.truncated = false;
+    }
+
+    /// Returns an iterator over the messages in the buffer.
+    ///
+    /// Messages of kinds which are not understood are skipped.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn messages(&self) -> Messages {
+        Messages { inner: sys::cmsg::Messages::new(&self.buffer[..self.length]) }
+    }
+
+    /// Appends an `SCM_RIGHTS` message passing the file descriptors `fds`.
+    ///
+    /// The descriptors are duplicated into the receiving process when the
+    /// message is sent; the sender keeps ownership of its own copies.
+    ///
+    /// Returns false, leaving the buffer unchanged, if there is not enough
+    /// space left for the message.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn add_fds(&mut self, fds: &[RawFd]) -> bool {
+        self.add(fds, libc::SOL_SOCKET, libc::SCM_RIGHTS)
+    }
+
+    /// Appends an `SCM_CREDENTIALS` message carrying `creds`.
+    ///
+    /// Unless the sender is privileged, the kernel only accepts credentials
+    /// matching the sending process. The receiving socket must have enabled
+    /// [`set_passcred`] to receive them.
+    ///
+    /// Returns false, leaving the buffer unchanged, if there is not enough
+    /// space left for the message.
+    ///
+    /// [`set_passcred`]: struct.UnixStream.html#method.set_passcred
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn add_creds(&mut self, creds: &[SocketCred]) -> bool {
+        self.add(creds, libc::SOL_SOCKET, libc::SCM_CREDENTIALS)
+    }
+
+    fn add<T: Copy>(&mut self, data: &[T], level: libc::c_int, ty: libc::c_int) -> bool {
+        let data_len = match data.len().checked_mul(mem::size_of::<T>()) {
+            Some(len) => len,
+            None => return false,
+        };
+        let new_length = match self.length.checked_add(sys::cmsg::space(data_len)) {
+            Some(len) if len <= self.buffer.len() => len,
+            _ => return false,
+        };
+
+        unsafe {
+            sys::cmsg::write(&mut self.buffer[self.length..new_length], level, ty, data);
+        }
+        self.length = new_length;
+        true
+    }
+}
+
+/// An iterator over the messages in a [`SocketAncillary`] buffer.
+///
+/// [`SocketAncillary`]: struct.SocketAncillary.html
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Debug)]
+pub struct Messages<'a> {
+    inner: sys::cmsg::Messages<'a>,
+}
+
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+impl<'a> Iterator for Messages<'a> {
+    type Item = AncillaryData<'a>;
+
+    fn next(&mut self) -> Option<AncillaryData<'a>> {
+        for (level, ty, data) in &mut self.inner {
+            if level != libc::SOL_SOCKET {
+                continue
+            }
+            match ty {
+                libc::SCM_RIGHTS => return Some(AncillaryData::ScmRights(ScmRights(data))),
+                #[cfg(any(target_os = "linux", target_os = "android"))]
+                libc::SCM_CREDENTIALS => {
+                    return Some(AncillaryData::ScmCredentials(ScmCredentials(data)))
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// A message in a [`SocketAncillary`] buffer.
+///
+/// [`SocketAncillary`]: struct.SocketAncillary.html
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Debug)]
+pub enum AncillaryData<'a> {
+    /// File descriptors passed with `SCM_RIGHTS`.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    ScmRights(ScmRights<'a>),
+    /// Process credentials passed with `SCM_CREDENTIALS`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    ScmCredentials(ScmCredentials<'a>),
+}
+
+/// An iterator over the file descriptors in an `SCM_RIGHTS` message.
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Debug)]
+pub struct ScmRights<'a>(&'a [u8]);
+
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+impl<'a> Iterator for ScmRights<'a> {
+    type Item = RawFd;
+
+    fn next(&mut self) -> Option<RawFd> {
+        let size = mem::size_of::<RawFd>();
+        if self.0.len() < size {
+            return None
+        }
+        let fd = unsafe { ptr::read_unaligned(self.0.as_ptr() as *const RawFd) };
+        self.0 = &self.0[size..];
+        Some(fd)
+    }
+}
+
+/// An iterator over the credentials in an `SCM_CREDENTIALS` message.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Debug)]
+pub struct ScmCredentials<'a>(&'a [u8]);
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+impl<'a> Iterator for ScmCredentials<'a> {
+    type Item = SocketCred;
+
+    fn next(&mut self) -> Option<SocketCred> {
+        let size = mem::size_of::<libc::ucred>();
+        if self.0.len() < size {
+            return None
+        }
+        let cred = unsafe { ptr::read_unaligned(self.0.as_ptr() as *const libc::ucred) };
+        self.0 = &self.0[size..];
+        Some(SocketCred(cred))
+    }
+}
+
+/// The credentials of a process: its process, user and group ids.
+///
+/// Credentials are sent with [`SocketAncillary::add_creds`], and those of
+/// the process at the other end of a stream can be queried with
+/// [`UnixStream::peer_cred`].
+///
+/// [`SocketAncillary::add_creds`]: struct.SocketAncillary.html#method.add_creds
+/// [`UnixStream::peer_cred`]: struct.UnixStream.html#method.peer_cred
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+#[derive(Clone, Copy)]
+pub struct SocketCred(libc::ucred);
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl SocketCred {
+    /// Returns the credentials of the calling process.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn current() -> SocketCred {
+        unsafe {
+            SocketCred(libc::ucred {
+                pid: libc::getpid(),
+                uid: libc::getuid(),
+                gid: libc::getgid(),
+            })
+        }
+    }
+
+    /// Returns the process id.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn pid(&self) -> i32 {
+        self.0.pid
+    }
+
+    /// Returns the user id.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn uid(&self) -> u32 {
+        self.0.uid
+    }
+
+    /// Returns the group id.
+    #[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+    pub fn gid(&self) -> u32 {
+        self.0.gid
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[unstable(feature = "unix_socket_ancillary_data", issue = "0")]
+impl fmt::Debug for SocketCred {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SocketCred")
+            .field("pid", &self.0.pid)
+            .field("uid", &self.0.uid)
+            .field("gid", &self.0.gid)
+            .finish()
+    }
+}
+
+fn send_with_ancillary(socket: &Socket,
+                       buf: &[u8],
+                       ancillary: &mut SocketAncillary,
+                       path: Option<&Path>) -> io::Result<usize> {
+    unsafe {
+        let mut msg: libc::msghdr = mem::zeroed();
+        let mut addr: libc::sockaddr_un = mem::zeroed();
+        if let Some(path) = path {
+            let (a, len) = sockaddr_un(path)?;
+            addr = a;
+            msg.msg_name = &mut addr as *mut _ as *mut _;
+            msg.msg_namelen = len;
+        }
+        let mut iov = libc::iovec {
+            iov_base: buf.as_ptr() as *mut _,
+            iov_len: buf.len(),
+        };
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        if ancillary.length > 0 {
+            msg.msg_control = ancillary.buffer.as_mut_ptr() as *mut _;
+            msg.msg_controllen = ancillary.length as _;
+        }
+        ancillary.truncated = false;
+
+        let count = cvt(libc::sendmsg(*socket.as_inner(), &msg, MSG_NOSIGNAL))?;
+        Ok(count as usize)
+    }
+}
+
+// Returns the number of bytes received, whether the data was truncated, and
+// the address of the sender.
+fn recv_with_ancillary(socket: &Socket,
+                       buf: &mut [u8],
+                       ancillary: &mut SocketAncillary)
+                       -> io::Result<(usize, bool, SocketAddr)> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    const FLAGS: libc::c_int = libc::MSG_CMSG_CLOEXEC;
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    const FLAGS: libc::c_int = 0;
+
+    unsafe {
+        let mut addr: libc::sockaddr_un = mem::zeroed();
+        let mut msg: libc::msghdr = mem::zeroed();
+        msg.msg_name = &mut addr as *mut _ as *mut _;
+        msg.msg_namelen = mem::size_of::<libc::sockaddr_un>() as libc::socklen_t;
+        let mut iov = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut _,
+            iov_len: buf.len(),
+        };
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        if ancillary.buffer.len() > 0 {
+            msg.msg_control = ancillary.buffer.as_mut_ptr() as *mut _;
+            msg.msg_controllen = ancillary.buffer.len() as _;
+        }
+
+        let count = cvt(libc::recvmsg(*socket.as_inner(), &mut msg, FLAGS))?;
+        ancillary.length = msg.msg_controllen as usize;
+        ancillary.truncated = msg.msg_flags & libc::MSG_CTRUNC != 0;
+        let truncated = msg.msg_flags & libc::MSG_TRUNC != 0;
+        let addr = SocketAddr::from_parts(addr, msg.msg_namelen)?;
+        Ok((count as usize, truncated, addr))
+    }
+}
+
 #[cfg(all(test, not(target_os = "emscripten")))]
 mod test {
     use thread;
     use io::{self, ErrorKind};
     use io::prelude::*;
+    use os::unix::io::{AsRawFd, FromRawFd};
     use time::Duration;
     use sys_common::io::test::tmpdir;
 
@@ -1810,6 +2485,106 @@ mod test {
         assert_eq!(err.kind(), ErrorKind::InvalidInput);
     }
 
+    #[test]
+    fn send_recv_fds() {
+        let (s1, s2) = or_panic!(UnixStream::pair());
+        let (r, mut w) = or_panic!(UnixStream::pair());
+
+        let mut buf = [0; 64];
+        let mut ancillary = SocketAncillary::new(&mut buf);
+        assert!(ancillary.add_fds(&[r.as_raw_fd()]));
+        assert_eq!(or_panic!(s1.send_with_ancillary(b"fd", &mut ancillary)), 2);
+        drop(r);
+
+        let mut data = [0; 2];
+        let mut buf = [0; 64];
+        let mut ancillary = SocketAncillary::new(&mut buf);
+        assert_eq!(or_panic!(s2.recv_with_ancillary(&mut data, &mut ancillary)), 2);
+        assert_eq!(&data, b"fd");
+        assert!(!ancillary.truncated());
+
+        let mut fds = vec![];
+        for message in ancillary.messages() {
+            if let AncillaryData::ScmRights(rights) = message {
+                fds.extend(rights);
+            }
+        }
+        assert_eq!(fds.len(), 1);
+        let mut received = unsafe { UnixStream::from_raw_fd(fds[0]) };
+
+        or_panic!(w.write_all(b"hello"));
+        let mut buf = [0; 5];
+        or_panic!(received.read_exact(&mut buf));
+        assert_eq!(&buf, b"hello");
+    }
+
+    #[test]
+    fn recv_fds_truncated() {
+        let (s1, s2) = or_panic!(UnixDatagram::pair());
+
+        let mut buf = [0; 64];
+        let mut ancillary = SocketAncillary::new(&mut buf);
+        assert!(ancillary.add_fds(&[0, 1, 2]));
+        or_panic!(s1.send_with_ancillary(b"hello", &mut ancillary));
+
+        let mut data = [0; 2];
+        let mut buf = [0; 0];
+        let mut ancillary = SocketAncillary::new(&mut buf);
+        let (n, truncated, _) = or_panic!(s2.recv_from_with_ancillary(&mut data,
+                                                                      &mut ancillary));
+        assert_eq!(n, 2);
+        assert!(truncated);
+        assert!(ancillary.truncated());
+        assert_eq!(ancillary.messages().count(), 0);
+    }
+
+    #[test]
+    fn ancillary_capacity() {
+        let mut buf = [0; 8];
+        let mut ancillary = SocketAncillary::new(&mut buf);
+        assert!(!ancillary.add_fds(&[0; 64]));
+        assert!(ancillary.is_empty());
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[test]
+    fn send_recv_creds() {
+        let (s1, s2) = or_panic!(UnixDatagram::pair());
+        or_panic!(s2.set_passcred(true));
+        assert!(or_panic!(s2.passcred()));
+
+        let mut buf = [0; 64];
+        let mut ancillary = SocketAncillary::new(&mut buf);
+        assert!(ancillary.add_creds(&[SocketCred::current()]));
+        or_panic!(s1.send_with_ancillary(b"creds", &mut ancillary));
+
+        let mut data = [0; 5];
+        let mut buf = [0; 64];
+        let mut ancillary = SocketAncillary::new(&mut buf);
+        or_panic!(s2.recv_with_ancillary(&mut data, &mut ancillary));
+
+        let current = SocketCred::current();
+        let mut creds = vec![];
+        for message in ancillary.messages() {
+            if let AncillaryData::ScmCredentials(c) = message {
+                creds.extend(c);
+            }
+        }
+        assert_eq!(creds.len(), 1);
+        assert_eq!(creds[0].pid(), current.pid());
+        assert_eq!(creds[0].uid(), current.uid());
+        assert_eq!(creds[0].gid(), current.gid());
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[test]
+    fn peer_cred() {
+        let (s1, _s2) = or_panic!(UnixStream::pair());
+        let cred = or_panic!(s1.peer_cred());
+        assert_eq!(cred.pid(), SocketCred::current().pid());
+        assert_eq!(cred.uid(), SocketCred::current().uid());
+    }
+
     #[test]
     fn abstract_namespace_not_allowed() {
         assert!(UnixStream::connect("\0asdf").is_err());
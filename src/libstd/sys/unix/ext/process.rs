@@ -12,11 +12,13 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
+use fmt;
 use io;
 use os::unix::io::{FromRawFd, RawFd, AsRawFd, IntoRawFd};
 use process;
 use sys;
 use sys_common::{AsInnerMut, AsInner, FromInner, IntoInner};
+use time::Duration;
 
 /// Unix-specific extensions to the `std::process::Command` builder
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -197,3 +199,92 @@ impl IntoRawFd for process::ChildStderr {
 pub fn parent_id() -> u32 {
     ::sys::os::getppid()
 }
+
+/// A watcher which is woken up whenever any child process of this process
+/// exits.
+///
+/// This allows a program managing many children to sleep until one of them
+/// exits and then collect the exit statuses with [`Child::try_wait`], rather
+/// than polling each child in turn. A wakeup says only that *some* child
+/// has changed state since the watcher was created or last woken up, and
+/// wakeups may occasionally be spurious, so callers should always be
+/// prepared for none of their children to have exited.
+///
+/// Create the watcher *before* checking on children: an exit which happens
+/// after the watcher is created will always be observed by the next call to
+/// [`wait`] or [`wait_timeout`].
+///
+/// The first watcher created installs a `SIGCHLD` handler which chains to
+/// any previously installed handler. If `SIGCHLD` is ignored the handler is
+/// not installed, since the kernel then reaps children itself. In that case
+/// there is nothing to block on, and watchers wake up spuriously every 50
+/// milliseconds instead.
+///
+/// [`Child::try_wait`]: ../../../process/struct.Child.html#method.try_wait
+/// [`wait`]: #method.wait
+/// [`wait_timeout`]: #method.wait_timeout
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_exit_watcher)]
+///
+/// use std::os::unix::process::ExitWatcher;
+/// use std::process::Command;
+///
+/// let mut watcher = ExitWatcher::new().unwrap();
+/// let mut children = (0..10).map(|_| Command::new("true").spawn().unwrap())
+///                           .collect::<Vec<_>>();
+///
+/// while !children.is_empty() {
+///     let mut i = 0;
+///     while i < children.len() {
+///         if let Some(status) = children[i].try_wait().unwrap() {
+///             println!("child exited with: {}", status);
+///             children.swap_remove(i);
+///         } else {
+///             i += 1;
+///         }
+///     }
+///     if !children.is_empty() {
+///         watcher.wait().unwrap();
+///     }
+/// }
+/// ```
+#[unstable(feature = "unix_exit_watcher", issue = "0")]
+#[cfg(not(target_os = "fuchsia"))]
+pub struct ExitWatcher(sys::process::ExitWatcher);
+
+#[unstable(feature = "unix_exit_watcher", issue = "0")]
+#[cfg(not(target_os = "fuchsia"))]
+impl ExitWatcher {
+    /// Creates a new watcher, installing the `SIGCHLD` handler if necessary.
+    #[unstable(feature = "unix_exit_watcher", issue = "0")]
+    pub fn new() -> io::Result<ExitWatcher> {
+        sys::process::ExitWatcher::new().map(ExitWatcher)
+    }
+
+    /// Blocks until a child process exits.
+    ///
+    /// Returns immediately if a child has exited since the watcher was
+    /// created or last woken up.
+    #[unstable(feature = "unix_exit_watcher", issue = "0")]
+    pub fn wait(&mut self) -> io::Result<()> {
+        self.0.wait_timeout(None).map(|_| ())
+    }
+
+    /// Blocks until a child process exits or `dur` elapses, returning whether
+    /// a child exited.
+    #[unstable(feature = "unix_exit_watcher", issue = "0")]
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<bool> {
+        self.0.wait_timeout(Some(dur))
+    }
+}
+
+#[unstable(feature = "unix_exit_watcher", issue = "0")]
+#[cfg(not(target_os = "fuchsia"))]
+impl fmt::Debug for ExitWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExitWatcher").finish()
+    }
+}
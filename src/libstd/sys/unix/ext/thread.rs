@@ -14,8 +14,9 @@
 
 #[allow(deprecated)]
 use os::unix::raw::pthread_t;
+use sys;
 use sys_common::{AsInner, IntoInner};
-use thread::JoinHandle;
+use thread::{Builder, JoinHandle};
 
 #[stable(feature = "thread_extensions", since = "1.9.0")]
 #[allow(deprecated)]
@@ -47,3 +48,82 @@ impl<T> JoinHandleExt for JoinHandle<T> {
         self.into_inner().into_id() as RawPthread
     }
 }
+
+/// Unix-specific extensions to [`thread::Builder`].
+///
+/// The settings are applied by the new thread itself before it runs its
+/// closure. If applying them fails, the thread exits without running the
+/// closure and `spawn` returns the error.
+///
+/// These settings are currently only supported on Linux; on other Unix
+/// platforms, spawning a thread configured with them returns an error.
+///
+/// [`thread::Builder`]: ../../../../std/thread/struct.Builder.html
+#[unstable(feature = "thread_builder_ext", issue = "0")]
+pub trait BuilderExt {
+    /// Restricts the new thread to run only on the given CPUs, as numbered
+    /// by the operating system.
+    ///
+    /// This corresponds to the `sched_setaffinity` system call. Spawning
+    /// fails with [`InvalidInput`] if a CPU number is out of range, and with
+    /// the OS error if none of the CPUs are available to the process.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(thread_builder_ext)]
+    ///
+    /// use std::os::unix::thread::BuilderExt;
+    /// use std::thread;
+    ///
+    /// let handle = thread::Builder::new()
+    ///     .cpu_affinity(&[0])
+    ///     .spawn(|| {
+    ///         // runs on the first CPU only
+    ///     })
+    ///     .unwrap();
+    /// handle.join().unwrap();
+    /// ```
+    ///
+    /// [`InvalidInput`]: ../../../../std/io/enum.ErrorKind.html#variant.InvalidInput
+    #[unstable(feature = "thread_builder_ext", issue = "0")]
+    fn cpu_affinity(self, cpus: &[usize]) -> Self;
+
+    /// Sets the scheduling priority of the new thread as a nice value.
+    ///
+    /// This corresponds to the `setpriority` system call. Lower values give
+    /// the thread more CPU time; lowering the value below that of the
+    /// process usually requires privileges, and spawning fails with the OS
+    /// error otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(thread_builder_ext)]
+    ///
+    /// use std::os::unix::thread::BuilderExt;
+    /// use std::thread;
+    ///
+    /// let handle = thread::Builder::new()
+    ///     .priority(10)
+    ///     .spawn(|| {
+    ///         // background work
+    ///     })
+    ///     .unwrap();
+    /// handle.join().unwrap();
+    /// ```
+    #[unstable(feature = "thread_builder_ext", issue = "0")]
+    fn priority(self, nice: i32) -> Self;
+}
+
+#[unstable(feature = "thread_builder_ext", issue = "0")]
+impl BuilderExt for Builder {
+    fn cpu_affinity(self, cpus: &[usize]) -> Builder {
+        let cpus = cpus.to_vec();
+        self.start_hook(move || sys::thread::set_affinity(&cpus))
+    }
+
+    fn priority(self, nice: i32) -> Builder {
+        self.start_hook(move || sys::thread::set_priority(nice))
+    }
+}
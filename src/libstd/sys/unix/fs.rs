@@ -12,7 +12,7 @@ use os::unix::prelude::*;
 
 use ffi::{CString, CStr, OsString, OsStr};
 use fmt;
-use io::{self, Error, ErrorKind, SeekFrom};
+use io::{self, Error, ErrorKind, SeekFrom, IoVec, IoVecMut};
 use libc::{self, c_int, mode_t};
 use mem;
 use path::{Path, PathBuf};
@@ -482,6 +482,56 @@ impl File {
         unsafe fn os_datasync(fd: c_int) -> c_int { libc::fsync(fd) }
     }
 
+    // Returns `Ok(false)` if `wait` is false and the lock is already held.
+    #[cfg(not(target_os = "solaris"))]
+    pub fn lock(&self, exclusive: bool, wait: bool) -> io::Result<bool> {
+        let mut operation = if exclusive { libc::LOCK_EX } else { libc::LOCK_SH };
+        if !wait {
+            operation |= libc::LOCK_NB;
+        }
+        match cvt_r(|| unsafe { libc::flock(self.0.raw(), operation) }) {
+            Ok(_) => Ok(true),
+            Err(ref e) if e.raw_os_error() == Some(libc::EWOULDBLOCK) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[cfg(not(target_os = "solaris"))]
+    pub fn unlock(&self) -> io::Result<()> {
+        cvt(unsafe { libc::flock(self.0.raw(), libc::LOCK_UN) })?;
+        Ok(())
+    }
+
+    // Solaris has no `flock`, so fall back to POSIX record locks covering the
+    // whole file. These are released when *any* descriptor for the file is
+    // closed by the process, and require the file to be open for reading
+    // (shared) or writing (exclusive).
+    #[cfg(target_os = "solaris")]
+    pub fn lock(&self, exclusive: bool, wait: bool) -> io::Result<bool> {
+        let lock_type = if exclusive { libc::F_WRLCK } else { libc::F_RDLCK };
+        let cmd = if wait { libc::F_SETLKW } else { libc::F_SETLK };
+        match self.fcntl_lock(cmd, lock_type) {
+            Ok(()) => Ok(true),
+            Err(ref e) if e.raw_os_error() == Some(libc::EAGAIN) ||
+                          e.raw_os_error() == Some(libc::EACCES) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[cfg(target_os = "solaris")]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.fcntl_lock(libc::F_SETLK, libc::F_UNLCK)
+    }
+
+    #[cfg(target_os = "solaris")]
+    fn fcntl_lock(&self, cmd: c_int, lock_type: c_int) -> io::Result<()> {
+        let mut flock: libc::flock = unsafe { mem::zeroed() };
+        flock.l_type = lock_type as libc::c_short;
+        flock.l_whence = libc::SEEK_SET as libc::c_short;
+        cvt_r(|| unsafe { libc::fcntl(self.0.raw(), cmd, &flock) })?;
+        Ok(())
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         #[cfg(target_os = "android")]
         return ::sys::android::ftruncate64(self.0.raw(), size);
@@ -496,6 +546,10 @@ impl File {
         self.0.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     pub fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
         self.0.read_at(buf, offset)
     }
@@ -504,6 +558,10 @@ impl File {
         self.0.write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     pub fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
         self.0.write_at(buf, offset)
     }
@@ -656,6 +714,18 @@ pub fn rename(old: &Path, new: &Path) -> io::Result<()> {
     Ok(())
 }
 
+pub fn sync_dir(p: &Path) -> io::Result<()> {
+    // Opening a directory read-only is enough to `fsync` it, which makes
+    // changes to its entries, such as a rename into it, durable.
+    let mut opts = OpenOptions::new();
+    opts.read(true);
+    let dir = File::open(p, &opts)?;
+    if !dir.file_attr()?.file_type().is_dir() {
+        return Err(io::Error::from_raw_os_error(libc::ENOTDIR))
+    }
+    dir.fsync()
+}
+
 pub fn set_perm(p: &Path, perm: FilePermissions) -> io::Result<()> {
     let p = cstr(p)?;
     cvt_r(|| unsafe { libc::chmod(p.as_ptr(), perm.mode) })?;
@@ -0,0 +1,183 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Zero-copy fast paths for `io::copy` on Linux.
+//!
+//! When both ends of a copy are known to be backed by file descriptors the
+//! data can be moved by the kernel instead of through a userspace buffer:
+//! `copy_file_range` between regular files, `sendfile` from a regular file
+//! into anything else, and `splice` when either end is a pipe. Each of them
+//! falls through to the next when the kernel or the file types don't support
+//! it, and finally to the buffered copy in `io::copy`.
+//!
+//! All three calls are made with null offsets, so they read from and write
+//! to the current positions of the descriptors and advance them, just as
+//! `read` and `write` would.
+
+use fs::File;
+use io::{self, Read, Write};
+use libc::{self, c_int};
+use mem;
+use net::TcpStream;
+use os::unix::io::AsRawFd;
+use os::unix::net::UnixStream;
+use process::{ChildStdin, ChildStdout, ChildStderr};
+use ptr;
+use sys::{cvt, os};
+
+/// The largest count Linux moves in a single call.
+const MAX_CHUNK: usize = 0x7fff_f000;
+
+pub enum CopyResult {
+    /// The reader reached EOF after this many bytes were moved.
+    Ended(u64),
+    /// This many bytes were moved before the kernel declined to go on; the
+    /// rest has to be copied through a buffer.
+    Fallback(u64),
+}
+
+/// Copies from `reader` to `writer` inside the kernel if both are plain
+/// file descriptors.
+pub fn copy_spec<R: Read + ?Sized, W: Write + ?Sized>(reader: &mut R, writer: &mut W)
+                                                    -> io::Result<CopyResult> {
+    // Called through the traits rather than as methods, which would pick
+    // the blanket impl for `&mut R`.
+    let (rfd, wfd) = match (<R as CopyFd>::copy_fd(reader), <W as CopyFd>::copy_fd(writer)) {
+        (Some(rfd), Some(wfd)) => (rfd, wfd),
+        _ => return Ok(CopyResult::Fallback(0)),
+    };
+    let (rkind, wkind) = match (FdKind::of(rfd), FdKind::of(wfd)) {
+        (Ok(rkind), Ok(wkind)) => (rkind, wkind),
+        _ => return Ok(CopyResult::Fallback(0)),
+    };
+
+    let mut written = 0;
+    if rkind == FdKind::File && wkind == FdKind::File {
+        if copy_loop(&mut written, |len| copy_file_range(rfd, wfd, len))? {
+            return Ok(CopyResult::Ended(written));
+        }
+    }
+    if rkind == FdKind::File {
+        if copy_loop(&mut written, |len| unsafe {
+            libc::sendfile(wfd, rfd, ptr::null_mut(), len)
+        })? {
+            return Ok(CopyResult::Ended(written));
+        }
+    }
+    if rkind == FdKind::Pipe || wkind == FdKind::Pipe {
+        if copy_loop(&mut written, |len| unsafe {
+            libc::splice(rfd, ptr::null_mut(), wfd, ptr::null_mut(), len, 0)
+        })? {
+            return Ok(CopyResult::Ended(written));
+        }
+    }
+    Ok(CopyResult::Fallback(written))
+}
+
+/// Repeats a copying system call until EOF, adding the bytes moved to
+/// `written`.
+///
+/// Returns `Ok(false)` if the call turned out not to be usable for these
+/// descriptors, in which case the caller should try something else.
+fn copy_loop<F>(written: &mut u64, mut f: F) -> io::Result<bool>
+    where F: FnMut(usize) -> libc::ssize_t
+{
+    let mut first = true;
+    loop {
+        match cvt(f(MAX_CHUNK)) {
+            // Some files, such as those in procfs and sysfs, report a size
+            // of zero and can't be copied by the kernel. A first call
+            // returning 0 could also be an empty file, so let the buffered
+            // copy find out which.
+            Ok(0) if first => return Ok(false),
+            Ok(0) => return Ok(true),
+            Ok(n) => *written += n as u64,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => {
+                match e.raw_os_error() {
+                    Some(libc::ENOSYS) |
+                    Some(libc::EINVAL) |
+                    Some(libc::EXDEV) |
+                    Some(libc::EPERM) |
+                    Some(libc::EBADF) |
+                    Some(libc::EOPNOTSUPP) => return Ok(false),
+                    _ => return Err(e),
+                }
+            }
+        }
+        first = false;
+    }
+}
+
+fn copy_file_range(rfd: c_int, wfd: c_int, len: usize) -> libc::ssize_t {
+    // Added in glibc 2.27; older C libraries fall back to `sendfile`.
+    weak! {
+        fn copy_file_range(c_int, *mut libc::off64_t, c_int, *mut libc::off64_t,
+                           libc::size_t, libc::c_uint) -> libc::ssize_t
+    }
+    match copy_file_range.get() {
+        Some(f) => unsafe {
+            f(rfd, ptr::null_mut(), wfd, ptr::null_mut(), len, 0)
+        },
+        None => {
+            os::set_errno(libc::ENOSYS);
+            -1
+        }
+    }
+}
+
+#[derive(PartialEq, Eq)]
+enum FdKind {
+    File,
+    Pipe,
+    Other,
+}
+
+impl FdKind {
+    fn of(fd: c_int) -> io::Result<FdKind> {
+        let mut stat: libc::stat64 = unsafe { mem::zeroed() };
+        cvt(unsafe { libc::fstat64(fd, &mut stat) })?;
+        Ok(match stat.st_mode & libc::S_IFMT {
+            libc::S_IFREG => FdKind::File,
+            libc::S_IFIFO => FdKind::Pipe,
+            _ => FdKind::Other,
+        })
+    }
+}
+
+/// Exposes the file descriptor behind the readers and writers which are
+/// known not to buffer anything in userspace.
+trait CopyFd {
+    fn copy_fd(&self) -> Option<c_int>;
+}
+
+impl<T: ?Sized> CopyFd for T {
+    default fn copy_fd(&self) -> Option<c_int> {
+        None
+    }
+}
+
+macro_rules! copy_fd {
+    ($($t:ty)*) => ($(
+        impl CopyFd for $t {
+            fn copy_fd(&self) -> Option<c_int> {
+                Some(self.as_raw_fd())
+            }
+        }
+
+        impl<'a> CopyFd for &'a $t {
+            fn copy_fd(&self) -> Option<c_int> {
+                Some((**self).as_raw_fd())
+            }
+        }
+    )*)
+}
+
+copy_fd! { File TcpStream UnixStream ChildStdin ChildStdout ChildStderr }
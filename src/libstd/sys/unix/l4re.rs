@@ -15,9 +15,9 @@ macro_rules! unimpl {
 pub mod net {
     #![allow(warnings)]
     use fmt;
-    use io;
+    use io::{self, IoVec, IoVecMut};
     use libc;
-    use net::{SocketAddr, Shutdown, Ipv4Addr, Ipv6Addr};
+    use net::{SocketAddr, Shutdown, Ipv4Addr, Ipv6Addr, PacketInfo, RecvMeta};
     use sys_common::{AsInner, FromInner, IntoInner};
     use sys::fd::FileDesc;
     use time::Duration;
@@ -56,6 +56,10 @@ pub mod net {
             unimpl!();
         }
 
+        pub fn read_vectored(&self, _: &mut [IoVecMut]) -> io::Result<usize> {
+            unimpl!();
+        }
+
         pub fn peek(&self, _: &mut [u8]) -> io::Result<usize> {
             unimpl!();
         }
@@ -72,6 +76,10 @@ pub mod net {
             unimpl!();
         }
 
+        pub fn write_vectored(&self, _: &[IoVec]) -> io::Result<usize> {
+            unimpl!();
+        }
+
         pub fn set_timeout(&self, _: Option<Duration>, _: libc::c_int) -> io::Result<()> {
             unimpl!();
         }
@@ -154,10 +162,18 @@ pub mod net {
             unimpl!();
         }
 
+        pub fn read_vectored(&self, _: &mut [IoVecMut]) -> io::Result<usize> {
+            unimpl!();
+        }
+
         pub fn write(&self, _: &[u8]) -> io::Result<usize> {
             unimpl!();
         }
 
+        pub fn write_vectored(&self, _: &[IoVec]) -> io::Result<usize> {
+            unimpl!();
+        }
+
         pub fn peer_addr(&self) -> io::Result<SocketAddr> {
             unimpl!();
         }
@@ -190,6 +206,22 @@ pub mod net {
             unimpl!();
         }
 
+        pub fn set_keepalive(&self, _: Option<Duration>) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn keepalive(&self) -> io::Result<Option<Duration>> {
+            unimpl!();
+        }
+
+        pub fn set_linger(&self, _: Option<Duration>) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn linger(&self) -> io::Result<Option<Duration>> {
+            unimpl!();
+        }
+
         pub fn take_error(&self) -> io::Result<Option<io::Error>> {
             unimpl!();
         }
@@ -273,6 +305,104 @@ pub mod net {
         }
     }
 
+    pub struct TcpBuilder {
+        inner: Socket,
+    }
+
+    impl TcpBuilder {
+        pub fn new_v4() -> io::Result<TcpBuilder> {
+            unimpl!();
+        }
+
+        pub fn new_v6() -> io::Result<TcpBuilder> {
+            unimpl!();
+        }
+
+        pub fn socket(&self) -> &Socket { &self.inner }
+
+        pub fn into_socket(self) -> Socket { self.inner }
+
+        pub fn set_reuse_address(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn reuse_address(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn reuse_port(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_only_v6(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn only_v6(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn recv_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
+        pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn send_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
+        pub fn set_ttl(&self, _: u32) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn ttl(&self) -> io::Result<u32> {
+            unimpl!();
+        }
+
+        pub fn bind(&self, _: &SocketAddr) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn listen(self, _: u32) -> io::Result<TcpListener> {
+            unimpl!();
+        }
+
+        pub fn connect(self, _: &SocketAddr) -> io::Result<TcpStream> {
+            unimpl!();
+        }
+
+        pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+            unimpl!();
+        }
+
+        pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+            unimpl!();
+        }
+    }
+
+    impl FromInner<Socket> for TcpBuilder {
+        fn from_inner(socket: Socket) -> TcpBuilder {
+            TcpBuilder { inner: socket }
+        }
+    }
+
+    impl fmt::Debug for TcpBuilder {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "No networking support available on L4Re.")
+        }
+    }
+
     pub struct UdpSocket {
         inner: Socket,
     }
@@ -382,6 +512,30 @@ pub mod net {
             unimpl!();
         }
 
+        pub fn set_recv_packet_info(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn recv_packet_info(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_recv_timestamp(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn recv_timestamp(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn recv_msg(&self, _: &mut [u8]) -> io::Result<(usize, RecvMeta)> {
+            unimpl!();
+        }
+
+        pub fn send_msg(&self, _: &[u8], _: &SocketAddr, _: &PacketInfo) -> io::Result<usize> {
+            unimpl!();
+        }
+
         pub fn take_error(&self) -> io::Result<Option<io::Error>> {
             unimpl!();
         }
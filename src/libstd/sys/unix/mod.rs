@@ -40,12 +40,16 @@ pub mod android;
 #[cfg(feature = "backtrace")]
 pub mod backtrace;
 pub mod cmath;
+pub mod cmsg;
 pub mod condvar;
 pub mod env;
 pub mod ext;
 pub mod fast_thread_local;
 pub mod fd;
 pub mod fs;
+pub mod io;
+#[cfg(target_os = "linux")]
+pub mod kernel_copy;
 pub mod memchr;
 pub mod mutex;
 #[cfg(not(target_os = "l4re"))]
@@ -58,6 +62,7 @@ pub mod os;
 pub mod os_str;
 pub mod path;
 pub mod pipe;
+pub mod poll;
 pub mod process;
 pub mod rand;
 pub mod rwlock;
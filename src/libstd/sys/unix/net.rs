@@ -9,10 +9,10 @@
 // except according to those terms.
 
 use ffi::CStr;
-use io;
+use io::{self, IoVec, IoVecMut};
 use libc::{self, c_int, c_void, size_t, sockaddr, socklen_t, EAI_SYSTEM, MSG_PEEK};
 use mem;
-use net::{SocketAddr, Shutdown};
+use net::{SocketAddr, Shutdown, PacketInfo, RecvMeta};
 use str;
 use sys::fd::FileDesc;
 use sys_common::{AsInner, FromInner, IntoInner};
@@ -43,6 +43,70 @@ use libc::SO_NOSIGPIPE;
 #[cfg(not(target_vendor = "apple"))]
 const SO_NOSIGPIPE: c_int = 0;
 
+// The option setting the idle time before keepalive probes are sent has a
+// different name on Apple platforms, and does not exist on some others.
+#[cfg(any(target_os = "linux", target_os = "android",
+          target_os = "freebsd", target_os = "dragonfly",
+          target_os = "fuchsia"))]
+use libc::TCP_KEEPIDLE as KEEPALIVE_TIME;
+#[cfg(target_vendor = "apple")]
+const KEEPALIVE_TIME: c_int = 0x10; // TCP_KEEPALIVE
+
+// On Apple platforms, SO_LINGER counts in clock ticks rather than seconds.
+#[cfg(target_vendor = "apple")]
+const SO_LINGER: c_int = 0x1080; // SO_LINGER_SEC
+#[cfg(not(target_vendor = "apple"))]
+use libc::SO_LINGER;
+
+// Options and control messages used by `recv_msg` and `send_msg`, which are
+// only supported where the `IP_PKTINFO` interface is available. The `libc`
+// crate does not define these yet.
+#[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+mod msg {
+    use libc::{c_int, c_uint, in_addr, in6_addr};
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const IP_RECVPKTINFO: c_int = 8; // IP_PKTINFO
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const IP_PKTINFO: c_int = 8;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const IPV6_RECVPKTINFO: c_int = 49;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const IPV6_PKTINFO: c_int = 50;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const SO_TIMESTAMP: c_int = 29;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const SCM_TIMESTAMP: c_int = SO_TIMESTAMP;
+
+    #[cfg(target_vendor = "apple")]
+    pub const IP_RECVPKTINFO: c_int = 26;
+    #[cfg(target_vendor = "apple")]
+    pub const IP_PKTINFO: c_int = 26;
+    #[cfg(target_vendor = "apple")]
+    pub const IPV6_RECVPKTINFO: c_int = 61;
+    #[cfg(target_vendor = "apple")]
+    pub const IPV6_PKTINFO: c_int = 46;
+    #[cfg(target_vendor = "apple")]
+    pub const SO_TIMESTAMP: c_int = 0x0400;
+    #[cfg(target_vendor = "apple")]
+    pub const SCM_TIMESTAMP: c_int = 0x02;
+
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct in_pktinfo {
+        pub ipi_ifindex: c_uint,
+        pub ipi_spec_dst: in_addr,
+        pub ipi_addr: in_addr,
+    }
+
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct in6_pktinfo {
+        pub ipi6_addr: in6_addr,
+        pub ipi6_ifindex: c_uint,
+    }
+}
+
 pub struct Socket(FileDesc);
 
 pub fn init() {}
@@ -244,6 +308,10 @@ impl Socket {
         self.recv_with_flags(buf, 0)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.recv_with_flags(buf, MSG_PEEK)
     }
@@ -276,6 +344,10 @@ impl Socket {
         self.0.write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     pub fn set_timeout(&self, dur: Option<Duration>, kind: libc::c_int) -> io::Result<()> {
         let timeout = match dur {
             Some(dur) => {
@@ -338,6 +410,276 @@ impl Socket {
         Ok(raw != 0)
     }
 
+    pub fn set_keepalive(&self, keepalive: Option<Duration>) -> io::Result<()> {
+        if let Some(dur) = keepalive {
+            if dur.as_secs() == 0 && dur.subsec_nanos() == 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                          "cannot set a 0 duration keepalive time"));
+            }
+            self.set_keepalive_time(dur)?;
+        }
+        setsockopt(self, libc::SOL_SOCKET, libc::SO_KEEPALIVE, keepalive.is_some() as c_int)
+    }
+
+    pub fn keepalive(&self) -> io::Result<Option<Duration>> {
+        let raw: c_int = getsockopt(self, libc::SOL_SOCKET, libc::SO_KEEPALIVE)?;
+        if raw == 0 {
+            Ok(None)
+        } else {
+            self.keepalive_time().map(Some)
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android",
+              target_os = "freebsd", target_os = "dragonfly",
+              target_os = "fuchsia", target_vendor = "apple"))]
+    fn set_keepalive_time(&self, dur: Duration) -> io::Result<()> {
+        // Round sub-second times up rather than down to zero.
+        let secs = cmp::max(cmp::min(dur.as_secs(), c_int::max_value() as u64), 1);
+        setsockopt(self, libc::IPPROTO_TCP, KEEPALIVE_TIME, secs as c_int)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android",
+              target_os = "freebsd", target_os = "dragonfly",
+              target_os = "fuchsia", target_vendor = "apple"))]
+    fn keepalive_time(&self) -> io::Result<Duration> {
+        let secs: c_int = getsockopt(self, libc::IPPROTO_TCP, KEEPALIVE_TIME)?;
+        Ok(Duration::from_secs(secs as u64))
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android",
+                  target_os = "freebsd", target_os = "dragonfly",
+                  target_os = "fuchsia", target_vendor = "apple")))]
+    fn set_keepalive_time(&self, _dur: Duration) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "keepalive time is not configurable on this platform"))
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android",
+                  target_os = "freebsd", target_os = "dragonfly",
+                  target_os = "fuchsia", target_vendor = "apple")))]
+    fn keepalive_time(&self) -> io::Result<Duration> {
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "keepalive time is not configurable on this platform"))
+    }
+
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        let linger = libc::linger {
+            l_onoff: linger.is_some() as c_int,
+            l_linger: linger.map_or(0, |dur| {
+                cmp::min(dur.as_secs(), c_int::max_value() as u64) as c_int
+            }),
+        };
+        setsockopt(self, libc::SOL_SOCKET, SO_LINGER, linger)
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        let raw: libc::linger = getsockopt(self, libc::SOL_SOCKET, SO_LINGER)?;
+        if raw.l_onoff == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(Duration::from_secs(raw.l_linger as u64)))
+        }
+    }
+
+    #[cfg(not(any(target_os = "solaris", target_os = "haiku")))]
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(self, libc::SOL_SOCKET, libc::SO_REUSEPORT, reuse as c_int)
+    }
+
+    #[cfg(not(any(target_os = "solaris", target_os = "haiku")))]
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(self, libc::SOL_SOCKET, libc::SO_REUSEPORT)?;
+        Ok(raw != 0)
+    }
+
+    #[cfg(any(target_os = "solaris", target_os = "haiku"))]
+    pub fn set_reuse_port(&self, _reuse: bool) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "SO_REUSEPORT is not supported on this platform"))
+    }
+
+    #[cfg(any(target_os = "solaris", target_os = "haiku"))]
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "SO_REUSEPORT is not supported on this platform"))
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+    pub fn set_recv_packet_info(&self, v6: bool, recv: bool) -> io::Result<()> {
+        if v6 {
+            setsockopt(self, libc::IPPROTO_IPV6, msg::IPV6_RECVPKTINFO, recv as c_int)
+        } else {
+            setsockopt(self, libc::IPPROTO_IP, msg::IP_RECVPKTINFO, recv as c_int)
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+    pub fn recv_packet_info(&self, v6: bool) -> io::Result<bool> {
+        let raw: c_int = if v6 {
+            getsockopt(self, libc::IPPROTO_IPV6, msg::IPV6_RECVPKTINFO)?
+        } else {
+            getsockopt(self, libc::IPPROTO_IP, msg::IP_RECVPKTINFO)?
+        };
+        Ok(raw != 0)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+    pub fn set_recv_timestamp(&self, recv: bool) -> io::Result<()> {
+        setsockopt(self, libc::SOL_SOCKET, msg::SO_TIMESTAMP, recv as c_int)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+    pub fn recv_timestamp(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(self, libc::SOL_SOCKET, msg::SO_TIMESTAMP)?;
+        Ok(raw != 0)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+    pub fn recv_msg(&self, buf: &mut [u8]) -> io::Result<(usize, RecvMeta)> {
+        use net::{IpAddr, Ipv4Addr, Ipv6Addr};
+        use ptr;
+        use slice;
+        use sys::cmsg;
+        use time::UNIX_EPOCH;
+
+        // Enough room for a packet information and a timestamp message, and
+        // aligned for their headers.
+        let mut control = [0u64; 16];
+        unsafe {
+            let mut storage: libc::sockaddr_storage = mem::zeroed();
+            let mut iov = libc::iovec {
+                iov_base: buf.as_mut_ptr() as *mut c_void,
+                iov_len: buf.len(),
+            };
+            let mut hdr: libc::msghdr = mem::zeroed();
+            hdr.msg_name = &mut storage as *mut _ as *mut c_void;
+            hdr.msg_namelen = mem::size_of_val(&storage) as socklen_t;
+            hdr.msg_iov = &mut iov;
+            hdr.msg_iovlen = 1;
+            hdr.msg_control = control.as_mut_ptr() as *mut c_void;
+            hdr.msg_controllen = mem::size_of_val(&control) as _;
+
+            let n = cvt(libc::recvmsg(self.0.raw(), &mut hdr, 0))?;
+            let source = sockaddr_to_addr(&storage, hdr.msg_namelen as usize)?;
+
+            let control = slice::from_raw_parts(control.as_ptr() as *const u8,
+                                                hdr.msg_controllen as usize);
+            let mut packet_info = None;
+            let mut timestamp = None;
+            for (level, ty, data) in cmsg::Messages::new(control) {
+                match (level, ty) {
+                    (libc::IPPROTO_IP, msg::IP_PKTINFO)
+                        if data.len() >= mem::size_of::<msg::in_pktinfo>() => {
+                        let info: msg::in_pktinfo =
+                            ptr::read_unaligned(data.as_ptr() as *const _);
+                        let addr = Ipv4Addr::from(u32::from_be(info.ipi_addr.s_addr));
+                        packet_info = Some(PacketInfo::new(IpAddr::V4(addr),
+                                                           info.ipi_ifindex as u32));
+                    }
+                    (libc::IPPROTO_IPV6, msg::IPV6_PKTINFO)
+                        if data.len() >= mem::size_of::<msg::in6_pktinfo>() => {
+                        let info: msg::in6_pktinfo =
+                            ptr::read_unaligned(data.as_ptr() as *const _);
+                        let addr = Ipv6Addr::from(info.ipi6_addr.s6_addr);
+                        packet_info = Some(PacketInfo::new(IpAddr::V6(addr),
+                                                           info.ipi6_ifindex as u32));
+                    }
+                    (libc::SOL_SOCKET, msg::SCM_TIMESTAMP)
+                        if data.len() >= mem::size_of::<libc::timeval>() => {
+                        let tv: libc::timeval =
+                            ptr::read_unaligned(data.as_ptr() as *const _);
+                        let since_epoch = Duration::new(tv.tv_sec as u64,
+                                                        (tv.tv_usec as u32) * 1000);
+                        timestamp = Some(UNIX_EPOCH + since_epoch);
+                    }
+                    _ => {}
+                }
+            }
+
+            let truncated = hdr.msg_flags & libc::MSG_TRUNC != 0;
+            Ok((n as usize, RecvMeta::new(source, packet_info, timestamp, truncated)))
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+    pub fn send_msg(&self, buf: &[u8], dst: &SocketAddr, info: &PacketInfo)
+                    -> io::Result<usize> {
+        use net::IpAddr;
+        use slice;
+        use sys::cmsg;
+
+        let mut control = [0u64; 8];
+        unsafe {
+            let control_bytes = slice::from_raw_parts_mut(control.as_mut_ptr() as *mut u8,
+                                                          mem::size_of_val(&control));
+            let control_len = match info.addr() {
+                IpAddr::V4(addr) => {
+                    let pktinfo = msg::in_pktinfo {
+                        ipi_ifindex: info.interface() as _,
+                        ipi_spec_dst: *addr.as_inner(),
+                        ipi_addr: mem::zeroed(),
+                    };
+                    cmsg::write(control_bytes, libc::IPPROTO_IP, msg::IP_PKTINFO, &[pktinfo])
+                }
+                IpAddr::V6(addr) => {
+                    let pktinfo = msg::in6_pktinfo {
+                        ipi6_addr: *addr.as_inner(),
+                        ipi6_ifindex: info.interface() as _,
+                    };
+                    cmsg::write(control_bytes, libc::IPPROTO_IPV6, msg::IPV6_PKTINFO, &[pktinfo])
+                }
+            };
+
+            let (dstp, dstlen) = dst.into_inner();
+            let mut iov = libc::iovec {
+                iov_base: buf.as_ptr() as *mut c_void,
+                iov_len: buf.len(),
+            };
+            let mut hdr: libc::msghdr = mem::zeroed();
+            hdr.msg_name = dstp as *mut c_void;
+            hdr.msg_namelen = dstlen;
+            hdr.msg_iov = &mut iov;
+            hdr.msg_iovlen = 1;
+            hdr.msg_control = control.as_mut_ptr() as *mut c_void;
+            hdr.msg_controllen = control_len as _;
+
+            let n = cvt(libc::sendmsg(self.0.raw(), &hdr, 0))?;
+            Ok(n as usize)
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+    pub fn set_recv_packet_info(&self, _v6: bool, _recv: bool) -> io::Result<()> {
+        Err(msg_unsupported())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+    pub fn recv_packet_info(&self, _v6: bool) -> io::Result<bool> {
+        Err(msg_unsupported())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+    pub fn set_recv_timestamp(&self, _recv: bool) -> io::Result<()> {
+        Err(msg_unsupported())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+    pub fn recv_timestamp(&self) -> io::Result<bool> {
+        Err(msg_unsupported())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+    pub fn recv_msg(&self, _buf: &mut [u8]) -> io::Result<(usize, RecvMeta)> {
+        Err(msg_unsupported())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+    pub fn send_msg(&self, _buf: &[u8], _dst: &SocketAddr, _info: &PacketInfo)
+                    -> io::Result<usize> {
+        Err(msg_unsupported())
+    }
+
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         let mut nonblocking = nonblocking as libc::c_int;
         cvt(unsafe { libc::ioctl(*self.as_inner(), libc::FIONBIO, &mut nonblocking) }).map(|_| ())
@@ -353,6 +695,12 @@ impl Socket {
     }
 }
 
+#[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+fn msg_unsupported() -> io::Error {
+    io::Error::new(io::ErrorKind::Other,
+                   "packet information is not supported on this platform")
+}
+
 impl AsInner<c_int> for Socket {
     fn as_inner(&self) -> &c_int { self.0.as_inner() }
 }
@@ -0,0 +1,358 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Readiness notification for `net::Poller`, backed by epoll on Linux and
+//! kqueue on the BSDs. All registrations are edge-triggered.
+
+use libc::c_int;
+
+pub use self::imp::*;
+
+pub type RawSource = c_int;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod imp {
+    use cmp;
+    use io;
+    use libc::{self, c_int};
+    use net::{Event, Interest};
+    use sys::cvt;
+    use sys::fd::FileDesc;
+    use time::Duration;
+    use vec::Vec;
+
+    pub struct Poller {
+        epfd: FileDesc,
+    }
+
+    pub struct Events {
+        list: Vec<libc::epoll_event>,
+    }
+
+    impl Poller {
+        pub fn new() -> io::Result<Poller> {
+            let fd = cvt(unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) })?;
+            Ok(Poller { epfd: FileDesc::new(fd) })
+        }
+
+        pub fn register(&self, fd: c_int, token: usize, interest: Interest)
+                        -> io::Result<()> {
+            self.ctl(libc::EPOLL_CTL_ADD, fd, token, interest)
+        }
+
+        pub fn reregister(&self, fd: c_int, token: usize, interest: Interest)
+                          -> io::Result<()> {
+            self.ctl(libc::EPOLL_CTL_MOD, fd, token, interest)
+        }
+
+        pub fn deregister(&self, fd: c_int) -> io::Result<()> {
+            // Kernels before 2.6.9 require a non-null event even though it
+            // is ignored for EPOLL_CTL_DEL.
+            let mut ev = libc::epoll_event { events: 0, u64: 0 };
+            cvt(unsafe {
+                libc::epoll_ctl(self.epfd.raw(), libc::EPOLL_CTL_DEL, fd, &mut ev)
+            })?;
+            Ok(())
+        }
+
+        pub fn poll(&self, events: &mut Events, timeout: Option<Duration>)
+                    -> io::Result<usize> {
+            events.list.clear();
+            let cap = cmp::min(events.list.capacity(), c_int::max_value() as usize);
+            let n = cvt(unsafe {
+                libc::epoll_wait(self.epfd.raw(),
+                                 events.list.as_mut_ptr(),
+                                 cap as c_int,
+                                 timeout_ms(timeout))
+            })?;
+            unsafe { events.list.set_len(n as usize) };
+            Ok(n as usize)
+        }
+
+        fn ctl(&self, op: c_int, fd: c_int, token: usize, interest: Interest)
+               -> io::Result<()> {
+            let mut flags = libc::EPOLLET;
+            if interest.is_readable() {
+                flags |= libc::EPOLLIN | libc::EPOLLRDHUP;
+            }
+            if interest.is_writable() {
+                flags |= libc::EPOLLOUT;
+            }
+            let mut ev = libc::epoll_event { events: flags as u32, u64: token as u64 };
+            cvt(unsafe { libc::epoll_ctl(self.epfd.raw(), op, fd, &mut ev) })?;
+            Ok(())
+        }
+    }
+
+    impl Events {
+        pub fn with_capacity(capacity: usize) -> Events {
+            // `epoll_wait` rejects an empty buffer.
+            Events { list: Vec::with_capacity(cmp::max(capacity, 1)) }
+        }
+
+        pub fn capacity(&self) -> usize {
+            self.list.capacity()
+        }
+
+        pub fn len(&self) -> usize {
+            self.list.len()
+        }
+
+        pub fn clear(&mut self) {
+            self.list.clear()
+        }
+
+        pub fn get(&self, idx: usize) -> Option<Event> {
+            self.list.get(idx).map(|ev| {
+                let flags = ev.events as c_int;
+                Event::new(ev.u64 as usize,
+                           flags & (libc::EPOLLIN | libc::EPOLLPRI) != 0,
+                           flags & libc::EPOLLOUT != 0,
+                           flags & libc::EPOLLERR != 0,
+                           flags & (libc::EPOLLHUP | libc::EPOLLRDHUP) != 0)
+            })
+        }
+    }
+
+    fn timeout_ms(timeout: Option<Duration>) -> c_int {
+        match timeout {
+            None => -1,
+            Some(dur) => {
+                // Round up so that a sub-millisecond timeout doesn't turn
+                // into a busy loop.
+                let ms = dur.as_secs()
+                            .saturating_mul(1000)
+                            .saturating_add((dur.subsec_nanos() as u64 + 999_999) / 1_000_000);
+                cmp::min(ms, c_int::max_value() as u64) as c_int
+            }
+        }
+    }
+}
+
+#[cfg(any(target_os = "macos",
+          target_os = "ios",
+          target_os = "freebsd",
+          target_os = "dragonfly",
+          target_os = "netbsd",
+          target_os = "openbsd",
+          target_os = "bitrig"))]
+mod imp {
+    use cmp;
+    use io;
+    use libc::{self, c_int};
+    use mem;
+    use net::{Event, Interest};
+    use ptr;
+    use sys::cvt;
+    use sys::fd::FileDesc;
+    use time::Duration;
+    use vec::Vec;
+
+    pub struct Poller {
+        kq: FileDesc,
+    }
+
+    pub struct Events {
+        list: Vec<libc::kevent>,
+    }
+
+    impl Poller {
+        pub fn new() -> io::Result<Poller> {
+            let kq = FileDesc::new(cvt(unsafe { libc::kqueue() })?);
+            kq.set_cloexec()?;
+            Ok(Poller { kq: kq })
+        }
+
+        pub fn register(&self, fd: c_int, token: usize, interest: Interest)
+                        -> io::Result<()> {
+            if interest.is_readable() {
+                self.add(fd, libc::EVFILT_READ as i16, token)?;
+            }
+            if interest.is_writable() {
+                if let Err(e) = self.add(fd, libc::EVFILT_WRITE as i16, token) {
+                    // Don't leave the descriptor registered for reading only.
+                    if interest.is_readable() {
+                        let _ = self.delete(fd, libc::EVFILT_READ as i16);
+                    }
+                    return Err(e)
+                }
+            }
+            Ok(())
+        }
+
+        pub fn reregister(&self, fd: c_int, token: usize, interest: Interest)
+                          -> io::Result<()> {
+            if interest.is_readable() {
+                self.add(fd, libc::EVFILT_READ as i16, token)?;
+            } else {
+                self.delete(fd, libc::EVFILT_READ as i16)?;
+            }
+            if interest.is_writable() {
+                self.add(fd, libc::EVFILT_WRITE as i16, token)?;
+            } else {
+                self.delete(fd, libc::EVFILT_WRITE as i16)?;
+            }
+            Ok(())
+        }
+
+        pub fn deregister(&self, fd: c_int) -> io::Result<()> {
+            self.delete(fd, libc::EVFILT_READ as i16)?;
+            self.delete(fd, libc::EVFILT_WRITE as i16)
+        }
+
+        pub fn poll(&self, events: &mut Events, timeout: Option<Duration>)
+                    -> io::Result<usize> {
+            events.list.clear();
+            let cap = cmp::min(events.list.capacity(), c_int::max_value() as usize);
+            let ts = timeout.map(|dur| {
+                libc::timespec {
+                    tv_sec: cmp::min(dur.as_secs(), libc::time_t::max_value() as u64)
+                        as libc::time_t,
+                    tv_nsec: dur.subsec_nanos() as libc::c_long,
+                }
+            });
+            let ts_ptr = ts.as_ref().map(|ts| ts as *const _).unwrap_or(ptr::null());
+            let n = cvt(unsafe {
+                libc::kevent(self.kq.raw(),
+                             ptr::null(),
+                             0,
+                             events.list.as_mut_ptr(),
+                             cap as _,
+                             ts_ptr)
+            })?;
+            unsafe { events.list.set_len(n as usize) };
+            Ok(n as usize)
+        }
+
+        fn add(&self, fd: c_int, filter: i16, token: usize) -> io::Result<()> {
+            self.change(fd, filter, (libc::EV_ADD | libc::EV_CLEAR) as u16, token)
+        }
+
+        fn delete(&self, fd: c_int, filter: i16) -> io::Result<()> {
+            // Removing a filter which was never added is not an error here,
+            // as `reregister` and `deregister` don't track what was added.
+            match self.change(fd, filter, libc::EV_DELETE as u16, 0) {
+                Err(ref e) if e.raw_os_error() == Some(libc::ENOENT) => Ok(()),
+                r => r,
+            }
+        }
+
+        // The widths of `filter` and `flags` vary between the BSDs, so they
+        // are passed around as the narrowest common type.
+        fn change(&self, fd: c_int, filter: i16, flags: u16, token: usize)
+                  -> io::Result<()> {
+            let mut ev: libc::kevent = unsafe { mem::zeroed() };
+            ev.ident = fd as _;
+            ev.filter = filter as _;
+            ev.flags = flags as _;
+            ev.udata = token as _;
+            cvt(unsafe {
+                libc::kevent(self.kq.raw(), &ev, 1, ptr::null_mut(), 0, ptr::null())
+            })?;
+            Ok(())
+        }
+    }
+
+    impl Events {
+        pub fn with_capacity(capacity: usize) -> Events {
+            Events { list: Vec::with_capacity(cmp::max(capacity, 1)) }
+        }
+
+        pub fn capacity(&self) -> usize {
+            self.list.capacity()
+        }
+
+        pub fn len(&self) -> usize {
+            self.list.len()
+        }
+
+        pub fn clear(&mut self) {
+            self.list.clear()
+        }
+
+        pub fn get(&self, idx: usize) -> Option<Event> {
+            self.list.get(idx).map(|ev| {
+                Event::new(ev.udata as usize,
+                           ev.filter as i16 == libc::EVFILT_READ as i16,
+                           ev.filter as i16 == libc::EVFILT_WRITE as i16,
+                           ev.flags as u16 & libc::EV_ERROR as u16 != 0,
+                           ev.flags as u16 & libc::EV_EOF as u16 != 0)
+            })
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux",
+              target_os = "android",
+              target_os = "macos",
+              target_os = "ios",
+              target_os = "freebsd",
+              target_os = "dragonfly",
+              target_os = "netbsd",
+              target_os = "openbsd",
+              target_os = "bitrig")))]
+mod imp {
+    use io;
+    use libc::c_int;
+    use net::{Event, Interest};
+    use time::Duration;
+
+    enum Void {}
+
+    pub struct Poller(Void);
+
+    pub struct Events(usize);
+
+    impl Poller {
+        pub fn new() -> io::Result<Poller> {
+            Err(io::Error::new(io::ErrorKind::Other,
+                               "readiness polling is not supported on this platform"))
+        }
+
+        pub fn register(&self, _fd: c_int, _token: usize, _interest: Interest)
+                        -> io::Result<()> {
+            match self.0 {}
+        }
+
+        pub fn reregister(&self, _fd: c_int, _token: usize, _interest: Interest)
+                          -> io::Result<()> {
+            match self.0 {}
+        }
+
+        pub fn deregister(&self, _fd: c_int) -> io::Result<()> {
+            match self.0 {}
+        }
+
+        pub fn poll(&self, _events: &mut Events, _timeout: Option<Duration>)
+                    -> io::Result<usize> {
+            match self.0 {}
+        }
+    }
+
+    impl Events {
+        pub fn with_capacity(capacity: usize) -> Events {
+            Events(capacity)
+        }
+
+        pub fn capacity(&self) -> usize {
+            self.0
+        }
+
+        pub fn len(&self) -> usize {
+            0
+        }
+
+        pub fn clear(&mut self) {}
+
+        pub fn get(&self, _idx: usize) -> Option<Event> {
+            None
+        }
+    }
+}
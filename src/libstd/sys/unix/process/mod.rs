@@ -10,9 +10,13 @@
 
 pub use self::process_common::{Command, ExitStatus, Stdio, StdioPipes};
 pub use self::process_inner::Process;
+#[cfg(not(target_os = "fuchsia"))]
+pub use self::sigchld::ExitWatcher;
 
 mod process_common;
 #[cfg(not(target_os = "fuchsia"))]
+mod sigchld;
+#[cfg(not(target_os = "fuchsia"))]
 #[path = "process_unix.rs"]
 mod process_inner;
 #[cfg(target_os = "fuchsia")]
@@ -15,6 +15,7 @@ use ptr;
 
 use sys::process::zircon::{Handle, zx_handle_t};
 use sys::process::process_common::*;
+use time::Duration;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Command
@@ -169,6 +170,23 @@ impl Process {
     }
 
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.wait_deadline(0)
+    }
+
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<Option<ExitStatus>> {
+        use sys::process::zircon::*;
+
+        let nanos = dur.as_secs()
+            .checked_mul(1_000_000_000)
+            .and_then(|nanos| nanos.checked_add(dur.subsec_nanos() as u64));
+        let deadline = match nanos {
+            Some(nanos) => unsafe { zx_deadline_after(nanos) },
+            None => ZX_TIME_INFINITE,
+        };
+        self.wait_deadline(deadline)
+    }
+
+    fn wait_deadline(&mut self, deadline: u64) -> io::Result<Option<ExitStatus>> {
         use default::Default;
         use sys::process::zircon::*;
 
@@ -178,7 +196,7 @@ impl Process {
 
         unsafe {
             let status = zx_object_wait_one(self.handle.raw(), ZX_TASK_TERMINATED,
-                                            0, ptr::null_mut());
+                                            deadline, ptr::null_mut());
             match status {
                 0 => { }, // Success
                 x if x == ERR_TIMED_OUT => {
@@ -14,6 +14,8 @@ use ptr;
 
 use sys::cvt;
 use sys::process::process_common::*;
+use sys::process::sigchld;
+use time::{Duration, Instant};
 
 ////////////////////////////////////////////////////////////////////////////////
 // Command
@@ -284,4 +286,25 @@ impl Process {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.status {
+            return Ok(Some(status))
+        }
+        let start = Instant::now();
+        let mut seen = sigchld::generation()?;
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status))
+            }
+            let remaining = match dur.checked_sub(start.elapsed()) {
+                Some(remaining) => remaining,
+                None => return Ok(None),
+            };
+            match sigchld::wait_past(seen, Some(remaining))? {
+                Some(generation) => seen = generation,
+                None => return self.try_wait(),
+            }
+        }
+    }
 }
@@ -0,0 +1,209 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Notification of child process state changes.
+//!
+//! Unix offers no portable way to wait on a particular child with a timeout,
+//! so we install a `SIGCHLD` handler which bumps a global generation counter
+//! and writes a byte into a nonblocking "self-pipe". Waiters remember the
+//! generation they last observed, check on their children with `waitpid`
+//! and then `poll` the read end of the pipe until the generation moves on.
+//!
+//! The handler is installed lazily, the first time someone actually asks to
+//! be notified, and it chains to whatever handler was installed before it.
+//! If `SIGCHLD` was being ignored we leave it alone: children are reaped
+//! automatically in that case, and `waitpid` would never report them anyway.
+//! With no signal to wait for, waiters fall back to polling: every sleep of
+//! `MAX_SLEEP_MS` counts as a new generation, so callers wake up that often
+//! and check on their children again.
+//!
+//! Draining the pipe from one thread can race with another thread that is
+//! just about to go to sleep in `poll`, so sleeps are capped at
+//! `MAX_SLEEP_MS`. Losing a wakeup therefore costs a little latency rather
+//! than a hang.
+
+use cmp;
+use io;
+use libc::{self, c_int, c_void};
+use mem;
+use ptr;
+use sync::Once;
+use sync::atomic::{AtomicUsize, AtomicBool, Ordering};
+use sys::{cvt, cvt_r};
+use sys::os;
+use sys::pipe::anon_pipe;
+use time::{Duration, Instant};
+
+const MAX_SLEEP_MS: u64 = 50;
+
+static INIT: Once = Once::new();
+static INIT_ERRNO: AtomicUsize = AtomicUsize::new(0);
+static IGNORED: AtomicBool = AtomicBool::new(false);
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+static READ_FD: AtomicUsize = AtomicUsize::new(0);
+static WRITE_FD: AtomicUsize = AtomicUsize::new(0);
+static mut PREVIOUS: Option<libc::sigaction> = None;
+
+/// Returns the current generation, installing the `SIGCHLD` handler first
+/// if this is the first call.
+///
+/// The generation must be read *before* checking on children, otherwise an
+/// exit which happens in between would go unnoticed.
+pub fn generation() -> io::Result<usize> {
+    INIT.call_once(|| {
+        if let Err(e) = unsafe { install() } {
+            let errno = e.raw_os_error().unwrap_or(libc::EINVAL);
+            INIT_ERRNO.store(errno as usize, Ordering::SeqCst);
+        }
+    });
+    match INIT_ERRNO.load(Ordering::SeqCst) {
+        0 => Ok(GENERATION.load(Ordering::SeqCst)),
+        errno => Err(io::Error::from_raw_os_error(errno as i32)),
+    }
+}
+
+/// Blocks until the generation differs from `seen`, returning the new
+/// generation, or until `timeout` has elapsed, returning `None`.
+///
+/// A `timeout` of `None` waits forever.
+pub fn wait_past(seen: usize, timeout: Option<Duration>)
+                 -> io::Result<Option<usize>> {
+    let start = Instant::now();
+    loop {
+        let current = GENERATION.load(Ordering::SeqCst);
+        if current != seen {
+            return Ok(Some(current))
+        }
+
+        let mut sleep_ms = MAX_SLEEP_MS;
+        if let Some(timeout) = timeout {
+            let remaining = match timeout.checked_sub(start.elapsed()) {
+                Some(remaining) => remaining,
+                None => return Ok(None),
+            };
+            // Round up so that we never wake up just short of the deadline
+            // and spin with a zero timeout.
+            let ms = remaining.as_secs()
+                .saturating_mul(1_000)
+                .saturating_add((remaining.subsec_nanos() as u64 + 999_999) / 1_000_000);
+            if ms == 0 {
+                return Ok(None)
+            }
+            sleep_ms = cmp::min(sleep_ms, ms);
+        }
+
+        if IGNORED.load(Ordering::SeqCst) {
+            // No signal will ever arrive, so there is nothing to block on.
+            // Treat each sleep of at most `MAX_SLEEP_MS` as a (spurious)
+            // wakeup and let the caller check on its children again.
+            unsafe { libc::usleep((sleep_ms * 1_000) as libc::c_uint); }
+            GENERATION.fetch_add(1, Ordering::SeqCst);
+            continue
+        }
+
+        let mut pollfd = libc::pollfd {
+            fd: READ_FD.load(Ordering::SeqCst) as c_int,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        if cvt_r(|| unsafe { libc::poll(&mut pollfd, 1, sleep_ms as c_int) })? > 0 {
+            drain(pollfd.fd);
+        }
+    }
+}
+
+fn drain(fd: c_int) {
+    let mut buf = [0u8; 64];
+    loop {
+        let ret = unsafe {
+            libc::read(fd, buf.as_mut_ptr() as *mut c_void, buf.len())
+        };
+        if ret < 0 && os::errno() == libc::EINTR {
+            continue
+        }
+        if ret <= 0 {
+            break
+        }
+    }
+}
+
+unsafe fn install() -> io::Result<()> {
+    let mut previous: libc::sigaction = mem::zeroed();
+    cvt(libc::sigaction(libc::SIGCHLD, ptr::null(), &mut previous))?;
+    if previous.sa_sigaction == libc::SIG_IGN {
+        IGNORED.store(true, Ordering::SeqCst);
+        return Ok(())
+    }
+
+    let (reader, writer) = anon_pipe()?;
+    reader.fd().set_nonblocking(true)?;
+    writer.fd().set_nonblocking(true)?;
+    READ_FD.store(reader.fd().raw() as usize, Ordering::SeqCst);
+    WRITE_FD.store(writer.fd().raw() as usize, Ordering::SeqCst);
+    if previous.sa_sigaction != libc::SIG_DFL {
+        PREVIOUS = Some(previous);
+    }
+
+    let mut action: libc::sigaction = mem::zeroed();
+    action.sa_flags = libc::SA_SIGINFO | libc::SA_RESTART | libc::SA_NOCLDSTOP;
+    action.sa_sigaction = handler as libc::sighandler_t;
+    // If this fails the handler was never installed, so nothing can be
+    // using the pipe and it is closed again as `reader` and `writer` drop.
+    cvt(libc::sigaction(libc::SIGCHLD, &action, ptr::null_mut()))?;
+
+    // The handler is live now, so the pipe has to stay open for good.
+    reader.into_fd().into_raw();
+    writer.into_fd().into_raw();
+    Ok(())
+}
+
+extern fn handler(signum: c_int, info: *mut libc::siginfo_t, ctx: *mut c_void) {
+    let errno = os::errno();
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    unsafe {
+        // If the pipe is full then a wakeup is already pending, so a failed
+        // write can be safely ignored.
+        let fd = WRITE_FD.load(Ordering::SeqCst) as c_int;
+        libc::write(fd, b"\0".as_ptr() as *const c_void, 1);
+
+        if let Some(ref previous) = PREVIOUS {
+            if previous.sa_flags & libc::SA_SIGINFO != 0 {
+                let f: extern fn(c_int, *mut libc::siginfo_t, *mut c_void) =
+                    mem::transmute(previous.sa_sigaction);
+                f(signum, info, ctx);
+            } else {
+                let f: extern fn(c_int) = mem::transmute(previous.sa_sigaction);
+                f(signum);
+            }
+        }
+    }
+    os::set_errno(errno);
+}
+
+/// A handle which waits for any child process to change state.
+pub struct ExitWatcher {
+    seen: usize,
+}
+
+impl ExitWatcher {
+    pub fn new() -> io::Result<ExitWatcher> {
+        Ok(ExitWatcher { seen: generation()? })
+    }
+
+    pub fn wait_timeout(&mut self, timeout: Option<Duration>) -> io::Result<bool> {
+        match wait_past(self.seen, timeout)? {
+            Some(generation) => {
+                self.seen = generation;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
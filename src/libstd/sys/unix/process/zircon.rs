@@ -27,6 +27,8 @@ pub const ZX_HANDLE_INVALID: zx_handle_t = 0;
 pub type zx_time_t = u64;
 pub const ZX_TIME_INFINITE : zx_time_t = u64::MAX;
 
+pub type zx_duration_t = u64;
+
 pub type zx_signals_t = u32;
 
 pub const ZX_OBJECT_SIGNAL_3         : zx_signals_t = 1 << 3;
@@ -109,6 +111,8 @@ extern {
     pub fn zx_handle_duplicate(handle: zx_handle_t, rights: zx_rights_t,
                                out: *const zx_handle_t) -> zx_handle_t;
 
+    pub fn zx_deadline_after(nanoseconds: zx_duration_t) -> zx_time_t;
+
     pub fn zx_object_wait_one(handle: zx_handle_t, signals: zx_signals_t, timeout: zx_time_t,
                               pending: *mut zx_signals_t) -> zx_status_t;
 
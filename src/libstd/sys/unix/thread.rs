@@ -196,6 +196,224 @@ impl Drop for Thread {
     }
 }
 
+#[cfg(target_os = "linux")]
+pub fn available_concurrency() -> io::Result<usize> {
+    use sys::cvt;
+
+    let mut count = unsafe {
+        let mut set: libc::cpu_set_t = mem::zeroed();
+        if libc::sched_getaffinity(0, mem::size_of_val(&set), &mut set) == 0 {
+            let bytes = ::slice::from_raw_parts(&set as *const _ as *const u8,
+                                                mem::size_of_val(&set));
+            bytes.iter().map(|b| b.count_ones() as usize).sum()
+        } else {
+            0
+        }
+    };
+    if count == 0 {
+        count = cvt(unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) })? as usize;
+    }
+    if let Some(quota) = cgroup::quota() {
+        count = cmp::min(count, quota);
+    }
+    Ok(cmp::max(count, 1))
+}
+
+#[cfg(any(target_os = "android",
+          target_os = "emscripten",
+          target_os = "fuchsia",
+          target_os = "ios",
+          target_os = "l4re",
+          target_os = "macos",
+          target_os = "solaris"))]
+pub fn available_concurrency() -> io::Result<usize> {
+    use sys::cvt;
+
+    let count = cvt(unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) })?;
+    Ok(cmp::max(count as usize, 1))
+}
+
+#[cfg(any(target_os = "freebsd",
+          target_os = "dragonfly",
+          target_os = "bitrig",
+          target_os = "netbsd",
+          target_os = "openbsd"))]
+pub fn available_concurrency() -> io::Result<usize> {
+    use sys::cvt;
+
+    let mut cpus: libc::c_uint = 0;
+    let mut cpus_size = mem::size_of_val(&cpus);
+    let mut mib = [libc::CTL_HW, libc::HW_NCPU, 0, 0];
+    cvt(unsafe {
+        libc::sysctl(mib.as_mut_ptr(),
+                     2,
+                     &mut cpus as *mut _ as *mut _,
+                     &mut cpus_size as *mut _ as *mut _,
+                     ptr::null_mut(),
+                     0)
+    })?;
+    Ok(cmp::max(cpus as usize, 1))
+}
+
+#[cfg(target_os = "haiku")]
+pub fn available_concurrency() -> io::Result<usize> {
+    Err(io::Error::new(io::ErrorKind::Other,
+                       "the number of hardware threads is not known for this platform"))
+}
+
+#[cfg(target_os = "linux")]
+mod cgroup {
+    //! Reads the CPU bandwidth limit of the process's cgroup, as set up by
+    //! container runtimes. Both the unified (v2) hierarchy and the `cpu`
+    //! controller of the legacy (v1) hierarchy are understood.
+
+    use fs;
+
+    /// Returns the quota rounded up to whole CPUs, or `None` if the
+    /// process is not limited or the limit cannot be determined.
+    pub fn quota() -> Option<usize> {
+        let (quota, period) = match v2() {
+            Some(limit) => limit,
+            None => v1()?,
+        };
+        if quota == 0 || period == 0 {
+            return None
+        }
+        Some(((quota + period - 1) / period) as usize)
+    }
+
+    fn v2() -> Option<(u64, u64)> {
+        // The unified hierarchy is listed as `0::/path/of/group`.
+        let cgroups = fs::read_to_string("/proc/self/cgroup").ok()?;
+        let path = cgroups.lines()
+            .filter(|line| line.starts_with("0::"))
+            .map(|line| line[3..].trim_right_matches('/'))
+            .next()?;
+        let max = fs::read_to_string(format!("/sys/fs/cgroup{}/cpu.max", path)).ok()?;
+        // The file contains "$MAX $PERIOD", where $MAX may be "max".
+        let mut fields = max.split_whitespace();
+        let quota = fields.next()?.parse().ok()?;
+        let period = fields.next()?.parse().ok()?;
+        Some((quota, period))
+    }
+
+    fn v1() -> Option<(u64, u64)> {
+        let dir = v1_cpu_dir()?;
+        let read = |name: &str| -> Option<i64> {
+            let path = format!("{}/{}", dir, name);
+            fs::read_to_string(path).ok()?.trim().parse().ok()
+        };
+        // A quota of -1 means that the group is unlimited.
+        let quota = read("cpu.cfs_quota_us")?;
+        let period = read("cpu.cfs_period_us")?;
+        if quota <= 0 || period <= 0 {
+            return None
+        }
+        Some((quota as u64, period as u64))
+    }
+
+    /// Finds the directory of the process's own group in the v1 `cpu`
+    /// hierarchy.
+    ///
+    /// `/proc/self/cgroup` lists the group as `ID:cpu,cpuacct:/path`,
+    /// relative to the root of the hierarchy, and `/proc/self/mountinfo`
+    /// says where that hierarchy, or only a subtree of it inside a
+    /// container, is mounted.
+    fn v1_cpu_dir() -> Option<String> {
+        let cgroups = fs::read_to_string("/proc/self/cgroup").ok()?;
+        let group = cgroups.lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, ':');
+                let _id = fields.next()?;
+                let controllers = fields.next()?;
+                let path = fields.next()?;
+                if has_cpu_controller(controllers) { Some(path) } else { None }
+            })
+            .next()?;
+
+        let mountinfo = fs::read_to_string("/proc/self/mountinfo").ok()?;
+        for line in mountinfo.lines() {
+            // "ID PARENT MAJ:MIN ROOT MOUNT_POINT OPTIONS [TAGS...] - TYPE SOURCE SUPER_OPTIONS"
+            let mut halves = line.splitn(2, " - ");
+            let (mount, filesystem) = match (halves.next(), halves.next()) {
+                (Some(mount), Some(filesystem)) => (mount, filesystem),
+                _ => continue,
+            };
+            let mut filesystem = filesystem.split(' ');
+            if filesystem.next() != Some("cgroup") ||
+               !filesystem.nth(1).map_or(false, has_cpu_controller) {
+                continue
+            }
+            let mut mount = mount.split(' ');
+            let (root, mount_point) = match (mount.nth(3), mount.next()) {
+                (Some(root), Some(mount_point)) => (root, mount_point),
+                _ => continue,
+            };
+            let relative = if root == "/" {
+                group
+            } else if group.starts_with(root) &&
+                      (group.len() == root.len() || group[root.len()..].starts_with('/')) {
+                &group[root.len()..]
+            } else {
+                continue
+            };
+            return Some(format!("{}{}", mount_point, relative.trim_right_matches('/')))
+        }
+        None
+    }
+
+    fn has_cpu_controller(list: &str) -> bool {
+        list.split(',').any(|controller| controller == "cpu")
+    }
+}
+
+/// Restricts the calling thread to the given set of CPUs.
+#[cfg(target_os = "linux")]
+pub fn set_affinity(cpus: &[usize]) -> io::Result<()> {
+    use sys::cvt;
+
+    unsafe {
+        let mut set: libc::cpu_set_t = mem::zeroed();
+        let max = mem::size_of_val(&set) * 8;
+        for &cpu in cpus {
+            if cpu >= max {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                          "CPU index out of range"))
+            }
+            libc::CPU_SET(cpu, &mut set);
+        }
+        cvt(libc::sched_setaffinity(0, mem::size_of_val(&set), &set))?;
+    }
+    Ok(())
+}
+
+/// Sets the nice value of the calling thread.
+///
+/// On Linux the nice value is a per-thread attribute, addressed by passing
+/// the kernel thread id to `setpriority`.
+#[cfg(target_os = "linux")]
+pub fn set_priority(nice: i32) -> io::Result<()> {
+    use sys::cvt;
+
+    unsafe {
+        let tid = libc::syscall(libc::SYS_gettid) as libc::id_t;
+        cvt(libc::setpriority(libc::PRIO_PROCESS, tid, nice as libc::c_int))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_affinity(_cpus: &[usize]) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Other,
+                       "thread affinity is not supported on this platform"))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_priority(_nice: i32) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Other,
+                       "per-thread priorities are not supported on this platform"))
+}
+
 #[cfg(all(not(all(target_os = "linux", not(target_env = "musl"))),
           not(target_os = "freebsd"),
           not(target_os = "macos"),
@@ -11,7 +11,7 @@
 use ffi::OsString;
 use fmt;
 use hash::{Hash, Hasher};
-use io::{self, SeekFrom};
+use io::{self, SeekFrom, IoVec, IoVecMut};
 use path::{Path, PathBuf};
 use sys::time::SystemTime;
 use sys::{unsupported, Void};
@@ -202,6 +202,14 @@ impl File {
         match self.0 {}
     }
 
+    pub fn lock(&self, _exclusive: bool, _wait: bool) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        match self.0 {}
+    }
+
     pub fn truncate(&self, _size: u64) -> io::Result<()> {
         match self.0 {}
     }
@@ -210,10 +218,26 @@ impl File {
         match self.0 {}
     }
 
+    pub fn read_vectored(&self, _bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        match self.0 {}
+    }
+
+    pub fn read_at(&self, _buf: &mut [u8], _offset: u64) -> io::Result<usize> {
+        match self.0 {}
+    }
+
     pub fn write(&self, _buf: &[u8]) -> io::Result<usize> {
         match self.0 {}
     }
 
+    pub fn write_at(&self, _buf: &[u8], _offset: u64) -> io::Result<usize> {
+        match self.0 {}
+    }
+
+    pub fn write_vectored(&self, _bufs: &[IoVec]) -> io::Result<usize> {
+        match self.0 {}
+    }
+
     pub fn flush(&self) -> io::Result<()> {
         match self.0 {}
     }
@@ -263,6 +287,10 @@ pub fn rename(_old: &Path, _new: &Path) -> io::Result<()> {
     unsupported()
 }
 
+pub fn sync_dir(_p: &Path) -> io::Result<()> {
+    unsupported()
+}
+
 pub fn set_perm(_p: &Path, perm: FilePermissions) -> io::Result<()> {
     match perm.0 {}
 }
@@ -39,6 +39,7 @@ pub mod cmath;
 pub mod condvar;
 pub mod env;
 pub mod fs;
+pub mod io;
 pub mod memchr;
 pub mod mutex;
 pub mod net;
@@ -46,6 +47,7 @@ pub mod os;
 pub mod os_str;
 pub mod path;
 pub mod pipe;
+pub mod poll;
 pub mod process;
 pub mod rwlock;
 pub mod stack_overflow;
@@ -207,3 +207,25 @@ impl IntoRawSocket for net::UdpSocket {
         self.into_inner().into_socket().into_inner()
     }
 }
+
+#[unstable(feature = "tcp_builder", issue = "0")]
+impl AsRawSocket for net::TcpBuilder {
+    fn as_raw_socket(&self) -> RawSocket {
+        *self.as_inner().socket().as_inner()
+    }
+}
+
+#[unstable(feature = "tcp_builder", issue = "0")]
+impl FromRawSocket for net::TcpBuilder {
+    unsafe fn from_raw_socket(sock: RawSocket) -> net::TcpBuilder {
+        let sock = sys::net::Socket::from_inner(sock);
+        net::TcpBuilder::from_inner(sys_common::net::TcpBuilder::from_inner(sock))
+    }
+}
+
+#[unstable(feature = "tcp_builder", issue = "0")]
+impl IntoRawSocket for net::TcpBuilder {
+    fn into_raw_socket(self) -> RawSocket {
+        self.into_inner().into_socket().into_inner()
+    }
+}
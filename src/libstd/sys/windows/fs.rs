@@ -12,7 +12,7 @@ use os::windows::prelude::*;
 
 use ffi::OsString;
 use fmt;
-use io::{self, Error, SeekFrom};
+use io::{self, Error, SeekFrom, IoVec, IoVecMut};
 use mem;
 use path::{Path, PathBuf};
 use ptr;
@@ -280,6 +280,50 @@ impl File {
 
     pub fn datasync(&self) -> io::Result<()> { self.fsync() }
 
+    // Returns `Ok(false)` if `wait` is false and the lock is already held.
+    pub fn lock(&self, exclusive: bool, wait: bool) -> io::Result<bool> {
+        let mut flags = 0;
+        if exclusive {
+            flags |= c::LOCKFILE_EXCLUSIVE_LOCK;
+        }
+        if !wait {
+            flags |= c::LOCKFILE_FAIL_IMMEDIATELY;
+        }
+        unsafe {
+            let mut overlapped: c::OVERLAPPED = mem::zeroed();
+            let ret = c::LockFileEx(self.handle.raw(), flags, 0,
+                                    c::DWORD::max_value(), c::DWORD::max_value(),
+                                    &mut overlapped);
+            if ret != 0 {
+                Ok(true)
+            } else {
+                let err = Error::last_os_error();
+                if err.raw_os_error() == Some(c::ERROR_LOCK_VIOLATION as i32) {
+                    Ok(false)
+                } else {
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        unsafe {
+            let mut overlapped: c::OVERLAPPED = mem::zeroed();
+            let ret = c::UnlockFileEx(self.handle.raw(), 0,
+                                      c::DWORD::max_value(), c::DWORD::max_value(),
+                                      &mut overlapped);
+            if ret == 0 {
+                let err = Error::last_os_error();
+                // Unlocking a file which isn't locked is not an error.
+                if err.raw_os_error() != Some(c::ERROR_NOT_LOCKED as i32) {
+                    return Err(err)
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         let mut info = c::FILE_END_OF_FILE_INFO {
             EndOfFile: size as c::LARGE_INTEGER,
@@ -321,6 +365,10 @@ impl File {
         self.handle.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.handle.read_vectored(bufs)
+    }
+
     pub fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
         self.handle.read_at(buf, offset)
     }
@@ -329,6 +377,10 @@ impl File {
         self.handle.write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.handle.write_vectored(bufs)
+    }
+
     pub fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
         self.handle.write_at(buf, offset)
     }
@@ -595,6 +647,17 @@ pub fn rename(old: &Path, new: &Path) -> io::Result<()> {
     Ok(())
 }
 
+pub fn sync_dir(p: &Path) -> io::Result<()> {
+    // NTFS journals changes to directory entries, and there is no supported
+    // way of flushing a directory handle, so all we can do is check that the
+    // directory is there.
+    if stat(p)?.file_type().is_dir() {
+        Ok(())
+    } else {
+        Err(io::Error::from_raw_os_error(c::ERROR_DIRECTORY as i32))
+    }
+}
+
 pub fn rmdir(p: &Path) -> io::Result<()> {
     let p = to_u16s(p)?;
     cvt(unsafe { c::RemoveDirectoryW(p.as_ptr()) })?;
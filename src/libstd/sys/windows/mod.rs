@@ -35,6 +35,7 @@ pub mod ext;
 pub mod fast_thread_local;
 pub mod fs;
 pub mod handle;
+pub mod io;
 pub mod memchr;
 pub mod mutex;
 pub mod net;
@@ -42,6 +43,7 @@ pub mod os;
 pub mod os_str;
 pub mod path;
 pub mod pipe;
+pub mod poll;
 pub mod process;
 pub mod rand;
 pub mod rwlock;
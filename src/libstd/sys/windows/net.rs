@@ -11,10 +11,10 @@
 #![unstable(issue = "0", feature = "windows_net")]
 
 use cmp;
-use io::{self, Read};
+use io::{self, Read, IoVec, IoVecMut};
 use libc::{c_int, c_void, c_ulong, c_long};
 use mem;
-use net::{SocketAddr, Shutdown};
+use net::{SocketAddr, Shutdown, PacketInfo, RecvMeta};
 use ptr;
 use sync::Once;
 use sys::c;
@@ -55,6 +55,15 @@ fn last_error() -> io::Error {
     io::Error::from_raw_os_error(unsafe { c::WSAGetLastError() })
 }
 
+/// Returns the error for the `recv_msg`/`send_msg` family, which is not
+/// implemented on Windows.
+// FIXME: WSARecvMsg has to be looked up through WSAIoctl before it can be
+// used, which is not done yet.
+fn msg_unsupported() -> io::Error {
+    io::Error::new(io::ErrorKind::Other,
+                   "packet information is not supported on Windows yet")
+}
+
 #[doc(hidden)]
 pub trait IsMinusOne {
     fn is_minus_one(&self) -> bool;
@@ -104,6 +113,10 @@ impl Socket {
             SocketAddr::V4(..) => c::AF_INET,
             SocketAddr::V6(..) => c::AF_INET6,
         };
+        Socket::new_raw(fam, ty)
+    }
+
+    pub fn new_raw(fam: c_int, ty: c_int) -> io::Result<Socket> {
         let socket = unsafe {
             match c::WSASocketW(fam, ty, 0, ptr::null_mut(), 0,
                                 c::WSA_FLAG_OVERLAPPED) {
@@ -217,6 +230,43 @@ impl Socket {
         self.recv_with_flags(buf, 0)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        // On unix when a socket is shut down all further reads return 0, so we
+        // do the same on windows to map a shut down socket to returning EOF.
+        let len = cmp::min(bufs.len(), c::DWORD::max_value() as usize) as c::DWORD;
+        let mut nread = 0;
+        let mut flags = 0;
+        unsafe {
+            let ret = c::WSARecv(self.0,
+                                 bufs.as_mut_ptr() as *mut c::WSABUF,
+                                 len,
+                                 &mut nread,
+                                 &mut flags,
+                                 ptr::null_mut(),
+                                 ptr::null_mut());
+            match ret {
+                0 => Ok(nread as usize),
+                _ if c::WSAGetLastError() == c::WSAESHUTDOWN => Ok(0),
+                _ => Err(last_error()),
+            }
+        }
+    }
+
+    pub fn write_vectored(&self, bufs: &[IoVec]) -> io::Result<usize> {
+        let len = cmp::min(bufs.len(), c::DWORD::max_value() as usize) as c::DWORD;
+        let mut nwritten = 0;
+        unsafe {
+            cvt(c::WSASend(self.0,
+                           bufs.as_ptr() as *const c::WSABUF as *mut c::WSABUF,
+                           len,
+                           &mut nwritten,
+                           0,
+                           ptr::null_mut(),
+                           ptr::null_mut()))?;
+        }
+        Ok(nwritten as usize)
+    }
+
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.recv_with_flags(buf, c::MSG_PEEK)
     }
@@ -316,6 +366,95 @@ impl Socket {
         Ok(raw != 0)
     }
 
+    pub fn set_keepalive(&self, keepalive: Option<Duration>) -> io::Result<()> {
+        let time = match keepalive {
+            Some(dur) => {
+                let time = sys::dur2timeout(dur);
+                if time == 0 {
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                              "cannot set a 0 duration keepalive time"));
+                }
+                time
+            }
+            None => 0,
+        };
+        // The interval between probes is one second by default, and has to
+        // be given here as well.
+        let mut vals = c::tcp_keepalive {
+            onoff: keepalive.is_some() as c::ULONG,
+            keepalivetime: time,
+            keepaliveinterval: 1000,
+        };
+        let mut returned = 0;
+        cvt(unsafe {
+            c::WSAIoctl(self.0,
+                        c::SIO_KEEPALIVE_VALS,
+                        &mut vals as *mut _ as c::LPVOID,
+                        mem::size_of_val(&vals) as c::DWORD,
+                        ptr::null_mut(),
+                        0,
+                        &mut returned,
+                        ptr::null_mut(),
+                        ptr::null_mut())
+        })?;
+        Ok(())
+    }
+
+    pub fn keepalive(&self) -> io::Result<Option<Duration>> {
+        let raw: c::BOOL = net::getsockopt(self, c::SOL_SOCKET, c::SO_KEEPALIVE)?;
+        if raw == 0 {
+            Ok(None)
+        } else {
+            // SIO_KEEPALIVE_VALS can only be set, so the time is unknown.
+            Err(io::Error::new(io::ErrorKind::Other,
+                               "keepalive time cannot be queried on Windows"))
+        }
+    }
+
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        let linger = c::linger {
+            l_onoff: linger.is_some() as c::USHORT,
+            l_linger: linger.map_or(0, |dur| {
+                cmp::min(dur.as_secs(), c::USHORT::max_value() as u64) as c::USHORT
+            }),
+        };
+        net::setsockopt(self, c::SOL_SOCKET, c::SO_LINGER, linger)
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        let raw: c::linger = net::getsockopt(self, c::SOL_SOCKET, c::SO_LINGER)?;
+        if raw.l_onoff == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(Duration::from_secs(raw.l_linger as u64)))
+        }
+    }
+
+    pub fn set_recv_packet_info(&self, _v6: bool, _recv: bool) -> io::Result<()> {
+        Err(msg_unsupported())
+    }
+
+    pub fn recv_packet_info(&self, _v6: bool) -> io::Result<bool> {
+        Err(msg_unsupported())
+    }
+
+    pub fn set_recv_timestamp(&self, _recv: bool) -> io::Result<()> {
+        Err(msg_unsupported())
+    }
+
+    pub fn recv_timestamp(&self) -> io::Result<bool> {
+        Err(msg_unsupported())
+    }
+
+    pub fn recv_msg(&self, _buf: &mut [u8]) -> io::Result<(usize, RecvMeta)> {
+        Err(msg_unsupported())
+    }
+
+    pub fn send_msg(&self, _buf: &[u8], _dst: &SocketAddr, _info: &PacketInfo)
+                    -> io::Result<usize> {
+        Err(msg_unsupported())
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         let raw: c_int = net::getsockopt(self, c::SOL_SOCKET, c::SO_ERROR)?;
         if raw == 0 {
@@ -9,12 +9,14 @@
 // except according to those terms.
 
 use alloc::boxed::FnBox;
+use cmp;
 use io;
 use ffi::CStr;
 use mem;
 use libc::c_void;
 use ptr;
 use sys::c;
+use sys::cvt;
 use sys::handle::Handle;
 use sys_common::thread::*;
 use time::Duration;
@@ -91,6 +93,17 @@ impl Thread {
     pub fn into_handle(self) -> Handle { self.handle }
 }
 
+pub fn available_concurrency() -> io::Result<usize> {
+    let mut process_mask = 0;
+    let mut system_mask = 0;
+    cvt(unsafe {
+        c::GetProcessAffinityMask(c::GetCurrentProcess(),
+                                  &mut process_mask,
+                                  &mut system_mask)
+    })?;
+    Ok(cmp::max(process_mask.count_ones() as usize, 1))
+}
+
 #[cfg_attr(test, allow(dead_code))]
 pub mod guard {
     pub type Guard = !;
@@ -11,10 +11,10 @@
 use cmp;
 use ffi::CString;
 use fmt;
-use io::{self, Error, ErrorKind};
+use io::{self, Error, ErrorKind, IoVec, IoVecMut};
 use libc::{c_int, c_void};
 use mem;
-use net::{SocketAddr, Shutdown, Ipv4Addr, Ipv6Addr};
+use net::{SocketAddr, Shutdown, Ipv4Addr, Ipv6Addr, PacketInfo, RecvMeta};
 use ptr;
 use sys::net::{cvt, cvt_r, cvt_gai, Socket, init, wrlen_t};
 use sys::net::netc as c;
@@ -227,6 +227,10 @@ impl TcpStream {
         self.inner.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     pub fn write(&self, buf: &[u8]) -> io::Result<usize> {
         let len = cmp::min(buf.len(), <wrlen_t>::max_value() as usize) as wrlen_t;
         let ret = cvt(unsafe {
@@ -238,6 +242,10 @@ impl TcpStream {
         Ok(ret as usize)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
+
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         sockname(|buf, len| unsafe {
             c::getpeername(*self.inner.as_inner(), buf, len)
@@ -275,6 +283,22 @@ impl TcpStream {
         Ok(raw as u32)
     }
 
+    pub fn set_keepalive(&self, keepalive: Option<Duration>) -> io::Result<()> {
+        self.inner.set_keepalive(keepalive)
+    }
+
+    pub fn keepalive(&self) -> io::Result<Option<Duration>> {
+        self.inner.keepalive()
+    }
+
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.inner.set_linger(linger)
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.inner.linger()
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
@@ -409,6 +433,135 @@ impl fmt::Debug for TcpListener {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// TCP builders
+////////////////////////////////////////////////////////////////////////////////
+
+pub struct TcpBuilder {
+    inner: Socket,
+}
+
+impl TcpBuilder {
+    pub fn new_v4() -> io::Result<TcpBuilder> {
+        init();
+        Socket::new_raw(c::AF_INET, c::SOCK_STREAM).map(|inner| TcpBuilder { inner })
+    }
+
+    pub fn new_v6() -> io::Result<TcpBuilder> {
+        init();
+        Socket::new_raw(c::AF_INET6, c::SOCK_STREAM).map(|inner| TcpBuilder { inner })
+    }
+
+    pub fn socket(&self) -> &Socket { &self.inner }
+
+    pub fn into_socket(self) -> Socket { self.inner }
+
+    pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR, reuse as c_int)
+    }
+
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR)?;
+        Ok(raw != 0)
+    }
+
+    #[cfg(unix)]
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        self.inner.set_reuse_port(reuse)
+    }
+
+    #[cfg(unix)]
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        self.inner.reuse_port()
+    }
+
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY, only_v6 as c_int)
+    }
+
+    pub fn only_v6(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::max_value() as usize) as c_int;
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_RCVBUF)?;
+        Ok(raw as usize)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::max_value() as usize) as c_int;
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_SNDBUF)?;
+        Ok(raw as usize)
+    }
+
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        setsockopt(&self.inner, c::IPPROTO_IP, c::IP_TTL, ttl as c_int)
+    }
+
+    pub fn ttl(&self) -> io::Result<u32> {
+        let raw: c_int = getsockopt(&self.inner, c::IPPROTO_IP, c::IP_TTL)?;
+        Ok(raw as u32)
+    }
+
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        let (addrp, len) = addr.into_inner();
+        cvt(unsafe { c::bind(*self.inner.as_inner(), addrp, len as _) })?;
+        Ok(())
+    }
+
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let backlog = cmp::min(backlog, c_int::max_value() as u32) as c_int;
+        cvt(unsafe { c::listen(*self.inner.as_inner(), backlog) })?;
+        Ok(TcpListener { inner: self.inner })
+    }
+
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<TcpStream> {
+        let (addrp, len) = addr.into_inner();
+        cvt_r(|| unsafe { c::connect(*self.inner.as_inner(), addrp, len) })?;
+        Ok(TcpStream { inner: self.inner })
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        sockname(|buf, len| unsafe {
+            c::getsockname(*self.inner.as_inner(), buf, len)
+        })
+    }
+
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.inner.take_error()
+    }
+}
+
+impl FromInner<Socket> for TcpBuilder {
+    fn from_inner(socket: Socket) -> TcpBuilder {
+        TcpBuilder { inner: socket }
+    }
+}
+
+impl fmt::Debug for TcpBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut res = f.debug_struct("TcpBuilder");
+
+        if let Ok(addr) = self.socket_addr() {
+            res.field("addr", &addr);
+        }
+
+        let name = if cfg!(windows) {"socket"} else {"fd"};
+        res.field(name, &self.inner.as_inner())
+            .finish()
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // UDP
 ////////////////////////////////////////////////////////////////////////////////
@@ -557,6 +710,33 @@ impl UdpSocket {
         Ok(raw as u32)
     }
 
+    pub fn set_recv_packet_info(&self, recv_packet_info: bool) -> io::Result<()> {
+        let v6 = self.socket_addr()?.is_ipv6();
+        self.inner.set_recv_packet_info(v6, recv_packet_info)
+    }
+
+    pub fn recv_packet_info(&self) -> io::Result<bool> {
+        let v6 = self.socket_addr()?.is_ipv6();
+        self.inner.recv_packet_info(v6)
+    }
+
+    pub fn set_recv_timestamp(&self, recv_timestamp: bool) -> io::Result<()> {
+        self.inner.set_recv_timestamp(recv_timestamp)
+    }
+
+    pub fn recv_timestamp(&self) -> io::Result<bool> {
+        self.inner.recv_timestamp()
+    }
+
+    pub fn recv_msg(&self, buf: &mut [u8]) -> io::Result<(usize, RecvMeta)> {
+        self.inner.recv_msg(buf)
+    }
+
+    pub fn send_msg(&self, buf: &[u8], dst: &SocketAddr, info: &PacketInfo)
+                    -> io::Result<usize> {
+        self.inner.send_msg(buf, dst, info)
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
@@ -174,7 +174,7 @@ use io;
 use panic;
 use panicking;
 use str;
-use sync::{Mutex, Condvar, Arc};
+use sync::{mpsc, Mutex, Condvar, Arc};
 use sync::atomic::AtomicUsize;
 use sync::atomic::Ordering::SeqCst;
 use sys::thread as imp;
@@ -208,6 +208,15 @@ pub use self::local::{LocalKey, AccessError};
 #[unstable(feature = "libstd_thread_internals", issue = "0")]
 #[doc(hidden)] pub use self::local::os::Key as __OsLocalKeyInner;
 
+////////////////////////////////////////////////////////////////////////////////
+// Scoped threads
+////////////////////////////////////////////////////////////////////////////////
+
+mod scoped;
+
+#[unstable(feature = "scoped_threads", issue = "0")]
+pub use self::scoped::{scope, Scope, ScopedJoinHandle};
+
 ////////////////////////////////////////////////////////////////////////////////
 // Builder
 ////////////////////////////////////////////////////////////////////////////////
@@ -255,12 +264,24 @@ pub use self::local::{LocalKey, AccessError};
 /// [naming-threads]: ./index.html#naming-threads
 /// [stack-size]: ./index.html#stack-size
 #[stable(feature = "rust1", since = "1.0.0")]
-#[derive(Debug)]
 pub struct Builder {
     // A name for the thread-to-be, for identification in panic messages
     name: Option<String>,
     // The size of the stack for the spawned thread in bytes
     stack_size: Option<usize>,
+    // Platform-specific setup run on the new thread before the closure, such
+    // as pinning it to CPUs. An error from any of them fails the spawn.
+    start_hooks: Vec<Box<Fn() -> io::Result<()> + Send + Sync>>,
+}
+
+#[stable(feature = "std_debug", since = "1.16.0")]
+impl fmt::Debug for Builder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("name", &self.name)
+            .field("stack_size", &self.stack_size)
+            .finish()
+    }
 }
 
 impl Builder {
@@ -287,6 +308,7 @@ impl Builder {
         Builder {
             name: None,
             stack_size: None,
+            start_hooks: Vec::new(),
         }
     }
 
@@ -384,7 +406,29 @@ impl Builder {
     pub fn spawn<F, T>(self, f: F) -> io::Result<JoinHandle<T>> where
         F: FnOnce() -> T, F: Send + 'static, T: Send + 'static
     {
-        let Builder { name, stack_size } = self;
+        unsafe { self.spawn_unchecked(f) }
+    }
+
+    /// Adds a function to run on the new thread before its closure.
+    ///
+    /// This is how platform-specific extensions, such as the CPU affinity
+    /// settings in `os::unix::thread::BuilderExt`, configure the thread.
+    pub(crate) fn start_hook<F>(mut self, hook: F) -> Builder
+        where F: Fn() -> io::Result<()> + Send + Sync + 'static
+    {
+        self.start_hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Spawns a new thread without requiring `'static` bounds on the
+    /// closure and its result.
+    ///
+    /// The caller must guarantee that the thread is joined before anything
+    /// borrowed by `f` or `T` goes out of scope.
+    unsafe fn spawn_unchecked<'a, F, T>(self, f: F) -> io::Result<JoinHandle<T>> where
+        F: FnOnce() -> T, F: Send + 'a, T: Send + 'a
+    {
+        let Builder { name, stack_size, start_hooks } = self;
 
         let stack_size = stack_size.unwrap_or_else(thread::min_stack);
 
@@ -395,26 +439,47 @@ impl Builder {
             = Arc::new(UnsafeCell::new(None));
         let their_packet = my_packet.clone();
 
+        // If there is any setup to run on the new thread, wait for it to
+        // finish so that errors can be reported from here.
+        let (started_tx, started_rx) = if start_hooks.is_empty() {
+            (None, None)
+        } else {
+            let (tx, rx) = mpsc::channel();
+            (Some(tx), Some(rx))
+        };
+
         let main = move || {
             if let Some(name) = their_thread.cname() {
                 imp::Thread::set_name(name);
             }
-            unsafe {
-                thread_info::set(imp::guard::current(), their_thread);
-                #[cfg(feature = "backtrace")]
-                let try_result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
-                    ::sys_common::backtrace::__rust_begin_short_backtrace(f)
-                }));
-                #[cfg(not(feature = "backtrace"))]
-                let try_result = panic::catch_unwind(panic::AssertUnwindSafe(f));
-                *their_packet.get() = Some(try_result);
+            if let Some(started_tx) = started_tx {
+                let result = start_hooks.iter().map(|hook| hook()).collect::<io::Result<()>>();
+                let failed = result.is_err();
+                drop(started_tx.send(result));
+                if failed {
+                    return
+                }
             }
+            thread_info::set(imp::guard::current(), their_thread);
+            #[cfg(feature = "backtrace")]
+            let try_result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                ::sys_common::backtrace::__rust_begin_short_backtrace(f)
+            }));
+            #[cfg(not(feature = "backtrace"))]
+            let try_result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+            *their_packet.get() = Some(try_result);
         };
 
+        let native = imp::Thread::new(stack_size, Box::new(main))?;
+        if let Some(started_rx) = started_rx {
+            if let Ok(Err(e)) = started_rx.recv() {
+                native.join();
+                return Err(e)
+            }
+        }
+
         Ok(JoinHandle(JoinInner {
-            native: unsafe {
-                Some(imp::Thread::new(stack_size, Box::new(main))?)
-            },
+            native: Some(native),
             thread: my_thread,
             packet: Packet(my_packet),
         }))
@@ -700,6 +765,44 @@ pub fn sleep(dur: Duration) {
     imp::Thread::sleep(dur)
 }
 
+/// Returns an estimate of the number of threads which can run in parallel
+/// in this process.
+///
+/// This is a hint for sizing thread pools and similar. It is always at
+/// least 1, but may be smaller than the number of CPUs in the machine.
+///
+/// # Platform-specific behavior
+///
+/// On Linux, this honours the CPU affinity mask of the calling thread, which
+/// is inherited from the thread that spawned it and is usually set for the
+/// whole process by `taskset`, as well as any CPU bandwidth quota configured
+/// for the process's cgroup, as container runtimes do. Quotas set only on
+/// an ancestor of that cgroup are not taken into account. On Windows, the
+/// process affinity mask is honoured. Elsewhere, this is the number of
+/// online CPUs. Note that this [may change in the future][changes].
+///
+/// [changes]: ../io/index.html#platform-specific-behavior
+///
+/// # Errors
+///
+/// Returns an error if the number cannot be determined, such as on
+/// platforms which do not support threads.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(available_concurrency)]
+///
+/// use std::thread;
+///
+/// let workers = thread::available_concurrency().unwrap_or(1);
+/// println!("starting {} workers", workers);
+/// ```
+#[unstable(feature = "available_concurrency", issue = "0")]
+pub fn available_concurrency() -> io::Result<usize> {
+    imp::available_concurrency()
+}
+
 // constants for park/unpark
 const EMPTY: usize = 0;
 const PARKED: usize = 1;
@@ -1595,6 +1698,102 @@ mod tests {
         assert!(thread::current().id() != spawned_id);
     }
 
+    #[test]
+    fn test_scope_borrows() {
+        let mut data = vec![1, 2, 3, 4];
+        let total = {
+            let (left, right) = data.split_at_mut(2);
+            thread::scope(|s| {
+                s.spawn(|| {
+                    for n in right.iter_mut() {
+                        *n *= 10;
+                    }
+                });
+                let sum = s.spawn(|| left.iter().sum::<i32>());
+                sum.join().unwrap()
+            })
+        };
+        assert_eq!(total, 3);
+        assert_eq!(data, [1, 2, 30, 40]);
+    }
+
+    #[test]
+    fn test_scope_joins_all_threads() {
+        use sync::atomic::{AtomicUsize, Ordering};
+
+        let count = AtomicUsize::new(0);
+        thread::scope(|s| {
+            for _ in 0..8 {
+                s.spawn(|| {
+                    thread::sleep(Duration::from_millis(10));
+                    count.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+        });
+        assert_eq!(count.load(Ordering::SeqCst), 8);
+    }
+
+    #[test]
+    fn test_scope_propagates_panic() {
+        let result = thread::spawn(|| {
+            thread::scope(|s| {
+                s.spawn(|| panic!("child panicked"));
+            })
+        }).join();
+        let payload = result.unwrap_err();
+        assert_eq!(*payload.downcast::<&'static str>().unwrap(), "child panicked");
+    }
+
+    #[test]
+    fn test_scope_joined_panic_not_propagated() {
+        let result = thread::scope(|s| {
+            let handle = s.spawn(|| -> i32 { panic!() });
+            handle.join().is_err()
+        });
+        assert!(result);
+    }
+
+    #[test]
+    fn test_available_concurrency() {
+        assert!(thread::available_concurrency().unwrap() >= 1);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_cpu_affinity() {
+        use io::ErrorKind;
+        use libc;
+        use mem;
+        use os::unix::thread::BuilderExt;
+
+        // Pin the thread to a CPU which we are actually allowed to run on,
+        // which need not include CPU 0 under `taskset` or cpusets.
+        let cpu = unsafe {
+            let mut set: libc::cpu_set_t = mem::zeroed();
+            let size = mem::size_of_val(&set);
+            assert_eq!(libc::sched_getaffinity(0, size, &mut set), 0);
+            (0..size * 8).find(|&cpu| libc::CPU_ISSET(cpu, &set)).unwrap()
+        };
+        let handle = Builder::new().cpu_affinity(&[cpu]).spawn(|| {
+            thread::available_concurrency().unwrap()
+        }).unwrap();
+        assert_eq!(handle.join().unwrap(), 1);
+
+        let err = Builder::new().cpu_affinity(&[usize::max_value()]).spawn(|| {
+            panic!("thread should not have run");
+        }).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_priority() {
+        use os::unix::thread::BuilderExt;
+
+        // Raising the nice value never requires privileges.
+        Builder::new().priority(19).spawn(|| ()).unwrap().join().unwrap();
+    }
+
     // NOTE: the corresponding test for stderr is in run-pass/thread-stderr, due
     // to the test harness apparently interfering with stderr configuration.
 }
@@ -0,0 +1,262 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Scoped threads, which may borrow from the stack of the thread which
+//! spawned them.
+//!
+//! Soundness relies on every thread spawned in a scope being joined before
+//! `scope` returns, whether the scope closure returns normally or panics.
+//! Handles are not `Send` and `Scope` is not `Sync`, so all spawning and
+//! joining happens on the thread that called `scope`.
+
+use any::Any;
+use cell::RefCell;
+use fmt;
+use io;
+use marker::PhantomData;
+use panic;
+use rc::Rc;
+use super::{Builder, JoinInner, Result, Thread};
+
+/// Creates a scope for spawning threads which may borrow non-`'static` data.
+///
+/// The closure `f` is given a [`Scope`] which can be used to spawn threads.
+/// Unlike with [`thread::spawn`], these threads may borrow anything which
+/// outlives the call to `scope`, such as local variables of the caller.
+///
+/// All threads spawned in the scope which have not been joined manually
+/// are joined before `scope` returns, even if `f` panics.
+///
+/// # Panics
+///
+/// If `f` panics, the panic is propagated once all threads have been joined.
+/// Otherwise, if a thread which was not joined manually panicked, the panic
+/// of the first such thread is propagated to the caller. Panics of threads
+/// joined through a [`ScopedJoinHandle`] are reported by its [`join`]
+/// method instead.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(scoped_threads)]
+///
+/// use std::thread;
+///
+/// let mut numbers = vec![1, 2, 3, 4, 5, 6];
+/// let total = {
+///     let (left, right) = numbers.split_at_mut(3);
+///     thread::scope(|s| {
+///         let sum = s.spawn(|| left.iter().sum::<i32>());
+///         s.spawn(|| {
+///             for n in right.iter_mut() {
+///                 *n *= 10;
+///             }
+///         });
+///         sum.join().unwrap()
+///     })
+/// };
+///
+/// assert_eq!(total, 6);
+/// assert_eq!(numbers, [1, 2, 3, 40, 50, 60]);
+/// ```
+///
+/// [`Scope`]: struct.Scope.html
+/// [`ScopedJoinHandle`]: struct.ScopedJoinHandle.html
+/// [`join`]: struct.ScopedJoinHandle.html#method.join
+/// [`thread::spawn`]: fn.spawn.html
+#[unstable(feature = "scoped_threads", issue = "0")]
+pub fn scope<'env, F, T>(f: F) -> T
+    where F: FnOnce(&Scope<'env>) -> T
+{
+    let scope = Scope {
+        threads: RefCell::new(Vec::new()),
+        _marker: PhantomData,
+    };
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| f(&scope)));
+    let child_panic = scope.join_all();
+    match result {
+        Err(payload) => panic::resume_unwind(payload),
+        Ok(value) => {
+            if let Some(payload) = child_panic {
+                panic::resume_unwind(payload)
+            }
+            value
+        }
+    }
+}
+
+/// A scope in which threads borrowing from the enclosing environment can be
+/// spawned.
+///
+/// This is created by the [`scope`] function; see its documentation for
+/// more details.
+///
+/// [`scope`]: fn.scope.html
+#[unstable(feature = "scoped_threads", issue = "0")]
+pub struct Scope<'env> {
+    threads: RefCell<Vec<Rc<RefCell<ScopedThread + 'env>>>>,
+    // Keep `'env` invariant, so that a scope cannot be used to spawn threads
+    // borrowing data which lives for less than the whole scope.
+    _marker: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'env> Scope<'env> {
+    /// Spawns a new thread within the scope, returning a
+    /// [`ScopedJoinHandle`] for it.
+    ///
+    /// The closure may borrow anything which outlives the scope. The thread
+    /// is joined at the end of the scope at the latest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS fails to create a thread; use
+    /// [`Builder::spawn_scoped`] to recover from such errors.
+    ///
+    /// [`ScopedJoinHandle`]: struct.ScopedJoinHandle.html
+    /// [`Builder::spawn_scoped`]: struct.Builder.html#method.spawn_scoped
+    #[unstable(feature = "scoped_threads", issue = "0")]
+    pub fn spawn<'scope, F, T>(&'scope self, f: F) -> ScopedJoinHandle<'scope, T>
+        where F: FnOnce() -> T, F: Send + 'env, T: Send + 'env
+    {
+        Builder::new().spawn_scoped(self, f).expect("failed to spawn thread")
+    }
+
+    // Joins every thread which has not been joined yet, returning the panic
+    // payload of the first one which panicked.
+    fn join_all(&self) -> Option<Box<Any + Send + 'static>> {
+        let mut first_panic = None;
+        for thread in self.threads.borrow_mut().drain(..) {
+            if let Some(payload) = thread.borrow_mut().join_scoped() {
+                if first_panic.is_none() {
+                    first_panic = Some(payload);
+                }
+            }
+        }
+        first_panic
+    }
+}
+
+#[unstable(feature = "scoped_threads", issue = "0")]
+impl<'env> fmt::Debug for Scope<'env> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Scope")
+            .field("threads", &self.threads.borrow().len())
+            .finish()
+    }
+}
+
+impl Builder {
+    /// Spawns a new scoped thread using the settings of this `Builder`.
+    ///
+    /// Unlike [`Scope::spawn`], this method yields an [`io::Result`] to
+    /// capture any failure to create the thread at the OS level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(scoped_threads)]
+    ///
+    /// use std::thread;
+    ///
+    /// let greeting = String::from("hello");
+    /// thread::scope(|s| {
+    ///     thread::Builder::new()
+    ///         .name("greeter".into())
+    ///         .spawn_scoped(s, || println!("{}", greeting))
+    ///         .unwrap();
+    /// });
+    /// ```
+    ///
+    /// [`Scope::spawn`]: struct.Scope.html#method.spawn
+    /// [`io::Result`]: ../../std/io/type.Result.html
+    #[unstable(feature = "scoped_threads", issue = "0")]
+    pub fn spawn_scoped<'scope, 'env, F, T>(self, scope: &'scope Scope<'env>, f: F)
+                                            -> io::Result<ScopedJoinHandle<'scope, T>>
+        where F: FnOnce() -> T, F: Send + 'env, T: Send + 'env
+    {
+        // This is sound because the scope joins the thread before anything
+        // borrowed for `'env` can go out of scope.
+        let handle = unsafe { self.spawn_unchecked(f)? };
+        let thread = handle.thread().clone();
+        let inner = Rc::new(RefCell::new(handle.0));
+        scope.threads.borrow_mut().push(inner.clone());
+        Ok(ScopedJoinHandle {
+            inner,
+            thread,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// An owned permission to join on a scoped thread.
+///
+/// Dropping the handle does not detach the thread: it is still joined at
+/// the end of its scope.
+///
+/// This `struct` is created by the [`Scope::spawn`] and
+/// [`Builder::spawn_scoped`] methods.
+///
+/// [`Scope::spawn`]: struct.Scope.html#method.spawn
+/// [`Builder::spawn_scoped`]: struct.Builder.html#method.spawn_scoped
+#[unstable(feature = "scoped_threads", issue = "0")]
+pub struct ScopedJoinHandle<'scope, T> {
+    inner: Rc<RefCell<JoinInner<T>>>,
+    thread: Thread,
+    // Borrowing the scope keeps the handle from escaping it, which would
+    // allow joining a thread that the scope has already joined.
+    _marker: PhantomData<&'scope ()>,
+}
+
+impl<'scope, T> ScopedJoinHandle<'scope, T> {
+    /// Extracts a handle to the underlying thread.
+    #[unstable(feature = "scoped_threads", issue = "0")]
+    pub fn thread(&self) -> &Thread {
+        &self.thread
+    }
+
+    /// Waits for the associated thread to finish.
+    ///
+    /// If the child thread panics, [`Err`] is returned with the parameter
+    /// given to [`panic`], and the panic is not propagated again at the end
+    /// of the scope.
+    ///
+    /// [`Err`]: ../../std/result/enum.Result.html#variant.Err
+    /// [`panic`]: ../../std/macro.panic.html
+    #[unstable(feature = "scoped_threads", issue = "0")]
+    pub fn join(self) -> Result<T> {
+        self.inner.borrow_mut().join()
+    }
+}
+
+#[unstable(feature = "scoped_threads", issue = "0")]
+impl<'scope, T> fmt::Debug for ScopedJoinHandle<'scope, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("ScopedJoinHandle { .. }")
+    }
+}
+
+// A thread owned by a scope, with its result type erased.
+trait ScopedThread {
+    // Joins the thread unless that was already done through its handle,
+    // returning the panic payload if it panicked.
+    fn join_scoped(&mut self) -> Option<Box<Any + Send + 'static>>;
+}
+
+impl<T> ScopedThread for JoinInner<T> {
+    fn join_scoped(&mut self) -> Option<Box<Any + Send + 'static>> {
+        if self.native.is_none() {
+            return None
+        }
+        match self.join() {
+            Ok(_) => None,
+            Err(payload) => Some(payload),
+        }
+    }
+}
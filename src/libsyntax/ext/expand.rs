@@ -614,26 +614,17 @@ impl<'a, 'b> MacroExpander<'a, 'b> {
             }
 
             ProcMacro(ref expandfun) => {
-                if ident.name != keywords::Invalid.name() {
-                    let msg =
-                        format!("macro {}! expects no ident argument, given '{}'", path, ident);
+                // FIXME procedural macros do not have proper span info
+                // yet, when they do, we should use it here.
+                // FIXME probably want to follow macro_rules macros here.
+                if let Err(msg) = validate_and_set_expn_info(None, false, false) {
                     self.cx.span_err(path.span, &msg);
                     self.cx.trace_macros_diag();
                     kind.dummy(span)
                 } else {
-                    invoc.expansion_data.mark.set_expn_info(ExpnInfo {
-                        call_site: span,
-                        callee: NameAndSpan {
-                            format: macro_bang_format(path),
-                            // FIXME procedural macros do not have proper span info
-                            // yet, when they do, we should use it here.
-                            span: None,
-                            // FIXME probably want to follow macro_rules macros here.
-                            allow_internal_unstable: false,
-                            allow_internal_unsafe: false,
-                        },
-                    });
-
+                    // The expansion is parsed as whatever `kind` the invocation
+                    // stands in for: items, statements, an expression, a
+                    // pattern or a type.
                     let tok_result = expandfun.expand(self.cx, span, mac.node.stream());
                     self.parse_expansion(tok_result, kind, path, span)
                 }
@@ -1193,6 +1184,8 @@ pub struct ExpansionConfig<'feat> {
     pub features: Option<&'feat Features>,
     pub recursion_limit: usize,
     pub trace_mac: bool,
+    /// Whether to report how each `macro_rules!` arm matched an invocation
+    pub trace_matchers: bool,
     pub should_test: bool, // If false, strip `#[test]` nodes
     pub single_step: bool,
     pub keep_macs: bool,
@@ -1218,6 +1211,7 @@ impl<'feat> ExpansionConfig<'feat> {
             features: None,
             recursion_limit: 1024,
             trace_mac: false,
+            trace_matchers: false,
             should_test: false,
             single_step: false,
             keep_macs: false,
@@ -1233,6 +1227,8 @@ impl<'feat> ExpansionConfig<'feat> {
         fn enable_trace_macros = trace_macros,
         fn enable_allow_internal_unstable = allow_internal_unstable,
         fn enable_custom_derive = custom_derive,
+        fn enable_format_args_capture = format_args_capture,
+        fn enable_format_args_nl = format_args_nl,
         fn proc_macro_enabled = proc_macro,
     }
 }
@@ -0,0 +1,398 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reuse of procedural macro expansions.
+//!
+//! A procedural macro declared with `#[proc_macro(pure)]` or
+//! `#[proc_macro_attribute(pure)]` promises that its output depends on
+//! nothing but its input tokens. The output of such a macro is recorded in
+//! the `ProcMacroCache` of the `ParseSess`, keyed on the SVH of the crate
+//! defining the macro, the macro's name and a fingerprint of the input that
+//! ignores spans. Expansion happens before the query system is set up, so
+//! rather than being a query, the cache is loaded from and saved to the
+//! incremental compilation session directory by the driver.
+//!
+//! Spans are only meaningful within a single session, so every span of a
+//! recorded expansion is stored relative to the invocation that produced it:
+//! as the span of one of the input token trees, as the call site or def site
+//! of the expansion, or as the span the macro gave to a string it parsed.
+//! Expansions with spans that cannot be described this way, that contain
+//! interpolated AST fragments, or that emitted diagnostics are not recorded.
+//!
+//! Nothing enforces the promise: macros aren't sandboxed, so a macro which
+//! wrongly declares itself pure simply gets its stale output reused.
+
+use ast::Ident;
+use ext::base::ExtCtxt;
+use ext::hygiene::{Mark, SyntaxContext};
+use parse::ParseSess;
+use parse::token::{self, Token};
+use print::pprust;
+use symbol::Symbol;
+use tokenstream::{Delimited, TokenStream, TokenTree};
+
+use rustc_data_structures::stable_hasher::StableHasher;
+use serialize::{Decodable, Decoder, Encodable, Encoder};
+use syntax_pos::Span;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Identifies one expansion of a pure procedural macro.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, RustcEncodable, RustcDecodable)]
+pub struct ProcMacroCacheKey {
+    /// The SVH of the crate defining the macro.
+    pub crate_hash: u64,
+    /// The name the macro was registered under.
+    pub name: Symbol,
+    /// A fingerprint of the input token streams, ignoring spans.
+    pub input: u128,
+}
+
+/// Where the span of a recorded token tree came from.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, RustcEncodable, RustcDecodable)]
+enum SpanOrigin {
+    /// The span of the `n`th input token tree, in depth-first order.
+    Input(u32),
+    /// The call site of the expansion.
+    CallSite,
+    /// The def site of the expansion.
+    DefSite,
+    /// The span of the `n`th distinct string parsed by the macro.
+    Parsed(u32),
+}
+
+/// Where the syntax context of a recorded identifier or lifetime came from.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, RustcEncodable, RustcDecodable)]
+enum IdentCtxt {
+    /// The context of the token's span.
+    Span,
+    /// The empty context, as for tokens parsed from strings.
+    Empty,
+}
+
+#[derive(Clone, Debug, RustcEncodable, RustcDecodable)]
+enum CachedTree {
+    Token(SpanOrigin, Token),
+    /// An identifier or lifetime token, recorded with an empty context.
+    Ident(SpanOrigin, Token, IdentCtxt),
+    Delimited(SpanOrigin, token::DelimToken, Vec<(CachedTree, bool /* joint? */)>),
+}
+
+#[derive(Clone, Debug, RustcEncodable, RustcDecodable)]
+struct CachedExpansion {
+    trees: Vec<(CachedTree, bool /* joint? */)>,
+}
+
+/// Recorded expansions of pure procedural macros.
+#[derive(Default)]
+pub struct ProcMacroCache {
+    /// Expansions loaded from the previous session that have not been used yet.
+    previous: HashMap<ProcMacroCacheKey, CachedExpansion>,
+    /// Expansions used or recorded in this session. Only these are saved, so
+    /// expansions that are no longer needed are dropped from the cache.
+    current: HashMap<ProcMacroCacheKey, CachedExpansion>,
+}
+
+impl ProcMacroCache {
+    pub fn new() -> ProcMacroCache {
+        ProcMacroCache::default()
+    }
+
+    /// Makes the expansions saved by a previous session available for reuse.
+    pub fn load<D: Decoder>(&mut self, d: &mut D) -> Result<(), D::Error> {
+        self.previous = HashMap::decode(d)?;
+        Ok(())
+    }
+
+    /// Saves the expansions used or recorded in this session.
+    pub fn save<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+        self.current.encode(e)
+    }
+
+    fn get(&mut self, key: &ProcMacroCacheKey, input_spans: &[Span], mark: Mark)
+           -> Option<TokenStream> {
+        let expansion = match self.previous.remove(key) {
+            Some(expansion) => &*self.current.entry(*key).or_insert(expansion),
+            None => self.current.get(key)?,
+        };
+        let mut restore = Restore::new(input_spans, mark);
+        Some(restore.stream(&expansion.trees))
+    }
+
+    fn insert(&mut self, key: ProcMacroCacheKey, input_spans: &[Span], mark: Mark,
+              stream: &TokenStream) {
+        let mut capture = Capture::new(input_spans, mark);
+        if let Some(trees) = capture.stream(stream) {
+            self.current.insert(key, CachedExpansion { trees });
+        }
+    }
+}
+
+/// Expands a procedural macro through `expand`, reusing a recorded expansion
+/// of the same macro with the same input if there is one.
+///
+/// `pure` is the SVH of the crate defining the macro and the macro's name if
+/// the macro was declared pure; otherwise the macro is always run.
+pub fn expand_cached<'cx, F>(ecx: &mut ExtCtxt<'cx>,
+                             pure: Option<(u64, Symbol)>,
+                             inputs: &[&TokenStream],
+                             expand: F)
+                             -> TokenStream
+    where F: FnOnce(&mut ExtCtxt<'cx>) -> TokenStream
+{
+    let (crate_hash, name) = match pure {
+        Some(pure) => pure,
+        None => return expand(ecx),
+    };
+    let sess = ecx.parse_sess;
+    let (input, input_spans) = fingerprint(sess, inputs);
+    let key = ProcMacroCacheKey { crate_hash, name, input };
+    let mark = ecx.current_expansion.mark;
+
+    if let Some(stream) = sess.proc_macro_cache.borrow_mut().get(&key, &input_spans, mark) {
+        return stream;
+    }
+
+    let (stream, diagnostics) = sess.span_diagnostic.track_diagnostics(|| expand(ecx));
+    if diagnostics.is_empty() {
+        sess.proc_macro_cache.borrow_mut().insert(key, &input_spans, mark, &stream);
+    }
+    stream
+}
+
+/// Computes a span-independent fingerprint of `streams`, along with the spans
+/// of all their token trees in depth-first order.
+///
+/// Interpolated AST fragments are hashed as the token streams `proc_macro`
+/// presents them as, so that e.g. the items given to attribute macros can be
+/// fingerprinted.
+fn fingerprint(sess: &ParseSess, streams: &[&TokenStream]) -> (u128, Vec<Span>) {
+    fn hash_stream(sess: &ParseSess,
+                   stream: &TokenStream,
+                   hasher: &mut StableHasher<u128>,
+                   spans: &mut Vec<Span>) {
+        let mut cursor = stream.trees();
+        while let Some(stream) = cursor.next_as_stream() {
+            let (tree, is_joint) = stream.as_tree();
+            spans.push(tree.span());
+            is_joint.hash(hasher);
+            match tree {
+                TokenTree::Token(span, token @ Token::Interpolated(..)) => {
+                    0u8.hash(hasher);
+                    let stream = token.interpolated_to_tokenstream(sess, span);
+                    hash_stream(sess, &stream, hasher, spans);
+                }
+                TokenTree::Token(_, token) => {
+                    1u8.hash(hasher);
+                    pprust::token_to_string(&token).hash(hasher);
+                }
+                TokenTree::Delimited(_, delimited) => {
+                    2u8.hash(hasher);
+                    delimited.delim.hash(hasher);
+                    hash_stream(sess, &delimited.stream(), hasher, spans);
+                }
+            }
+        }
+        3u8.hash(hasher);
+    }
+
+    let mut hasher = StableHasher::new();
+    let mut spans = Vec::new();
+    for stream in streams {
+        hash_stream(sess, stream, &mut hasher, &mut spans);
+    }
+    (hasher.finish(), spans)
+}
+
+fn call_site(mark: Mark) -> Span {
+    mark.expn_info().unwrap().call_site
+}
+
+fn def_site(mark: Mark) -> Span {
+    call_site(mark).with_ctxt(SyntaxContext::empty().apply_mark(mark))
+}
+
+/// Turns the output of an expansion into its recorded form.
+struct Capture {
+    input_spans: HashMap<Span, u32>,
+    mark: Mark,
+    call_site: Span,
+    def_site: Span,
+    parsed: Vec<SyntaxContext>,
+}
+
+impl Capture {
+    fn new(input_spans: &[Span], mark: Mark) -> Capture {
+        let mut spans = HashMap::new();
+        for (i, &span) in input_spans.iter().enumerate() {
+            spans.entry(span).or_insert(i as u32);
+        }
+        Capture {
+            input_spans: spans,
+            mark,
+            call_site: call_site(mark),
+            def_site: def_site(mark),
+            parsed: Vec::new(),
+        }
+    }
+
+    fn stream(&mut self, stream: &TokenStream) -> Option<Vec<(CachedTree, bool)>> {
+        let mut trees = Vec::new();
+        let mut cursor = stream.trees();
+        while let Some(stream) = cursor.next_as_stream() {
+            let (tree, is_joint) = stream.as_tree();
+            trees.push((self.tree(tree)?, is_joint));
+        }
+        Some(trees)
+    }
+
+    fn tree(&mut self, tree: TokenTree) -> Option<CachedTree> {
+        match tree {
+            TokenTree::Token(span, token) => {
+                let origin = self.origin(span)?;
+                let (ident, is_lifetime) = match token {
+                    Token::Interpolated(..) => return None,
+                    Token::Ident(ident) => (ident, false),
+                    Token::Lifetime(ident) => (ident, true),
+                    token => return Some(CachedTree::Token(origin, token)),
+                };
+                let ctxt = if ident.ctxt == span.ctxt() {
+                    IdentCtxt::Span
+                } else if ident.ctxt == SyntaxContext::empty() {
+                    IdentCtxt::Empty
+                } else {
+                    return None;
+                };
+                if !is_interned(ident.name) {
+                    return None;
+                }
+                let ident = Ident::with_empty_ctxt(ident.name);
+                let token = if is_lifetime { Token::Lifetime(ident) } else { Token::Ident(ident) };
+                Some(CachedTree::Ident(origin, token, ctxt))
+            }
+            TokenTree::Delimited(span, delimited) => {
+                let origin = self.origin(span)?;
+                let trees = self.stream(&delimited.stream())?;
+                Some(CachedTree::Delimited(origin, delimited.delim, trees))
+            }
+        }
+    }
+
+    fn origin(&mut self, span: Span) -> Option<SpanOrigin> {
+        if let Some(&i) = self.input_spans.get(&span) {
+            return Some(SpanOrigin::Input(i));
+        }
+        if span == self.call_site {
+            return Some(SpanOrigin::CallSite);
+        }
+        if span == self.def_site {
+            return Some(SpanOrigin::DefSite);
+        }
+
+        // Strings parsed by the macro get the call site with the context of a
+        // fresh mark that is a child of the expansion's mark.
+        if span.lo() != self.call_site.lo() || span.hi() != self.call_site.hi() {
+            return None;
+        }
+        let ctxt = span.ctxt();
+        let outer = ctxt.outer();
+        if outer == self.mark || !outer.is_descendant_of(self.mark) ||
+           SyntaxContext::empty().apply_mark(outer) != ctxt {
+            return None;
+        }
+        let i = match self.parsed.iter().position(|&parsed| parsed == ctxt) {
+            Some(i) => i,
+            None => {
+                self.parsed.push(ctxt);
+                self.parsed.len() - 1
+            }
+        };
+        Some(SpanOrigin::Parsed(i as u32))
+    }
+}
+
+fn is_interned(name: Symbol) -> bool {
+    Symbol::intern(&name.as_str()) == name
+}
+
+/// Turns a recorded expansion back into a token stream for a new invocation.
+struct Restore<'a> {
+    input_spans: &'a [Span],
+    mark: Mark,
+    call_site: Span,
+    def_site: Span,
+    parsed: Vec<Span>,
+}
+
+impl<'a> Restore<'a> {
+    fn new(input_spans: &'a [Span], mark: Mark) -> Restore<'a> {
+        Restore {
+            input_spans,
+            mark,
+            call_site: call_site(mark),
+            def_site: def_site(mark),
+            parsed: Vec::new(),
+        }
+    }
+
+    fn stream(&mut self, trees: &[(CachedTree, bool)]) -> TokenStream {
+        trees.iter().map(|&(ref tree, is_joint)| {
+            let tree = self.tree(tree);
+            if is_joint { tree.joint() } else { tree.into() }
+        }).collect()
+    }
+
+    fn tree(&mut self, tree: &CachedTree) -> TokenTree {
+        match *tree {
+            CachedTree::Token(origin, ref token) => {
+                TokenTree::Token(self.span(origin), token.clone())
+            }
+            CachedTree::Ident(origin, ref token, ctxt) => {
+                let span = self.span(origin);
+                let ctxt = match ctxt {
+                    IdentCtxt::Span => span.ctxt(),
+                    IdentCtxt::Empty => SyntaxContext::empty(),
+                };
+                let token = match *token {
+                    Token::Ident(ident) => Token::Ident(Ident { ctxt, ..ident }),
+                    Token::Lifetime(ident) => Token::Lifetime(Ident { ctxt, ..ident }),
+                    ref token => token.clone(),
+                };
+                TokenTree::Token(span, token)
+            }
+            CachedTree::Delimited(origin, delim, ref trees) => {
+                let span = self.span(origin);
+                TokenTree::Delimited(span, Delimited {
+                    delim,
+                    tts: self.stream(trees).into(),
+                })
+            }
+        }
+    }
+
+    fn span(&mut self, origin: SpanOrigin) -> Span {
+        match origin {
+            SpanOrigin::Input(i) => self.input_spans[i as usize],
+            SpanOrigin::CallSite => self.call_site,
+            SpanOrigin::DefSite => self.def_site,
+            SpanOrigin::Parsed(i) => {
+                // Mirror `TokenStream::from_str` in `proc_macro`.
+                while self.parsed.len() <= i as usize {
+                    let mark = Mark::fresh(self.mark);
+                    mark.set_expn_info(self.mark.expn_info().unwrap());
+                    let ctxt = SyntaxContext::empty().apply_mark(mark);
+                    self.parsed.push(self.call_site.with_ctxt(ctxt));
+                }
+                self.parsed[i as usize]
+            }
+        }
+    }
+}
@@ -222,13 +222,41 @@ pub enum ParseResult<T> {
 /// This represents the mapping of metavars to the token trees they bind to.
 pub type NamedParseResult = ParseResult<HashMap<Ident, Rc<NamedMatch>>>;
 
+/// Something a matcher was waiting for when it stopped matching. These are collected by
+/// `parse_traced` for `-Z trace-macro-matchers`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expectation {
+    /// The position in the matcher of the token or fragment which was expected, or `DUMMY_SP` if
+    /// the end of the invocation was expected.
+    pub span: Span,
+    /// What was expected, e.g. "`,`" or "`$e:expr`".
+    pub description: String,
+}
+
+impl Expectation {
+    fn token(span: Span, tok: &Token) -> Expectation {
+        Expectation { span, description: format!("`{}`", pprust::token_to_string(tok)) }
+    }
+
+    fn fragment(span: Span, bind: Ident, kind: Ident) -> Expectation {
+        Expectation { span, description: format!("`${}:{}`", bind, kind) }
+    }
+
+    fn end() -> Expectation {
+        Expectation {
+            span: syntax_pos::DUMMY_SP,
+            description: "the end of the macro invocation".to_string(),
+        }
+    }
+}
+
 /// Count how many metavars are named in the given matcher `ms`.
 pub fn count_names(ms: &[TokenTree]) -> usize {
     ms.iter().fold(0, |count, elt| {
         count + match *elt {
             TokenTree::Sequence(_, ref seq) => seq.num_captures,
             TokenTree::Delimited(_, ref delim) => count_names(&delim.tts),
-            TokenTree::MetaVar(..) => 0,
+            TokenTree::MetaVar(..) | TokenTree::MetaVarExpr(..) => 0,
             TokenTree::MetaVarDecl(..) => 1,
             TokenTree::Token(..) => 0,
         }
@@ -333,7 +361,7 @@ fn nameize<I: Iterator<Item = NamedMatch>>(
                     }
                 }
             }
-            TokenTree::MetaVar(..) | TokenTree::Token(..) => (),
+            TokenTree::MetaVar(..) | TokenTree::MetaVarExpr(..) | TokenTree::Token(..) => (),
         }
 
         Ok(())
@@ -391,6 +419,8 @@ fn token_name_eq(t1: &Token, t2: &Token) -> bool {
 /// - `token`: the current token of the parser.
 /// - `span`: the `Span` in the source code corresponding to the token trees we are trying to match
 ///   against the matcher positions in `cur_items`.
+/// - `expected`: if given, every token or fragment that an item needed but `token` can't be is
+///   added to it.
 ///
 /// # Returns
 ///
@@ -403,6 +433,7 @@ fn inner_parse_loop(
     bb_items: &mut SmallVector<Box<MatcherPos>>,
     token: &Token,
     span: syntax_pos::Span,
+    mut expected: Option<&mut Vec<Expectation>>,
 ) -> ParseResult<()> {
     // Pop items from `cur_items` until it is empty.
     while let Some(mut item) = cur_items.pop() {
@@ -466,6 +497,12 @@ fn inner_parse_loop(
                     {
                         item.idx += 1;
                         next_items.push(item);
+                    } else if let Some(ref mut expected) = expected {
+                        let seq_span = match item.top_elts {
+                            Tt(ref tt) => tt.span(),
+                            TtSeq(_) => syntax_pos::DUMMY_SP,
+                        };
+                        expected.push(Expectation::token(seq_span, item.sep.as_ref().unwrap()));
                     }
                 }
                 // We don't need a separator. Move the "dot" back to the beginning of the matcher
@@ -527,11 +564,13 @@ fn inner_parse_loop(
 
                 // We need to match a metavar with a valid ident... call out to the black-box
                 // parser by adding an item to `bb_items`.
-                TokenTree::MetaVarDecl(_, _, id) => {
+                TokenTree::MetaVarDecl(span, bind, id) => {
                     // Built-in nonterminals never start with these tokens,
                     // so we can eliminate them from consideration.
                     if may_begin_with(&*id.name.as_str(), token) {
                         bb_items.push(item);
+                    } else if let Some(ref mut expected) = expected {
+                        expected.push(Expectation::fragment(span, bind, id));
                     }
                 }
 
@@ -562,7 +601,12 @@ fn inner_parse_loop(
                 // rules. NOTE that this is not necessarily an error unless _all_ items in
                 // `cur_items` end up doing this. There may still be some other matchers that do
                 // end up working out.
-                TokenTree::Token(..) | TokenTree::MetaVar(..) => {}
+                TokenTree::Token(span, ref t) => {
+                    if let Some(ref mut expected) = expected {
+                        expected.push(Expectation::token(span, t));
+                    }
+                }
+                TokenTree::MetaVar(..) | TokenTree::MetaVarExpr(..) => {}
             }
         }
     }
@@ -588,6 +632,20 @@ pub fn parse(
     ms: &[TokenTree],
     directory: Option<Directory>,
     recurse_into_modules: bool,
+) -> NamedParseResult {
+    parse_traced(sess, tts, ms, directory, recurse_into_modules, None)
+}
+
+/// Like `parse`, but if matching fails and `expected` is given, it is filled with what the
+/// matcher positions that were still alive expected instead of the token that was found. This
+/// shows how far the matcher got.
+pub fn parse_traced(
+    sess: &ParseSess,
+    tts: TokenStream,
+    ms: &[TokenTree],
+    directory: Option<Directory>,
+    recurse_into_modules: bool,
+    mut expected: Option<&mut Vec<Expectation>>,
 ) -> NamedParseResult {
     // Create a parser that can be used for the "black box" parts.
     let mut parser = Parser::new(sess, tts, directory, recurse_into_modules, true);
@@ -608,6 +666,11 @@ pub fn parse(
         let mut eof_items = SmallVector::new();
         assert!(next_items.is_empty());
 
+        // Only the expectations for the token that failed to match are interesting.
+        if let Some(ref mut expected) = expected {
+            expected.clear();
+        }
+
         // Process `cur_items` until either we have finished the input or we need to get some
         // parsing from the black-box parser done. The result is that `next_items` will contain a
         // bunch of possible next matcher positions in `next_items`.
@@ -619,6 +682,7 @@ pub fn parse(
             &mut bb_items,
             &parser.token,
             parser.span,
+            expected.as_mut().map(|expected| &mut **expected),
         ) {
             Success(_) => {}
             Failure(sp, tok) => return Failure(sp, tok),
@@ -677,6 +741,11 @@ pub fn parse(
         // If there are no posible next positions AND we aren't waiting for the black-box parser,
         // then their is a syntax error.
         else if bb_items.is_empty() && next_items.is_empty() {
+            if let Some(ref mut expected) = expected {
+                if !eof_items.is_empty() {
+                    expected.push(Expectation::end());
+                }
+            }
             return Failure(parser.span, parser.token);
         }
         // Dump all possible `next_items` into `cur_items` for the next iteration.
@@ -753,6 +822,17 @@ fn may_begin_with(name: &str, token: &Token) -> bool {
             },
             _ => false,
         },
+        "literal" => match *token {
+            Token::Literal(..) | Token::BinOp(token::Minus) => true,
+            Token::Ident(ident) => {
+                ident.name == keywords::True.name() || ident.name == keywords::False.name()
+            }
+            Token::Interpolated(ref nt) => match nt.0 {
+                token::NtLiteral(_) => true,
+                _ => false,
+            },
+            _ => false,
+        },
         "pat" => match *token {
             Token::Ident(_) |               // box, ref, mut, and other identifiers (can stricten)
             Token::OpenDelim(token::Paren) |    // tuple pattern
@@ -834,6 +914,7 @@ fn parse_nt<'a>(p: &mut Parser<'a>, sp: Span, name: &str) -> Nonterminal {
         "meta" => token::NtMeta(panictry!(p.parse_meta_item())),
         "vis" => token::NtVis(panictry!(p.parse_visibility(true))),
         "lifetime" => token::NtLifetime(p.expect_lifetime()),
+        "literal" => token::NtLiteral(panictry!(p.parse_pat_literal_maybe_minus())),
         // this is not supposed to happen, since it has been checked
         // when compiling the macro.
         _ => p.span_bug(sp, "invalid fragment specifier"),
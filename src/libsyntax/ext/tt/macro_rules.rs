@@ -9,13 +9,14 @@
 // except according to those terms.
 
 use {ast, attr};
-use syntax_pos::{Span, DUMMY_SP};
+use syntax_pos::{MultiSpan, Span, DUMMY_SP};
 use ext::base::{DummyResult, ExtCtxt, MacResult, SyntaxExtension};
 use ext::base::{NormalTT, TTMacroExpander};
 use ext::expand::{Expansion, ExpansionKind};
+use ext::hygiene::SyntaxContext;
 use ext::tt::macro_parser::{Success, Error, Failure};
 use ext::tt::macro_parser::{MatchedSeq, MatchedNonterminal};
-use ext::tt::macro_parser::{parse, parse_failure_msg};
+use ext::tt::macro_parser::{parse, parse_traced, parse_failure_msg, Expectation};
 use ext::tt::quoted;
 use ext::tt::transcribe::transcribe;
 use feature_gate::{self, emit_feature_err, Features, GateIssue};
@@ -23,6 +24,7 @@ use parse::{Directory, ParseSess};
 use parse::parser::Parser;
 use parse::token::{self, NtTT};
 use parse::token::Token::*;
+use print::pprust;
 use symbol::Symbol;
 use tokenstream::{TokenStream, TokenTree};
 
@@ -91,6 +93,116 @@ fn trace_macros_note(cx: &mut ExtCtxt, sp: Span, message: String) {
     values.push(message);
 }
 
+/// How matching one arm of a `macro_rules!` macro went, for `-Z trace-macro-matchers`.
+struct ArmTrace {
+    /// The span of the arm's matcher.
+    lhs: Span,
+    /// Why the arm didn't match, or `None` if it did.
+    failure: Option<ArmFailure>,
+}
+
+enum ArmFailure {
+    /// None of the matcher positions that were still alive accepted the token at `span`.
+    /// `expected` lists what they were waiting for instead, with spans in the matcher.
+    Mismatch { span: Span, found: token::Token, expected: Vec<Expectation> },
+    /// The matcher was malformed or ambiguous.
+    Error { span: Span, msg: String },
+}
+
+/// The span of the invocation tokens that a token tree of an expansion was substituted from, or
+/// the token tree's own span if it was written in the macro body.
+fn transcribed_from(tt: &TokenTree) -> Span {
+    if let TokenTree::Token(sp, Interpolated(ref nt)) = *tt {
+        return match nt.0 {
+            token::NtItem(ref item) => item.span,
+            token::NtBlock(ref block) => block.span,
+            token::NtStmt(ref stmt) => stmt.span,
+            token::NtPat(ref pat) => pat.span,
+            token::NtExpr(ref expr) | token::NtLiteral(ref expr) => expr.span,
+            token::NtTy(ref ty) => ty.span,
+            token::NtIdent(ref ident) => ident.span,
+            token::NtMeta(ref meta) => meta.span,
+            token::NtPath(ref path) => path.span,
+            token::NtVis(ref vis) => vis.span,
+            token::NtTT(ref tt) => tt.span(),
+            token::NtLifetime(ref lifetime) => lifetime.span,
+            _ => sp,
+        };
+    }
+    tt.span()
+}
+
+/// Joins descriptions of tokens as "`a`, `b` or `c`".
+fn describe_alternatives(descriptions: &[&str]) -> String {
+    match descriptions.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        Some((last, _)) => last.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Reports how far each arm got matching the invocation at `sp` and why it stopped. If an arm
+/// matched, `transcription` gives the span of its transcriber, what it was transcribed to and,
+/// for each transcribed token tree, where it came from.
+fn trace_matchers_diag(cx: &ExtCtxt,
+                       sp: Span,
+                       name: ast::Ident,
+                       arms: &[ArmTrace],
+                       transcription: Option<(Span, &TokenStream, &[Span])>) {
+    // Only point at locations, without any "in this macro invocation" labels.
+    let location = |span: Span| span.substitute_dummy(sp).with_ctxt(SyntaxContext::empty());
+
+    let msg = format!("matching an invocation of `{}!`", name);
+    let mut db = cx.parse_sess.span_diagnostic.span_note_diag(location(sp), &msg);
+    for (i, arm) in arms.iter().enumerate() {
+        let arm_no = i + 1;
+        match arm.failure {
+            None => {
+                db.span_note(location(arm.lhs), &format!("arm #{} matched", arm_no));
+            }
+            Some(ArmFailure::Mismatch { span, ref found, ref expected }) => {
+                let found = match *found {
+                    Eof => "the end of the macro invocation".to_string(),
+                    ref tok => format!("`{}`", pprust::token_to_string(tok)),
+                };
+                let mut descriptions = Vec::new();
+                let mut matcher_spans = Vec::new();
+                for expectation in expected {
+                    if !descriptions.contains(&&*expectation.description) {
+                        descriptions.push(&*expectation.description);
+                    }
+                    let matcher_span = location(expectation.span);
+                    if !matcher_spans.contains(&matcher_span) {
+                        matcher_spans.push(matcher_span);
+                    }
+                }
+                if descriptions.is_empty() {
+                    db.span_note(location(span),
+                                 &format!("arm #{} did not match: found {}", arm_no, found));
+                } else {
+                    db.span_note(location(span),
+                                 &format!("arm #{} did not match: expected {}, found {}",
+                                          arm_no, describe_alternatives(&descriptions), found));
+                    db.span_note(MultiSpan::from_spans(matcher_spans),
+                                 &format!("arm #{} stopped at this point of its matcher", arm_no));
+                }
+            }
+            Some(ArmFailure::Error { span, ref msg }) => {
+                db.span_note(location(span), &format!("arm #{} failed: {}", arm_no, msg));
+            }
+        }
+    }
+    if let Some((rhs_sp, tts, sources)) = transcription {
+        db.span_note(location(rhs_sp),
+                     &format!("arm #{} transcribed to `{}`", arms.len(), tts));
+        for (tt, &source) in tts.trees().zip(sources) {
+            db.span_note(location(source),
+                         &format!("`{}` was transcribed from here", pprust::tt_to_string(tt)));
+        }
+    }
+    db.emit();
+}
+
 /// Given `lhses` and `rhses`, this is the new macro we create
 fn generic_extension<'cx>(cx: &'cx mut ExtCtxt,
                           sp: Span,
@@ -107,13 +219,28 @@ fn generic_extension<'cx>(cx: &'cx mut ExtCtxt,
     let mut best_fail_spot = DUMMY_SP;
     let mut best_fail_tok = None;
 
+    let trace_matchers = cx.ecfg.trace_matchers;
+    let mut arm_traces = Vec::new();
+
     for (i, lhs) in lhses.iter().enumerate() { // try each arm's matchers
         let lhs_tt = match *lhs {
             quoted::TokenTree::Delimited(_, ref delim) => &delim.tts[..],
             _ => cx.span_bug(sp, "malformed macro lhs")
         };
 
-        match TokenTree::parse(cx, lhs_tt, arg.clone()) {
+        let mut expected = Vec::new();
+        let result = if trace_matchers {
+            let directory = Directory {
+                path: cx.current_expansion.module.directory.clone(),
+                ownership: cx.current_expansion.directory_ownership,
+            };
+            parse_traced(cx.parse_sess(), arg.clone(), lhs_tt, Some(directory), true,
+                         Some(&mut expected))
+        } else {
+            TokenTree::parse(cx, lhs_tt, arg.clone())
+        };
+
+        match result {
             Success(named_matches) => {
                 let rhs = match rhses[i] {
                     // ignore delimiters
@@ -125,6 +252,14 @@ fn generic_extension<'cx>(cx: &'cx mut ExtCtxt,
                 // rhs has holes ( `$id` and `$(...)` that need filled)
                 let mut tts = transcribe(cx, Some(named_matches), rhs);
 
+                // The spans are about to be replaced, so find out where each token tree was
+                // transcribed from first.
+                let sources = if trace_matchers {
+                    tts.trees().map(|tt| transcribed_from(&tt)).collect()
+                } else {
+                    Vec::new()
+                };
+
                 // Replace all the tokens for the corresponding positions in the macro, to maintain
                 // proper positions in error reporting, while maintaining the macro_backtrace.
                 if rhs_spans.len() == tts.len() {
@@ -141,6 +276,12 @@ fn generic_extension<'cx>(cx: &'cx mut ExtCtxt,
                     trace_macros_note(cx, sp, format!("to `{}`", tts));
                 }
 
+                if trace_matchers {
+                    arm_traces.push(ArmTrace { lhs: lhs.span(), failure: None });
+                    let transcription = (rhses[i].span(), &tts, &sources[..]);
+                    trace_matchers_diag(cx, sp, name, &arm_traces, Some(transcription));
+                }
+
                 let directory = Directory {
                     path: cx.current_expansion.module.directory.clone(),
                     ownership: cx.current_expansion.directory_ownership,
@@ -162,16 +303,45 @@ fn generic_extension<'cx>(cx: &'cx mut ExtCtxt,
                     macro_ident: name
                 })
             }
-            Failure(sp, tok) => if sp.lo() >= best_fail_spot.lo() {
-                best_fail_spot = sp;
-                best_fail_tok = Some(tok);
-            },
+            Failure(fail_sp, tok) => {
+                if trace_matchers {
+                    // An expected end of the invocation is shown at the end of the matcher.
+                    let end = match *lhs {
+                        quoted::TokenTree::Delimited(span, ref delim) => {
+                            delim.close_tt(span).span()
+                        }
+                        _ => lhs.span(),
+                    };
+                    for expectation in &mut expected {
+                        expectation.span = expectation.span.substitute_dummy(end);
+                    }
+                    let failure = ArmFailure::Mismatch {
+                        span: fail_sp,
+                        found: tok.clone(),
+                        expected,
+                    };
+                    arm_traces.push(ArmTrace { lhs: lhs.span(), failure: Some(failure) });
+                }
+                if fail_sp.lo() >= best_fail_spot.lo() {
+                    best_fail_spot = fail_sp;
+                    best_fail_tok = Some(tok);
+                }
+            }
             Error(err_sp, ref msg) => {
+                if trace_matchers {
+                    let failure = ArmFailure::Error { span: err_sp, msg: msg.clone() };
+                    arm_traces.push(ArmTrace { lhs: lhs.span(), failure: Some(failure) });
+                    trace_matchers_diag(cx, sp, name, &arm_traces, None);
+                }
                 cx.span_fatal(err_sp.substitute_dummy(sp), &msg[..])
             }
         }
     }
 
+    if trace_matchers {
+        trace_matchers_diag(cx, sp, name, &arm_traces, None);
+    }
+
     let best_fail_msg = parse_failure_msg(best_fail_tok.expect("ran no matchers"));
     cx.span_err(best_fail_spot.substitute_dummy(sp), &best_fail_msg);
     cx.trace_macros_diag();
@@ -318,7 +488,10 @@ fn check_lhs_no_empty_seq(sess: &ParseSess, tts: &[quoted::TokenTree]) -> bool {
     use self::quoted::TokenTree;
     for tt in tts {
         match *tt {
-            TokenTree::Token(..) | TokenTree::MetaVar(..) | TokenTree::MetaVarDecl(..) => (),
+            TokenTree::Token(..)
+            | TokenTree::MetaVar(..)
+            | TokenTree::MetaVarDecl(..)
+            | TokenTree::MetaVarExpr(..) => (),
             TokenTree::Delimited(_, ref del) => if !check_lhs_no_empty_seq(sess, &del.tts) {
                 return false;
             },
@@ -400,7 +573,10 @@ impl FirstSets {
             let mut first = TokenSet::empty();
             for tt in tts.iter().rev() {
                 match *tt {
-                    TokenTree::Token(..) | TokenTree::MetaVar(..) | TokenTree::MetaVarDecl(..) => {
+                    TokenTree::Token(..)
+                    | TokenTree::MetaVar(..)
+                    | TokenTree::MetaVarDecl(..)
+                    | TokenTree::MetaVarExpr(..) => {
                         first.replace_with(tt.clone());
                     }
                     TokenTree::Delimited(span, ref delimited) => {
@@ -460,7 +636,10 @@ impl FirstSets {
         for tt in tts.iter() {
             assert!(first.maybe_empty);
             match *tt {
-                TokenTree::Token(..) | TokenTree::MetaVar(..) | TokenTree::MetaVarDecl(..) => {
+                TokenTree::Token(..)
+                | TokenTree::MetaVar(..)
+                | TokenTree::MetaVarDecl(..)
+                | TokenTree::MetaVarExpr(..) => {
                     first.add_one(tt.clone());
                     return first;
                 }
@@ -631,7 +810,10 @@ fn check_matcher_core(sess: &ParseSess,
         // First, update `last` so that it corresponds to the set
         // of NT tokens that might end the sequence `... token`.
         match *token {
-            TokenTree::Token(..) | TokenTree::MetaVar(..) | TokenTree::MetaVarDecl(..) => {
+            TokenTree::Token(..)
+            | TokenTree::MetaVar(..)
+            | TokenTree::MetaVarDecl(..)
+            | TokenTree::MetaVarExpr(..) => {
                 let can_be_followed_by_any;
                 if let Err(bad_frag) = has_legal_fragment_specifier(sess, features, attrs, token) {
                     let msg = format!("invalid fragment specifier `{}`", bad_frag);
@@ -774,6 +956,7 @@ fn frag_can_be_followed_by_any(frag: &str) -> bool {
         "item"     | // always terminated by `}` or `;`
         "block"    | // exactly one token tree
         "ident"    | // exactly one token tree
+        "literal"  | // exactly one token tree, or a `-` and one
         "meta"     | // exactly one token tree
         "lifetime" | // exactly one token tree
         "tt" =>   // exactly one token tree
@@ -836,8 +1019,9 @@ fn is_in_follow(tok: &quoted::TokenTree, frag: &str) -> Result<bool, (String, &'
                 TokenTree::MetaVarDecl(_, _, frag) if frag.name == "block" => Ok(true),
                 _ => Ok(false),
             },
-            "ident" | "lifetime" => {
-                // being a single token, idents and lifetimes are harmless
+            "ident" | "lifetime" | "literal" => {
+                // being a single token (plus an optional `-` for literals), idents, lifetimes
+                // and literals are harmless
                 Ok(true)
             },
             "meta" | "tt" => {
@@ -915,6 +1099,18 @@ fn is_legal_fragment_specifier(sess: &ParseSess,
             }
             true
         },
+        "literal" => {
+            if !features.borrow().macro_literal_matcher &&
+               !attr::contains_name(attrs, "allow_internal_unstable") {
+                let explain = feature_gate::EXPLAIN_LITERAL_MATCHER;
+                emit_feature_err(sess,
+                                 "macro_literal_matcher",
+                                 frag_span,
+                                 GateIssue::Language,
+                                 explain);
+            }
+            true
+        },
         _ => false,
     }
 }
@@ -84,6 +84,17 @@ pub enum KleeneOp {
     ZeroOrOne,
 }
 
+/// A metavariable expression such as `${count(x)}`, expanded to an integer literal during
+/// transcription.
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug, Copy)]
+pub enum MetaVarExpr {
+    /// `${count(x)}`: the number of times `$x` matched inside the current repetition
+    Count(ast::Ident),
+    /// `${index()}` or `${index(depth)}`: the index of the current iteration of the repetition
+    /// `depth` levels out from the innermost one
+    Index(usize),
+}
+
 /// Similar to `tokenstream::TokenTree`, except that `$i`, `$i:ident`, and `$(...)`
 /// are "first-class" token trees. Useful for parsing macros.
 #[derive(Debug, Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash)]
@@ -100,6 +111,8 @@ pub enum TokenTree {
         ast::Ident, /* name to bind */
         ast::Ident, /* kind of nonterminal */
     ),
+    /// E.g. `${count(var)}`. This is only used in the right hand side of MBE macros.
+    MetaVarExpr(Span, MetaVarExpr),
 }
 
 impl TokenTree {
@@ -154,6 +167,7 @@ impl TokenTree {
             TokenTree::Token(sp, _)
             | TokenTree::MetaVar(sp, _)
             | TokenTree::MetaVarDecl(sp, _, _)
+            | TokenTree::MetaVarExpr(sp, _)
             | TokenTree::Delimited(sp, _)
             | TokenTree::Sequence(sp, _) => sp,
         }
@@ -261,6 +275,26 @@ where
     match tree {
         // `tree` is a `$` token. Look at the next token in `trees`
         tokenstream::TokenTree::Token(span, token::Dollar) => match trees.next() {
+            // `tree` is followed by a brace-delimited group in the body of a macro. This is a
+            // metavariable expression (e.g. `${count(x)}`).
+            Some(tokenstream::TokenTree::Delimited(span, ref delimited))
+                if delimited.delim == token::Brace && !expect_matchers =>
+            {
+                if !features.borrow().macro_metavar_expr
+                    && !attr::contains_name(attrs, "allow_internal_unstable")
+                {
+                    let explain = feature_gate::EXPLAIN_MACRO_METAVAR_EXPR;
+                    emit_feature_err(
+                        sess,
+                        "macro_metavar_expr",
+                        span,
+                        GateIssue::Language,
+                        explain,
+                    );
+                }
+                parse_metavar_expr(span, delimited.stream(), sess)
+            }
+
             // `tree` is followed by a delimited set of token trees. This indicates the beginning
             // of a repetition sequence in the macro (e.g. `$(pat)*`).
             Some(tokenstream::TokenTree::Delimited(span, delimited)) => {
@@ -332,6 +366,44 @@ where
     }
 }
 
+/// Parses the contents of `${...}` into a `TokenTree::MetaVarExpr`. The accepted forms are
+/// `count(ident)`, `index()` and `index(depth)`, where `depth` is an unsuffixed integer.
+fn parse_metavar_expr(span: Span, input: tokenstream::TokenStream, sess: &ParseSess) -> TokenTree {
+    let mut trees = input.trees();
+    let expr = match (trees.next(), trees.next(), trees.next()) {
+        (
+            Some(tokenstream::TokenTree::Token(_, token::Ident(func))),
+            Some(tokenstream::TokenTree::Delimited(_, ref args)),
+            None,
+        ) if args.delim == token::Paren => {
+            let args: Vec<_> = args.stream().trees().collect();
+            let arg = match (args.len(), args.first()) {
+                (0, _) => Ok(None),
+                (1, Some(&tokenstream::TokenTree::Token(_, ref tok))) => Ok(Some(tok.clone())),
+                _ => Err(()),
+            };
+            match (&*func.name.as_str(), arg) {
+                ("count", Ok(Some(token::Ident(var)))) => Some(MetaVarExpr::Count(var)),
+                ("index", Ok(None)) => Some(MetaVarExpr::Index(0)),
+                ("index", Ok(Some(token::Literal(token::Integer(depth), None)))) => {
+                    depth.as_str().parse().ok().map(MetaVarExpr::Index)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
+    match expr {
+        Some(expr) => TokenTree::MetaVarExpr(span, expr),
+        None => {
+            let msg = "expected one of `count(ident)`, `index()` or `index(depth)`";
+            sess.span_diagnostic.span_err(span, msg);
+            TokenTree::MetaVar(span, keywords::Invalid.ident())
+        }
+    }
+}
+
 /// Takes a token and returns `Some(KleeneOp)` if the token is `+` `*` or `?`. Otherwise, return
 /// `None`.
 fn kleene_op(token: &token::Token) -> Option<KleeneOp> {
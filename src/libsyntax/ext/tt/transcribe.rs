@@ -15,6 +15,7 @@ use ext::tt::macro_parser::{NamedMatch, MatchedSeq, MatchedNonterminal};
 use ext::tt::quoted;
 use fold::noop_fold_tt;
 use parse::token::{self, Token, NtTT};
+use symbol::Symbol;
 use syntax_pos::{Span, DUMMY_SP};
 use tokenstream::{TokenStream, TokenTree, Delimited};
 use util::small_vector::SmallVector;
@@ -63,8 +64,8 @@ impl Iterator for Frame {
     }
 }
 
-/// This can do Macro-By-Example transcription. On the other hand, if
-/// `src` contains no `TokenTree::{Sequence, MetaVar, MetaVarDecl}`s, `interp` can
+/// This can do Macro-By-Example transcription. On the other hand, if `src` contains no
+/// `TokenTree::{Sequence, MetaVar, MetaVarDecl, MetaVarExpr}`s, `interp` can
 /// (and should) be None.
 pub fn transcribe(cx: &ExtCtxt,
                   interp: Option<HashMap<Ident, Rc<NamedMatch>>>,
@@ -172,6 +173,24 @@ pub fn transcribe(cx: &ExtCtxt,
                     result.push(TokenTree::Token(sp, token::Ident(ident)).into());
                 }
             }
+            quoted::TokenTree::MetaVarExpr(mut sp, expr) => {
+                let value = match expr {
+                    quoted::MetaVarExpr::Count(ident) => {
+                        count_matches(cx, sp, ident, &interpolations, &repeats)
+                    }
+                    quoted::MetaVarExpr::Index(depth) => {
+                        match repeats.len().checked_sub(depth + 1) {
+                            Some(i) => repeats[i].0,
+                            None => cx.span_fatal(sp, /* blame the macro writer */
+                                &format!("`index({})` used outside of {} nested repetition(s)",
+                                         depth, depth + 1)),
+                        }
+                    }
+                };
+                sp = sp.with_ctxt(sp.ctxt().apply_mark(cx.current_expansion.mark));
+                let lit = token::Literal(token::Integer(Symbol::intern(&value.to_string())), None);
+                result.push(TokenTree::Token(sp, lit).into());
+            }
             quoted::TokenTree::Delimited(mut span, delimited) => {
                 span = span.with_ctxt(span.ctxt().apply_mark(cx.current_expansion.mark));
                 stack.push(Frame::Delimited { forest: delimited, idx: 0, span: span });
@@ -204,6 +223,31 @@ fn lookup_cur_matched(ident: Ident,
     })
 }
 
+/// Count how many times `ident` matched inside the repetition currently being transcribed,
+/// including the matches from any repetitions nested within it.
+fn count_matches(cx: &ExtCtxt,
+                 sp: Span,
+                 ident: Ident,
+                 interpolations: &HashMap<Ident, Rc<NamedMatch>>,
+                 repeats: &[(usize, usize)])
+                 -> usize {
+    fn count(matched: &NamedMatch) -> usize {
+        match *matched {
+            MatchedNonterminal(_) => 1,
+            MatchedSeq(ref ads, _) => ads.iter().map(count).sum(),
+        }
+    }
+
+    match lookup_cur_matched(ident, interpolations, repeats) {
+        Some(matched) => match *matched {
+            MatchedSeq(..) => count(&matched),
+            MatchedNonterminal(_) => cx.span_fatal(sp, /* blame the macro writer */
+                &format!("variable '{}' is not repeating at this depth", ident)),
+        },
+        None => cx.span_fatal(sp, &format!("unknown macro variable `{}`", ident)),
+    }
+}
+
 #[derive(Clone)]
 enum LockstepIterSize {
     Unconstrained,
@@ -257,6 +301,6 @@ fn lockstep_iter_size(tree: &quoted::TokenTree,
                 },
                 _ => LockstepIterSize::Unconstrained
             },
-        TokenTree::Token(..) => LockstepIterSize::Unconstrained,
+        TokenTree::Token(..) | TokenTree::MetaVarExpr(..) => LockstepIterSize::Unconstrained,
     }
 }
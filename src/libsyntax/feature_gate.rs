@@ -449,6 +449,19 @@ declare_features! (
 
     // Parentheses in patterns
     (active, pattern_parentheses, "1.26.0", None),
+
+    // Implicit capture of identifiers named by `format_args!` placeholders
+    (active, format_args_capture, "1.26.0", None),
+
+    // `format_args_nl!`, used by `println!` and friends
+    // rustc internal
+    (active, format_args_nl, "1.26.0", None),
+
+    // The `literal` fragment specifier in `macro_rules!`
+    (active, macro_literal_matcher, "1.26.0", None),
+
+    // `${count(x)}` and `${index()}` metavariable expressions in `macro_rules!` bodies
+    (active, macro_metavar_expr, "1.26.0", None),
 );
 
 declare_features! (
@@ -1227,6 +1240,9 @@ pub const EXPLAIN_LOG_SYNTAX: &'static str =
 pub const EXPLAIN_CONCAT_IDENTS: &'static str =
     "`concat_idents` is not stable enough for use and is subject to change";
 
+pub const EXPLAIN_FORMAT_ARGS_NL: &'static str =
+    "`format_args_nl` is only for internal language use and is subject to change";
+
 pub const EXPLAIN_TRACE_MACROS: &'static str =
     "`trace_macros` is not stable enough for use and is subject to change";
 pub const EXPLAIN_ALLOW_INTERNAL_UNSTABLE: &'static str =
@@ -1250,6 +1266,9 @@ pub const EXPLAIN_VIS_MATCHER: &'static str =
 pub const EXPLAIN_LIFETIME_MATCHER: &'static str =
     ":lifetime fragment specifier is experimental and subject to change";
 
+pub const EXPLAIN_LITERAL_MATCHER: &'static str =
+    ":literal fragment specifier is experimental and subject to change";
+
 pub const EXPLAIN_PLACEMENT_IN: &'static str =
     "placement-in expression syntax is experimental and subject to change.";
 
@@ -1259,6 +1278,9 @@ pub const EXPLAIN_UNSIZED_TUPLE_COERCION: &'static str =
 pub const EXPLAIN_MACRO_AT_MOST_ONCE_REP: &'static str =
     "Using the `?` macro Kleene operator for \"at most one\" repetition is unstable";
 
+pub const EXPLAIN_MACRO_METAVAR_EXPR: &'static str =
+    "macro metavariable expressions such as `${count(x)}` are unstable";
+
 struct PostExpansionVisitor<'a> {
     context: &'a Context<'a>,
 }
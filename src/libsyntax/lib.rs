@@ -138,6 +138,7 @@ pub mod ext {
     pub mod derive;
     pub mod expand;
     pub mod placeholders;
+    pub mod proc_macro_cache;
     pub mod quote;
     pub mod source_util;
 
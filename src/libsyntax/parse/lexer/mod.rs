@@ -1741,6 +1741,7 @@ mod tests {
     use syntax_pos::{BytePos, Span, NO_EXPANSION};
     use codemap::CodeMap;
     use errors;
+    use ext::proc_macro_cache::ProcMacroCache;
     use feature_gate::UnstableFeatures;
     use parse::token;
     use std::cell::RefCell;
@@ -1760,6 +1761,7 @@ mod tests {
             code_map: cm,
             missing_fragment_specifiers: RefCell::new(HashSet::new()),
             non_modrs_mods: RefCell::new(vec![]),
+            proc_macro_cache: RefCell::new(ProcMacroCache::new()),
         }
     }
 
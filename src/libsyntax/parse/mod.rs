@@ -13,6 +13,7 @@
 use rustc_data_structures::sync::Lrc;
 use ast::{self, CrateConfig};
 use codemap::{CodeMap, FilePathMapping};
+use ext::proc_macro_cache::ProcMacroCache;
 use syntax_pos::{self, Span, FileMap, NO_EXPANSION, FileName};
 use errors::{Handler, ColorConfig, DiagnosticBuilder};
 use feature_gate::UnstableFeatures;
@@ -53,6 +54,8 @@ pub struct ParseSess {
     /// Used to determine and report recursive mod inclusions
     included_mod_stack: RefCell<Vec<PathBuf>>,
     code_map: Lrc<CodeMap>,
+    /// Recorded expansions of pure procedural macros
+    pub proc_macro_cache: RefCell<ProcMacroCache>,
 }
 
 impl ParseSess {
@@ -74,6 +77,7 @@ impl ParseSess {
             included_mod_stack: RefCell::new(vec![]),
             code_map,
             non_modrs_mods: RefCell::new(vec![]),
+            proc_macro_cache: RefCell::new(ProcMacroCache::new()),
         }
     }
 
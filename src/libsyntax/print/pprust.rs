@@ -263,6 +263,7 @@ pub fn token_to_string(tok: &Token) -> String {
 
         token::Interpolated(ref nt) => match nt.0 {
             token::NtExpr(ref e)        => expr_to_string(e),
+            token::NtLiteral(ref e)     => expr_to_string(e),
             token::NtMeta(ref e)        => meta_item_to_string(e),
             token::NtTy(ref e)          => ty_to_string(e),
             token::NtPath(ref e)        => path_to_string(e),
@@ -17,10 +17,12 @@ use syntax::ast;
 use syntax::ext::base::*;
 use syntax::ext::base;
 use syntax::ext::build::AstBuilder;
+use syntax::feature_gate;
 use syntax::parse::token;
 use syntax::ptr::P;
 use syntax::symbol::Symbol;
-use syntax_pos::{Span, DUMMY_SP};
+use syntax::util::lev_distance::find_best_match_for_name;
+use syntax_pos::{Span, SyntaxContext, DUMMY_SP};
 use syntax::tokenstream;
 
 use std::collections::{HashMap, HashSet};
@@ -64,6 +66,13 @@ struct Context<'a, 'b: 'a> {
     arg_unique_types: Vec<Vec<ArgumentType>>,
     /// Map from named arguments to their resolved indices.
     names: HashMap<String, usize>,
+    /// Number of named arguments which were not given explicitly, but
+    /// captured from the surrounding scope. These come last in `args`.
+    num_captured_args: usize,
+    /// The syntax context of the format string, if it was written as a
+    /// literal at the call site. Captured identifiers are resolved in this
+    /// context, and nothing is captured if it is `None`.
+    capture_ctxt: Option<SyntaxContext>,
 
     /// The latest consecutive literal strings, or empty if there weren't any.
     literal: String,
@@ -252,7 +261,7 @@ impl<'a, 'b> Context<'a, 'b> {
     }
 
     fn describe_num_args(&self) -> String {
-        match self.args.len() {
+        match self.args.len() - self.num_captured_args {
             0 => "no arguments were given".to_string(),
             1 => "there is 1 argument".to_string(),
             x => format!("there are {} arguments", x),
@@ -299,53 +308,99 @@ impl<'a, 'b> Context<'a, 'b> {
     fn verify_arg_type(&mut self, arg: Position, ty: ArgumentType) {
         match arg {
             Exact(arg) => {
-                if self.args.len() <= arg {
+                // Captured arguments can only be referred to by name.
+                if self.args.len() - self.num_captured_args <= arg {
                     self.invalid_refs.push(arg);
                     return;
                 }
-                match ty {
-                    Placeholder(_) => {
-                        // record every (position, type) combination only once
-                        let ref mut seen_ty = self.arg_unique_types[arg];
-                        let i = match seen_ty.iter().position(|x| *x == ty) {
-                            Some(i) => i,
-                            None => {
-                                let i = seen_ty.len();
-                                seen_ty.push(ty);
-                                i
-                            }
-                        };
-                        self.arg_types[arg].push(i);
-                    }
-                    Count => {
-                        match self.count_positions.entry(arg) {
-                            Entry::Vacant(e) => {
-                                let i = self.count_positions_count;
-                                e.insert(i);
-                                self.count_args.push(Exact(arg));
-                                self.count_positions_count += 1;
-                            }
-                            Entry::Occupied(_) => {}
-                        }
-                    }
-                }
+                self.track_arg_type(arg, ty)
             }
 
             Named(name) => {
                 let idx = match self.names.get(&name) {
                     Some(e) => *e,
+                    None => match self.capture_arg(&name) {
+                        Some(idx) => idx,
+                        None => return,
+                    },
+                };
+                self.track_arg_type(idx, ty)
+            }
+        }
+    }
+
+    /// Tracks a placeholder of type `ty` for the argument at index `arg`,
+    /// which is known to exist.
+    fn track_arg_type(&mut self, arg: usize, ty: ArgumentType) {
+        match ty {
+            Placeholder(_) => {
+                // record every (position, type) combination only once
+                let ref mut seen_ty = self.arg_unique_types[arg];
+                let i = match seen_ty.iter().position(|x| *x == ty) {
+                    Some(i) => i,
                     None => {
-                        let msg = format!("there is no argument named `{}`", name);
-                        self.ecx.span_err(self.fmtsp, &msg[..]);
-                        return;
+                        let i = seen_ty.len();
+                        seen_ty.push(ty);
+                        i
                     }
                 };
-                // Treat as positional arg.
-                self.verify_arg_type(Exact(idx), ty)
+                self.arg_types[arg].push(i);
+            }
+            Count => {
+                match self.count_positions.entry(arg) {
+                    Entry::Vacant(e) => {
+                        let i = self.count_positions_count;
+                        e.insert(i);
+                        self.count_args.push(Exact(arg));
+                        self.count_positions_count += 1;
+                    }
+                    Entry::Occupied(_) => {}
+                }
             }
         }
     }
 
+    /// Appends an argument which refers to the identifier `name` in the
+    /// scope of the format string, as if `name = name` had been written.
+    ///
+    /// Reports an error and returns `None` if that is not possible.
+    fn capture_arg(&mut self, name: &str) -> Option<usize> {
+        let ctxt = match self.capture_ctxt {
+            Some(ctxt) if self.ecx.ecfg.enable_format_args_capture() => ctxt,
+            capture_ctxt => {
+                let msg = format!("there is no argument named `{}`", name);
+                let mut err = self.ecx.struct_span_err(self.fmtsp, &msg);
+
+                let names = self.names.keys().map(|n| Symbol::intern(n)).collect::<Vec<_>>();
+                if let Some(similar) = find_best_match_for_name(names.iter(), name, None) {
+                    err.help(&format!("a named argument with a similar name exists: `{}`",
+                                      similar));
+                }
+                if capture_ctxt.is_none() {
+                    err.note("identifiers can't be captured when the format string is \
+                              expanded from a macro");
+                } else {
+                    err.help(&format!("to capture `{}` from the surrounding scope, add \
+                                       #![feature(format_args_capture)] to the crate \
+                                       attributes", name));
+                }
+                err.emit();
+                return None;
+            }
+        };
+
+        // Name resolution reports unknown identifiers, with suggestions for
+        // similarly named bindings, so they can be assumed to exist here.
+        let ident = ast::Ident { name: Symbol::intern(name), ctxt };
+        let idx = self.args.len();
+        self.args.push(self.ecx.expr_ident(self.fmtsp, ident));
+        self.arg_types.push(Vec::new());
+        self.arg_unique_types.push(Vec::new());
+        self.names.insert(name.to_string(), idx);
+        self.num_captured_args += 1;
+        Some(idx)
+    }
+
     /// Builds the mapping between format placeholders and argument objects.
     fn build_index_map(&mut self) {
         // NOTE: Keep the ordering the same as `into_expr`'s expansion would do!
@@ -681,7 +736,32 @@ pub fn expand_format_args<'cx>(ecx: &'cx mut ExtCtxt,
     sp = sp.with_ctxt(sp.ctxt().apply_mark(ecx.current_expansion.mark));
     match parse_args(ecx, sp, tts) {
         Some((efmt, args, names)) => {
-            MacEager::expr(expand_preparsed_format_args(ecx, sp, efmt, args, names))
+            MacEager::expr(expand_preparsed_format_args(ecx, sp, efmt, args, names, false))
+        }
+        None => DummyResult::expr(sp),
+    }
+}
+
+/// `format_args_nl!(efmt, args...)`, which is `format_args!` with a newline
+/// appended to the format string. The printing macros use it rather than
+/// `concat!`, which would hide the format string literal from
+/// `format_args!`.
+pub fn expand_format_args_nl<'cx>(ecx: &'cx mut ExtCtxt,
+                                  mut sp: Span,
+                                  tts: &[tokenstream::TokenTree])
+                                  -> Box<base::MacResult + 'cx> {
+    if !sp.allows_unstable() && !ecx.ecfg.enable_format_args_nl() {
+        feature_gate::emit_feature_err(&ecx.parse_sess,
+                                       "format_args_nl",
+                                       sp,
+                                       feature_gate::GateIssue::Language,
+                                       feature_gate::EXPLAIN_FORMAT_ARGS_NL);
+        return DummyResult::expr(sp);
+    }
+    sp = sp.with_ctxt(sp.ctxt().apply_mark(ecx.current_expansion.mark));
+    match parse_args(ecx, sp, tts) {
+        Some((efmt, args, names)) => {
+            MacEager::expr(expand_preparsed_format_args(ecx, sp, efmt, args, names, true))
         }
         None => DummyResult::expr(sp),
     }
@@ -689,11 +769,15 @@ pub fn expand_format_args<'cx>(ecx: &'cx mut ExtCtxt,
 
 /// Take the various parts of `format_args!(efmt, args..., name=names...)`
 /// and construct the appropriate formatting expression.
+///
+/// If `append_newline` is set, the format string is treated as if it ended
+/// with `\n`.
 pub fn expand_preparsed_format_args(ecx: &mut ExtCtxt,
                                     sp: Span,
                                     efmt: P<ast::Expr>,
                                     args: Vec<P<ast::Expr>>,
-                                    names: HashMap<String, usize>)
+                                    names: HashMap<String, usize>,
+                                    append_newline: bool)
                                     -> P<ast::Expr> {
     // NOTE: this verbose way of initializing `Vec<Vec<ArgumentType>>` is because
     // `ArgumentType` does not derive `Clone`.
@@ -701,11 +785,21 @@ pub fn expand_preparsed_format_args(ecx: &mut ExtCtxt,
     let arg_unique_types: Vec<_> = (0..args.len()).map(|_| Vec::new()).collect();
     let mut macsp = ecx.call_site();
     macsp = macsp.with_ctxt(macsp.ctxt().apply_mark(ecx.current_expansion.mark));
+    // Only a literal written at the call site determines which scope
+    // identifiers would be captured from; one produced by `concat!` or
+    // another macro does not.
+    let capture_ctxt = match efmt.node {
+        ast::ExprKind::Lit(_) => Some(efmt.span.ctxt()),
+        _ => None,
+    };
     let msg = "format argument must be a string literal.";
-    let fmt = match expr_to_spanned_string(ecx, efmt, msg) {
+    let mut fmt = match expr_to_spanned_string(ecx, efmt, msg) {
         Some(fmt) => fmt,
         None => return DummyResult::raw_expr(sp),
     };
+    if append_newline {
+        fmt.node.0 = Symbol::intern(&format!("{}\n", fmt.node.0));
+    }
 
     let mut cx = Context {
         ecx,
@@ -713,6 +807,8 @@ pub fn expand_preparsed_format_args(ecx: &mut ExtCtxt,
         arg_types,
         arg_unique_types,
         names,
+        num_captured_args: 0,
+        capture_ctxt,
         curarg: 0,
         arg_index_map: Vec::new(),
         count_args: Vec::new(),
@@ -121,6 +121,13 @@ pub fn register_builtins(resolver: &mut syntax::ext::base::Resolver,
                 allow_internal_unstable: true,
                 allow_internal_unsafe: false,
             });
+    register(Symbol::intern("format_args_nl"),
+             NormalTT {
+                expander: Box::new(format::expand_format_args_nl),
+                def_info: None,
+                allow_internal_unstable: true,
+                allow_internal_unsafe: false,
+            });
 
     for (name, ext) in user_exts {
         register(name, ext);
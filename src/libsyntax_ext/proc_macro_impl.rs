@@ -14,6 +14,8 @@ use errors::FatalError;
 
 use syntax::codemap::Span;
 use syntax::ext::base::*;
+use syntax::ext::proc_macro_cache;
+use syntax::symbol::Symbol;
 use syntax::tokenstream::TokenStream;
 use syntax::ext::base;
 
@@ -22,6 +24,9 @@ use proc_macro::__internal;
 
 pub struct AttrProcMacro {
     pub inner: fn(TsShim, TsShim) -> TsShim,
+    /// The SVH of the defining crate and the macro's name, if the macro was
+    /// declared pure and its expansions may be reused.
+    pub pure: Option<(u64, Symbol)>,
 }
 
 impl base::AttrProcMacro for AttrProcMacro {
@@ -31,6 +36,19 @@ impl base::AttrProcMacro for AttrProcMacro {
                    annotation: TokenStream,
                    annotated: TokenStream)
                    -> TokenStream {
+        proc_macro_cache::expand_cached(ecx, self.pure, &[&annotation, &annotated], |ecx| {
+            self.expand_uncached(ecx, span, annotation.clone(), annotated.clone())
+        })
+    }
+}
+
+impl AttrProcMacro {
+    fn expand_uncached(&self,
+                       ecx: &mut ExtCtxt,
+                       span: Span,
+                       annotation: TokenStream,
+                       annotated: TokenStream)
+                       -> TokenStream {
         let annotation = __internal::token_stream_wrap(annotation);
         let annotated = __internal::token_stream_wrap(annotated);
 
@@ -59,6 +77,9 @@ impl base::AttrProcMacro for AttrProcMacro {
 
 pub struct BangProcMacro {
     pub inner: fn(TsShim) -> TsShim,
+    /// The SVH of the defining crate and the macro's name, if the macro was
+    /// declared pure and its expansions may be reused.
+    pub pure: Option<(u64, Symbol)>,
 }
 
 impl base::ProcMacro for BangProcMacro {
@@ -67,6 +88,14 @@ impl base::ProcMacro for BangProcMacro {
                    span: Span,
                    input: TokenStream)
                    -> TokenStream {
+        proc_macro_cache::expand_cached(ecx, self.pure, &[&input], |ecx| {
+            self.expand_uncached(ecx, span, input.clone())
+        })
+    }
+}
+
+impl BangProcMacro {
+    fn expand_uncached(&self, ecx: &mut ExtCtxt, span: Span, input: TokenStream) -> TokenStream {
         let input = __internal::token_stream_wrap(input);
 
         let res = __internal::set_sess(ecx, || {
@@ -42,6 +42,7 @@ struct ProcMacroDerive {
 struct ProcMacroDef {
     function_name: Ident,
     span: Span,
+    pure: bool,
 }
 
 struct CollectProcMacros<'a> {
@@ -199,17 +200,33 @@ impl<'a> CollectProcMacros<'a> {
         }
     }
 
-    fn collect_attr_proc_macro(&mut self, item: &'a ast::Item, attr: &'a ast::Attribute) {
-        if let Some(_) = attr.meta_item_list() {
-            self.handler.span_err(attr.span, "`#[proc_macro_attribute]` attribute
-                does not take any arguments");
-            return;
+    // `#[proc_macro]` and `#[proc_macro_attribute]` accept a single `pure`
+    // argument, declaring that the macro's output only depends on its input.
+    fn parse_pure(&self, attr: &'a ast::Attribute) -> Option<bool> {
+        let list = match attr.meta_item_list() {
+            Some(list) => list,
+            None => return Some(false),
+        };
+        if list.len() == 1 && list[0].is_word() && list[0].check_name("pure") {
+            Some(true)
+        } else {
+            let msg = format!("`#[{}]` attribute only accepts the `pure` argument", attr.path);
+            self.handler.span_err(attr.span, &msg);
+            None
         }
+    }
+
+    fn collect_attr_proc_macro(&mut self, item: &'a ast::Item, attr: &'a ast::Attribute) {
+        let pure = match self.parse_pure(attr) {
+            Some(pure) => pure,
+            None => return,
+        };
 
         if self.in_root && item.vis.node == ast::VisibilityKind::Public {
             self.attr_macros.push(ProcMacroDef {
                 span: item.span,
                 function_name: item.ident,
+                pure,
             });
         } else {
             let msg = if !self.in_root {
@@ -223,16 +240,16 @@ impl<'a> CollectProcMacros<'a> {
     }
 
     fn collect_bang_proc_macro(&mut self, item: &'a ast::Item, attr: &'a ast::Attribute) {
-        if let Some(_) = attr.meta_item_list() {
-            self.handler.span_err(attr.span, "`#[proc_macro]` attribute
-                does not take any arguments");
-            return;
-        }
+        let pure = match self.parse_pure(attr) {
+            Some(pure) => pure,
+            None => return,
+        };
 
         if self.in_root && item.vis.node == ast::VisibilityKind::Public {
             self.bang_macros.push(ProcMacroDef {
                 span: item.span,
                 function_name: item.ident,
+                pure,
             });
         } else {
             let msg = if !self.in_root {
@@ -385,6 +402,8 @@ fn mk_registrar(cx: &mut ExtCtxt,
     let register_custom_derive = Ident::from_str("register_custom_derive");
     let register_attr_proc_macro = Ident::from_str("register_attr_proc_macro");
     let register_bang_proc_macro = Ident::from_str("register_bang_proc_macro");
+    let register_pure_attr_proc_macro = Ident::from_str("register_pure_attr_proc_macro");
+    let register_pure_bang_proc_macro = Ident::from_str("register_pure_bang_proc_macro");
 
     let mut stmts = custom_derives.iter().map(|cd| {
         let path = cx.path_global(cd.span, vec![cd.function_name]);
@@ -406,9 +425,13 @@ fn mk_registrar(cx: &mut ExtCtxt,
         let name = cx.expr_str(ca.span, ca.function_name.name);
         let path = cx.path_global(ca.span, vec![ca.function_name]);
         let registrar = cx.expr_ident(ca.span, registrar);
+        let register = if ca.pure {
+            register_pure_attr_proc_macro
+        } else {
+            register_attr_proc_macro
+        };
 
-        let ufcs_path = cx.path(span,
-                                vec![proc_macro, __internal, registry, register_attr_proc_macro]);
+        let ufcs_path = cx.path(span, vec![proc_macro, __internal, registry, register]);
 
         cx.stmt_expr(cx.expr_call(span, cx.expr_path(ufcs_path),
                                   vec![registrar, name, cx.expr_path(path)]))
@@ -418,9 +441,13 @@ fn mk_registrar(cx: &mut ExtCtxt,
         let name = cx.expr_str(cm.span, cm.function_name.name);
         let path = cx.path_global(cm.span, vec![cm.function_name]);
         let registrar = cx.expr_ident(cm.span, registrar);
+        let register = if cm.pure {
+            register_pure_bang_proc_macro
+        } else {
+            register_bang_proc_macro
+        };
 
-        let ufcs_path = cx.path(span,
-                                vec![proc_macro, __internal, registry, register_bang_proc_macro]);
+        let ufcs_path = cx.path(span, vec![proc_macro, __internal, registry, register]);
 
         cx.stmt_expr(cx.expr_call(span, cx.expr_path(ufcs_path),
                                   vec![registrar, name, cx.expr_path(path)]))
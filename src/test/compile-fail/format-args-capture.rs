@@ -0,0 +1,36 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(format_args_capture)]
+
+fn main() {
+    let value = 1;
+    let valuea = 2;
+
+    format!(concat!("{val", "ue}"));
+    //~^ ERROR there is no argument named `value`
+
+    format!("{valueb}", valuea = valuea);
+    //~^ ERROR cannot find value `valueb` in this scope
+    //~^^ ERROR named argument never used
+
+    format!("{vaule}");
+    //~^ ERROR cannot find value `vaule` in this scope
+
+    format!("{:wdith$}", value);
+    //~^ ERROR cannot find value `wdith` in this scope
+
+    // Captured identifiers don't count as positional arguments.
+    format!("{value} {}");
+    //~^ ERROR invalid reference to positional argument 0 (no arguments were given)
+
+    format!("{value} {} {}", 5);
+    //~^ ERROR invalid reference to positional argument 1 (there is 1 argument)
+}
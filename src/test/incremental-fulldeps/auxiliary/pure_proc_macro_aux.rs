@@ -0,0 +1,66 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// no-prefer-dynamic
+
+#![feature(proc_macro)]
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, quote};
+use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
+#[proc_macro(pure)]
+pub fn double(input: TokenStream) -> TokenStream {
+    quote!(($input) * 2)
+}
+
+#[proc_macro(pure)]
+pub fn add_hidden(input: TokenStream) -> TokenStream {
+    quote!({
+        let value = 10;
+        $input + value
+    })
+}
+
+#[proc_macro(pure)]
+pub fn make_answer(input: TokenStream) -> TokenStream {
+    format!("fn {}() -> u32 {{ 42 }}", input).parse().unwrap()
+}
+
+#[proc_macro_attribute(pure)]
+pub fn add_one(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = item.to_string();
+    let body = item.rfind('}').unwrap();
+    format!("{} + 1 {}", &item[..body], &item[body..]).parse().unwrap()
+}
+
+// Not actually pure: counts how often it has been run, in a file in the
+// directory named by `RUN_COUNT_DIR`, and expands to that count. Purity isn't
+// checked, so a session which reuses its expansion sees the count of the
+// session which recorded it.
+#[proc_macro(pure)]
+pub fn run_count(_input: TokenStream) -> TokenStream {
+    let dir = env::var_os("RUN_COUNT_DIR").expect("`run_count!` needs `RUN_COUNT_DIR`");
+    let path = PathBuf::from(dir).join("run_count");
+
+    let mut count = String::new();
+    if let Ok(mut file) = File::open(&path) {
+        file.read_to_string(&mut count).unwrap();
+    }
+    let count = count.parse::<u32>().unwrap_or(0) + 1;
+    File::create(&path).unwrap().write_all(count.to_string().as_bytes()).unwrap();
+
+    format!("{}u32", count).parse().unwrap()
+}
@@ -0,0 +1,45 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:pure_proc_macro_aux.rs
+// ignore-stage1
+// revisions: rpass1 rpass2
+// rustc-env:RUN_COUNT_DIR={{incremental-dir}}
+
+// Expansions of pure proc macros are reused by the second session. Make sure
+// the reused expansions keep their spans and hygiene. `run_count!` expands to
+// the number of times it has been run so far, so it only stays 1 in the
+// second session if its expansion is taken from the cache.
+
+#![feature(proc_macro)]
+
+extern crate pure_proc_macro_aux;
+
+use pure_proc_macro_aux::{add_hidden, add_one, double, make_answer, run_count};
+
+make_answer!(answer);
+
+#[add_one]
+fn two() -> u32 { 1 }
+
+#[cfg(rpass2)]
+fn changed() -> u32 { 0 }
+
+fn main() {
+    let value = 1;
+    assert_eq!(double!(value + 2), 6);
+    assert_eq!(add_hidden!(value), 11);
+    assert_eq!(answer(), 42);
+    assert_eq!(two(), 2);
+    assert_eq!(run_count!(), 1);
+
+    #[cfg(rpass2)]
+    assert_eq!(changed(), 0);
+}
@@ -0,0 +1,42 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// no-prefer-dynamic
+
+#![feature(proc_macro)]
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, quote};
+
+#[proc_macro]
+pub fn double(input: TokenStream) -> TokenStream {
+    quote!(($input) * 2)
+}
+
+#[proc_macro]
+pub fn define(input: TokenStream) -> TokenStream {
+    quote!(let $input;)
+}
+
+#[proc_macro]
+pub fn define_hidden(input: TokenStream) -> TokenStream {
+    quote! {
+        let value = $input;
+        let _ = value;
+    }
+}
+
+#[proc_macro]
+pub fn some_pair(input: TokenStream) -> TokenStream {
+    quote!(Some(($input)))
+}
+
@@ -0,0 +1,81 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// no-prefer-dynamic
+
+#![feature(proc_macro)]
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, TokenTree, TokenNode, Term, Literal, Span, quote};
+
+fn describe(lit: &Literal) -> String {
+    let suffix = lit.suffix().map(|s| s.as_str().to_string()).unwrap_or_default();
+    if let Some(n) = lit.integer_value() {
+        format!("int {}{}", n, suffix)
+    } else if let Some(x) = lit.float_value() {
+        format!("float {}{}", x, suffix)
+    } else if let Some(s) = lit.string_value() {
+        format!("str {:?}", s)
+    } else if let Some(c) = lit.character_value() {
+        format!("char {:?}", c)
+    } else if let Some(b) = lit.byte_value() {
+        format!("byte {}", b)
+    } else if let Some(b) = lit.byte_string_value() {
+        format!("bytes {:?}", b)
+    } else {
+        "other".to_string()
+    }
+}
+
+#[proc_macro]
+pub fn describe_literals(input: TokenStream) -> TokenStream {
+    let descriptions = input.into_iter().map(|tree| {
+        match tree.kind {
+            TokenNode::Literal(ref lit) => describe(lit),
+            _ => panic!("expected a literal, found `{}`", tree),
+        }
+    }).collect::<Vec<_>>();
+
+    TokenNode::Literal(Literal::string(&descriptions.join("; "))).into()
+}
+
+#[proc_macro]
+pub fn define_getter(input: TokenStream) -> TokenStream {
+    let trees = input.into_iter().collect::<Vec<_>>();
+    let (name, value) = match (&trees[0].kind, &trees[1].kind, &trees[2].kind) {
+        (&TokenNode::Term(name), &TokenNode::Op('=', _), &TokenNode::Literal(ref value)) => {
+            (name, value.integer_value().expect("expected an integer"))
+        }
+        _ => panic!("expected `name = integer`"),
+    };
+
+    // The getter is visible to the caller, the local is not.
+    let getter = TokenTree {
+        span: Span::call_site(),
+        kind: TokenNode::Term(Term::intern(&format!("get_{}", name.as_str()))),
+    };
+    let local = TokenTree {
+        span: Span::def_site(),
+        kind: TokenNode::Term(Term::intern("value")),
+    };
+    let value = TokenTree {
+        span: trees[2].span,
+        kind: TokenNode::Literal(Literal::u64(value as u64)),
+    };
+
+    quote! {
+        fn $getter() -> u64 {
+            let $local = $value;
+            $local
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:bang-macro-positions.rs
+// ignore-stage1
+
+#![feature(proc_macro)]
+
+extern crate bang_macro_positions;
+
+use bang_macro_positions::{double, define, define_hidden, some_pair};
+
+fn main() {
+    // Expressions, including nested invocations.
+    assert_eq!(double!(1 + 2), 6);
+    assert_eq!(double!(double!(5)), 20);
+    let doubled: Vec<i32> = vec![1, 2].into_iter().map(|x| double!(x)).collect();
+    assert_eq!(doubled, [2, 4]);
+
+    // Statements: names written by the caller are visible to it...
+    define!(seven = 7);
+    assert_eq!(seven, 7);
+
+    // ...names introduced by the macro itself are not.
+    let value = "outer";
+    define_hidden!(1);
+    assert_eq!(value, "outer");
+
+    // Patterns.
+    match Some((1, 2)) {
+        some_pair!(a, b) => assert_eq!(a + b, 3),
+        None => panic!("expected a pair"),
+    }
+    if let some_pair!(ref name, _) = Some(("name", 0)) {
+        assert_eq!(*name, "name");
+    } else {
+        panic!("expected a pair");
+    }
+}
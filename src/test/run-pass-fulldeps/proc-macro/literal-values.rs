@@ -0,0 +1,34 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:literal-values.rs
+// ignore-stage1
+
+#![feature(proc_macro)]
+
+extern crate literal_values;
+
+use literal_values::{describe_literals, define_getter};
+
+define_getter!(answer = 0x2a);
+
+fn main() {
+    assert_eq!(describe_literals!(1u8 0x_ff 0o17 0b101 1_000_000),
+               "int 1u8; int 255; int 15; int 5; int 1000000");
+    assert_eq!(describe_literals!(2.5 1e3f32 3f64),
+               "float 2.5; float 1000f32; float 3f64");
+    assert_eq!(describe_literals!("a\tb" r"c\d" '\'' '\u{e9}' b'A' b"h\x69" br"\n"),
+               r#"str "a\tb"; str "c\\d"; char '\''; char 'é'; byte 65; bytes [104, 105]; bytes [92, 110]"#);
+    assert_eq!(describe_literals!(340282366920938463463374607431768211456), "other");
+
+    let value = 1;
+    assert_eq!(get_answer(), 42);
+    assert_eq!(value, 1);
+}
@@ -0,0 +1,50 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(format_args_capture)]
+
+use std::fmt::Write;
+
+macro_rules! hygienic {
+    () => {{
+        let a = "macro";
+        format!("{a}")
+    }}
+}
+
+fn main() {
+    let a = 1;
+    let b = "two";
+    let width = 6;
+    let prec = 2;
+    let x = 3.14159;
+
+    assert_eq!(format!("{a} {b}"), "1 two");
+    assert_eq!(format!("{a} {a:?} {b:?}"), "1 1 \"two\"");
+    assert_eq!(format!("{b:>width$}"), "   two");
+    assert_eq!(format!("{x:.prec$}"), "3.14");
+    assert_eq!(format!("{x:width$.prec$}"), "  3.14");
+    assert_eq!(format!("{:width$}|{}", b, a), "two   |1");
+
+    // Explicit arguments take precedence over captured ones.
+    assert_eq!(format!("{a} {b}", b = 2), "1 2");
+    assert_eq!(format!("{0} {a}", 5), "5 1");
+
+    // The format string of a macro sees the macro's bindings, not the caller's.
+    assert_eq!(hygienic!(), "macro");
+
+    let mut s = String::new();
+    write!(s, "{a}").unwrap();
+    writeln!(s, "{b}").unwrap();
+    assert_eq!(s, "1two\n");
+
+    println!("{a} {b:?}");
+    eprintln!("{x:.prec$}");
+}
@@ -0,0 +1,64 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(macro_literal_matcher)]
+
+macro_rules! mtester {
+    ($l:literal) => {
+        format!("macro caught literal: {}", $l)
+    };
+    ($e:expr) => {
+        format!("macro caught expr: {}", $e)
+    };
+}
+
+macro_rules! two_literals {
+    ($l1:literal $l2:literal) => {
+        ($l1, $l2)
+    };
+}
+
+macro_rules! is_literal_pat {
+    ($l:literal) => {
+        match 3 {
+            $l => true,
+            _ => false,
+        }
+    };
+}
+
+macro_rules! forward {
+    ($l:literal) => {
+        two_literals!($l 0)
+    };
+}
+
+macro_rules! sum {
+    ($l:literal $($e:expr),*) => {
+        $l $(+ $e)*
+    };
+}
+
+pub fn main() {
+    let x = 5;
+    assert_eq!(mtester!("str"), "macro caught literal: str");
+    assert_eq!(mtester!(2), "macro caught literal: 2");
+    assert_eq!(mtester!(2.2), "macro caught literal: 2.2");
+    assert_eq!(mtester!(-1), "macro caught literal: -1");
+    assert_eq!(mtester!(true), "macro caught literal: true");
+    assert_eq!(mtester!(1 + 1), "macro caught expr: 2");
+    assert_eq!(mtester!(x), "macro caught expr: 5");
+
+    assert_eq!(two_literals!(-1 'c'), (-1, 'c'));
+    assert!(is_literal_pat!(3));
+    assert!(!is_literal_pat!(-3));
+    assert_eq!(forward!(-7), (-7, 0));
+    assert_eq!(sum!(-1 2, x), 6);
+}
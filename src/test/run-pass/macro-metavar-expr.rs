@@ -0,0 +1,39 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(macro_metavar_expr)]
+
+macro_rules! table {
+    ($name:ident: $($value:expr),*) => {
+        const $name: [(usize, &'static str); ${count(value)}] = [$((${index()}, $value)),*];
+    };
+}
+
+macro_rules! grid {
+    ($($($cell:expr),*);*) => {
+        (${count(cell)}, vec![$((${count(cell)}, vec![$((${index(1)}, ${index()}, $cell)),*])),*])
+    };
+}
+
+table!(EMPTY:);
+table!(COLORS: "red", "green", "blue");
+
+pub fn main() {
+    assert_eq!(EMPTY.len(), 0);
+    assert_eq!(COLORS, [(0, "red"), (1, "green"), (2, "blue")]);
+
+    let (total, rows) = grid!(1, 2; 3; 4, 5, 6);
+    assert_eq!(total, 6);
+    assert_eq!(rows, vec![
+        (2, vec![(0, 0, 1), (0, 1, 2)]),
+        (1, vec![(1, 0, 3)]),
+        (3, vec![(2, 0, 4), (2, 1, 5), (2, 2, 6)]),
+    ]);
+}
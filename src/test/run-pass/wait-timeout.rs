@@ -0,0 +1,104 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-cloudabi no processes
+// ignore-emscripten no processes
+
+#![feature(child_wait_timeout)]
+#![cfg_attr(unix, feature(unix_exit_watcher))]
+
+use std::env;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    if args.len() != 1 {
+        match &args[1][..] {
+            "sleep" => thread::sleep(Duration::new(1_000, 0)),
+            _ => {}
+        }
+        return
+    }
+
+    // A child which never exits times out, and is left running.
+    let mut me = Command::new(env::current_exe().unwrap())
+                         .arg("sleep")
+                         .spawn()
+                         .unwrap();
+    let start = Instant::now();
+    let maybe_status = me.wait_timeout(Duration::from_millis(100)).unwrap();
+    assert!(maybe_status.is_none());
+    assert!(start.elapsed() >= Duration::from_millis(100));
+    assert!(me.try_wait().unwrap().is_none());
+
+    me.kill().unwrap();
+    let status = me.wait_timeout(Duration::new(1_000, 0)).unwrap().unwrap();
+    assert!(!status.success());
+    let status = me.wait_timeout(Duration::new(0, 0)).unwrap().unwrap();
+    assert!(!status.success());
+
+    // A child which exits quickly is noticed long before the timeout.
+    let mut me = Command::new(env::current_exe().unwrap())
+                         .arg("return-quickly")
+                         .spawn()
+                         .unwrap();
+    let start = Instant::now();
+    let status = me.wait_timeout(Duration::new(1_000, 0)).unwrap().unwrap();
+    assert!(status.success());
+    assert!(start.elapsed() < Duration::new(500, 0));
+
+    // Many children waited on concurrently from different threads.
+    let threads = (0..8).map(|_| {
+        thread::spawn(|| {
+            let mut me = Command::new(env::current_exe().unwrap())
+                                 .arg("return-quickly")
+                                 .spawn()
+                                 .unwrap();
+            let status = me.wait_timeout(Duration::new(1_000, 0)).unwrap().unwrap();
+            assert!(status.success());
+        })
+    }).collect::<Vec<_>>();
+    for t in threads {
+        t.join().unwrap();
+    }
+
+    #[cfg(unix)]
+    exit_watcher();
+}
+
+#[cfg(unix)]
+fn exit_watcher() {
+    use std::os::unix::process::ExitWatcher;
+
+    let mut watcher = ExitWatcher::new().unwrap();
+    let mut children = (0..4).map(|_| {
+        Command::new(env::current_exe().unwrap())
+                .arg("return-quickly")
+                .spawn()
+                .unwrap()
+    }).collect::<Vec<_>>();
+
+    while !children.is_empty() {
+        let mut i = 0;
+        while i < children.len() {
+            if let Some(status) = children[i].try_wait().unwrap() {
+                assert!(status.success());
+                children.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+        if !children.is_empty() {
+            watcher.wait().unwrap();
+        }
+    }
+}
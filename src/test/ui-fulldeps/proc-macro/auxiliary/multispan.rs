@@ -0,0 +1,49 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// no-prefer-dynamic
+
+#![feature(proc_macro)]
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, TokenNode, Span, Diagnostic, Level};
+
+#[proc_macro]
+pub fn hello(input: TokenStream) -> TokenStream {
+    let spans: Vec<Span> = input.into_iter().filter(|tree| {
+        match tree.kind {
+            TokenNode::Term(term) => term.as_str() == "hi",
+            _ => false,
+        }
+    }).map(|tree| tree.span).collect();
+
+    if !spans.is_empty() {
+        Diagnostic::spanned(spans, Level::Error, "hello to you, too!").emit();
+    }
+
+    TokenStream::empty()
+}
+
+#[proc_macro]
+pub fn check_suffixes(input: TokenStream) -> TokenStream {
+    for tree in input {
+        if let TokenNode::Literal(ref lit) = tree.kind {
+            if lit.integer_value().is_some() && lit.suffix().is_none() {
+                tree.span.warning("unsuffixed integer literal")
+                    .help("add a suffix such as `u32`")
+                    .emit();
+            }
+        }
+    }
+
+    TokenStream::empty()
+}
@@ -0,0 +1,26 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:multispan.rs
+// ignore-stage1
+
+#![feature(proc_macro)]
+
+extern crate multispan;
+
+use multispan::{hello, check_suffixes};
+
+fn main() {
+    // One primary span for each `hi`.
+    hello!(hi there, hi); //~ ERROR hello to you, too!
+
+    // Only unsuffixed integers are warned about.
+    check_suffixes!(1u8 2 'c' 3.0); //~ WARN unsuffixed integer literal
+}
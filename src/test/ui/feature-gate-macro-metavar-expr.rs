@@ -0,0 +1,21 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that metavariable expressions cannot be used when the macro_metavar_expr
+// feature gate is not used.
+
+macro_rules! count {
+    ($($x:expr),*) => { ${count(x)} }
+    //~^ ERROR macro metavariable expressions such as `${count(x)}` are unstable
+}
+
+fn main() {
+    assert_eq!(count!(1, 2, 3), 3);
+}
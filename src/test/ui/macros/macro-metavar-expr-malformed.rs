@@ -0,0 +1,25 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(macro_metavar_expr)]
+#![allow(unused_macros)]
+
+macro_rules! bad {
+    ($($x:ident)*) => {
+        ${length()}
+        //~^ ERROR expected one of `count(ident)`, `index()` or `index(depth)`
+        ${count($x)}
+        //~^ ERROR expected one of `count(ident)`, `index()` or `index(depth)`
+        ${index(x)}
+        //~^ ERROR expected one of `count(ident)`, `index()` or `index(depth)`
+    };
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z trace-macro-matchers
+
+macro_rules! pick {
+    (zero) => { 0 };
+    (one $x:expr) => { $x + 1 };
+}
+
+fn main() {
+    let _ = pick!(one 2 3); //~ ERROR no rules expected the token `3`
+    let _ = pick!(one); //~ ERROR unexpected end of macro invocation
+}
@@ -2309,6 +2309,8 @@ impl<'test> TestCx<'test> {
         // - compile foo/bar.rs with -Z incremental=.../foo/bar.incremental and -C rpass3
         //   - because name of revision starts with "rpass", expect success
         // - execute build/foo/bar.exe and save output
+        // - `{{incremental-dir}}` in a `rustc-env` value stands for the
+        //   incremental directory, so that tests can hand it to their macros
         //
         // FIXME -- use non-incremental mode as an oracle? That doesn't apply
         // to #[rustc_dirty] and clean tests I guess
@@ -2325,6 +2327,9 @@ impl<'test> TestCx<'test> {
 
         // Add an extra flag pointing at the incremental directory.
         let mut revision_props = self.props.clone();
+        for &mut (_, ref mut value) in &mut revision_props.rustc_env {
+            *value = value.replace("{{incremental-dir}}", &incremental_dir.to_string_lossy());
+        }
         revision_props.incremental_dir = Some(incremental_dir);
 
         let revision_cx = TestCx {